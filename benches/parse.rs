@@ -0,0 +1,22 @@
+#![feature(test)]
+
+extern crate test;
+
+use lark_intern::Untern;
+use lark_parser::{ParserDatabase, ParserDatabaseExt};
+use lark_test::{db_with_test, synthetic_source};
+use test::Bencher;
+
+/// Parses a large file, and all of its fn bodies, from scratch.
+#[bench]
+fn parse_synthetic_file(b: &mut Bencher) {
+    let text = synthetic_source(500);
+    b.iter(|| {
+        let db = db_with_test("bench.lark", &text);
+        for &entity in db.top_level_entities_in_file("bench.lark").iter() {
+            if entity.untern(&db).has_fn_body() {
+                test::black_box(db.fn_body(entity));
+            }
+        }
+    });
+}
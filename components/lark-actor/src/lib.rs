@@ -44,7 +44,9 @@ pub enum LspResponse {
     Completions(TaskId, Vec<(String, String)>),
     Initialized(TaskId),
     Nothing(TaskId),
-    Diagnostics(Url, Vec<(Range, String)>),
+    /// The diagnostics for a file, as a JSON array of LSP
+    /// `Diagnostic` objects.
+    Diagnostics(Url, serde_json::Value),
 }
 
 /// An actor in the task system. This gives a uniform way to
@@ -5,6 +5,7 @@ use lark_hir as hir;
 use lark_intern::{Intern, Untern};
 use lark_parser::{ParserDatabase, ParserDatabaseExt};
 use lark_query_system::LarkDatabase;
+use lark_ty::PermKind;
 use lark_ty::Ty;
 
 fn build_variable_name(
@@ -24,6 +25,11 @@ fn build_entity_name(db: &LarkDatabase, entity: Entity) -> String {
         EntityData::LangItem(LangItem::True) => "true".into(),
         EntityData::LangItem(LangItem::Debug) => "println!".into(),
         EntityData::ItemName { id, .. } => id.untern(db).to_string(),
+        EntityData::MemberName {
+            base,
+            kind: MemberKind::Variant,
+            id,
+        } => format!("{}::{}", build_entity_name(db, base), id.untern(db)),
         x => unimplemented!("Unsupported entity name: {:#?}", x),
     }
 }
@@ -45,19 +51,56 @@ pub fn build_place(
                 identifier.text.untern(db).to_string()
             )
         }
+        hir::PlaceData::TupleField { owner, index, .. } => {
+            format!("{}.{}", build_place(db, fn_body, *owner), index)
+        }
         hir::PlaceData::Temporary(expression) => build_expression(db, fn_body, *expression),
     }
 }
 
+pub fn build_pattern(
+    db: &LarkDatabase,
+    fn_body: &std::sync::Arc<hir::FnBody>,
+    pattern: hir::Pattern,
+) -> String {
+    match fn_body.tables[pattern] {
+        hir::PatternData::Wildcard => "_".to_string(),
+        hir::PatternData::Binding(variable) => {
+            format!("mut {}", build_variable_name(db, fn_body, variable))
+        }
+        hir::PatternData::Literal(literal) => build_expression(db, fn_body, literal),
+        hir::PatternData::Struct { entity, fields } => {
+            let mut output = String::new();
+
+            output.push_str(&build_entity_name(db, entity));
+            output.push_str(" { ");
+            for field in fields.iter(fn_body) {
+                let identified_pattern = fn_body.tables[field];
+                output.push_str(&format!(
+                    "{}: {}, ",
+                    fn_body.tables[identified_pattern.identifier]
+                        .text
+                        .untern(db),
+                    build_pattern(db, fn_body, identified_pattern.pattern),
+                ));
+            }
+            output.push_str(".. }");
+            output
+        }
+    }
+}
+
 pub fn build_type(db: &LarkDatabase, ty: &Ty<lark_ty::declaration::Declaration>) -> String {
     let boolean_entity = EntityData::LangItem(LangItem::Boolean).intern(db);
     let uint_entity = EntityData::LangItem(LangItem::Uint).intern(db);
     let int_entity = EntityData::LangItem(LangItem::Int).intern(db);
+    let float_entity = EntityData::LangItem(LangItem::Float).intern(db);
+    let char_entity = EntityData::LangItem(LangItem::Char).intern(db);
     let string_entity = EntityData::LangItem(LangItem::String).intern(db);
     let void_entity = EntityData::LangItem(LangItem::Tuple(0)).intern(db);
 
     match ty.base.untern(db) {
-        lark_ty::BoundVarOr::BoundVar(_) => unimplemented!("Bound variables not yet supported"),
+        lark_ty::BoundVarOr::BoundVar(bv) => format!("T{}", bv.as_usize()),
         lark_ty::BoundVarOr::Known(ty) => match ty.kind {
             lark_ty::BaseKind::Named(entity) => {
                 if entity == boolean_entity {
@@ -66,6 +109,10 @@ pub fn build_type(db: &LarkDatabase, ty: &Ty<lark_ty::declaration::Declaration>)
                     "u32".into()
                 } else if entity == int_entity {
                     "i32".into()
+                } else if entity == float_entity {
+                    "f64".into()
+                } else if entity == char_entity {
+                    "char".into()
                 } else if entity == string_entity {
                     "String".into()
                 } else if entity == void_entity {
@@ -76,7 +123,31 @@ pub fn build_type(db: &LarkDatabase, ty: &Ty<lark_ty::declaration::Declaration>)
                             kind: ItemKind::Struct,
                             id,
                             ..
-                        } => id.untern(db).to_string(),
+                        }
+                        | EntityData::ItemName {
+                            kind: ItemKind::Enum,
+                            id,
+                            ..
+                        } => {
+                            let arguments: Vec<_> = ty
+                                .generics
+                                .iter()
+                                .map(|generic| build_type(db, &generic.assert_ty()))
+                                .collect();
+                            if arguments.is_empty() {
+                                id.untern(db).to_string()
+                            } else {
+                                format!("{}<{}>", id.untern(db), arguments.join(", "))
+                            }
+                        }
+                        EntityData::LangItem(LangItem::Tuple(_)) => {
+                            let elements: Vec<_> = ty
+                                .generics
+                                .iter()
+                                .map(|generic| build_type(db, &generic.assert_ty()))
+                                .collect();
+                            format!("({},)", elements.join(", "))
+                        }
                         _ => unimplemented!("Unknown type: {:#?}", entity.debug_with(db)),
                     }
                 }
@@ -86,6 +157,22 @@ pub fn build_type(db: &LarkDatabase, ty: &Ty<lark_ty::declaration::Declaration>)
     }
 }
 
+/// The generic parameters of `entity`, like `<T0, T1>`; empty if
+/// it has none. Within the item, the bound variable `i` is `Ti`.
+fn build_generic_parameters(db: &LarkDatabase, entity: Entity) -> String {
+    let count = match db.generic_declarations(entity).into_value() {
+        Ok(generic_declarations) => generic_declarations.declarations.len(),
+        Err(_) => 0,
+    };
+
+    if count == 0 {
+        String::new()
+    } else {
+        let parameters: Vec<_> = (0..count).map(|i| format!("T{}", i)).collect();
+        format!("<{}>", parameters.join(", "))
+    }
+}
+
 pub fn codegen_struct(
     db: &LarkDatabase,
     entity: Entity,
@@ -96,7 +183,9 @@ pub fn codegen_struct(
     let mut output = String::new();
     let mut errors: Vec<Diagnostic> = vec![];
 
-    output.push_str(&format!("struct {} {{\n", name));
+    let generic_parameters = build_generic_parameters(db, entity);
+
+    output.push_str(&format!("struct {}{} {{\n", name, generic_parameters));
 
     // for Rust output, output the fields first between the curlies
     for member in members.iter() {
@@ -121,7 +210,10 @@ pub fn codegen_struct(
     output.push_str("}\n");
 
     // output the methods in a separate impl
-    output.push_str(&format!("impl {} {{\n", name));
+    output.push_str(&format!(
+        "impl{} {}{} {{\n",
+        generic_parameters, name, generic_parameters
+    ));
     for member in members.iter() {
         match member.entity.untern(db) {
             EntityData::MemberName {
@@ -146,6 +238,62 @@ pub fn codegen_struct(
     }
 }
 
+pub fn codegen_enum(
+    db: &LarkDatabase,
+    entity: Entity,
+    id: lark_string::GlobalIdentifier,
+) -> WithError<String> {
+    let name = id.untern(db);
+    let members = db.members(entity).unwrap();
+    let mut output = String::new();
+    let mut errors: Vec<Diagnostic> = vec![];
+
+    output.push_str(&format!("enum {} {{\n", name));
+
+    for member in members.iter() {
+        let member_name = member.name.untern(db);
+        let signature = db
+            .signature(member.entity)
+            .accumulate_errors_into(&mut errors)
+            .unwrap();
+
+        if signature.inputs.is_empty() {
+            output.push_str(&format!("{},\n", member_name));
+        } else {
+            let fields: Vec<_> = signature
+                .inputs
+                .iter()
+                .map(|input| build_type(db, input))
+                .collect();
+            output.push_str(&format!("{}({}),\n", member_name, fields.join(", ")));
+        }
+    }
+    output.push_str("}\n");
+
+    WithError {
+        value: output,
+        errors,
+    }
+}
+
+fn build_binary_operator(operator: hir::BinaryOperator) -> &'static str {
+    match operator {
+        hir::BinaryOperator::Add => "+",
+        hir::BinaryOperator::Subtract => "-",
+        hir::BinaryOperator::Multiply => "*",
+        hir::BinaryOperator::Divide => "/",
+        hir::BinaryOperator::Remainder => "%",
+        hir::BinaryOperator::Equals => "==",
+        hir::BinaryOperator::NotEquals => "!=",
+        hir::BinaryOperator::LessThan => "<",
+        hir::BinaryOperator::LessThanOrEqual => "<=",
+        hir::BinaryOperator::GreaterThan => ">",
+        hir::BinaryOperator::GreaterThanOrEqual => ">=",
+        hir::BinaryOperator::And => "&&",
+        hir::BinaryOperator::Or => "||",
+    }
+}
+
 pub fn build_expression(
     db: &LarkDatabase,
     fn_body: &std::sync::Arc<hir::FnBody>,
@@ -154,17 +302,35 @@ pub fn build_expression(
     match fn_body.tables[expression] {
         hir::ExpressionData::Let {
             variable,
+            ty,
             initializer,
             body,
-        } => match initializer {
-            Some(init_expression) => format!(
-                "{{ let {} = {};\n{}}}",
-                build_variable_name(db, fn_body, variable),
-                build_expression(db, fn_body, init_expression),
-                build_expression(db, fn_body, body),
-            ),
-            None => format!("let {};\n", build_variable_name(db, fn_body, variable)),
-        },
+        } => {
+            let declaration = match ty {
+                Some(ty) => format!(
+                    "{}: {}",
+                    build_variable_name(db, fn_body, variable),
+                    build_type(db, &ty)
+                ),
+                None => build_variable_name(db, fn_body, variable),
+            };
+
+            // Lark variables can always be reassigned, so their Rust
+            // counterparts must be `mut`.
+            match initializer {
+                Some(init_expression) => format!(
+                    "{{ let mut {} = {};\n{}}}",
+                    declaration,
+                    build_expression(db, fn_body, init_expression),
+                    build_expression(db, fn_body, body),
+                ),
+                None => format!(
+                    "{{ let mut {};\n{}}}",
+                    declaration,
+                    build_expression(db, fn_body, body)
+                ),
+            }
+        }
 
         hir::ExpressionData::Place { place } => build_place(db, fn_body, place),
 
@@ -174,6 +340,17 @@ pub fn build_expression(
             build_expression(db, fn_body, value)
         ),
 
+        hir::ExpressionData::CompoundAssignment {
+            operator,
+            place,
+            value,
+        } => format!(
+            "{} {}= {};\n",
+            build_place(db, fn_body, place),
+            build_binary_operator(operator),
+            build_expression(db, fn_body, value)
+        ),
+
         hir::ExpressionData::MethodCall { method, arguments } => {
             let mut arguments = arguments.iter(fn_body);
             let mut output = String::new();
@@ -238,11 +415,16 @@ pub fn build_expression(
             output
         }
 
-        hir::ExpressionData::Sequence { first, second } => format!(
-            "{};\n {}",
-            build_expression(db, fn_body, first),
-            build_expression(db, fn_body, second)
-        ),
+        hir::ExpressionData::Sequence { first, second } => match fn_body[second] {
+            // Leave a trailing unit off entirely, so that a block like
+            // `{ return 0; }` diverges in Rust too rather than being `()`.
+            hir::ExpressionData::Unit {} => format!("{};", build_expression(db, fn_body, first)),
+            _ => format!(
+                "{};\n {}",
+                build_expression(db, fn_body, first),
+                build_expression(db, fn_body, second)
+            ),
+        },
 
         hir::ExpressionData::If {
             condition,
@@ -255,6 +437,55 @@ pub fn build_expression(
             build_expression(db, fn_body, if_false)
         ),
 
+        hir::ExpressionData::While { condition, body } => format!(
+            "while {} {{ {}; \n}}",
+            build_expression(db, fn_body, condition),
+            build_expression(db, fn_body, body)
+        ),
+
+        hir::ExpressionData::For {
+            variable,
+            start,
+            end,
+            body,
+        } => format!(
+            "for {} in {}..{} {{ {}; \n}}",
+            build_variable_name(db, fn_body, variable),
+            build_expression(db, fn_body, start),
+            build_expression(db, fn_body, end),
+            build_expression(db, fn_body, body)
+        ),
+
+        hir::ExpressionData::Match { scrutinee, arms } => {
+            let mut output = format!("match {} {{\n", build_expression(db, fn_body, scrutinee));
+            for arm in arms.iter(fn_body) {
+                let hir::MatchArmData { pattern, body } = fn_body.tables[arm];
+                output.push_str(&format!(
+                    "{} => {},\n",
+                    build_pattern(db, fn_body, pattern),
+                    build_expression(db, fn_body, body)
+                ));
+            }
+            output.push_str("}");
+            output
+        }
+
+        hir::ExpressionData::Loop { body } => {
+            format!("loop {{ {}; \n}}", build_expression(db, fn_body, body))
+        }
+
+        // Rust only permits `break` with a value inside of `loop`, so
+        // leave off the elided unit value.
+        hir::ExpressionData::Break { value } => match fn_body[value] {
+            hir::ExpressionData::Unit {} => "break".to_string(),
+            _ => format!("break {}", build_expression(db, fn_body, value)),
+        },
+
+        hir::ExpressionData::Return { value } => match fn_body[value] {
+            hir::ExpressionData::Unit {} => "return".to_string(),
+            _ => format!("return {}", build_expression(db, fn_body, value)),
+        },
+
         hir::ExpressionData::Binary {
             operator,
             left,
@@ -262,14 +493,7 @@ pub fn build_expression(
         } => format!(
             "({} {} {})",
             build_expression(db, fn_body, left),
-            match operator {
-                hir::BinaryOperator::Add => "+",
-                hir::BinaryOperator::Subtract => "-",
-                hir::BinaryOperator::Multiply => "*",
-                hir::BinaryOperator::Divide => "/",
-                hir::BinaryOperator::Equals => "==",
-                hir::BinaryOperator::NotEquals => "!=",
-            },
+            build_binary_operator(operator),
             build_expression(db, fn_body, right),
         ),
 
@@ -277,21 +501,66 @@ pub fn build_expression(
             "{}({})",
             match operator {
                 hir::UnaryOperator::Not => "!",
+                hir::UnaryOperator::Negate => "-",
             },
             build_expression(db, fn_body, value)
         ),
 
+        hir::ExpressionData::Reference { perm, place } => format!(
+            "{}{}",
+            match perm {
+                PermKind::Own => "",
+                PermKind::Share => "&",
+                PermKind::Borrow => "&mut ",
+            },
+            build_place(db, fn_body, place)
+        ),
+
         hir::ExpressionData::Literal { data } => match data {
             hir::LiteralData {
                 kind: hir::LiteralKind::String,
                 value,
-            } => format!("{}.to_string()", value.untern(db)),
+            } => format!("{:?}.to_string()", &value.untern(db)[..]),
             hir::LiteralData {
                 kind: hir::LiteralKind::UnsignedInteger,
                 value,
             } => format!("{}", value.untern(db)),
+            hir::LiteralData {
+                kind: hir::LiteralKind::Float,
+                value,
+            } => format!("{}f64", value.untern(db)),
+            hir::LiteralData {
+                kind: hir::LiteralKind::Char,
+                value,
+            } => format!("{:?}", value.untern(db).chars().next().unwrap()),
+            hir::LiteralData {
+                kind: hir::LiteralKind::Boolean,
+                value,
+            } => format!("{}", value.untern(db)),
         },
 
+        hir::ExpressionData::Range {
+            start,
+            end,
+            inclusive,
+        } => format!(
+            "({}{}{})",
+            start
+                .map(|e| build_expression(db, fn_body, e))
+                .unwrap_or_default(),
+            if inclusive { "..=" } else { ".." },
+            end.map(|e| build_expression(db, fn_body, e))
+                .unwrap_or_default(),
+        ),
+
+        hir::ExpressionData::Tuple { elements } => {
+            let elements: Vec<_> = elements
+                .iter(fn_body)
+                .map(|element| build_expression(db, fn_body, element))
+                .collect();
+            format!("({},)", elements.join(", "))
+        }
+
         hir::ExpressionData::Unit {} => "()".to_string(),
 
         hir::ExpressionData::Aggregate { entity, fields } => {
@@ -345,7 +614,11 @@ pub fn codegen_function(
 
     let name = id.untern(db);
 
-    output.push_str(&format!("fn {}(", name));
+    output.push_str(&format!(
+        "fn {}{}(",
+        name,
+        build_generic_parameters(db, entity)
+    ));
 
     let mut first = true;
     for (argument, argument_type) in arguments.iter(&fn_body).zip(signature.inputs.iter()) {
@@ -377,6 +650,29 @@ pub fn codegen_function(
     }
 }
 
+pub fn codegen_const(
+    db: &LarkDatabase,
+    entity: Entity,
+    id: lark_string::GlobalIdentifier,
+) -> WithError<String> {
+    let mut errors: Vec<Diagnostic> = vec![];
+
+    let fn_body = db.fn_body(entity).accumulate_errors_into(&mut errors);
+    let ty = db.ty(entity).accumulate_errors_into(&mut errors);
+
+    let output = format!(
+        "const {}: {} = {};\n",
+        id.untern(db),
+        build_type(db, &ty),
+        build_expression(db, &fn_body, fn_body.root_expression)
+    );
+
+    WithError {
+        value: output,
+        errors,
+    }
+}
+
 /// Converts the MIR context of definitions into Rust source
 pub fn codegen_rust(db: &LarkDatabase) -> WithError<String> {
     let mut output = String::new();
@@ -400,6 +696,18 @@ pub fn codegen_rust(db: &LarkDatabase) -> WithError<String> {
                         output.push_str(&result.value);
                     }
                 }
+                EntityData::ItemName {
+                    kind: ItemKind::Const,
+                    id,
+                    ..
+                } => {
+                    let mut result = codegen_const(db, entity, id);
+                    if result.errors.len() > 0 {
+                        errors.append(&mut result.errors);
+                    } else {
+                        output.push_str(&result.value);
+                    }
+                }
                 EntityData::ItemName {
                     kind: ItemKind::Struct,
                     id,
@@ -412,6 +720,39 @@ pub fn codegen_rust(db: &LarkDatabase) -> WithError<String> {
                         output.push_str(&result.value);
                     }
                 }
+                EntityData::ItemName {
+                    kind: ItemKind::Enum,
+                    id,
+                    ..
+                } => {
+                    let mut result = codegen_enum(db, entity, id);
+                    if result.errors.len() > 0 {
+                        errors.append(&mut result.errors);
+                    } else {
+                        output.push_str(&result.value);
+                    }
+                }
+                EntityData::ItemName {
+                    kind: ItemKind::Trait,
+                    ..
+                } => {
+                    // Traits only declare signatures, so there is
+                    // nothing to emit for them.
+                }
+                EntityData::ItemName {
+                    kind: ItemKind::Impl,
+                    ..
+                } => {
+                    // The methods of an impl are members of its
+                    // struct, so they are emitted along with it.
+                }
+                EntityData::ItemName {
+                    kind: ItemKind::TypeAlias,
+                    ..
+                } => {
+                    // Aliases are replaced by their targets when types
+                    // are converted, so there is nothing to emit.
+                }
                 x => unimplemented!("Can not codegen {:#?}", x.debug_with(db)),
             }
         }
@@ -107,10 +107,19 @@ impl LarkDatabaseExt for LarkDatabase {
                 let range = ranged_diagnostic.range;
                 let error = Diagnostic::new(Severity::Error, ranged_diagnostic.label);
 
+                // The range is an LSP range, whose columns count UTF-16
+                // code units.
+                let line_index = db.line_index(file_id);
                 let span = Span::new(
                     file_id,
-                    db.byte_index(file_id, range.start.line, range.start.character),
-                    db.byte_index(file_id, range.end.line, range.end.character),
+                    line_index.utf16_line_col_to_offset(
+                        range.start.line as usize,
+                        range.start.character as usize,
+                    ),
+                    line_index.utf16_line_col_to_offset(
+                        range.end.line as usize,
+                        range.end.character as usize,
+                    ),
                 );
 
                 let error = error.with_label(Label::new_primary(span));
@@ -1,6 +1,6 @@
 use crate::build::LarkDatabaseExt;
 use lark_debug_with::DebugWith;
-use lark_entity::{EntityData, ItemKind};
+use lark_entity::{Entity, EntityData, ItemKind};
 use lark_eval::Value;
 use lark_hir as hir;
 use lark_intern::{Intern, Untern};
@@ -15,7 +15,7 @@ use termcolor::{ColorChoice, StandardStream, WriteColor};
 
 const REPL_FILENAME: &str = "__REPL__.lark";
 
-pub fn get_body(db: &LarkDatabase) -> lark_error::WithError<std::sync::Arc<lark_hir::FnBody>> {
+pub fn get_main(db: &LarkDatabase) -> Entity {
     let main_name = "main".intern(&db);
     let repl_filename = REPL_FILENAME.intern(&db);
     let entities = db.top_level_entities_in_file(repl_filename);
@@ -28,8 +28,7 @@ pub fn get_body(db: &LarkDatabase) -> lark_error::WithError<std::sync::Arc<lark_
                 ..
             } => {
                 if id == main_name {
-                    let body = db.fn_body(entity);
-                    return body;
+                    return entity;
                 }
             }
             _ => {}
@@ -39,6 +38,10 @@ pub fn get_body(db: &LarkDatabase) -> lark_error::WithError<std::sync::Arc<lark_
     panic!("Internal error: Lost track of function bytecode")
 }
 
+pub fn get_body(db: &LarkDatabase) -> lark_error::WithError<std::sync::Arc<lark_hir::FnBody>> {
+    db.fn_body(get_main(db))
+}
+
 pub fn repl() {
     let mut virtual_fn: Vec<String> = vec![];
     let mut io_handler = lark_eval::IOHandler::new(false);
@@ -53,6 +56,7 @@ pub fn repl() {
 
     let mut eval_state = lark_eval::EvalState::new();
     eval_state.is_repl = true;
+    eval_state.fn_entities.push(get_main(&db));
 
     println!("Lark repl (:? - command help)");
     loop {
@@ -114,6 +118,11 @@ pub fn repl() {
 
             eval_state.skip_until = eval_state.current_expression;
 
+            if let Some(error) = eval_state.error.take() {
+                println!("error: {}", error);
+                continue;
+            }
+
             match output {
                 Value::Void => {}
                 x => println!("{}", x),
@@ -292,3 +292,34 @@ where
             .finish()
     }
 }
+
+/// Serialized as a plain sequence of the elements; the indices are
+/// implied by their positions.
+#[cfg(feature = "serde")]
+impl<I, T> serde::Serialize for IndexVec<I, T>
+where
+    I: U32Index,
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&self.vec, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, T> serde::Deserialize<'de> for IndexVec<I, T>
+where
+    I: U32Index,
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let vec: Vec<T> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(IndexVec::from(vec))
+    }
+}
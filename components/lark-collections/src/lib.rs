@@ -5,7 +5,6 @@
 
 use indexmap::IndexMap;
 use indexmap::IndexSet;
-use rustc_hash::FxHasher;
 use std::hash::BuildHasherDefault;
 
 mod indices;
@@ -14,6 +13,12 @@ mod test;
 
 pub use crate::indices::{IndexVec, U32Index};
 pub use crate::seq::Seq;
+pub use rustc_hash::FxHasher;
+
+// Used by the `index_type!` macro.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
 
 pub type FxIndexMap<K, V> = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
 pub type FxIndexSet<K> = IndexSet<K, BuildHasherDefault<FxHasher>>;
@@ -40,6 +45,8 @@ pub use indexmap::Equivalent;
 /// - `foo.as_usize()` -- extract the inner value as a `usize`
 /// - `Foo: From<usize>` -- you can also use the `From` trait to construct from a usize
 /// - `Foo: From<u32>` -- ...or a u32
+/// - `Foo: Serialize + Deserialize` -- serialized as the inner `u32` (only
+///   with the `serde` feature of this crate)
 ///
 /// Index types also implement the usual suspects (Copy, Clone, Debug, PartialOrd,
 /// Ord, PartialEq, Eq, Hash) so they can be used with maps and things.
@@ -225,6 +232,8 @@ macro_rules! index_type {
             }
         }
 
+        $crate::__index_type_serde_impl!($name);
+
         $crate::index_type! {
             @debug_impl {
                 name[$name],
@@ -258,3 +267,39 @@ macro_rules! index_type {
         }
     };
 }
+
+// The `serde` impls for `index_type!`. This is a separate macro so
+// that whether they are generated depends on the features of this
+// crate, rather than those of the crate invoking `index_type!`.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __index_type_serde_impl {
+    ($name:ident) => {
+        impl $crate::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                serializer.serialize_u32(self.as_u32())
+            }
+        }
+
+        impl<'de> $crate::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let index: u32 = $crate::serde::Deserialize::deserialize(deserializer)?;
+                Ok($name::from_u32(index))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __index_type_serde_impl {
+    ($name:ident) => {};
+}
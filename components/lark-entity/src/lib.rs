@@ -7,6 +7,8 @@ use lark_error::{ErrorReported, ErrorSentinel};
 use lark_intern::{Intern, Untern};
 use lark_span::FileName;
 use lark_string::{GlobalIdentifier, GlobalIdentifierTables};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 lark_collections::index_type! {
@@ -59,6 +61,7 @@ impl Entity {
 }
 
 #[derive(Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EntityData {
     /// Indicates that fetching the entity somehow failed with an
     /// error (which has been separately reported).
@@ -125,24 +128,55 @@ impl EntityData {
                 kind: ItemKind::Struct,
                 ..
             }
+            | EntityData::ItemName {
+                kind: ItemKind::Enum,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Trait,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Impl,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::TypeAlias,
+                ..
+            }
             | EntityData::LangItem(LangItem::Int)
             | EntityData::LangItem(LangItem::Tuple(_))
+            | EntityData::LangItem(LangItem::Range)
             | EntityData::LangItem(LangItem::String)
             | EntityData::LangItem(LangItem::Uint)
+            | EntityData::LangItem(LangItem::Float)
+            | EntityData::LangItem(LangItem::Char)
             | EntityData::LangItem(LangItem::Boolean) => false,
 
             EntityData::ItemName {
                 kind: ItemKind::Function,
                 ..
             }
+            | EntityData::ItemName {
+                kind: ItemKind::Const,
+                ..
+            }
             | EntityData::MemberName {
                 kind: MemberKind::Method,
                 ..
             }
+            | EntityData::MemberName {
+                kind: MemberKind::MethodSignature,
+                ..
+            }
             | EntityData::MemberName {
                 kind: MemberKind::Field,
                 ..
             }
+            | EntityData::MemberName {
+                kind: MemberKind::Variant,
+                ..
+            }
             | EntityData::LangItem(LangItem::True)
             | EntityData::LangItem(LangItem::False)
             | EntityData::LangItem(LangItem::Debug)
@@ -158,10 +192,34 @@ impl EntityData {
                 kind: ItemKind::Struct,
                 ..
             }
+            | EntityData::ItemName {
+                kind: ItemKind::Enum,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Trait,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Impl,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::TypeAlias,
+                ..
+            }
             | EntityData::MemberName {
                 kind: MemberKind::Field,
                 ..
             }
+            | EntityData::MemberName {
+                kind: MemberKind::Variant,
+                ..
+            }
+            | EntityData::MemberName {
+                kind: MemberKind::MethodSignature,
+                ..
+            }
             | EntityData::LangItem(_)
             | EntityData::Error(_) => false,
 
@@ -169,6 +227,10 @@ impl EntityData {
                 kind: ItemKind::Function,
                 ..
             }
+            | EntityData::ItemName {
+                kind: ItemKind::Const,
+                ..
+            }
             | EntityData::MemberName {
                 kind: MemberKind::Method,
                 ..
@@ -182,11 +244,18 @@ impl EntityData {
 /// Eventually, I would like these to be structs declared in some kind
 /// of libcore -- though I'm not sure how tuple would work there.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LangItem {
     Boolean,
     Int,
     Uint,
+    Float,
+    Char,
     Tuple(usize),
+
+    /// The type of `a..b` and friends; has one type parameter, the
+    /// type of the endpoints.
+    Range,
     String,
     True,
     False,
@@ -194,15 +263,65 @@ pub enum LangItem {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ItemKind {
     Struct,
+    Enum,
+    Trait,
+
+    /// An `impl Foo { .. }` block; named after the struct `Foo`.
+    Impl,
     Function,
+
+    /// A `const NAME: Ty = expr` item; its initializer is stored as
+    /// a fn body with no arguments.
+    Const,
+
+    /// A `type Name = Ty` item; references to `Name` are replaced by
+    /// `Ty` when types are converted.
+    TypeAlias,
+}
+
+impl ItemKind {
+    /// The keyword that introduces an item of this kind.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            ItemKind::Struct => "struct",
+            ItemKind::Enum => "enum",
+            ItemKind::Trait => "trait",
+            ItemKind::Impl => "impl",
+            ItemKind::Function => "def",
+            ItemKind::Const => "const",
+            ItemKind::TypeAlias => "type",
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MemberKind {
     Field,
     Method,
+
+    /// A variant of an enum, like `A` or `B(int)`.
+    Variant,
+
+    /// A method declared (without a body) in a trait.
+    MethodSignature,
+}
+
+/// Whether an entity may be referenced from outside of its
+/// enclosing scope. Entities are private unless declared with `pub`.
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    Public,
+    Private,
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Visibility::Private
+    }
 }
 
 lark_intern::intern_tables! {
@@ -226,7 +345,8 @@ where
 }
 
 impl Entity {
-    /// The input file in which an entity appears (if any).
+    /// The input file in which an entity appears (if any), found by
+    /// walking up its parents. Lang items do not appear in any file.
     pub fn input_file(self, db: &dyn AsRef<EntityTables>) -> Option<FileName> {
         match self.untern(db) {
             EntityData::LangItem(_) => None,
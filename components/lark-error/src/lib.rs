@@ -31,12 +31,15 @@
 use lark_collections::{seq, Seq};
 use lark_debug_derive::DebugWith;
 use lark_span::{FileName, Span};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Unit type used in `Result` to indicate a value derived from other
 /// value where an error was already reported. The span is "some span"
 /// from one of the errors.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ErrorReported(pub Span<FileName>);
 
 impl ErrorReported {
@@ -65,14 +68,39 @@ impl ErrorReported {
 pub struct Diagnostic {
     pub span: Span<FileName>,
     pub label: String,
+    pub severity: Severity,
 }
 
 impl Diagnostic {
+    /// Creates a diagnostic with `Severity::Error`.
     pub fn new(label: String, span: Span<FileName>) -> Self {
-        Diagnostic { label, span }
+        Diagnostic {
+            label,
+            span,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Creates a diagnostic with `Severity::Warning`.
+    pub fn warning(label: String, span: Span<FileName>) -> Self {
+        Diagnostic {
+            label,
+            span,
+            severity: Severity::Warning,
+        }
     }
 }
 
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Severity {
+    /// The program is wrong.
+    Error,
+
+    /// Something suspicious, but not wrong.
+    Warning,
+}
+
 /// Used to indicate an operation that may report an error.  Note that
 /// there is a subtle -- but important! -- difference between
 /// `ErrorReported` and this type -- returning `Err(ErrorReported)`
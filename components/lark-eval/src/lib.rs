@@ -4,6 +4,10 @@ use lark_hir as hir;
 use lark_intern::{Intern, Untern};
 use lark_parser::{ParserDatabase, ParserDatabaseExt};
 use lark_query_system::LarkDatabase;
+use lark_string::GlobalIdentifier;
+use lark_ty::BaseKind;
+use lark_type_check::TypeCheckDatabase;
+use std::cmp::Ordering::{self, Equal, Greater, Less};
 use std::collections::HashMap;
 use std::fmt;
 
@@ -12,6 +16,22 @@ pub struct EvalState {
     pub skip_until: Option<hir::Expression>,
     pub current_expression: Option<hir::Expression>,
     pub is_repl: bool,
+
+    // Set by `break` to the value it carries; the enclosing loop
+    // takes it back out again once evaluation unwinds to it.
+    pub breaking: Option<Value>,
+
+    // Set by `return` to the value it carries; taken back out by the
+    // function call once evaluation unwinds to it.
+    pub returning: Option<Value>,
+
+    // Set by a runtime error (see `report_error`); evaluation unwinds
+    // all the way out, and `eval` prints it.
+    pub error: Option<String>,
+
+    // The fns (or consts) whose bodies are being evaluated, innermost
+    // last; integer literals take the type inferred for them there.
+    pub fn_entities: Vec<Entity>,
 }
 
 impl EvalState {
@@ -36,6 +56,10 @@ impl EvalState {
             skip_until: None,
             current_expression: None,
             is_repl: false,
+            breaking: None,
+            returning: None,
+            error: None,
+            fn_entities: vec![],
         }
     }
 
@@ -54,6 +78,22 @@ impl EvalState {
     pub fn ready_to_execute(&self) -> bool {
         self.skip_until.is_none()
     }
+
+    /// True if a `break`, `return` or runtime error is in progress,
+    /// in which case the remaining expressions in a sequence must not
+    /// be evaluated.
+    pub fn unwinding(&self) -> bool {
+        self.breaking.is_some() || self.returning.is_some() || self.error.is_some()
+    }
+
+    /// Records a runtime error, unless one is already being reported,
+    /// and returns a placeholder value to unwind with.
+    pub fn report_error(&mut self, message: impl Into<String>) -> Value {
+        if self.error.is_none() {
+            self.error = Some(message.into());
+        }
+        Value::Void
+    }
 }
 
 pub struct IOHandler {
@@ -85,9 +125,17 @@ impl IOHandler {
 pub enum Value {
     Void,
     Bool(bool),
-    U32(u32),
+    U32(u32), // a `uint`
+    I32(i32), // an `int`
+    F64(f64),
+    Char(char),
     Str(String),
     Struct(Entity, HashMap<lark_string::GlobalIdentifier, Value>),
+    Tuple(Vec<Value>),
+
+    // `start..end`, or `start..=end` if inclusive
+    Range(Option<i64>, Option<i64>, bool),
+
     Reference(usize), // a reference into the value stack
 
     // REPL: placeholder value to denote we're currently skipping eval
@@ -101,11 +149,24 @@ impl fmt::Display for Value {
             "{}",
             match self {
                 Value::U32(u) => u.to_string(),
+                Value::I32(i) => i.to_string(),
+                Value::F64(f) => f.to_string(),
+                Value::Char(c) => c.to_string(),
                 Value::Str(s) => s.clone(),
                 Value::Bool(b) => b.to_string(),
+                Value::Range(start, end, inclusive) => format!(
+                    "{}{}{}",
+                    start.map(|s| s.to_string()).unwrap_or_default(),
+                    if *inclusive { "..=" } else { ".." },
+                    end.map(|e| e.to_string()).unwrap_or_default(),
+                ),
                 Value::Reference(r) => format!("reference to {}", r),
                 Value::Void => "<void>".into(),
                 Value::Struct(_, s) => format!("{:?}", s),
+                Value::Tuple(elements) => {
+                    let elements: Vec<_> = elements.iter().map(|e| e.to_string()).collect();
+                    format!("({})", elements.join(", "))
+                }
                 Value::Skipped => "<repl placeholder>".into(),
             }
         )
@@ -124,6 +185,22 @@ pub fn eval_place(
         hir::PlaceData::Entity(entity) => match entity.untern(db) {
             EntityData::LangItem(LangItem::True) => Value::Bool(true),
             EntityData::LangItem(LangItem::False) => Value::Bool(false),
+            EntityData::ItemName {
+                kind: ItemKind::Const,
+                ..
+            } => {
+                // The initializer has no access to the caller's
+                // variables, so evaluate it in a fresh state.
+                let initializer = db.fn_body(*entity).value;
+                let mut const_state = EvalState::new();
+                const_state.fn_entities.push(*entity);
+                let mut io_handler = IOHandler::new(true);
+                let value = eval_function(db, &initializer, &mut const_state, &mut io_handler);
+                match const_state.error {
+                    Some(error) => state.report_error(error),
+                    None => value,
+                }
+            }
             _ => unimplemented!("EntityData not yet support in eval"),
         },
         hir::PlaceData::Variable(variable) => {
@@ -139,10 +216,58 @@ pub fn eval_place(
                 _ => panic!("Member access (.) into value that is not a struct"),
             }
         }
+        hir::PlaceData::TupleField { owner, index, .. } => {
+            let target = eval_place(db, fn_body, *owner, state);
+            match target {
+                Value::Tuple(mut elements) => elements.swap_remove(*index),
+                _ => panic!("Tuple index (.N) into value that is not a tuple"),
+            }
+        }
         hir::PlaceData::Temporary { .. } => unimplemented!("Can't yet eval temporary places"),
     }
 }
 
+/// Stores `value` into `place`; for a field, this means updating the
+/// value that owns it.
+fn assign_to_place(
+    db: &LarkDatabase,
+    fn_body: &hir::FnBody,
+    place: hir::Place,
+    value: Value,
+    state: &mut EvalState,
+) {
+    match fn_body.tables[place] {
+        hir::PlaceData::Variable(variable) => state.assign_to_variable(variable, value),
+        hir::PlaceData::Field { owner, name } => {
+            let mut target = eval_place(db, fn_body, owner, state);
+            match &mut target {
+                Value::Struct(_, s) => {
+                    s.insert(fn_body.tables[name].text, value);
+                }
+                _ => {
+                    state.report_error("Member access (.) into value that is not a struct");
+                    return;
+                }
+            }
+            assign_to_place(db, fn_body, owner, target, state);
+        }
+        hir::PlaceData::TupleField { owner, index, .. } => {
+            let mut target = eval_place(db, fn_body, owner, state);
+            match &mut target {
+                Value::Tuple(elements) => elements[index] = value,
+                _ => {
+                    state.report_error("Tuple index (.N) into value that is not a tuple");
+                    return;
+                }
+            }
+            assign_to_place(db, fn_body, owner, target, state);
+        }
+        hir::PlaceData::Entity(_) | hir::PlaceData::Temporary { .. } => {
+            state.report_error("Cannot assign to this place");
+        }
+    }
+}
+
 fn eval_fn_call(
     db: &LarkDatabase,
     fn_body: &hir::FnBody,
@@ -164,7 +289,10 @@ fn eval_fn_call(
     }
 
     let return_value = if ready_to_execute {
-        eval_function(db, &target, state, io_handler)
+        state.fn_entities.push(entity);
+        let value = eval_function(db, &target, state, io_handler);
+        state.fn_entities.pop();
+        value
     } else {
         Value::Skipped
     };
@@ -188,11 +316,17 @@ pub fn eval_expression(
         _ => state.set_current_expression(expression),
     }
 
+    // After a runtime error, nothing more is evaluated.
+    if state.error.is_some() {
+        return Value::Void;
+    }
+
     let ready_to_execute = state.ready_to_execute();
 
     match fn_body.tables[expression] {
         hir::ExpressionData::Let {
             variable,
+            ty: _,
             initializer,
             body,
         } => {
@@ -208,7 +342,11 @@ pub fn eval_expression(
                 }
             }
 
-            let body_result = eval_expression(db, fn_body, body, state, io_handler);
+            let body_result = if state.unwinding() {
+                Value::Void
+            } else {
+                eval_expression(db, fn_body, body, state, io_handler)
+            };
 
             if !state.is_repl {
                 state.pop_variable(variable);
@@ -217,7 +355,9 @@ pub fn eval_expression(
             body_result
         }
 
-        hir::ExpressionData::Place { place } => {
+        // References are not yet modeled at runtime; we just read the
+        // value out of the place.
+        hir::ExpressionData::Place { place } | hir::ExpressionData::Reference { place, .. } => {
             if ready_to_execute {
                 eval_place(db, fn_body, place, state)
             } else {
@@ -227,13 +367,22 @@ pub fn eval_expression(
 
         hir::ExpressionData::Assignment { place, value } => {
             let rhs = eval_expression(db, fn_body, value, state, io_handler);
-            match &fn_body.tables[place] {
-                hir::PlaceData::Variable(variable) => {
-                    if ready_to_execute {
-                        state.assign_to_variable(*variable, rhs);
-                    }
-                }
-                _ => unimplemented!("PlaceData not yet supported in eval"),
+            if ready_to_execute && !state.unwinding() {
+                assign_to_place(db, fn_body, place, rhs, state);
+            }
+            Value::Void
+        }
+
+        hir::ExpressionData::CompoundAssignment {
+            operator,
+            place,
+            value,
+        } => {
+            let rhs = eval_expression(db, fn_body, value, state, io_handler);
+            if ready_to_execute && !state.unwinding() {
+                let lhs = eval_place(db, fn_body, place, state);
+                let result = eval_binary(operator, lhs, rhs, state);
+                assign_to_place(db, fn_body, place, result, state);
             }
             Value::Void
         }
@@ -339,7 +488,7 @@ pub fn eval_expression(
                         for argument in arguments.iter(fn_body) {
                             let result = eval_expression(db, fn_body, argument, state, io_handler);
 
-                            if ready_to_execute {
+                            if ready_to_execute && !state.unwinding() {
                                 io_handler.println(format!("{}", result));
                             }
                         }
@@ -367,9 +516,37 @@ pub fn eval_expression(
 
         hir::ExpressionData::Sequence { first, second } => {
             eval_expression(db, fn_body, first, state, io_handler);
+
+            if state.unwinding() {
+                return Value::Void;
+            }
+
             eval_expression(db, fn_body, second, state, io_handler)
         }
 
+        // `&&` and `||` only evaluate their right-hand side if needed.
+        hir::ExpressionData::Binary {
+            operator: operator @ hir::BinaryOperator::And,
+            left,
+            right,
+        }
+        | hir::ExpressionData::Binary {
+            operator: operator @ hir::BinaryOperator::Or,
+            left,
+            right,
+        } => {
+            let lhs_eval = eval_expression(db, fn_body, left, state, io_handler);
+
+            match (operator, lhs_eval) {
+                (hir::BinaryOperator::And, Value::Bool(false)) => Value::Bool(false),
+                (hir::BinaryOperator::Or, Value::Bool(true)) => Value::Bool(true),
+                (_, Value::Bool(_)) | (_, Value::Skipped) => {
+                    eval_expression(db, fn_body, right, state, io_handler)
+                }
+                _ => state.report_error("Logical operator applied to non-boolean value"),
+            }
+        }
+
         hir::ExpressionData::Binary {
             operator,
             left,
@@ -379,17 +556,7 @@ pub fn eval_expression(
             let rhs_eval = eval_expression(db, fn_body, right, state, io_handler);
 
             if ready_to_execute {
-                match operator {
-                    hir::BinaryOperator::Add => match (lhs_eval, rhs_eval) {
-                        (Value::U32(l), Value::U32(r)) => Value::U32(l + r),
-                        _ => panic!("Addition of non-numeric values"),
-                    },
-                    hir::BinaryOperator::Subtract => match (lhs_eval, rhs_eval) {
-                        (Value::U32(l), Value::U32(r)) => Value::U32(l - r),
-                        _ => panic!("Subtraction of non-numeric values"),
-                    },
-                    _ => unimplemented!("Operator not yet supported"),
-                }
+                eval_binary(operator, lhs_eval, rhs_eval, state)
             } else {
                 Value::Skipped
             }
@@ -401,9 +568,41 @@ pub fn eval_expression(
                 value,
             } => {
                 if ready_to_execute {
+                    // The type checker rejects literals that are out
+                    // of range, but we may be run in spite of errors.
                     let string = value.untern(db);
-                    let value: u32 = string.parse().unwrap();
-                    Value::U32(value)
+                    let value = if is_uint(db, state, expression) {
+                        string.parse().map(Value::U32)
+                    } else {
+                        string.parse().map(Value::I32)
+                    };
+                    match value {
+                        Ok(value) => value,
+                        Err(_) => state
+                            .report_error(format!("integer literal `{}` is out of range", string)),
+                    }
+                } else {
+                    Value::Skipped
+                }
+            }
+            hir::LiteralData {
+                kind: hir::LiteralKind::Float,
+                value,
+            } => {
+                if ready_to_execute {
+                    let string = value.untern(db);
+                    let digits: String = string.chars().filter(|&c| c != '_').collect();
+                    Value::F64(digits.parse().unwrap())
+                } else {
+                    Value::Skipped
+                }
+            }
+            hir::LiteralData {
+                kind: hir::LiteralKind::Char,
+                value,
+            } => {
+                if ready_to_execute {
+                    Value::Char(value.untern(db).chars().next().unwrap())
                 } else {
                     Value::Skipped
                 }
@@ -414,9 +613,17 @@ pub fn eval_expression(
             } => {
                 if ready_to_execute {
                     let text = value.untern(db);
-                    let string = text.to_string();
-                    let string = string[1..string.len()-1].to_string();
-                    Value::Str(string)
+                    Value::Str(text.to_string())
+                } else {
+                    Value::Skipped
+                }
+            }
+            hir::LiteralData {
+                kind: hir::LiteralKind::Boolean,
+                value,
+            } => {
+                if ready_to_execute {
+                    Value::Bool(&*value.untern(db) == "true")
                 } else {
                     Value::Skipped
                 }
@@ -443,6 +650,43 @@ pub fn eval_expression(
             }
         }
 
+        hir::ExpressionData::Tuple { elements } => {
+            let elements: Vec<_> = elements
+                .iter(fn_body)
+                .map(|element| eval_expression(db, fn_body, element, state, io_handler))
+                .collect();
+
+            if ready_to_execute {
+                Value::Tuple(elements)
+            } else {
+                Value::Skipped
+            }
+        }
+
+        hir::ExpressionData::Range {
+            start,
+            end,
+            inclusive,
+        } => {
+            let start = start.map(|start| eval_expression(db, fn_body, start, state, io_handler));
+            let end = end.map(|end| eval_expression(db, fn_body, end, state, io_handler));
+
+            if !ready_to_execute {
+                return Value::Skipped;
+            }
+
+            let bound = |value: Option<Value>| match value {
+                None => Ok(None),
+                Some(Value::U32(u)) => Ok(Some(i64::from(u))),
+                Some(Value::I32(i)) => Ok(Some(i64::from(i))),
+                Some(_) => Err(()),
+            };
+            match (bound(start), bound(end)) {
+                (Ok(start), Ok(end)) => Value::Range(start, end, inclusive),
+                _ => state.report_error("Range bounds must be integers"),
+            }
+        }
+
         hir::ExpressionData::Unit {} => Value::Void,
 
         hir::ExpressionData::If {
@@ -466,7 +710,214 @@ pub fn eval_expression(
 
                     result
                 }
-                _ => panic!("Unsupported conditional in 'if'"),
+                _ => state.report_error("Unsupported conditional in 'if'"),
+            }
+        }
+
+        hir::ExpressionData::While { condition, body } => {
+            loop {
+                match eval_expression(db, fn_body, condition, state, io_handler) {
+                    Value::Bool(true) => {
+                        eval_expression(db, fn_body, body, state, io_handler);
+
+                        if state.breaking.take().is_some() || state.unwinding() {
+                            break;
+                        }
+                    }
+                    Value::Bool(false) => break,
+                    Value::Skipped => {
+                        // As with `if`, we look in the body for where
+                        // to continue; if it is not there, the loop
+                        // is skipped entirely.
+                        eval_expression(db, fn_body, body, state, io_handler);
+
+                        if !state.ready_to_execute() {
+                            break;
+                        }
+                    }
+                    _ => {
+                        state.report_error("Unsupported conditional in 'while'");
+                        break;
+                    }
+                }
+            }
+
+            Value::Void
+        }
+
+        hir::ExpressionData::For {
+            variable,
+            start,
+            end,
+            body,
+        } => {
+            let start = eval_expression(db, fn_body, start, state, io_handler);
+            let end = eval_expression(db, fn_body, end, state, io_handler);
+
+            let values: Option<Box<dyn Iterator<Item = Value>>> = match (&start, &end) {
+                (&Value::U32(start), &Value::U32(end)) => {
+                    Some(Box::new((start..end).map(Value::U32)))
+                }
+                (&Value::I32(start), &Value::I32(end)) => {
+                    Some(Box::new((start..end).map(Value::I32)))
+                }
+                _ => None,
+            };
+
+            match (values, start, end) {
+                (Some(values), _, _) => {
+                    state.create_variable(variable);
+
+                    for value in values {
+                        state.assign_to_variable(variable, value);
+                        eval_expression(db, fn_body, body, state, io_handler);
+
+                        if state.breaking.take().is_some() || state.unwinding() {
+                            break;
+                        }
+                    }
+
+                    state.pop_variable(variable);
+                }
+                (None, Value::Skipped, _) | (None, _, Value::Skipped) => {
+                    // As with `while`, look in the body for where to
+                    // continue.
+                    eval_expression(db, fn_body, body, state, io_handler);
+                }
+                _ => {
+                    state.report_error("Unsupported range in 'for'");
+                }
+            }
+
+            Value::Void
+        }
+
+        hir::ExpressionData::Match { scrutinee, arms } => {
+            let value = eval_expression(db, fn_body, scrutinee, state, io_handler);
+
+            if let Value::Skipped = value {
+                // As with `if`, we look in each arm for where to
+                // continue.
+                let mut result = Value::Skipped;
+                for arm in arms.iter(fn_body) {
+                    result = eval_expression(db, fn_body, fn_body[arm].body, state, io_handler);
+
+                    if state.ready_to_execute() {
+                        break;
+                    }
+                }
+                return result;
+            }
+
+            for arm in arms.iter(fn_body) {
+                let hir::MatchArmData { pattern, body } = fn_body.tables[arm];
+
+                let mut bindings = vec![];
+                let matched = match_pattern(
+                    db,
+                    fn_body,
+                    pattern,
+                    &value,
+                    &mut bindings,
+                    state,
+                    io_handler,
+                );
+                if !matched {
+                    continue;
+                }
+
+                for (variable, value) in bindings.iter().cloned() {
+                    state.create_variable(variable);
+                    state.assign_to_variable(variable, value);
+                }
+
+                let result = eval_expression(db, fn_body, body, state, io_handler);
+
+                if !state.is_repl {
+                    for &(variable, _) in &bindings {
+                        state.pop_variable(variable);
+                    }
+                }
+
+                return result;
+            }
+
+            state.report_error("No arm of 'match' matched")
+        }
+
+        hir::ExpressionData::Loop { body } => loop {
+            eval_expression(db, fn_body, body, state, io_handler);
+
+            if let Some(value) = state.breaking.take() {
+                break value;
+            }
+
+            if state.unwinding() {
+                break Value::Void;
+            }
+
+            // As with `while`, if we are skipping ahead (during REPL)
+            // and did not find where to continue in the body, skip
+            // the loop entirely.
+            if !state.ready_to_execute() {
+                break Value::Skipped;
+            }
+        },
+
+        hir::ExpressionData::Break { value } => {
+            let value = eval_expression(db, fn_body, value, state, io_handler);
+
+            if ready_to_execute {
+                state.breaking = Some(value);
+            }
+
+            Value::Void
+        }
+
+        hir::ExpressionData::Return { value } => {
+            let value = eval_expression(db, fn_body, value, state, io_handler);
+
+            if ready_to_execute {
+                state.returning = Some(value);
+            }
+
+            Value::Void
+        }
+
+        // `-2147483648` is an `int` though `2147483648` is not, so a
+        // negated integer literal is read along with its sign.
+        hir::ExpressionData::Unary {
+            operator: hir::UnaryOperator::Negate,
+            value,
+        } if integer_literal_text(fn_body, value).is_some() => {
+            if ready_to_execute {
+                let text = integer_literal_text(fn_body, value).unwrap();
+                let string = format!("-{}", text.untern(db));
+                match string.parse() {
+                    Ok(value) => Value::I32(value),
+                    Err(_) => {
+                        state.report_error(format!("integer literal `{}` is out of range", string))
+                    }
+                }
+            } else {
+                Value::Skipped
+            }
+        }
+
+        hir::ExpressionData::Unary { operator, value } => {
+            let value = eval_expression(db, fn_body, value, state, io_handler);
+
+            if ready_to_execute {
+                match (operator, value) {
+                    (hir::UnaryOperator::Not, Value::Bool(b)) => Value::Bool(!b),
+                    (hir::UnaryOperator::Negate, Value::I32(i)) => {
+                        checked_int(i.checked_neg().map(Value::I32), state)
+                    }
+                    (hir::UnaryOperator::Negate, Value::F64(f)) => Value::F64(-f),
+                    _ => state.report_error("Unary operator applied to unsupported value"),
+                }
+            } else {
+                Value::Skipped
             }
         }
 
@@ -477,13 +928,195 @@ pub fn eval_expression(
     }
 }
 
+/// Tests whether `value` matches `pattern`, pushing the values of
+/// any variables that the pattern binds onto `bindings`.
+fn match_pattern(
+    db: &LarkDatabase,
+    fn_body: &hir::FnBody,
+    pattern: hir::Pattern,
+    value: &Value,
+    bindings: &mut Vec<(hir::Variable, Value)>,
+    state: &mut EvalState,
+    io_handler: &mut IOHandler,
+) -> bool {
+    match fn_body.tables[pattern] {
+        hir::PatternData::Wildcard => true,
+
+        hir::PatternData::Binding(variable) => {
+            bindings.push((variable, value.clone()));
+            true
+        }
+
+        hir::PatternData::Literal(literal) => {
+            let literal_value = eval_expression(db, fn_body, literal, state, io_handler);
+            compare(value, &literal_value) == Some(Equal)
+        }
+
+        hir::PatternData::Struct { entity, fields } => match value {
+            Value::Struct(value_entity, field_values) if *value_entity == entity => {
+                for field in fields.iter(fn_body) {
+                    let hir::IdentifiedPatternData {
+                        identifier,
+                        pattern,
+                    } = fn_body.tables[field];
+                    let field_value = &field_values[&fn_body.tables[identifier].text];
+                    let matched = match_pattern(
+                        db,
+                        fn_body,
+                        pattern,
+                        field_value,
+                        bindings,
+                        state,
+                        io_handler,
+                    );
+
+                    if !matched {
+                        return false;
+                    }
+                }
+
+                true
+            }
+
+            _ => false,
+        },
+    }
+}
+
+/// Applies a (non-short-circuiting) binary operator to two values.
+fn eval_binary(
+    operator: hir::BinaryOperator,
+    lhs_eval: Value,
+    rhs_eval: Value,
+    state: &mut EvalState,
+) -> Value {
+    match operator {
+        hir::BinaryOperator::Add => match (lhs_eval, rhs_eval) {
+            (Value::U32(l), Value::U32(r)) => checked_int(l.checked_add(r).map(Value::U32), state),
+            (Value::I32(l), Value::I32(r)) => checked_int(l.checked_add(r).map(Value::I32), state),
+            (Value::F64(l), Value::F64(r)) => Value::F64(l + r),
+            _ => state.report_error("Addition of non-numeric values"),
+        },
+        hir::BinaryOperator::Subtract => match (lhs_eval, rhs_eval) {
+            (Value::U32(l), Value::U32(r)) => checked_int(l.checked_sub(r).map(Value::U32), state),
+            (Value::I32(l), Value::I32(r)) => checked_int(l.checked_sub(r).map(Value::I32), state),
+            (Value::F64(l), Value::F64(r)) => Value::F64(l - r),
+            _ => state.report_error("Subtraction of non-numeric values"),
+        },
+        hir::BinaryOperator::Multiply => match (lhs_eval, rhs_eval) {
+            (Value::U32(l), Value::U32(r)) => checked_int(l.checked_mul(r).map(Value::U32), state),
+            (Value::I32(l), Value::I32(r)) => checked_int(l.checked_mul(r).map(Value::I32), state),
+            (Value::F64(l), Value::F64(r)) => Value::F64(l * r),
+            _ => state.report_error("Multiplication of non-numeric values"),
+        },
+        hir::BinaryOperator::Divide => match (lhs_eval, rhs_eval) {
+            (Value::U32(_), Value::U32(0)) | (Value::I32(_), Value::I32(0)) => {
+                state.report_error("Division by zero")
+            }
+            (Value::U32(l), Value::U32(r)) => checked_int(l.checked_div(r).map(Value::U32), state),
+            (Value::I32(l), Value::I32(r)) => checked_int(l.checked_div(r).map(Value::I32), state),
+            (Value::F64(l), Value::F64(r)) => Value::F64(l / r),
+            _ => state.report_error("Division of non-numeric values"),
+        },
+        hir::BinaryOperator::Remainder => match (lhs_eval, rhs_eval) {
+            (Value::U32(_), Value::U32(0)) | (Value::I32(_), Value::I32(0)) => {
+                state.report_error("Remainder by zero")
+            }
+            (Value::U32(l), Value::U32(r)) => checked_int(l.checked_rem(r).map(Value::U32), state),
+            (Value::I32(l), Value::I32(r)) => checked_int(l.checked_rem(r).map(Value::I32), state),
+            (Value::F64(l), Value::F64(r)) => Value::F64(l % r),
+            _ => state.report_error("Remainder of non-numeric values"),
+        },
+        hir::BinaryOperator::Equals
+        | hir::BinaryOperator::NotEquals
+        | hir::BinaryOperator::LessThan
+        | hir::BinaryOperator::LessThanOrEqual
+        | hir::BinaryOperator::GreaterThan
+        | hir::BinaryOperator::GreaterThanOrEqual => {
+            let ordering = match compare(&lhs_eval, &rhs_eval) {
+                Some(ordering) => ordering,
+                None => return state.report_error("Comparison of unsupported values"),
+            };
+
+            Value::Bool(match operator {
+                hir::BinaryOperator::Equals => ordering == Equal,
+                hir::BinaryOperator::NotEquals => ordering != Equal,
+                hir::BinaryOperator::LessThan => ordering == Less,
+                hir::BinaryOperator::LessThanOrEqual => ordering != Greater,
+                hir::BinaryOperator::GreaterThan => ordering == Greater,
+                _ => ordering != Less,
+            })
+        }
+        hir::BinaryOperator::And | hir::BinaryOperator::Or => unreachable!(),
+    }
+}
+
+/// The result of integer arithmetic, or an error if it overflowed.
+/// As in the compiled code, `uint` and `int` are 32 bits wide.
+fn checked_int(result: Option<Value>, state: &mut EvalState) -> Value {
+    match result {
+        Some(value) => value,
+        None => state.report_error("Integer overflow"),
+    }
+}
+
+/// True if the type checker inferred the integer `expression` (in the
+/// innermost body being evaluated) to be a `uint`; otherwise, it is
+/// an `int`.
+/// The text of `expression`, if it is an integer literal.
+fn integer_literal_text(
+    fn_body: &hir::FnBody,
+    expression: hir::Expression,
+) -> Option<GlobalIdentifier> {
+    match fn_body.tables[expression] {
+        hir::ExpressionData::Literal {
+            data:
+                hir::LiteralData {
+                    kind: hir::LiteralKind::UnsignedInteger,
+                    value,
+                },
+        } => Some(value),
+        _ => None,
+    }
+}
+
+fn is_uint(db: &LarkDatabase, state: &EvalState, expression: hir::Expression) -> bool {
+    let fn_entity = match state.fn_entities.last() {
+        Some(&fn_entity) => fn_entity,
+        None => return false,
+    };
+
+    match db.ty_of_expression(fn_entity, expression) {
+        Some(ty) => match ty.base.untern(db).kind {
+            BaseKind::Named(entity) => entity == EntityData::LangItem(LangItem::Uint).intern(db),
+            BaseKind::Placeholder(_) | BaseKind::Error => false,
+        },
+        None => false,
+    }
+}
+
+/// Compares two primitive values of the same type, as needed by the
+/// comparison operators.
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::U32(l), Value::U32(r)) => Some(l.cmp(r)),
+        (Value::I32(l), Value::I32(r)) => Some(l.cmp(r)),
+        (Value::F64(l), Value::F64(r)) => l.partial_cmp(r),
+        (Value::Char(l), Value::Char(r)) => Some(l.cmp(r)),
+        (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+        (Value::Str(l), Value::Str(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
 pub fn eval_function(
     db: &LarkDatabase,
     fn_body: &hir::FnBody,
     state: &mut EvalState,
     io_handler: &mut IOHandler,
 ) -> Value {
-    eval_expression(db, fn_body, fn_body.root_expression, state, io_handler)
+    let result = eval_expression(db, fn_body, fn_body.root_expression, state, io_handler);
+    state.returning.take().unwrap_or(result)
 }
 
 pub fn eval(db: &LarkDatabase, io_handler: &mut IOHandler) {
@@ -506,7 +1139,13 @@ pub fn eval(db: &LarkDatabase, io_handler: &mut IOHandler) {
                     if id == main_name {
                         let fn_body = db.fn_body(entity);
 
+                        eval_state.fn_entities.push(entity);
                         eval_function(db, &fn_body.value, &mut eval_state, io_handler);
+                        eval_state.fn_entities.pop();
+
+                        if let Some(error) = eval_state.error.take() {
+                            io_handler.println(format!("error: {}", error));
+                        }
                     }
                 }
                 _ => {}
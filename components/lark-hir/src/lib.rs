@@ -15,8 +15,18 @@ use lark_error::ErrorReported;
 use lark_error::ErrorSentinel;
 use lark_span::{FileName, Span};
 use lark_string::GlobalIdentifier;
+use lark_ty::declaration::Declaration;
+use lark_ty::PermKind;
+use lark_ty::Ty;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "serde")]
+pub use self::serialize::SerializedFnBody;
+
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
 pub struct Member {
     pub name: GlobalIdentifier,
@@ -25,6 +35,7 @@ pub struct Member {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FnBody {
     /// List of arguments to the function. The type of each argument
     /// is given by the function signature (which can be separately queried).
@@ -68,6 +79,7 @@ where
 
 /// All the data for a fn-body is stored in these tables.a
 #[derive(Clone, Debug, DebugWith, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FnBodyTables {
     /// Map each expression index to its associated data.
     pub expressions: IndexVec<Expression, ExpressionData>,
@@ -78,6 +90,15 @@ pub struct FnBodyTables {
     /// Map each place index to its associated data.
     pub places: IndexVec<Place, PlaceData>,
 
+    /// Map each match arm index to its associated data.
+    pub match_arms: IndexVec<MatchArm, MatchArmData>,
+
+    /// Map each pattern index to its associated data.
+    pub patterns: IndexVec<Pattern, PatternData>,
+
+    /// A `a: P` pair within a struct pattern.
+    pub identified_patterns: IndexVec<IdentifiedPattern, IdentifiedPatternData>,
+
     /// Map each variable index to its associated data.
     pub variables: IndexVec<Variable, VariableData>,
 
@@ -88,6 +109,7 @@ pub struct FnBodyTables {
     pub errors: IndexVec<Error, ErrorData>,
 
     /// Spans corresponding to each index
+    #[cfg_attr(feature = "serde", serde(with = "serialize::spans"))]
     pub spans: FxIndexMap<MetaIndex, Span<FileName>>,
 
     /// The data values for any `List<I>` values that appear elsewhere
@@ -186,6 +208,11 @@ impl FnBody {
     pub fn span(&self, index: impl SpanIndex) -> Span<FileName> {
         index.span_from(&self.tables)
     }
+
+    /// Get the span for the given part of the HIR, if it has one.
+    pub fn opt_span(&self, index: impl Into<MetaIndex>) -> Option<Span<FileName>> {
+        self.tables.spans.get(&index.into()).cloned()
+    }
 }
 
 impl FnBodyTables {
@@ -276,6 +303,7 @@ macro_rules! define_meta_index {
         /// reach into it. This enum brings them together into
         /// a sort of "meta index". It's useful sometimes.
         #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         pub enum MetaIndex {
             $(
                 $index_ty($index_ty),
@@ -301,10 +329,14 @@ define_meta_index! {
     (Variable, VariableData, variables),
     (Identifier, IdentifierData, identifiers),
     (Error, ErrorData, errors),
+    (MatchArm, MatchArmData, match_arms),
+    (Pattern, PatternData, patterns),
+    (IdentifiedPattern, IdentifiedPatternData, identified_patterns),
 }
 
 /// A list of "HIR indices" of type `I`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct List<I: HirIndex> {
     start_index: u32,
     len: u32,
@@ -424,10 +456,12 @@ lark_collections::index_type! {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExpressionData {
-    /// `let <var> = <initializer> in <body>`
+    /// `let <var>: <ty> = <initializer> in <body>`
     Let {
         variable: Variable,
+        ty: Option<Ty<Declaration>>,
         initializer: Option<Expression>,
         body: Expression,
     },
@@ -438,6 +472,13 @@ pub enum ExpressionData {
     /// `<place> = <value>`
     Assignment { place: Place, value: Expression },
 
+    /// `<place> (op)= <value>`, e.g. `x += 1`
+    CompoundAssignment {
+        operator: BinaryOperator,
+        place: Place,
+        value: Expression,
+    },
+
     /// `<arg0>.method(<arg1..>)`
     MethodCall {
         method: Identifier,
@@ -463,6 +504,37 @@ pub enum ExpressionData {
         if_false: Expression,
     },
 
+    /// while E1 { E2 }
+    While {
+        condition: Expression,
+        body: Expression,
+    },
+
+    /// for X in E1..E2 { E3 }
+    For {
+        variable: Variable,
+        start: Expression,
+        end: Expression,
+        body: Expression,
+    },
+
+    /// match E { P1 => E1, ..., PN => EN }
+    Match {
+        scrutinee: Expression,
+        arms: List<MatchArm>,
+    },
+
+    /// loop { E }
+    Loop { body: Expression },
+
+    /// `break E` -- exits the innermost enclosing loop. If no value
+    /// is given, `E` is an (elided) unit expression.
+    Break { value: Expression },
+
+    /// `return E` -- exits the enclosing function. If no value is
+    /// given, `E` is an (elided) unit expression.
+    Return { value: Expression },
+
     /// E1 (op) E2
     Binary {
         operator: BinaryOperator,
@@ -476,6 +548,18 @@ pub enum ExpressionData {
         value: Expression,
     },
 
+    /// `E1..E2`, or `E1..=E2` if `inclusive` is true. Either endpoint
+    /// may be omitted, as in `E1..`, `..E2` or `..`.
+    Range {
+        start: Option<Expression>,
+        end: Option<Expression>,
+        inclusive: bool,
+    },
+
+    /// `shared P` or `borrow P` -- explicitly takes a reference to
+    /// the place `P` with the given permission.
+    Reference { perm: PermKind, place: Place },
+
     /// A literal value
     Literal { data: LiteralData },
 
@@ -488,6 +572,10 @@ pub enum ExpressionData {
         fields: List<IdentifiedExpression>,
     },
 
+    /// `(E1, ..., EN)` -- construct a tuple. A one-element tuple is
+    /// written with a trailing comma, `(E1,)`.
+    Tuple { elements: List<Expression> },
+
     /// `()`
     Unit {},
 
@@ -496,18 +584,36 @@ pub enum ExpressionData {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BinaryOperator {
     Add,
     Subtract,
     Multiply,
     Divide,
+    Remainder,
     Equals,
     NotEquals,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+
+    /// Logical `&&`. Like `Or`, this short-circuits: the right-hand
+    /// side is only evaluated if the left-hand side is true. The HIR
+    /// itself does not model this, so anything lowering or executing
+    /// the HIR must take care to evaluate lazily.
+    And,
+
+    /// Logical `||`; the right-hand side is only evaluated if the
+    /// left-hand side is false.
+    Or,
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UnaryOperator {
     Not,
+    Negate,
 }
 
 lark_collections::index_type! {
@@ -515,24 +621,83 @@ lark_collections::index_type! {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IdentifiedExpressionData {
     pub identifier: Identifier,
     pub expression: Expression,
 }
 
+lark_collections::index_type! {
+    pub struct MatchArm { .. }
+}
+
+/// `P => E`
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MatchArmData {
+    pub pattern: Pattern,
+    pub body: Expression,
+}
+
+lark_collections::index_type! {
+    pub struct Pattern { .. }
+}
+
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PatternData {
+    /// `_` -- matches anything
+    Wildcard,
+
+    /// `x` -- matches anything, binding it to a fresh variable
+    Binding(Variable),
+
+    /// `22`, `"foo"`, `true` etc -- matches values equal to the given
+    /// literal expression
+    Literal(Expression),
+
+    /// `Foo(a: P1, b: P2)` -- matches a struct whose fields match the
+    /// given patterns; fields that are not mentioned match anything
+    Struct {
+        entity: Entity,
+        fields: List<IdentifiedPattern>,
+    },
+}
+
+lark_collections::index_type! {
+    pub struct IdentifiedPattern { .. }
+}
+
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdentifiedPatternData {
+    pub identifier: Identifier,
+    pub pattern: Pattern,
+}
+
 lark_collections::index_type! {
     pub struct Place { .. }
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PlaceData {
     Variable(Variable),
     Entity(Entity),
     Temporary(Expression),
     Field { owner: Place, name: Identifier },
+
+    /// `<owner>.N`, where `N` is an integer index into a tuple;
+    /// `index_name` is the `N` as written, which gives its span.
+    TupleField {
+        owner: Place,
+        index: usize,
+        index_name: Identifier,
+    },
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LiteralData {
     pub kind: LiteralKind,
 
@@ -543,9 +708,13 @@ pub struct LiteralData {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LiteralKind {
     UnsignedInteger,
+    Float,
+    Char,
     String,
+    Boolean,
 }
 
 lark_collections::index_type! {
@@ -553,6 +722,7 @@ lark_collections::index_type! {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VariableData {
     pub name: Identifier,
 }
@@ -562,6 +732,7 @@ lark_collections::index_type! {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IdentifierData {
     pub text: GlobalIdentifier,
 }
@@ -571,9 +742,14 @@ lark_collections::index_type! {
 }
 
 #[derive(Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ErrorData {
     Misc,
     CanOnlyConstructStructs,
     Unimplemented,
+
+    /// A name that resolved to neither a local variable nor an
+    /// entity. Unlike the other errors, this is not reported when the
+    /// HIR is constructed, but rather by the type checker.
     UnknownIdentifier { text: GlobalIdentifier },
 }
@@ -0,0 +1,150 @@
+//! Support for serializing fn bodies with serde (enabled by the
+//! `serde` feature).
+//!
+//! Interned values -- identifiers, entities and declared types -- are
+//! serialized as their numeric ids, which only mean something to the
+//! database that interned them. `SerializedFnBody` bundles a side
+//! table with the data behind each of those ids, so that
+//! `SerializedFnBody::into_fn_body` can re-intern them into another
+//! database.
+
+use crate::{ErrorData, ExpressionData, FnBody, PatternData, PlaceData};
+use lark_entity::EntityTables;
+use lark_error::ErrorReported;
+use lark_string::GlobalIdentifierTables;
+use lark_ty::declaration::{Declaration, DeclarationTables};
+use lark_ty::serialize::{SideTable, SideTableBuilder};
+use serde::{Deserialize, Serialize};
+
+/// A fn body along with the data behind every interned id it
+/// mentions (in its identifiers, literals, errors, spans, entities
+/// and types).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedFnBody {
+    pub side_table: SideTable<Declaration>,
+
+    pub fn_body: FnBody,
+}
+
+impl SerializedFnBody {
+    pub fn new<DB>(db: &DB, fn_body: FnBody) -> Self
+    where
+        DB: AsRef<GlobalIdentifierTables> + AsRef<EntityTables> + AsRef<DeclarationTables>,
+    {
+        let mut builder = SideTableBuilder::new(db);
+        if let Err(error) = &fn_body.arguments {
+            builder.record_span(error.span());
+        }
+
+        let tables = &fn_body.tables;
+        for data in tables.expressions.iter() {
+            match data {
+                ExpressionData::Let { ty: Some(ty), .. } => builder.record_ty(*ty),
+                ExpressionData::Literal { data } => builder.record_string(data.value),
+                ExpressionData::Aggregate { entity, .. } => builder.record_entity(*entity),
+                _ => {}
+            }
+        }
+        for data in tables.places.iter() {
+            if let PlaceData::Entity(entity) = data {
+                builder.record_entity(*entity);
+            }
+        }
+        for data in tables.patterns.iter() {
+            if let PatternData::Struct { entity, .. } = data {
+                builder.record_entity(*entity);
+            }
+        }
+        for data in tables.identifiers.iter() {
+            builder.record_string(data.text);
+        }
+        for data in tables.errors.iter() {
+            if let ErrorData::UnknownIdentifier { text } = data {
+                builder.record_string(*text);
+            }
+        }
+        for span in tables.spans.values() {
+            builder.record_span(*span);
+        }
+
+        SerializedFnBody {
+            side_table: builder.finish(),
+            fn_body,
+        }
+    }
+
+    /// Re-interns the data in the side table into `db`, returning the
+    /// fn body with each id replaced by its id in `db`.
+    pub fn into_fn_body<DB>(self, db: &DB) -> FnBody
+    where
+        DB: AsRef<GlobalIdentifierTables> + AsRef<EntityTables> + AsRef<DeclarationTables>,
+    {
+        let reinterner = self.side_table.reintern(db);
+        let mut fn_body = self.fn_body;
+        if let Err(ErrorReported(span)) = &mut fn_body.arguments {
+            *span = reinterner.span(*span);
+        }
+
+        let tables = &mut fn_body.tables;
+        for data in tables.expressions.iter_mut() {
+            match data {
+                ExpressionData::Let { ty: Some(ty), .. } => *ty = reinterner.ty(*ty),
+                ExpressionData::Literal { data } => data.value = reinterner.string(data.value),
+                ExpressionData::Aggregate { entity, .. } => *entity = reinterner.entity(*entity),
+                _ => {}
+            }
+        }
+        for data in tables.places.iter_mut() {
+            if let PlaceData::Entity(entity) = data {
+                *entity = reinterner.entity(*entity);
+            }
+        }
+        for data in tables.patterns.iter_mut() {
+            if let PatternData::Struct { entity, .. } = data {
+                *entity = reinterner.entity(*entity);
+            }
+        }
+        for data in tables.identifiers.iter_mut() {
+            data.text = reinterner.string(data.text);
+        }
+        for data in tables.errors.iter_mut() {
+            if let ErrorData::UnknownIdentifier { text } = data {
+                *text = reinterner.string(*text);
+            }
+        }
+        for span in tables.spans.values_mut() {
+            *span = reinterner.span(*span);
+        }
+
+        fn_body
+    }
+}
+
+/// Serializes the span table as a sequence of `(index, span)` pairs,
+/// since formats like JSON only permit strings as map keys.
+crate mod spans {
+    use crate::MetaIndex;
+    use lark_collections::FxIndexMap;
+    use lark_span::{FileName, Span};
+    use serde::Deserialize;
+
+    crate fn serialize<S>(
+        spans: &FxIndexMap<MetaIndex, Span<FileName>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(spans.iter())
+    }
+
+    crate fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<FxIndexMap<MetaIndex, Span<FileName>>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let spans: Vec<(MetaIndex, Span<FileName>)> = Deserialize::deserialize(deserializer)?;
+        Ok(spans.into_iter().collect())
+    }
+}
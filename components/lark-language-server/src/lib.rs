@@ -241,17 +241,10 @@ impl Actor for LspResponder {
                 send_response(id, result);
             }
             LspResponse::Diagnostics(url, diagnostics) => {
-                let lsp_diagnostics: Vec<languageserver_types::Diagnostic> = diagnostics
-                    .iter()
-                    .map(|(range, diag)| {
-                        languageserver_types::Diagnostic::new_simple(*range, diag.clone())
-                    })
-                    .collect();
-
-                let notice = languageserver_types::PublishDiagnosticsParams {
-                    uri: url,
-                    diagnostics: lsp_diagnostics,
-                };
+                let notice = serde_json::json!({
+                    "uri": url,
+                    "diagnostics": diagnostics,
+                });
 
                 send_notification("textDocument/publishDiagnostics".into(), notice);
             }
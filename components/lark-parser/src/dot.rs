@@ -0,0 +1,84 @@
+//! Renders the entities of all input files as a GraphViz digraph,
+//! with an edge from each entity to each of its children (files to
+//! items, items to their fields, variants and methods). Useful for
+//! seeing how a program was carved up into entities:
+//!
+//! ```text
+//! dot -Tsvg entities.dot > entities.svg
+//! ```
+
+use crate::ParserDatabase;
+use lark_entity::{Entity, EntityData, MemberKind};
+use lark_intern::{Intern, Untern};
+
+/// Renders every entity as a GraphViz digraph. Nodes are numbered in
+/// the order they are visited -- files in the order of `file_names`,
+/// then children in source order -- so the output is deterministic.
+pub fn entities_to_dot(db: &impl ParserDatabase) -> String {
+    let mut renderer = Renderer {
+        db,
+        nodes: String::new(),
+        edges: String::new(),
+        next_node: 0,
+    };
+
+    for &file in db.file_names().iter() {
+        renderer.entity(EntityData::InputFile { file }.intern(db));
+    }
+
+    format!(
+        "digraph entities {{\n{}{}}}\n",
+        renderer.nodes, renderer.edges
+    )
+}
+
+struct Renderer<'db, DB: ParserDatabase> {
+    db: &'db DB,
+    nodes: String,
+    edges: String,
+    next_node: usize,
+}
+
+impl<DB: ParserDatabase> Renderer<'_, DB> {
+    /// Adds `entity` and its descendants to the graph.
+    fn entity(&mut self, entity: Entity) {
+        let node = self.next_node;
+        self.next_node += 1;
+
+        let label = self.label(entity);
+        self.nodes
+            .push_str(&format!("    n{} [label=\"{}\"];\n", node, escape(&label)));
+
+        for &child in self.db.child_entities(entity).iter() {
+            let child_node = self.next_node;
+            self.edges
+                .push_str(&format!("    n{} -> n{};\n", node, child_node));
+            self.entity(child);
+        }
+    }
+
+    fn label(&self, entity: Entity) -> String {
+        let db = self.db;
+        match entity.untern(db) {
+            EntityData::InputFile { file } => format!("file {}", file.id.untern(db)),
+            EntityData::ItemName { kind, id, .. } => {
+                format!("{} {}", kind.keyword(), id.untern(db))
+            }
+            EntityData::MemberName { kind, id, .. } => {
+                let kind = match kind {
+                    MemberKind::Field => "field",
+                    MemberKind::Variant => "variant",
+                    MemberKind::Method => "def",
+                    MemberKind::MethodSignature => "method-signature",
+                };
+                format!("{} {}", kind, id.untern(db))
+            }
+            data @ EntityData::LangItem(_) | data @ EntityData::Error(_) => format!("{:?}", data),
+        }
+    }
+}
+
+/// Escapes `text` for use inside a double-quoted GraphViz string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -14,10 +14,17 @@ crate enum LexerState {
     StartIdent,
     ContinueIdent,
     StringLiteral,
-    Sigil,
+    StringEscape,
+    CharLiteral,
+    CharEscape,
     Slash,
     Number,
-    Comment(u32),
+    RadixNumber,
+    TupleIndex,
+    Fraction,
+    ExponentStart,
+    Exponent,
+    Unterminated,
     EolComment,
 }
 
@@ -36,52 +43,134 @@ impl LexerDelegateTrait for LexerState {
                 None => LexerNext::EOF,
                 Some(c) => match c {
                     '/' => LexerNext::begin(Slash),
-                    c if UnicodeXID::is_xid_start(c) => LexerNext::begin(StartIdent),
+                    'r' if is_raw_string_start(rest) => match raw_string_len(rest) {
+                        Ok(len) => consume_str(&rest[..len])
+                            .and_emit(LexToken::String)
+                            .and_transition(Top),
+                        Err(len) => consume_str(&rest[..len])
+                            .and_emit(LexToken::Error)
+                            .and_transition(Unterminated),
+                    },
+                    c if UnicodeXID::is_xid_start(c) || c == '_' => LexerNext::begin(StartIdent),
                     c if is_delimiter_sigil_char(c) => {
                         consume(c).and_emit(LexToken::Sigil).and_remain()
                     }
                     c if is_sigil_char(c) => {
-                        LexerNext::begin(Sigil)
-                        // LexerNext::dynamic_sigil(Token::Sigil)
+                        let sigil = &rest[..sigil_len(rest)];
+
+                        // A number right after a `.` is a tuple index,
+                        // so `t.0.1` must not lex the `0.1` as a float.
+                        if sigil == "." && starts_with_digit(&rest[1..]) {
+                            consume_str(sigil)
+                                .and_emit(LexToken::Sigil)
+                                .and_transition(TupleIndex)
+                        } else {
+                            consume_str(sigil).and_emit(LexToken::Sigil).and_remain()
+                        }
+                    }
+                    '0' if has_radix_prefix(rest) => {
+                        consume_str(&rest[..2]).and_transition(RadixNumber)
                     }
                     '0'..='9' => LexerNext::begin(Number),
                     '"' => consume(c).and_transition(StringLiteral),
+                    '\'' => consume(c).and_transition(CharLiteral),
                     '\n' => LexerNext::sigil(LexToken::Newline),
                     c if c.is_whitespace() => LexerNext::begin(Whitespace),
-                    _ if rest.starts_with("/*") => consume_str("/*").and_push(Comment(1)),
                     _ => consume(c).and_emit(LexToken::Error).and_remain(),
                 },
             },
 
-            LexerState::Slash => match c {
+            // We enter this state without consuming the `/`, so `rest`
+            // still starts with it.
+            LexerState::Slash => {
+                if rest.starts_with("//") {
+                    reconsume().and_transition(LexerState::EolComment)
+                } else if rest.starts_with("/*") {
+                    // Block comments are skipped in one step, as they
+                    // may nest and span many lines. If one is never
+                    // closed, we report the error at the opening `/*`
+                    // and discard the rest of the input.
+                    match block_comment_len(rest) {
+                        Some(len) => consume_str(&rest[..len])
+                            .and_emit(LexToken::Comment)
+                            .and_transition(LexerState::Top),
+                        None => consume_str("/*")
+                            .and_emit(LexToken::Error)
+                            .and_transition(LexerState::Unterminated),
+                    }
+                } else {
+                    consume_str(&rest[..sigil_len(rest)])
+                        .and_emit(LexToken::Sigil)
+                        .and_transition(LexerState::Top)
+                }
+            }
+
+            LexerState::Number => match c {
                 None => reconsume()
-                    .and_emit(LexToken::Sigil)
+                    .and_emit(LexToken::Integer)
                     .and_transition(LexerState::Top),
-                Some('/') => reconsume().and_transition(LexerState::EolComment),
-                Some(c) => consume(c).and_transition(LexerState::Sigil),
-            },
+                Some(c @ '0'..='9') => consume(c).and_remain(),
+                Some(c @ '_') => consume(c).and_remain(),
 
-            LexerState::Sigil => match c {
-                None => reconsume()
-                    .and_emit(LexToken::Sigil)
+                // `1.5` is a float, but `1.foo` is a member access
+                Some(c @ '.') if starts_with_digit(&rest[1..]) => {
+                    consume(c).and_transition(LexerState::Fraction)
+                }
+                Some(c @ 'e') | Some(c @ 'E') if is_exponent(rest) => {
+                    consume(c).and_transition(LexerState::ExponentStart)
+                }
+                Some(_) => reconsume()
+                    .and_emit(LexToken::Integer)
                     .and_transition(LexerState::Top),
-                Some(c) if is_delimiter_sigil_char(c) => reconsume()
-                    .and_emit(LexToken::Sigil)
+            },
+
+            LexerState::TupleIndex => match c {
+                Some(c @ '0'..='9') => consume(c).and_remain(),
+                _ => reconsume()
+                    .and_emit(LexToken::Integer)
                     .and_transition(LexerState::Top),
-                Some(c) if is_sigil_char(c) => consume(c).and_remain(),
+            },
+
+            // Everything alphanumeric after `0x`, `0o` or `0b` is part
+            // of the literal, so that the parser can point at an
+            // invalid digit like the `2` in `0b2`.
+            LexerState::RadixNumber => match c {
+                Some(c) if c.is_ascii_alphanumeric() || c == '_' => consume(c).and_remain(),
                 _ => reconsume()
-                    .and_emit(LexToken::Sigil)
+                    .and_emit(LexToken::Integer)
                     .and_transition(LexerState::Top),
             },
 
-            LexerState::Number => match c {
+            LexerState::Fraction => match c {
                 None => reconsume()
-                    .and_emit(LexToken::Integer)
+                    .and_emit(LexToken::Float)
                     .and_transition(LexerState::Top),
                 Some(c @ '0'..='9') => consume(c).and_remain(),
                 Some(c @ '_') => consume(c).and_remain(),
+                Some(c @ 'e') | Some(c @ 'E') if is_exponent(rest) => {
+                    consume(c).and_transition(LexerState::ExponentStart)
+                }
                 Some(_) => reconsume()
-                    .and_emit(LexToken::Integer)
+                    .and_emit(LexToken::Float)
+                    .and_transition(LexerState::Top),
+            },
+
+            // We only enter this state if `is_exponent` said there
+            // are digits to come, so an optional sign is all we need
+            // to handle here.
+            LexerState::ExponentStart => match c {
+                Some(c @ '+') | Some(c @ '-') => consume(c).and_transition(LexerState::Exponent),
+                _ => reconsume().and_transition(LexerState::Exponent),
+            },
+
+            LexerState::Exponent => match c {
+                None => reconsume()
+                    .and_emit(LexToken::Float)
+                    .and_transition(LexerState::Top),
+                Some(c @ '0'..='9') => consume(c).and_remain(),
+                Some(c @ '_') => consume(c).and_remain(),
+                Some(_) => reconsume()
+                    .and_emit(LexToken::Float)
                     .and_transition(LexerState::Top),
             },
 
@@ -93,10 +182,42 @@ impl LexerDelegateTrait for LexerState {
                     '"' => consume(c)
                         .and_emit(LexToken::String)
                         .and_transition(LexerState::Top),
+                    '\\' => consume(c).and_transition(LexerState::StringEscape),
+                    _ => consume(c).and_remain(),
+                },
+            },
+
+            // The character after a `\` is always part of the string;
+            // whether it is a valid escape is checked by the parser.
+            LexerState::StringEscape => match c {
+                None => reconsume()
+                    .and_emit(LexToken::Error)
+                    .and_transition(LexerState::Top),
+                Some(c) => consume(c).and_transition(LexerState::StringLiteral),
+            },
+
+            // Character literals may not span lines. As with strings,
+            // the contents are validated by the parser.
+            LexerState::CharLiteral => match c {
+                None | Some('\n') => reconsume()
+                    .and_emit(LexToken::Error)
+                    .and_transition(LexerState::Top),
+                Some(c) => match c {
+                    '\'' => consume(c)
+                        .and_emit(LexToken::Char)
+                        .and_transition(LexerState::Top),
+                    '\\' => consume(c).and_transition(LexerState::CharEscape),
                     _ => consume(c).and_remain(),
                 },
             },
 
+            LexerState::CharEscape => match c {
+                None | Some('\n') => reconsume()
+                    .and_emit(LexToken::Error)
+                    .and_transition(LexerState::Top),
+                Some(c) => consume(c).and_transition(LexerState::CharLiteral),
+            },
+
             LexerState::StartIdent => match c {
                 None => LexerNext::emit(LexToken::Identifier, LexerState::Top),
                 Some(c) => match c {
@@ -133,43 +254,13 @@ impl LexerDelegateTrait for LexerState {
                 },
             },
 
-            LexerState::Comment(1) => {
-                if rest.starts_with("/*") {
-                    consume_str("/*")
-                        .and_continue()
-                        .and_transition(LexerState::Comment(2))
-                } else if rest.starts_with("*/") {
-                    consume_str("*/")
-                        .and_emit(LexToken::Comment)
-                        .and_transition(LexerState::Top)
-                } else {
-                    match c {
-                        Some(c) => consume(c).and_remain(),
-                        None => reconsume()
-                            .and_emit(LexToken::Error)
-                            .and_transition(LexerState::Top),
-                    }
-                }
-            }
-
-            LexerState::Comment(n) => {
-                if rest.starts_with("/*") {
-                    consume_str("/*")
-                        .and_continue()
-                        .and_transition(LexerState::Comment(n + 1))
-                } else if rest.starts_with("*/") {
-                    consume_str("*/")
-                        .and_continue()
-                        .and_transition(LexerState::Comment(n - 1))
-                } else {
-                    match c {
-                        Some(c) => consume(c).and_remain(),
-                        None => reconsume()
-                            .and_emit(LexToken::Error)
-                            .and_transition(LexerState::Comment(n - 1)),
-                    }
-                }
-            }
+            // Following an unterminated block comment or raw string,
+            // we have already reported the error at its opening
+            // delimiter; the rest of the input is discarded.
+            LexerState::Unterminated => match c {
+                None => LexerNext::EOF,
+                Some(_) => consume_str(rest).and_discard().and_remain(),
+            },
 
             LexerState::EolComment => match c {
                 None => reconsume()
@@ -188,14 +279,105 @@ impl LexerDelegateTrait for LexerState {
 
 fn is_sigil_char(c: char) -> bool {
     match c {
-        '{' | '}' | '(' | ')' | '+' | '-' | '*' | '/' | ':' | ',' | '>' | '<' | '=' | '.' => true,
+        '{' | '}' | '(' | ')' | '[' | ']' | ';' | '+' | '-' | '*' | '/' | '%' | ':' | ',' | '>'
+        | '<' | '=' | '.' | '!' | '&' | '|' | '#' => true,
+        _ => false,
+    }
+}
+
+fn starts_with_digit(text: &str) -> bool {
+    match text.chars().next() {
+        Some('0'..='9') => true,
         _ => false,
     }
 }
 
+/// The sigils made of more than one character. Any other sigil
+/// character is a sigil on its own, so that (for example) `x=-1`
+/// lexes as `x`, `=`, `-`, `1`.
+const MULTI_CHAR_SIGILS: &[&str] = &[
+    "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "..", "+=", "-=", "*=", "/=",
+    "%=",
+];
+
+/// The length of the sigil at the start of `text` (which begins with
+/// a sigil character): the longest of `MULTI_CHAR_SIGILS` that `text`
+/// starts with, or else just its first character.
+fn sigil_len(text: &str) -> usize {
+    MULTI_CHAR_SIGILS
+        .iter()
+        .filter(|sigil| text.starts_with(*sigil))
+        .map(|sigil| sigil.len())
+        .max()
+        .unwrap_or(1)
+}
+
+/// True if `text` (which begins with `0`) starts with a radix
+/// prefix: `0x`, `0o` or `0b`.
+fn has_radix_prefix(text: &str) -> bool {
+    text.starts_with("0x") || text.starts_with("0o") || text.starts_with("0b")
+}
+
+/// True if `text` (which begins with `e` or `E`) is the exponent of a
+/// float literal, like `e10` or `e-3`.
+fn is_exponent(text: &str) -> bool {
+    let text = &text[1..];
+    if text.starts_with('+') || text.starts_with('-') {
+        starts_with_digit(&text[1..])
+    } else {
+        starts_with_digit(text)
+    }
+}
+
+/// Returns the length of the (possibly nested) block comment at the
+/// start of `text`, including its delimiters, or `None` if it is
+/// never closed.
+fn block_comment_len(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut index = 0;
+    while let Some(c) = text[index..].chars().next() {
+        let rest = &text[index..];
+        if rest.starts_with("/*") {
+            depth += 1;
+            index += 2;
+        } else if rest.starts_with("*/") {
+            depth -= 1;
+            index += 2;
+            if depth == 0 {
+                return Some(index);
+            }
+        } else {
+            index += c.len_utf8();
+        }
+    }
+    None
+}
+
+/// True if `text` (which begins with `r`) starts with the opening
+/// delimiter of a raw string, like `r"` or `r##"`.
+fn is_raw_string_start(text: &str) -> bool {
+    text[1..].trim_start_matches('#').starts_with('"')
+}
+
+/// Returns the length of the raw string literal at the start of
+/// `text`, including its delimiters. If it is never closed (by a `"`
+/// followed by as many `#` as it was opened with), returns `Err` with
+/// the length of the opening delimiter instead.
+fn raw_string_len(text: &str) -> Result<usize, usize> {
+    let hashes = text[1..].chars().take_while(|&c| c == '#').count();
+    let open = hashes + 2;
+    let close: String = std::iter::once('"')
+        .chain(std::iter::repeat('#').take(hashes))
+        .collect();
+    match text[open..].find(&close[..]) {
+        Some(index) => Ok(open + index + close.len()),
+        None => Err(open),
+    }
+}
+
 fn is_delimiter_sigil_char(c: char) -> bool {
     match c {
-        '{' | '}' | '(' | ')' => true,
+        '{' | '}' | '(' | ')' | '[' | ']' | ';' => true,
         _ => false,
     }
 }
@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use crate::lexer::definition::LexerState;
+use crate::lexer::token::LexToken;
 use crate::lexer::tools::Tokenizer;
 
 use lark_span::{CurrentFile, Span};
@@ -75,3 +76,233 @@ fn test_quicklex() -> Result<(), Span<CurrentFile>> {
 
     Ok(())
 }
+
+#[test]
+fn test_unicode_identifiers() -> Result<(), Span<CurrentFile>> {
+    // Spans are in bytes: `é` and each Greek letter take two.
+    let source = unindent(
+        r##"
+            café αβ _x1
+            00000122223444 Identifier Whitespace Identifier Whitespace Identifier
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_string_escapes() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            "a\"b" "c\\"
+            000000122222 String Whitespace String
+            "abc
+            0000 Error
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_raw_strings() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r###"
+            r"a\b" r#"x"y"# r
+            00000012222222234 String Whitespace String Whitespace Identifier
+            r##"abc"#
+            0000 Error
+            "###,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_float_literals() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            1.5 1e10 2.5E-3 1.foo
+            000122223444444567888 Float Whitespace Float Whitespace Float Whitespace Integer Sigil Identifier
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_tuple_indices() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            t.0.1 0..2
+            0123456778 Identifier Sigil Integer Sigil Integer Whitespace Integer Sigil Integer
+            (a, b).10
+            012345677 Sigil Identifier Sigil Whitespace Identifier Sigil Sigil Integer
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_comment_after_sigil() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            x=/*c*/1
+            01222223 Identifier Sigil Comment Integer
+            x+=/*c*/1
+            011222223 Identifier Sigil Comment Integer
+            t.//c
+            01222 Identifier Sigil Comment
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_radix_literals() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            0xFF 0b102 0o7.foo 0x
+            000012222234445666788 Integer Whitespace Integer Whitespace Integer Sigil Identifier Whitespace Integer
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_char_literals() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            'a' '\'' '\u{1F600}'
+            00012222344444444444 Char Whitespace Char Whitespace Char
+            'a
+            00 Error
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_compound_assignment_sigils() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            x += 1
+            012234 Identifier Whitespace Sigil Whitespace Integer
+            x + = 1
+            0123456 Identifier Whitespace Sigil Whitespace Sigil Whitespace Integer
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_square_brackets() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            Vec[uint]
+            000122223 Identifier Sigil Identifier Sigil
+            x: [(
+            01234 Identifier Sigil Whitespace Sigil Sigil
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_semicolons() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            { 1; }
+            012345 Sigil Whitespace Integer Sigil Whitespace Sigil
+            a;;-b
+            01234 Identifier Sigil Sigil Sigil Identifier
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_block_comments() -> Result<(), Span<CurrentFile>> {
+    let source = unindent(
+        r##"
+            /* a /* b */ c */ x
+            0000000000000000012 Comment Whitespace Identifier
+            /* a *//* b */
+            00000001111111 Comment Comment
+            a / b
+            01234 Identifier Whitespace Sigil Whitespace Identifier
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_unterminated_block_comment() -> Result<(), Span<CurrentFile>> {
+    // The error covers only the opening `/*`; the rest of the input
+    // is swallowed rather than lexed.
+    let tokens = Tokenizer::<LexerState>::new("x /* a /* b */ c").tokens()?;
+    let kinds: Vec<LexToken> = tokens.iter().map(|t| t.value).collect();
+    assert_eq!(
+        kinds,
+        vec![LexToken::Identifier, LexToken::Whitespace, LexToken::Error]
+    );
+    assert_eq!(tokens[2].span, Span::new(CurrentFile, 2usize, 4usize));
+
+    Ok(())
+}
+
+#[test]
+fn test_adjacent_sigils() -> Result<(), Span<CurrentFile>> {
+    // Sigils are lexed by longest match against the known operators,
+    // so an operator followed by a prefix operator is two sigils.
+    let source = unindent(
+        r##"
+            f(x,-1)
+            0123456 Identifier Sigil Identifier Sigil Sigil Integer Sigil
+            Foo(x:-1)
+            000123456 Identifier Sigil Identifier Sigil Sigil Integer Sigil
+            a*-b x=-1
+            012345678 Identifier Sigil Sigil Identifier Whitespace Identifier Sigil Sigil Integer
+            a==-1 !!x
+            011234567 Identifier Sigil Sigil Integer Whitespace Sigil Sigil Identifier
+            a..=b a&|b
+            0111234567 Identifier Sigil Identifier Whitespace Identifier Sigil Sigil Identifier
+            "##,
+    );
+
+    process(&source)?;
+
+    Ok(())
+}
@@ -9,14 +9,28 @@ pub enum LexToken {
     Whitespace,
     Identifier,
     Integer,
+    Float,
     Sigil,
     Comment,
     String,
+    Char,
     Newline,
     EOF,
     Error,
 }
 
+impl LexToken {
+    /// Whitespace and comments, which the parser skips over. Note
+    /// that newlines are *not* trivia, since they separate
+    /// statements, list elements and so forth.
+    pub fn is_trivia(self) -> bool {
+        match self {
+            LexToken::Whitespace | LexToken::Comment => true,
+            _ => false,
+        }
+    }
+}
+
 impl<Cx> ErrorSentinel<Cx> for LexToken {
     fn error_sentinel(_cx: Cx, _report: ErrorReported) -> Self {
         LexToken::Error
@@ -5,15 +5,17 @@
 #![feature(try_blocks)]
 #![allow(dead_code)]
 
-use crate::lexer::token::LexToken;
 use crate::macros::EntityMacroDefinition;
+use crate::syntax::attribute::ParsedAttribute;
 use crate::syntax::entity::ParsedEntity;
 use lark_collections::{FxIndexMap, Seq};
 use lark_debug_derive::DebugWith;
 use lark_entity::Entity;
 use lark_entity::EntityData;
 use lark_entity::EntityTables;
+use lark_entity::ItemKind;
 use lark_entity::MemberKind;
+use lark_entity::Visibility;
 use lark_error::Diagnostic;
 use lark_error::ErrorReported;
 use lark_error::WithError;
@@ -22,6 +24,7 @@ use lark_intern::Intern;
 use lark_span::ByteIndex;
 use lark_span::FileName;
 use lark_span::IntoFileName;
+use lark_span::LineIndex;
 use lark_span::Location;
 use lark_span::Span;
 use lark_span::Spanned;
@@ -34,16 +37,19 @@ use lark_ty::declaration::DeclarationTables;
 use std::sync::Arc;
 
 pub mod current_file;
+pub mod dot;
 mod ir;
 mod lexer;
 pub mod macros;
 mod parser;
 mod query_definitions;
 mod scope;
+pub mod sexpr;
 pub mod syntax;
 mod type_conversion;
 
 pub use self::ir::ParsedFile;
+pub use self::lexer::token::LexToken;
 
 #[salsa::query_group(ParserStorage)]
 pub trait ParserDatabase:
@@ -61,18 +67,25 @@ pub trait ParserDatabase:
     #[salsa::invoke(query_definitions::characteristic_entity_span)]
     fn characteristic_entity_span(&self, entity: Entity) -> Span<FileName>;
 
-    /// Returns, for each line in the given file, the start index
-    /// -- the final element is the length of the file (there is
-    /// kind of a "pseudo-empty line" at the end, so to speak). So
-    /// for the input "a\nb\r\nc" you would get `[0, 2, 5, 6]`.
-    #[salsa::invoke(query_definitions::line_offsets)]
-    fn line_offsets(&self, id: FileName) -> Seq<usize>;
+    /// The line index for the given file, used to map between byte
+    /// indices and (line, column) positions. It is only rebuilt when
+    /// the text of that file changes.
+    #[salsa::invoke(query_definitions::line_index)]
+    fn line_index(&self, id: FileName) -> Arc<LineIndex>;
+
+    /// The offset of each input file when their texts are laid end
+    /// to end, in the order of `file_names`. Adding the offset of a
+    /// file to a byte index within it gives a position that is
+    /// unique across all files.
+    #[salsa::invoke(query_definitions::file_offsets)]
+    fn file_offsets(&self, key: ()) -> Arc<FxIndexMap<FileName, usize>>;
 
     #[salsa::invoke(query_definitions::location)]
     fn location(&self, id: FileName, index: ByteIndex) -> Location;
 
     /// Given a (zero-based) line number `line` and column within
-    /// the line, gives a byte-index into the file's text.
+    /// the line, gives a byte-index into the file's text. The column
+    /// is counted in bytes, not characters.
     #[salsa::invoke(query_definitions::byte_index)]
     fn byte_index(&self, id: FileName, line: u64, column: u64) -> ByteIndex;
 
@@ -101,6 +114,13 @@ pub trait ParserDatabase:
     #[salsa::invoke(query_definitions::descendant_entities)]
     fn descendant_entities(&self, entity: Entity) -> Seq<Entity>;
 
+    /// Every entity declared in any input file, including nested
+    /// ones like fields, variants, and methods (but not the input
+    /// files themselves). Built from the `descendant_entities` of
+    /// each file, so editing one file does not reprocess the others.
+    #[salsa::invoke(query_definitions::all_entities)]
+    fn all_entities(&self, key: ()) -> Seq<Entity>;
+
     /// Get the fn-body for a given def-id.
     #[salsa::invoke(query_definitions::fn_body)]
     fn fn_body(&self, key: Entity) -> WithError<Arc<hir::FnBody>>;
@@ -109,6 +129,12 @@ pub trait ParserDatabase:
     #[salsa::invoke(query_definitions::hover_targets)]
     fn hover_targets(&self, file: FileName, index: ByteIndex) -> Seq<HoverTarget>;
 
+    /// An outline of the given file: its items, each with their
+    /// members (fields, methods, variants) and, for those with a fn
+    /// body, their parameters nested beneath them.
+    #[salsa::invoke(query_definitions::document_symbols)]
+    fn document_symbols(&self, file: FileName) -> Arc<Vec<SymbolNode>>;
+
     /// Get the list of member names and their def-ids for a given struct.
     #[salsa::invoke(query_definitions::members)]
     fn members(&self, key: Entity) -> Result<Seq<hir::Member>, ErrorReported>;
@@ -143,6 +169,38 @@ pub trait ParserDatabase:
     /// Resolve a type name that appears in the given entity.
     #[salsa::invoke(scope::resolve_name)]
     fn resolve_name(&self, scope: Entity, name: GlobalIdentifier) -> Option<Entity>;
+
+    /// Get the trait implemented by an impl (if any).
+    #[salsa::invoke(query_definitions::impl_trait)]
+    fn impl_trait(&self, key: Entity) -> WithError<Option<Entity>>;
+
+    /// Does some impl of `trait_entity` exist for the given struct?
+    #[salsa::invoke(query_definitions::implements_trait)]
+    fn implements_trait(&self, entity: Entity, trait_entity: Entity) -> bool;
+
+    /// Get the entities named in the target of a type alias (empty
+    /// for other entities).
+    #[salsa::invoke(query_definitions::alias_targets)]
+    fn alias_targets(&self, key: Entity) -> Seq<Entity>;
+
+    /// True if the given struct contains itself by value, directly or
+    /// through the fields of other structs, and hence would have
+    /// infinite size. Always false for other entities.
+    #[salsa::invoke(query_definitions::struct_is_recursive)]
+    fn struct_is_recursive(&self, key: Entity) -> bool;
+
+    /// Was the given entity declared `pub`? Enum variants and trait
+    /// methods share the visibility of their enclosing item.
+    #[salsa::invoke(query_definitions::visibility_of)]
+    fn visibility_of(&self, key: Entity) -> Visibility;
+
+    /// The doc comment (`///`) attached to the given entity, if any.
+    #[salsa::invoke(query_definitions::doc_of_entity)]
+    fn doc_of_entity(&self, key: Entity) -> Option<GlobalIdentifier>;
+
+    /// The attributes (like `#[inline]`) attached to the given entity.
+    #[salsa::invoke(query_definitions::attributes_of)]
+    fn attributes_of(&self, key: Entity) -> Seq<ParsedAttribute>;
 }
 
 #[derive(Clone, Debug, DebugWith, PartialEq, Eq)]
@@ -157,6 +215,21 @@ pub enum HoverTargetKind {
     MetaIndex(Entity, hir::MetaIndex),
 }
 
+#[derive(Clone, Debug, DebugWith, PartialEq, Eq)]
+pub struct SymbolNode {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub span: Span<FileName>,
+    pub children: Vec<SymbolNode>,
+}
+
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq)]
+pub enum SymbolKind {
+    Item(ItemKind),
+    Member(MemberKind),
+    Parameter,
+}
+
 pub trait ParserDatabaseExt: ParserDatabase {
     fn init_parser_db(&mut self) {
         self.set_file_names(Default::default());
@@ -172,6 +245,71 @@ pub trait ParserDatabaseExt: ParserDatabase {
         self.set_file_text(file_name, contents.into());
     }
 
+    /// Like `add_file`, but if a file named `path` was already added,
+    /// replaces its text instead.
+    fn set_file(&mut self, path: impl IntoFileName, contents: impl Into<Text>) {
+        let file_name = path.into_file_name(&self);
+
+        let file_names = self.file_names();
+        if !file_names.contains(&file_name) {
+            let mut file_names = file_names;
+            file_names.extend(Some(file_name));
+            self.set_file_names(file_names);
+        }
+
+        self.set_file_text(file_name, contents.into());
+    }
+
+    /// Replaces the text covered by `span` with `new_text`. Entities
+    /// are named by their path, not their position, so those that
+    /// survive the edit keep their identity; only their spans (and
+    /// whatever depends on them) shift.
+    ///
+    /// Editors may send stale spans, so a span that runs past the end
+    /// of the text or splits a character is first clamped to the text
+    /// and rounded down to character boundaries.
+    fn edit_file(&mut self, span: Span<FileName>, new_text: &str) {
+        let file_name = span.file();
+        let text = self.file_text(file_name);
+
+        let clamp = |offset: usize| {
+            let mut offset = offset.min(text.len());
+            while !text.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            offset
+        };
+        let start = clamp(span.start().to_usize());
+        let end = clamp(span.end().to_usize());
+
+        let mut contents = String::with_capacity(text.len() + new_text.len());
+        contents.push_str(&text[..start]);
+        contents.push_str(new_text);
+        contents.push_str(&text[end..]);
+
+        self.set_file_text(file_name, Text::from(contents));
+    }
+
+    /// The tokens of the given file, in order, for tools (such as
+    /// highlighters and formatters) that want the raw token stream
+    /// rather than a parse. Whitespace and comments are included
+    /// only if `include_trivia` is true; newlines always are. Text
+    /// the lexer could not make sense of is reported by `file_tokens`
+    /// and has no token.
+    fn tokens(
+        &self,
+        file: impl IntoFileName,
+        include_trivia: bool,
+    ) -> Vec<Spanned<LexToken, FileName>> {
+        let file = file.into_file_name(&self);
+        self.file_tokens(file)
+            .into_value()
+            .iter()
+            .filter(|token| include_trivia || !token.value.is_trivia())
+            .cloned()
+            .collect()
+    }
+
     /// Returns the "top-level" entities defined in the given file --
     /// does not descend to visit the children of those entities etc.
     fn top_level_entities_in_file(&self, file: impl IntoFileName) -> Seq<Entity> {
@@ -213,6 +351,11 @@ fn macro_definitions(
         macros(
             "struct" => macros::struct_declaration::StructDeclaration,
             "def" => macros::function_declaration::FunctionDeclaration,
+            "enum" => macros::enum_declaration::EnumDeclaration,
+            "trait" => macros::trait_declaration::TraitDeclaration,
+            "impl" => macros::impl_declaration::ImplDeclaration,
+            "const" => macros::const_declaration::ConstDeclaration,
+            "type" => macros::type_alias_declaration::TypeAliasDeclaration,
         ),
     )
 }
@@ -6,8 +6,13 @@ use lark_span::FileName;
 use lark_span::Spanned;
 use lark_string::GlobalIdentifier;
 
+crate mod const_declaration;
+crate mod enum_declaration;
 crate mod function_declaration;
+crate mod impl_declaration;
 crate mod struct_declaration;
+crate mod trait_declaration;
+crate mod type_alias_declaration;
 
 crate trait EntityMacroDefinition: Send {
     /// Invoked when the macro name has been recognized and
@@ -0,0 +1,216 @@
+use crate::lexer::token::LexToken;
+use crate::macros::EntityMacroDefinition;
+use crate::parser::Parser;
+use crate::syntax::entity::ErrorParsedEntity;
+use crate::syntax::entity::LazyParsedEntity;
+use crate::syntax::entity::ParsedEntity;
+use crate::syntax::entity::ParsedEntityThunk;
+use crate::syntax::fn_body;
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::matched::Matched;
+use crate::syntax::matched::ParsedMatch;
+use crate::syntax::sigil::{Colon, Curlies, Equals, Parentheses, Semicolon, SquareBrackets};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::type_reference::{ParsedTypeReference, TypeReference};
+use crate::syntax::Syntax;
+use crate::ParserDatabase;
+use lark_collections::Seq;
+use lark_debug_derive::DebugWith;
+use lark_debug_with::DebugWith;
+use lark_entity::Entity;
+use lark_entity::EntityData;
+use lark_entity::ItemKind;
+use lark_error::ErrorReported;
+use lark_error::ResultExt;
+use lark_error::WithError;
+use lark_hir as hir;
+use lark_intern::Intern;
+use lark_intern::Untern;
+use lark_span::FileName;
+use lark_span::Spanned;
+use lark_string::GlobalIdentifier;
+use lark_ty as ty;
+use lark_ty::declaration::Declaration;
+use std::sync::Arc;
+
+/// ```ignore
+/// `const` <id> `:` <ty> `=` <expr> [`;`]
+/// ```
+///
+/// The initializer extends until the end of the line (or the
+/// optional `;`); it is type-checked like the body of a function
+/// with no arguments that returns `<ty>`.
+#[derive(Default)]
+pub struct ConstDeclaration;
+
+impl EntityMacroDefinition for ConstDeclaration {
+    fn expect(
+        &self,
+        parser: &mut Parser<'_>,
+        base: Entity,
+        macro_name: Spanned<GlobalIdentifier, FileName>,
+    ) -> Result<ParsedEntity, ErrorReported> {
+        log::trace!(
+            "ConstDeclaration::parse(base={}, macro_name={})",
+            base.debug_with(parser),
+            macro_name.debug_with(parser)
+        );
+
+        let const_name = parser.expect(SkipNewline(SpannedGlobalIdentifier))?;
+
+        parser.expect(SkipNewline(Colon))?;
+        let ty = parser
+            .expect(SkipNewline(TypeReference))
+            .unwrap_or_error_sentinel(&*parser);
+
+        parser.expect(SkipNewline(Equals))?;
+        let value = parser.expect(SkipNewline(Initializer));
+
+        parser.parse_if_present(Semicolon);
+
+        let entity = EntityData::ItemName {
+            base,
+            kind: ItemKind::Const,
+            id: const_name.value,
+        }
+        .intern(parser);
+
+        let full_span = macro_name.span.extended_until_end_of(parser.last_span());
+        let characteristic_span = const_name.span;
+
+        Ok(ParsedEntity::new(
+            entity,
+            full_span,
+            characteristic_span,
+            ParsedEntityThunk::new(ParsedConstDeclaration { ty, value }),
+        ))
+    }
+}
+
+/// The (unparsed) tokens of a const initializer: everything up to the
+/// next newline or `;` that is not nested within delimiters.
+#[derive(DebugWith)]
+struct Initializer;
+
+impl Initializer {
+    fn at_end(parser: &Parser<'_>) -> bool {
+        parser.is(LexToken::EOF) || parser.is(LexToken::Newline) || parser.test(Semicolon)
+    }
+}
+
+impl Syntax<'parse> for Initializer {
+    type Data = Spanned<ParsedMatch, FileName>;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        !Initializer::at_end(parser)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        if Initializer::at_end(parser) {
+            let span = parser.peek_span();
+            return Err(parser.report_error("expected initializer expression", span));
+        }
+
+        let start_token = parser.peek_index();
+        let start_span = parser.peek_span();
+
+        while !Initializer::at_end(parser) {
+            if parser.test(Matched(Curlies)) {
+                parser.expect(Matched(Curlies))?;
+            } else if parser.test(Matched(Parentheses)) {
+                parser.expect(Matched(Parentheses))?;
+            } else if parser.test(Matched(SquareBrackets)) {
+                parser.expect(Matched(SquareBrackets))?;
+            } else {
+                parser.shift();
+            }
+        }
+
+        let end_token = parser.peek_index();
+        let full_span = start_span.extended_until_end_of(parser.last_span());
+        let range = ParsedMatch {
+            start_token,
+            end_token,
+        };
+        Ok(Spanned::new(range, full_span))
+    }
+}
+
+#[derive(Clone, DebugWith)]
+pub struct ParsedConstDeclaration {
+    pub ty: ParsedTypeReference,
+    pub value: Result<Spanned<ParsedMatch, FileName>, ErrorReported>,
+}
+
+impl LazyParsedEntity for ParsedConstDeclaration {
+    fn parse_children(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Seq<ParsedEntity>> {
+        WithError::ok(Seq::default())
+    }
+
+    fn parse_generic_declarations(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
+        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+    }
+
+    fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        self.ty.parse_type(entity, db)
+    }
+
+    fn parse_signature(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Result<ty::Signature<Declaration>, ErrorReported>> {
+        // The initializer is checked as a fn body that takes no
+        // arguments and returns the declared type.
+        self.ty.parse_type(entity, db).map(|output| {
+            Ok(ty::Signature {
+                inputs: Seq::default(),
+                output,
+            })
+        })
+    }
+
+    fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
+        match self.value {
+            Err(err) => ErrorParsedEntity { err }.parse_fn_body(entity, db),
+
+            Ok(Spanned {
+                span: _,
+                value:
+                    ParsedMatch {
+                        start_token,
+                        end_token,
+                    },
+            }) => {
+                let file_name = entity.untern(&db).file_name(&db).unwrap();
+                let input = db.file_text(file_name);
+                let tokens = db
+                    .file_tokens(file_name)
+                    .into_value()
+                    .extract(start_token..end_token);
+                let entity_macro_definitions = crate::macro_definitions(&db, entity);
+                fn_body::parse_fn_body(
+                    entity,
+                    db,
+                    &entity_macro_definitions,
+                    &input,
+                    &tokens,
+                    None,
+                    Seq::default(),
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use crate::macros::EntityMacroDefinition;
+use crate::parser::Parser;
+use crate::syntax::delimited::Delimited;
+use crate::syntax::entity::{
+    InvalidParsedEntity, LazyParsedEntity, ParsedEntity, ParsedEntityThunk,
+};
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::list::CommaList;
+use crate::syntax::sigil::{Curlies, Parentheses};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::type_reference::{ParsedTypeReference, TypeReference};
+use crate::syntax::Syntax;
+use crate::ParserDatabase;
+use lark_collections::Seq;
+use lark_debug_derive::DebugWith;
+use lark_debug_with::DebugWith;
+use lark_entity::Entity;
+use lark_entity::EntityData;
+use lark_entity::ItemKind;
+use lark_entity::MemberKind;
+use lark_error::ErrorReported;
+use lark_error::ErrorSentinel;
+use lark_error::WithError;
+use lark_hir as hir;
+use lark_intern::Intern;
+use lark_intern::Untern;
+use lark_span::FileName;
+use lark_span::Spanned;
+use lark_string::GlobalIdentifier;
+use lark_ty as ty;
+use lark_ty::declaration::Declaration;
+use std::sync::Arc;
+
+/// ```ignore
+/// enum <id> {
+///   <id> // separated by `,` or newline
+///   <id>(<ty>, ...)
+/// }
+/// ```
+#[derive(Default)]
+pub struct EnumDeclaration;
+
+impl EntityMacroDefinition for EnumDeclaration {
+    fn expect(
+        &self,
+        parser: &mut Parser<'_>,
+        base: Entity,
+        macro_name: Spanned<GlobalIdentifier, FileName>,
+    ) -> Result<ParsedEntity, ErrorReported> {
+        log::trace!(
+            "EnumDeclaration::parse(base={}, macro_name={})",
+            base.debug_with(parser),
+            macro_name.debug_with(parser)
+        );
+
+        log::trace!("EnumDeclaration::parse: parsing name");
+        let enum_name = parser.expect(SkipNewline(SpannedGlobalIdentifier))?;
+
+        log::trace!("EnumDeclaration::parse: parsing variants");
+        let variants = parser
+            .expect(SkipNewline(Delimited(Curlies, CommaList(Variant))))
+            .unwrap_or_else(|ErrorReported(_)| Seq::default());
+
+        log::trace!("EnumDeclaration::parse: done");
+        let entity = EntityData::ItemName {
+            base,
+            kind: ItemKind::Enum,
+            id: enum_name.value,
+        }
+        .intern(parser);
+
+        let full_span = macro_name.span.extended_until_end_of(parser.last_span());
+        let characteristic_span = enum_name.span;
+
+        Ok(ParsedEntity::new(
+            entity,
+            full_span,
+            characteristic_span,
+            ParsedEntityThunk::new(ParsedEnumDeclaration { variants }),
+        ))
+    }
+}
+
+struct ParsedEnumDeclaration {
+    variants: Seq<Spanned<ParsedVariant, FileName>>,
+}
+
+impl LazyParsedEntity for ParsedEnumDeclaration {
+    fn parse_children(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Seq<ParsedEntity>> {
+        WithError::ok(
+            self.variants
+                .iter()
+                .map(
+                    |Spanned {
+                         value: variant,
+                         span,
+                     }| {
+                        let variant_entity = EntityData::MemberName {
+                            base: entity,
+                            kind: MemberKind::Variant,
+                            id: variant.name.value,
+                        }
+                        .intern(&db);
+
+                        ParsedEntity::new(
+                            variant_entity,
+                            *span,
+                            variant.name.span,
+                            ParsedEntityThunk::new(variant.clone()),
+                        )
+                    },
+                )
+                .collect(),
+        )
+    }
+
+    fn parse_generic_declarations(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
+        // FIXME -- no support for generics yet
+        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+    }
+
+    fn parse_signature(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Result<ty::Signature<Declaration>, ErrorReported>> {
+        InvalidParsedEntity.parse_signature(entity, db)
+    }
+
+    fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        // For each enum `Foo`, the "type" is just `own Foo`
+        match db.generic_declarations(entity).into_value() {
+            Ok(generic_declarations) => {
+                assert!(generic_declarations.is_empty());
+                let ty = crate::type_conversion::declaration_ty_named(
+                    &db,
+                    entity,
+                    ty::declaration::DeclaredPermKind::Own,
+                    ty::ReprKind::Direct,
+                    ty::Generics::empty(),
+                );
+                WithError::ok(ty)
+            }
+            Err(err) => WithError::error_sentinel(&db, err),
+        }
+    }
+
+    fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
+        panic!(
+            "cannot parse fn body of an enum: {:?}",
+            entity.debug_with(db)
+        )
+    }
+}
+
+/// A single variant within an enum: either `Foo` or `Foo(T1, T2)`.
+#[derive(DebugWith)]
+struct Variant;
+
+impl Syntax<'parse> for Variant {
+    type Data = Spanned<ParsedVariant, FileName>;
+
+    fn test(&mut self, parser: &Parser<'_>) -> bool {
+        parser.test(SpannedGlobalIdentifier)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'_>) -> Result<Self::Data, ErrorReported> {
+        let name = parser.expect(SpannedGlobalIdentifier)?;
+
+        let fields = parser
+            .parse_if_present(Delimited(Parentheses, CommaList(TypeReference)))
+            .transpose()?;
+
+        let span = name.span.extended_until_end_of(parser.last_span());
+
+        Ok(Spanned {
+            value: ParsedVariant { name, fields },
+            span,
+        })
+    }
+}
+
+/// Represents a parse of a variant like `Foo` or `Foo(T1, T2)`.
+#[derive(Clone, DebugWith)]
+struct ParsedVariant {
+    name: Spanned<GlobalIdentifier, FileName>,
+
+    /// The types of the variant's fields; `None` for a unit variant.
+    fields: Option<Seq<ParsedTypeReference>>,
+}
+
+impl LazyParsedEntity for ParsedVariant {
+    fn parse_children(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Seq<ParsedEntity>> {
+        WithError::ok(Seq::default())
+    }
+
+    fn parse_generic_declarations(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
+        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+    }
+
+    fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        match self.fields {
+            // A unit variant `Foo` is a value of the enum type.
+            None => {
+                let enum_entity = entity.untern(&db).parent().unwrap();
+                db.ty(enum_entity)
+            }
+
+            // A variant `Foo(..)` is a constructor function, so (like
+            // functions) it gets a unique type of its own.
+            Some(_) => match db.generic_declarations(entity).into_value() {
+                Ok(generic_declarations) => {
+                    assert!(generic_declarations.is_empty());
+                    let ty = crate::type_conversion::declaration_ty_named(
+                        &db,
+                        entity,
+                        ty::declaration::DeclaredPermKind::Own,
+                        ty::ReprKind::Direct,
+                        ty::Generics::empty(),
+                    );
+                    WithError::ok(ty)
+                }
+                Err(err) => WithError::error_sentinel(&db, err),
+            },
+        }
+    }
+
+    fn parse_signature(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Result<ty::Signature<Declaration>, ErrorReported>> {
+        // Constructing a variant is like calling a function that
+        // takes the fields and returns the enum.
+        let mut errors = vec![];
+
+        let inputs: Seq<_> = self
+            .fields
+            .iter()
+            .flat_map(|fields| fields.iter())
+            .map(|field| {
+                field
+                    .parse_type(entity, db)
+                    .accumulate_errors_into(&mut errors)
+            })
+            .collect();
+
+        let enum_entity = entity.untern(&db).parent().unwrap();
+        let output = db.ty(enum_entity).into_value();
+
+        WithError {
+            value: Ok(ty::Signature { inputs, output }),
+            errors,
+        }
+    }
+
+    fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
+        InvalidParsedEntity.parse_fn_body(entity, db)
+    }
+}
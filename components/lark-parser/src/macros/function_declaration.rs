@@ -5,6 +5,7 @@ use crate::syntax::entity::ParsedEntity;
 use crate::syntax::entity::ParsedEntityThunk;
 use crate::syntax::fn_signature::FunctionSignature;
 use crate::syntax::fn_signature::ParsedFunctionSignature;
+use crate::syntax::generics::{self, GenericParameters};
 use crate::syntax::identifier::SpannedGlobalIdentifier;
 use crate::syntax::skip_newline::SkipNewline;
 use crate::ParserDatabase;
@@ -28,7 +29,8 @@ use lark_ty::GenericDeclarations;
 use std::sync::Arc;
 
 /// ```ignore
-/// `def` <id> `(` <id> `:` <ty> `)` [ `->` <ty> ] <block>
+/// `def` <id> [ `[` <id>, ... `]` ] `(` <id> `:` <ty> `)` [ `->` <ty> ]
+///     [ `where` <id> `:` <id>, ... ] <block>
 /// ```
 #[derive(Default)]
 pub struct FunctionDeclaration;
@@ -48,7 +50,18 @@ impl EntityMacroDefinition for FunctionDeclaration {
 
         let function_name = parser.expect(SkipNewline(SpannedGlobalIdentifier))?;
 
+        let generic_parameters = parser
+            .parse_if_present(GenericParameters)
+            .transpose()?
+            .unwrap_or_default();
+
         let signature = parser.expect(FunctionSignature)?;
+        if let Some(self_parameter) = signature.self_parameter {
+            parser.report_error(
+                "`self` parameters are only permitted on methods",
+                self_parameter.span,
+            );
+        }
 
         let entity = EntityData::ItemName {
             base,
@@ -64,13 +77,17 @@ impl EntityMacroDefinition for FunctionDeclaration {
             entity,
             full_span,
             characteristic_span,
-            ParsedEntityThunk::new(ParsedFunctionDeclaration { signature }),
+            ParsedEntityThunk::new(ParsedFunctionDeclaration {
+                generic_parameters,
+                signature,
+            }),
         ))
     }
 }
 
 #[derive(Clone, DebugWith)]
 pub struct ParsedFunctionDeclaration {
+    pub generic_parameters: Seq<Spanned<GlobalIdentifier, FileName>>,
     pub signature: ParsedFunctionSignature,
 }
 
@@ -85,11 +102,16 @@ impl LazyParsedEntity for ParsedFunctionDeclaration {
 
     fn parse_generic_declarations(
         &self,
-        _entity: Entity,
-        _db: &dyn ParserDatabase,
+        entity: Entity,
+        db: &dyn ParserDatabase,
     ) -> WithError<Result<Arc<GenericDeclarations>, ErrorReported>> {
-        // FIXME -- no support for generics yet
-        WithError::ok(Ok(GenericDeclarations::empty(None)))
+        generics::generic_declarations(
+            entity,
+            db,
+            &self.generic_parameters,
+            &self.signature.constraints,
+        )
+        .map(Ok)
     }
 
     fn parse_type(
@@ -97,17 +119,21 @@ impl LazyParsedEntity for ParsedFunctionDeclaration {
         entity: Entity,
         db: &dyn ParserDatabase,
     ) -> WithError<ty::Ty<Declaration>> {
-        // For each function `foo`, create a unique type `foo` as in
-        // Rust.
+        // For each function `foo[T..]`, create a unique type `foo[T..]`
+        // as in Rust. At each reference to `foo`, the type checker
+        // instantiates the generics with fresh inference variables.
         match db.generic_declarations(entity).into_value() {
             Ok(generic_declarations) => {
-                assert!(generic_declarations.is_empty());
+                assert!(generic_declarations.parent_item.is_none());
                 let ty = crate::type_conversion::declaration_ty_named(
                     &db,
                     entity,
                     ty::declaration::DeclaredPermKind::Own,
                     ty::ReprKind::Direct,
-                    ty::Generics::empty(),
+                    crate::type_conversion::bound_var_generics(
+                        &db,
+                        generic_declarations.declarations.len(),
+                    ),
                 );
                 WithError::ok(ty)
             }
@@ -126,4 +152,11 @@ impl LazyParsedEntity for ParsedFunctionDeclaration {
     fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
         self.signature.parse_fn_body(entity, db, None)
     }
+
+    fn parse_parameter_names(
+        &self,
+        _db: &dyn ParserDatabase,
+    ) -> Seq<Spanned<GlobalIdentifier, FileName>> {
+        self.signature.parameter_names(None)
+    }
 }
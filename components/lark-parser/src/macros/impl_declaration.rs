@@ -0,0 +1,230 @@
+use crate::macros::EntityMacroDefinition;
+use crate::parser::Parser;
+use crate::syntax::delimited::Delimited;
+use crate::syntax::entity::{
+    InvalidParsedEntity, LazyParsedEntity, ParsedEntity, ParsedEntityThunk,
+};
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::list::CommaList;
+use crate::syntax::member::{Member, ParsedMember, ParsedMethod};
+use crate::syntax::sigil::{Curlies, For};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::ParserDatabase;
+use lark_collections::Seq;
+use lark_debug_with::DebugWith;
+use lark_entity::Entity;
+use lark_entity::EntityData;
+use lark_entity::ItemKind;
+use lark_entity::MemberKind;
+use lark_error::ErrorReported;
+use lark_error::WithError;
+use lark_hir as hir;
+use lark_intern::Intern;
+use lark_intern::Untern;
+use lark_span::FileName;
+use lark_span::Spanned;
+use lark_string::GlobalIdentifier;
+use lark_ty as ty;
+use lark_ty::declaration::Declaration;
+use std::sync::Arc;
+
+/// ```ignore
+/// impl [<id> for] <id> {
+///   <id>(<id>: <ty>, ...) -> <ty> { ... } // separated by `,` or newline
+/// }
+/// ```
+///
+/// The methods are added to the members of the struct, just as if
+/// they had been declared in the struct itself. If a trait is given,
+/// the struct is considered to implement that trait. The impl is
+/// itself an entity, named after the struct that it is for.
+///
+/// FIXME -- since the impl is named after its struct, there can
+/// presently only be one impl per struct.
+#[derive(Default)]
+pub struct ImplDeclaration;
+
+impl EntityMacroDefinition for ImplDeclaration {
+    fn expect(
+        &self,
+        parser: &mut Parser<'_>,
+        base: Entity,
+        macro_name: Spanned<GlobalIdentifier, FileName>,
+    ) -> Result<ParsedEntity, ErrorReported> {
+        log::trace!(
+            "ImplDeclaration::parse(base={}, macro_name={})",
+            base.debug_with(parser),
+            macro_name.debug_with(parser)
+        );
+
+        log::trace!("ImplDeclaration::parse: parsing self type");
+        let name = parser.expect(SkipNewline(SpannedGlobalIdentifier))?;
+
+        // In `impl Trait for Type`, the first name was the trait.
+        let (trait_name, self_ty) = match parser.parse_if_present(For) {
+            Some(_) => (
+                Some(name),
+                parser.expect(SkipNewline(SpannedGlobalIdentifier))?,
+            ),
+            None => (None, name),
+        };
+
+        log::trace!("ImplDeclaration::parse: parsing methods");
+        let members = parser
+            .expect(SkipNewline(Delimited(Curlies, CommaList(Member))))
+            .unwrap_or_else(|ErrorReported(_)| Seq::default());
+
+        let methods: Seq<_> = members
+            .iter()
+            .filter_map(|Spanned { value: member, span }| match member {
+                ParsedMember::ParsedMethod(method) => Some(Spanned {
+                    value: method.clone(),
+                    span: *span,
+                }),
+                ParsedMember::ParsedField(field) => {
+                    parser.report_error("impls may only contain methods", field.name.span);
+                    None
+                }
+            })
+            .collect();
+
+        log::trace!("ImplDeclaration::parse: done");
+        let entity = EntityData::ItemName {
+            base,
+            kind: ItemKind::Impl,
+            id: self_ty.value,
+        }
+        .intern(parser);
+
+        let full_span = macro_name.span.extended_until_end_of(parser.last_span());
+        let characteristic_span = self_ty.span;
+
+        Ok(ParsedEntity::new(
+            entity,
+            full_span,
+            characteristic_span,
+            ParsedEntityThunk::new(ParsedImplDeclaration {
+                self_ty,
+                trait_name,
+                methods,
+            }),
+        ))
+    }
+}
+
+struct ParsedImplDeclaration {
+    self_ty: Spanned<GlobalIdentifier, FileName>,
+    trait_name: Option<Spanned<GlobalIdentifier, FileName>>,
+    methods: Seq<Spanned<ParsedMethod, FileName>>,
+}
+
+impl LazyParsedEntity for ParsedImplDeclaration {
+    fn parse_children(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Seq<ParsedEntity>> {
+        WithError::ok(
+            self.methods
+                .iter()
+                .map(
+                    |Spanned {
+                         value: method,
+                         span,
+                     }| {
+                        let method_entity = EntityData::MemberName {
+                            base: entity,
+                            kind: MemberKind::Method,
+                            id: method.name.value,
+                        }
+                        .intern(&db);
+
+                        ParsedEntity::new(
+                            method_entity,
+                            *span,
+                            method.name.span,
+                            ParsedEntityThunk::new(method.clone()),
+                        )
+                        .with_visibility(method.visibility)
+                    },
+                )
+                .collect(),
+        )
+    }
+
+    fn parse_generic_declarations(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
+        // FIXME -- no support for generics yet
+        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+    }
+
+    fn parse_signature(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Result<ty::Signature<Declaration>, ErrorReported>> {
+        InvalidParsedEntity.parse_signature(entity, db)
+    }
+
+    fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        // The "type" of an impl is the type that it is for; this is
+        // the type of `self` within its methods.
+        match db.resolve_name(entity, self.self_ty.value) {
+            Some(self_entity) => {
+                let ty = crate::type_conversion::declaration_ty_named(
+                    &db,
+                    self_entity,
+                    ty::declaration::DeclaredPermKind::Own,
+                    ty::ReprKind::Direct,
+                    ty::Generics::empty(),
+                );
+                WithError::ok(ty)
+            }
+            None => {
+                let msg = format!("unknown type: `{}`", self.self_ty.untern(&db));
+                WithError::report_error(&db, msg, self.self_ty.span)
+            }
+        }
+    }
+
+    fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
+        panic!(
+            "cannot parse fn body of an impl: {:?}",
+            entity.debug_with(db)
+        )
+    }
+
+    fn parse_impl_trait(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Option<Entity>> {
+        let trait_name = match self.trait_name {
+            Some(trait_name) => trait_name,
+            None => return WithError::ok(None),
+        };
+
+        let msg = match db.resolve_name(entity, trait_name.value) {
+            Some(trait_entity) => match trait_entity.untern(&db) {
+                EntityData::ItemName {
+                    kind: ItemKind::Trait,
+                    ..
+                } => return WithError::ok(Some(trait_entity)),
+                _ => format!("`{}` is not a trait", trait_name.untern(&db)),
+            },
+            None => format!("unknown trait: `{}`", trait_name.untern(&db)),
+        };
+
+        WithError {
+            value: None,
+            errors: vec![crate::diagnostic(msg, trait_name.span)],
+        }
+    }
+}
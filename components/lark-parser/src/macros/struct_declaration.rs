@@ -4,6 +4,7 @@ use crate::syntax::delimited::Delimited;
 use crate::syntax::entity::{
     InvalidParsedEntity, LazyParsedEntity, ParsedEntity, ParsedEntityThunk,
 };
+use crate::syntax::generics::{self, GenericParameters, ParsedConstraint, WhereClause};
 use crate::syntax::identifier::SpannedGlobalIdentifier;
 use crate::syntax::list::CommaList;
 use crate::syntax::member::{Member, ParsedMember};
@@ -20,7 +21,7 @@ use lark_error::ErrorReported;
 use lark_error::ErrorSentinel;
 use lark_error::WithError;
 use lark_hir as hir;
-use lark_intern::Intern;
+use lark_intern::{Intern, Untern};
 use lark_span::FileName;
 use lark_span::Spanned;
 use lark_string::GlobalIdentifier;
@@ -29,7 +30,7 @@ use lark_ty::declaration::Declaration;
 use std::sync::Arc;
 
 /// ```ignore
-/// struct <id> {
+/// struct <id>[<id>, ...] where <id>: <id>, ... { // the `[...]` and `where` are optional
 ///   <id>: <ty> // separated by `,` or newline
 /// }
 /// ```
@@ -52,6 +53,18 @@ impl EntityMacroDefinition for StructDeclaration {
         log::trace!("StructDeclaration::parse: parsing name");
         let struct_name = parser.expect(SkipNewline(SpannedGlobalIdentifier))?;
 
+        log::trace!("StructDeclaration::parse: parsing generic parameters");
+        let generic_parameters = parser
+            .parse_if_present(GenericParameters)
+            .transpose()?
+            .unwrap_or_default();
+
+        log::trace!("StructDeclaration::parse: parsing where clause");
+        let constraints = parser
+            .parse_if_present(SkipNewline(WhereClause))
+            .transpose()?
+            .unwrap_or_default();
+
         log::trace!("StructDeclaration::parse: parsing fields");
         let fields = parser
             .expect(SkipNewline(Delimited(Curlies, CommaList(Member))))
@@ -72,12 +85,20 @@ impl EntityMacroDefinition for StructDeclaration {
             entity,
             full_span,
             characteristic_span,
-            ParsedEntityThunk::new(ParsedStructDeclaration { fields }),
+            ParsedEntityThunk::new(ParsedStructDeclaration {
+                name: struct_name,
+                generic_parameters,
+                constraints,
+                fields,
+            }),
         ))
     }
 }
 
 struct ParsedStructDeclaration {
+    name: Spanned<GlobalIdentifier, FileName>,
+    generic_parameters: Seq<Spanned<GlobalIdentifier, FileName>>,
+    constraints: Seq<ParsedConstraint>,
     fields: Seq<Spanned<ParsedMember, FileName>>,
 }
 
@@ -110,6 +131,7 @@ impl LazyParsedEntity for ParsedStructDeclaration {
                                     field.name.span,
                                     ParsedEntityThunk::new(field.clone()),
                                 )
+                                .with_visibility(field.visibility)
                             }
                             ParsedMember::ParsedMethod(method) => {
                                 let method_entity = EntityData::MemberName {
@@ -125,6 +147,7 @@ impl LazyParsedEntity for ParsedStructDeclaration {
                                     method.name.span,
                                     ParsedEntityThunk::new(method.clone()),
                                 )
+                                .with_visibility(method.visibility)
                             }
                         }
                     },
@@ -135,11 +158,11 @@ impl LazyParsedEntity for ParsedStructDeclaration {
 
     fn parse_generic_declarations(
         &self,
-        _entity: Entity,
-        _db: &dyn ParserDatabase,
+        entity: Entity,
+        db: &dyn ParserDatabase,
     ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
-        // FIXME -- no support for generics yet
-        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+        generics::generic_declarations(entity, db, &self.generic_parameters, &self.constraints)
+            .map(Ok)
     }
 
     fn parse_signature(
@@ -155,18 +178,34 @@ impl LazyParsedEntity for ParsedStructDeclaration {
         entity: Entity,
         db: &dyn ParserDatabase,
     ) -> WithError<ty::Ty<Declaration>> {
-        // For each struct `Foo`, the "type" is just `own Foo`
+        // For each struct `Foo[T..]`, the "type" is just `own Foo[T..]`
         match db.generic_declarations(entity).into_value() {
             Ok(generic_declarations) => {
-                assert!(generic_declarations.is_empty());
+                assert!(generic_declarations.parent_item.is_none());
                 let ty = crate::type_conversion::declaration_ty_named(
                     &db,
                     entity,
                     ty::declaration::DeclaredPermKind::Own,
                     ty::ReprKind::Direct,
-                    ty::Generics::empty(),
+                    crate::type_conversion::bound_var_generics(
+                        &db,
+                        generic_declarations.declarations.len(),
+                    ),
                 );
-                WithError::ok(ty)
+
+                // The type itself is fine even if the struct is
+                // recursive, so we report the error but don't replace
+                // it with the error type.
+                let mut errors = vec![];
+                if db.struct_is_recursive(entity) {
+                    let msg = format!(
+                        "recursive struct `{}` has infinite size; \
+                         use a `shared` or `borrowed` field to break the cycle",
+                        self.name.value.untern(&db),
+                    );
+                    errors.push(crate::diagnostic(msg, self.name.span));
+                }
+                WithError { value: ty, errors }
             }
             Err(err) => WithError::error_sentinel(&db, err),
         }
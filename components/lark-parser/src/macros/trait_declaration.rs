@@ -0,0 +1,299 @@
+use crate::macros::EntityMacroDefinition;
+use crate::parser::Parser;
+use crate::syntax::delimited::Delimited;
+use crate::syntax::entity::{
+    InvalidParsedEntity, LazyParsedEntity, ParsedEntity, ParsedEntityThunk,
+};
+use crate::syntax::fn_signature::{
+    self_parameter_ty, Parameters, ParsedParameters, ParsedSelfParameter,
+};
+use crate::syntax::guard::Guard;
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::list::CommaList;
+use crate::syntax::member::ParsedField;
+use crate::syntax::sigil::{Curlies, RightArrow};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::type_reference::{ParsedTypeReference, TypeReference};
+use crate::syntax::Syntax;
+use crate::ParserDatabase;
+use lark_collections::Seq;
+use lark_debug_derive::DebugWith;
+use lark_debug_with::DebugWith;
+use lark_entity::Entity;
+use lark_entity::EntityData;
+use lark_entity::ItemKind;
+use lark_entity::MemberKind;
+use lark_error::ErrorReported;
+use lark_error::ErrorSentinel;
+use lark_error::ResultExt;
+use lark_error::WithError;
+use lark_hir as hir;
+use lark_intern::Intern;
+use lark_intern::Untern;
+use lark_span::FileName;
+use lark_span::Spanned;
+use lark_string::GlobalIdentifier;
+use lark_ty as ty;
+use lark_ty::declaration::Declaration;
+use std::sync::Arc;
+
+/// ```ignore
+/// trait <id> {
+///   <id>(<id>: <ty>, ...) -> <ty> // separated by `,` or newline
+/// }
+/// ```
+///
+/// As with struct methods, the `self` argument of each method is
+/// implicit.
+#[derive(Default)]
+pub struct TraitDeclaration;
+
+impl EntityMacroDefinition for TraitDeclaration {
+    fn expect(
+        &self,
+        parser: &mut Parser<'_>,
+        base: Entity,
+        macro_name: Spanned<GlobalIdentifier, FileName>,
+    ) -> Result<ParsedEntity, ErrorReported> {
+        log::trace!(
+            "TraitDeclaration::parse(base={}, macro_name={})",
+            base.debug_with(parser),
+            macro_name.debug_with(parser)
+        );
+
+        log::trace!("TraitDeclaration::parse: parsing name");
+        let trait_name = parser.expect(SkipNewline(SpannedGlobalIdentifier))?;
+
+        log::trace!("TraitDeclaration::parse: parsing methods");
+        let methods = parser
+            .expect(SkipNewline(Delimited(Curlies, CommaList(MethodSignature))))
+            .unwrap_or_else(|ErrorReported(_)| Seq::default());
+
+        log::trace!("TraitDeclaration::parse: done");
+        let entity = EntityData::ItemName {
+            base,
+            kind: ItemKind::Trait,
+            id: trait_name.value,
+        }
+        .intern(parser);
+
+        let full_span = macro_name.span.extended_until_end_of(parser.last_span());
+        let characteristic_span = trait_name.span;
+
+        Ok(ParsedEntity::new(
+            entity,
+            full_span,
+            characteristic_span,
+            ParsedEntityThunk::new(ParsedTraitDeclaration { methods }),
+        ))
+    }
+}
+
+struct ParsedTraitDeclaration {
+    methods: Seq<Spanned<ParsedMethodSignature, FileName>>,
+}
+
+impl LazyParsedEntity for ParsedTraitDeclaration {
+    fn parse_children(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Seq<ParsedEntity>> {
+        WithError::ok(
+            self.methods
+                .iter()
+                .map(
+                    |Spanned {
+                         value: method,
+                         span,
+                     }| {
+                        let method_entity = EntityData::MemberName {
+                            base: entity,
+                            kind: MemberKind::MethodSignature,
+                            id: method.name.value,
+                        }
+                        .intern(&db);
+
+                        ParsedEntity::new(
+                            method_entity,
+                            *span,
+                            method.name.span,
+                            ParsedEntityThunk::new(method.clone()),
+                        )
+                    },
+                )
+                .collect(),
+        )
+    }
+
+    fn parse_generic_declarations(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
+        // FIXME -- no support for generics yet
+        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+    }
+
+    fn parse_signature(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Result<ty::Signature<Declaration>, ErrorReported>> {
+        InvalidParsedEntity.parse_signature(entity, db)
+    }
+
+    fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        // For each trait `Foo`, the "type" is just `own Foo`; this is
+        // the type of `self` within the trait's methods.
+        match db.generic_declarations(entity).into_value() {
+            Ok(generic_declarations) => {
+                assert!(generic_declarations.is_empty());
+                let ty = crate::type_conversion::declaration_ty_named(
+                    &db,
+                    entity,
+                    ty::declaration::DeclaredPermKind::Own,
+                    ty::ReprKind::Direct,
+                    ty::Generics::empty(),
+                );
+                WithError::ok(ty)
+            }
+            Err(err) => WithError::error_sentinel(&db, err),
+        }
+    }
+
+    fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
+        panic!(
+            "cannot parse fn body of a trait: {:?}",
+            entity.debug_with(db)
+        )
+    }
+}
+
+/// The signature of a method within a trait, like `foo(x: uint) -> uint`.
+/// Unlike the methods of a struct, these have no body.
+#[derive(DebugWith)]
+struct MethodSignature;
+
+impl Syntax<'parse> for MethodSignature {
+    type Data = Spanned<ParsedMethodSignature, FileName>;
+
+    fn test(&mut self, parser: &Parser<'_>) -> bool {
+        parser.test(SpannedGlobalIdentifier)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'_>) -> Result<Self::Data, ErrorReported> {
+        let name = parser.expect(SpannedGlobalIdentifier)?;
+
+        let ParsedParameters {
+            self_parameter,
+            parameters,
+        } = parser
+            .expect(SkipNewline(Parameters))
+            .unwrap_or_else(|ErrorReported(_)| ParsedParameters::default());
+
+        let return_type = match parser
+            .parse_if_present(SkipNewline(Guard(RightArrow, SkipNewline(TypeReference))))
+        {
+            Some(ty) => ty.unwrap_or_error_sentinel(&*parser),
+            None => ParsedTypeReference::Elided(parser.elided_span()),
+        };
+
+        let span = name.span.extended_until_end_of(parser.last_span());
+
+        Ok(Spanned {
+            value: ParsedMethodSignature {
+                name,
+                self_parameter,
+                parameters,
+                return_type,
+            },
+            span,
+        })
+    }
+}
+
+#[derive(Clone, DebugWith)]
+struct ParsedMethodSignature {
+    name: Spanned<GlobalIdentifier, FileName>,
+    self_parameter: Option<ParsedSelfParameter>,
+    parameters: Seq<Spanned<ParsedField, FileName>>,
+    return_type: ParsedTypeReference,
+}
+
+impl LazyParsedEntity for ParsedMethodSignature {
+    fn parse_children(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Seq<ParsedEntity>> {
+        WithError::ok(Seq::default())
+    }
+
+    fn parse_generic_declarations(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
+        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+    }
+
+    fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        // As with methods, each method signature `foo` gets a unique
+        // type `foo`.
+        match db.generic_declarations(entity).into_value() {
+            Ok(generic_declarations) => {
+                assert!(generic_declarations.is_empty());
+                let ty = crate::type_conversion::declaration_ty_named(
+                    &db,
+                    entity,
+                    ty::declaration::DeclaredPermKind::Own,
+                    ty::ReprKind::Direct,
+                    ty::Generics::empty(),
+                );
+                WithError::ok(ty)
+            }
+            Err(err) => WithError::error_sentinel(&db, err),
+        }
+    }
+
+    fn parse_signature(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Result<ty::Signature<Declaration>, ErrorReported>> {
+        let mut errors = vec![];
+
+        let trait_entity = entity.untern(&db).parent().unwrap();
+        let self_ty = self_parameter_ty(self.self_parameter, db.ty(trait_entity).into_value(), db);
+
+        let inputs: Seq<_> = std::iter::once(self_ty)
+            .chain(self.parameters.iter().map(|p| {
+                p.ty.parse_type(entity, db)
+                    .accumulate_errors_into(&mut errors)
+            }))
+            .collect();
+
+        let output = self
+            .return_type
+            .parse_type(entity, db)
+            .accumulate_errors_into(&mut errors);
+
+        WithError {
+            value: Ok(ty::Signature { inputs, output }),
+            errors,
+        }
+    }
+
+    fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
+        InvalidParsedEntity.parse_fn_body(entity, db)
+    }
+}
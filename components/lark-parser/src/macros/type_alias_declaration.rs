@@ -0,0 +1,153 @@
+use crate::macros::EntityMacroDefinition;
+use crate::parser::Parser;
+use crate::syntax::entity::InvalidParsedEntity;
+use crate::syntax::entity::LazyParsedEntity;
+use crate::syntax::entity::ParsedEntity;
+use crate::syntax::entity::ParsedEntityThunk;
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::sigil::{Equals, Semicolon};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::type_reference::{ParsedTypeReference, TypeReference};
+use crate::ParserDatabase;
+use lark_collections::{FxIndexSet, Seq};
+use lark_debug_derive::DebugWith;
+use lark_debug_with::DebugWith;
+use lark_entity::Entity;
+use lark_entity::EntityData;
+use lark_entity::ItemKind;
+use lark_error::ErrorReported;
+use lark_error::ResultExt;
+use lark_error::WithError;
+use lark_hir as hir;
+use lark_intern::Intern;
+use lark_intern::Untern;
+use lark_span::FileName;
+use lark_span::Spanned;
+use lark_string::GlobalIdentifier;
+use lark_ty as ty;
+use lark_ty::declaration::Declaration;
+use std::sync::Arc;
+
+/// ```ignore
+/// `type` <id> `=` <ty> [`;`]
+/// ```
+#[derive(Default)]
+pub struct TypeAliasDeclaration;
+
+impl EntityMacroDefinition for TypeAliasDeclaration {
+    fn expect(
+        &self,
+        parser: &mut Parser<'_>,
+        base: Entity,
+        macro_name: Spanned<GlobalIdentifier, FileName>,
+    ) -> Result<ParsedEntity, ErrorReported> {
+        log::trace!(
+            "TypeAliasDeclaration::parse(base={}, macro_name={})",
+            base.debug_with(parser),
+            macro_name.debug_with(parser)
+        );
+
+        let alias_name = parser.expect(SkipNewline(SpannedGlobalIdentifier))?;
+
+        parser.expect(SkipNewline(Equals))?;
+        let target = parser
+            .expect(SkipNewline(TypeReference))
+            .unwrap_or_error_sentinel(&*parser);
+
+        parser.parse_if_present(Semicolon);
+
+        let entity = EntityData::ItemName {
+            base,
+            kind: ItemKind::TypeAlias,
+            id: alias_name.value,
+        }
+        .intern(parser);
+
+        let full_span = macro_name.span.extended_until_end_of(parser.last_span());
+        let characteristic_span = alias_name.span;
+
+        Ok(ParsedEntity::new(
+            entity,
+            full_span,
+            characteristic_span,
+            ParsedEntityThunk::new(ParsedTypeAliasDeclaration {
+                name: alias_name,
+                target,
+            }),
+        ))
+    }
+}
+
+#[derive(Clone, DebugWith)]
+pub struct ParsedTypeAliasDeclaration {
+    pub name: Spanned<GlobalIdentifier, FileName>,
+    pub target: ParsedTypeReference,
+}
+
+impl LazyParsedEntity for ParsedTypeAliasDeclaration {
+    fn parse_children(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Seq<ParsedEntity>> {
+        WithError::ok(Seq::default())
+    }
+
+    fn parse_generic_declarations(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
+        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+    }
+
+    fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        // Converting the target of a cyclic alias would recurse
+        // forever (and salsa would panic), so check first.
+        if is_cyclic_alias(db, entity) {
+            let msg = format!("cyclic type alias: `{}`", self.name.value.untern(&db));
+            return WithError::report_error(&db, msg, self.name.span);
+        }
+
+        self.target.parse_type(entity, db)
+    }
+
+    fn parse_signature(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Result<ty::Signature<Declaration>, ErrorReported>> {
+        InvalidParsedEntity.parse_signature(entity, db)
+    }
+
+    fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
+        InvalidParsedEntity.parse_fn_body(entity, db)
+    }
+
+    fn parse_alias_targets(&self, entity: Entity, db: &dyn ParserDatabase) -> Seq<Entity> {
+        let mut targets = vec![];
+        self.target.referenced_entities(entity, db, &mut targets);
+        Seq::from(targets)
+    }
+}
+
+/// True if `alias` can reach itself by following the entities named
+/// in alias targets.
+fn is_cyclic_alias(db: &dyn ParserDatabase, alias: Entity) -> bool {
+    let mut visited = FxIndexSet::default();
+    let mut stack: Vec<Entity> = db.alias_targets(alias).iter().cloned().collect();
+    while let Some(entity) = stack.pop() {
+        if entity == alias {
+            return true;
+        }
+
+        if visited.insert(entity) {
+            stack.extend(db.alias_targets(entity).iter().cloned());
+        }
+    }
+    false
+}
@@ -155,6 +155,11 @@ impl Parser<'parse> {
         self.input
     }
 
+    /// Extract the global identifier tables
+    crate fn global_identifier_tables(&self) -> &'parse GlobalIdentifierTables {
+        self.global_identifier_tables
+    }
+
     /// Extract the complete input
     crate fn entity_macro_definitions(
         &self,
@@ -236,6 +241,42 @@ impl Parser<'parse> {
         count > 0
     }
 
+    /// Returns the `///` doc comment immediately preceding the
+    /// current token (ignoring whitespace), with consecutive comment
+    /// lines joined by newlines. A blank line (or any other token)
+    /// in between detaches the comment.
+    crate fn preceding_doc_comment(&self) -> Option<String> {
+        let mut lines = vec![];
+
+        for token in self.tokens[..self.peek_index()].iter().rev() {
+            match token.value {
+                LexToken::Whitespace => continue,
+                LexToken::Comment => {
+                    // `////` and longer are ordinary comments, as in Rust.
+                    let text = &self.input[token.span];
+                    if !text.starts_with("///") || text.starts_with("////") {
+                        break;
+                    }
+
+                    let line = text["///".len()..].trim_end();
+                    if line.starts_with(' ') {
+                        lines.push(&line[1..]);
+                    } else {
+                        lines.push(line);
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
+
     /// Tests whether the syntax applies at the current point.
     crate fn test(&self, mut syntax: impl Syntax<'parse>) -> bool {
         log::trace!(
@@ -297,6 +338,18 @@ impl Parser<'parse> {
     ) -> ErrorReported {
         report_error(&mut self.errors, message, span)
     }
+
+    /// Number of errors reported so far; useful to tell whether
+    /// parsing some piece of syntax reported any.
+    crate fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Moves any errors from `value` into the parser's own list of
+    /// errors, returning the value itself.
+    crate fn accumulate_errors<T>(&mut self, value: WithError<T>) -> T {
+        value.accumulate_errors_into(&mut self.errors)
+    }
 }
 
 impl AsRef<GlobalIdentifierTables> for Parser<'_> {
@@ -333,9 +386,8 @@ fn advance_next_token(
 
         // Skip over whitespace/comments automatically (but not
         // newlines).
-        match token.value {
-            LexToken::Whitespace | LexToken::Comment => continue,
-            _ => return token,
+        if !token.value.is_trivia() {
+            return token;
         }
     }
 }
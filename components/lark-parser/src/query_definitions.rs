@@ -3,23 +3,32 @@ use crate::lexer::definition::LexerState;
 use crate::lexer::token::LexToken;
 use crate::lexer::tools::Tokenizer;
 use crate::parser::Parser;
+use crate::syntax::attribute::ParsedAttribute;
 use crate::syntax::entity::{EntitySyntax, ParsedEntity, ParsedEntityThunk};
 use crate::syntax::skip_newline::SkipNewline;
 use crate::HoverTarget;
 use crate::HoverTargetKind;
 use crate::ParserDatabase;
+use crate::SymbolKind;
+use crate::SymbolNode;
 
-use lark_collections::Seq;
+use lark_collections::{FxIndexMap, FxIndexSet, Seq};
 use lark_debug_with::DebugWith;
+use lark_entity::ItemKind;
+use lark_entity::LangItem;
 use lark_entity::MemberKind;
+use lark_entity::Visibility;
 use lark_entity::{Entity, EntityData};
 use lark_error::ErrorReported;
 use lark_error::ErrorSentinel;
 use lark_error::WithError;
 use lark_hir as hir;
 use lark_intern::{Intern, Untern};
-use lark_span::{ByteIndex, FileName, Location, Span, Spanned};
+use lark_span::{ByteIndex, FileName, LineIndex, Location, Span, Spanned};
 use lark_string::GlobalIdentifier;
+use lark_ty as ty;
+use lark_ty::declaration::{Declaration, DeclaredPermKind};
+use lark_ty::{BaseData, BaseKind, BoundVarOr};
 use std::sync::Arc;
 
 crate fn file_tokens(
@@ -32,7 +41,18 @@ crate fn file_tokens(
     let mut tokens = vec![];
     while let Some(token) = tokenizer.next() {
         match token {
-            Ok(t) => tokens.push(t.in_file_named(file_name)),
+            Ok(t) => {
+                let t = t.in_file_named(file_name);
+
+                // The lexer emits an unterminated block comment as an
+                // error token covering its opening `/*`; report it
+                // here rather than leaving it to confuse the parser.
+                if t.value == LexToken::Error && input[t.span].starts_with("/*") {
+                    errors.push(crate::diagnostic("unterminated block comment", t.span));
+                } else {
+                    tokens.push(t);
+                }
+            }
             Err(span) => errors.push(crate::diagnostic(
                 "unrecognized token",
                 span.in_file_named(file_name),
@@ -92,6 +112,9 @@ crate fn parsed_entity(db: &impl ParserDatabase, entity: Entity) -> ParsedEntity
                 full_span: parsed_file.span,
                 characteristic_span: parsed_file.span,
                 thunk: ParsedEntityThunk::new(parsed_file),
+                visibility: Visibility::Public,
+                doc: None,
+                attributes: Seq::default(),
             }
         }
 
@@ -151,52 +174,30 @@ crate fn characteristic_entity_span(db: &impl ParserDatabase, entity: Entity) ->
     )
 }
 
-crate fn line_offsets(db: &impl ParserDatabase, id: FileName) -> Seq<usize> {
-    let text: &str = &db.file_text(id);
-    let mut accumulator = 0;
-    text.lines()
-        .map(|line_text| {
-            let line_start = accumulator;
-            accumulator += line_text.len();
-            if text[accumulator..].starts_with("\r\n") {
-                accumulator += 2;
-            } else if text[accumulator..].starts_with("\n") {
-                accumulator += 1;
-            }
-            line_start
+crate fn line_index(db: &impl ParserDatabase, id: FileName) -> Arc<LineIndex> {
+    Arc::new(LineIndex::new(db.file_text(id)))
+}
+
+crate fn file_offsets(db: &impl ParserDatabase, (): ()) -> Arc<FxIndexMap<FileName, usize>> {
+    let mut offset = 0;
+    let offsets = db
+        .file_names()
+        .iter()
+        .map(|&file| {
+            let file_offset = offset;
+            offset += db.file_text(file).len();
+            (file, file_offset)
         })
-        .chain(std::iter::once(text.len()))
-        .collect()
+        .collect();
+    Arc::new(offsets)
 }
 
 crate fn location(db: &impl ParserDatabase, id: FileName, index: ByteIndex) -> Location {
-    let line_offsets = db.line_offsets(id);
-    match line_offsets.binary_search(&index.to_usize()) {
-        Ok(line) => {
-            // Found the start of a line directly:
-            return Location::new(line, 0, index);
-        }
-        Err(next_line) => {
-            let line = next_line - 1;
-
-            // Found something in the middle.
-            let line_start = line_offsets[line];
-
-            // count utf-8 characters to find column
-            let text: &str = &db.file_text(id);
-            let column = text[line_start..index.to_usize()].chars().count();
-
-            Location::new(line, column, index)
-        }
-    }
+    db.line_index(id).offset_to_line_col(index)
 }
 
 crate fn byte_index(db: &impl ParserDatabase, id: FileName, line: u64, column: u64) -> ByteIndex {
-    let line = line as usize;
-    let column = column as usize;
-    let line_offsets = db.line_offsets(id);
-    let line_start = line_offsets[line];
-    ByteIndex::from(line_start + column)
+    db.line_index(id).line_byte_col_to_offset(line as usize, column as usize)
 }
 
 crate fn descendant_entities(db: &impl ParserDatabase, root: Entity) -> Seq<Entity> {
@@ -213,6 +214,17 @@ crate fn descendant_entities(db: &impl ParserDatabase, root: Entity) -> Seq<Enti
     Seq::from(entities)
 }
 
+crate fn all_entities(db: &impl ParserDatabase, (): ()) -> Seq<Entity> {
+    let mut entities = vec![];
+    for &file in db.file_names().iter() {
+        let file_entity = EntityData::InputFile { file }.intern(db);
+
+        // The first descendant is the file entity itself.
+        entities.extend(db.descendant_entities(file_entity).iter().skip(1));
+    }
+    Seq::from(entities)
+}
+
 crate fn members(
     db: &impl ParserDatabase,
     owner: Entity,
@@ -222,20 +234,197 @@ crate fn members(
     // us to know when there were errors in the field list (i.e., by
     // returning `Err`) to suppress derived errors.  This setup won't
     // permit that.
-    Ok(db
+    let mut members: Vec<_> = db
         .child_entities(owner)
         .iter()
-        .cloned()
-        .filter_map(|child_entity| match child_entity.untern(db) {
-            EntityData::MemberName { id, kind, .. } => Some(hir::Member {
-                name: id,
-                kind,
-                entity: child_entity,
-            }),
-
-            _ => None,
-        })
-        .collect())
+        .filter_map(|&child_entity| member(db, child_entity))
+        .collect();
+
+    // The methods of any `impl` for `owner` are members too.
+    for impl_entity in impls_for(db, owner) {
+        members.extend(
+            db.child_entities(impl_entity)
+                .iter()
+                .filter_map(|&child_entity| member(db, child_entity)),
+        );
+    }
+
+    Ok(Seq::from(members))
+}
+
+/// Returns the `impl` items for `owner`, which are found alongside it.
+fn impls_for(db: &impl ParserDatabase, owner: Entity) -> Vec<Entity> {
+    match owner.untern(db) {
+        EntityData::ItemName { base, .. } => db
+            .child_entities(base)
+            .iter()
+            .filter(|&&sibling| match sibling.untern(db) {
+                EntityData::ItemName {
+                    kind: ItemKind::Impl,
+                    id,
+                    ..
+                } => db.resolve_name(sibling, id) == Some(owner),
+                _ => false,
+            })
+            .cloned()
+            .collect(),
+
+        _ => vec![],
+    }
+}
+
+crate fn impl_trait(db: &impl ParserDatabase, entity: Entity) -> WithError<Option<Entity>> {
+    db.parsed_entity(entity).thunk.parse_impl_trait(entity, db)
+}
+
+crate fn implements_trait(db: &impl ParserDatabase, entity: Entity, trait_entity: Entity) -> bool {
+    impls_for(db, entity)
+        .into_iter()
+        .any(|impl_entity| db.impl_trait(impl_entity).into_value() == Some(trait_entity))
+}
+
+crate fn alias_targets(db: &impl ParserDatabase, entity: Entity) -> Seq<Entity> {
+    match entity.untern(db) {
+        EntityData::ItemName {
+            kind: ItemKind::TypeAlias,
+            ..
+        } => db
+            .parsed_entity(entity)
+            .thunk
+            .parse_alias_targets(entity, db),
+
+        // Only aliases can be part of an alias cycle; in particular,
+        // lang items (which have no parsed entity) are never aliases.
+        _ => Seq::default(),
+    }
+}
+
+crate fn struct_is_recursive(db: &impl ParserDatabase, entity: Entity) -> bool {
+    let mut visited = FxIndexSet::default();
+    let mut stack = contained_structs(db, entity);
+    while let Some(contained) = stack.pop() {
+        if contained == entity {
+            return true;
+        }
+
+        if visited.insert(contained) {
+            stack.extend(contained_structs(db, contained));
+        }
+    }
+    false
+}
+
+/// The structs stored by value in the fields of `entity` (empty if
+/// `entity` is not a struct).
+fn contained_structs(db: &impl ParserDatabase, entity: Entity) -> Vec<Entity> {
+    let mut output = vec![];
+    if let EntityData::ItemName {
+        kind: ItemKind::Struct,
+        ..
+    } = entity.untern(db)
+    {
+        if let Ok(members) = db.members(entity) {
+            for member in members.iter() {
+                if let MemberKind::Field = member.kind {
+                    let ty = db.ty(member.entity).into_value();
+                    value_structs(db, ty, &mut output);
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Pushes onto `output` the structs that a value of type `ty` stores
+/// inline: `ty` itself, if it is an owned struct, or the elements of
+/// an owned tuple. Anything that is `shared` or `borrowed` lives
+/// elsewhere, and so breaks the cycle.
+///
+/// FIXME -- we don't look into the generic arguments of structs
+/// (e.g., `Wrapper[Foo]`), which would require substituting them into
+/// the fields of `Wrapper`.
+fn value_structs(db: &impl ParserDatabase, ty: ty::Ty<Declaration>, output: &mut Vec<Entity>) {
+    if ty.perm.untern(db) != DeclaredPermKind::Own {
+        return;
+    }
+
+    if let BoundVarOr::Known(BaseData {
+        kind: BaseKind::Named(entity),
+        generics,
+    }) = ty.base.untern(db)
+    {
+        match entity.untern(db) {
+            EntityData::ItemName {
+                kind: ItemKind::Struct,
+                ..
+            } => output.push(entity),
+
+            EntityData::LangItem(LangItem::Tuple(_)) => {
+                for element in generics.iter() {
+                    value_structs(db, element.assert_ty(), output);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+crate fn visibility_of(db: &impl ParserDatabase, entity: Entity) -> Visibility {
+    match entity.untern(db) {
+        EntityData::MemberName {
+            base,
+            kind: MemberKind::Variant,
+            ..
+        }
+        | EntityData::MemberName {
+            base,
+            kind: MemberKind::MethodSignature,
+            ..
+        } => db.visibility_of(base),
+
+        EntityData::ItemName { .. } | EntityData::MemberName { .. } => {
+            db.parsed_entity(entity).visibility
+        }
+
+        EntityData::InputFile { .. } | EntityData::LangItem(_) | EntityData::Error(_) => {
+            Visibility::Public
+        }
+    }
+}
+
+crate fn doc_of_entity(db: &impl ParserDatabase, entity: Entity) -> Option<GlobalIdentifier> {
+    match entity.untern(db) {
+        EntityData::ItemName { .. } | EntityData::MemberName { .. } => {
+            db.parsed_entity(entity).doc
+        }
+
+        EntityData::InputFile { .. } | EntityData::LangItem(_) | EntityData::Error(_) => None,
+    }
+}
+
+crate fn attributes_of(db: &impl ParserDatabase, entity: Entity) -> Seq<ParsedAttribute> {
+    match entity.untern(db) {
+        EntityData::ItemName { .. } | EntityData::MemberName { .. } => {
+            db.parsed_entity(entity).attributes
+        }
+
+        EntityData::InputFile { .. } | EntityData::LangItem(_) | EntityData::Error(_) => {
+            Seq::default()
+        }
+    }
+}
+
+fn member(db: &impl ParserDatabase, entity: Entity) -> Option<hir::Member> {
+    match entity.untern(db) {
+        EntityData::MemberName { id, kind, .. } => Some(hir::Member {
+            name: id,
+            kind,
+            entity,
+        }),
+
+        _ => None,
+    }
 }
 
 crate fn member_entity(
@@ -320,3 +509,48 @@ crate fn hover_targets(
     assert!(!targets.is_empty());
     Seq::from(targets)
 }
+
+crate fn document_symbols(db: &impl ParserDatabase, file: FileName) -> Arc<Vec<SymbolNode>> {
+    let file_entity = EntityData::InputFile { file }.intern(db);
+    let symbols = db
+        .child_entities(file_entity)
+        .iter()
+        .filter_map(|&entity| symbol_node(db, entity))
+        .collect();
+    Arc::new(symbols)
+}
+
+fn symbol_node(db: &impl ParserDatabase, entity: Entity) -> Option<SymbolNode> {
+    let (id, kind) = match entity.untern(db) {
+        EntityData::ItemName { kind, id, .. } => (id, SymbolKind::Item(kind)),
+        EntityData::MemberName { kind, id, .. } => (id, SymbolKind::Member(kind)),
+        EntityData::InputFile { .. } | EntityData::LangItem(_) | EntityData::Error(_) => {
+            return None;
+        }
+    };
+
+    // Parameter names come straight from the parsed signature, so
+    // the fn body need not be parsed.
+    let parameter_names = db.parsed_entity(entity).thunk.parse_parameter_names(db);
+    let mut children: Vec<_> = parameter_names
+        .iter()
+        .map(|name| SymbolNode {
+            name: name.value.untern(db).to_string(),
+            kind: SymbolKind::Parameter,
+            span: name.span,
+            children: vec![],
+        })
+        .collect();
+    children.extend(
+        db.child_entities(entity)
+            .iter()
+            .filter_map(|&child| symbol_node(db, child)),
+    );
+
+    Some(SymbolNode {
+        name: id.untern(db).to_string(),
+        kind,
+        span: db.entity_span(entity),
+        children,
+    })
+}
@@ -1,10 +1,14 @@
 use crate::ParserDatabase;
 use lark_entity::Entity;
 use lark_entity::EntityData;
+use lark_entity::ItemKind;
 use lark_entity::LangItem;
+use lark_entity::MemberKind;
 use lark_intern::Intern;
 use lark_intern::Untern;
+use lark_span::{FileName, Spanned};
 use lark_string::GlobalIdentifier;
+use std::ffi::OsStr;
 
 crate fn resolve_name(
     db: &impl ParserDatabase,
@@ -17,6 +21,13 @@ crate fn resolve_name(
                 .iter()
                 .cloned()
                 .filter(|entity| match entity.untern(db) {
+                    // An impl is named after its struct, but it is
+                    // not what that name refers to.
+                    EntityData::ItemName {
+                        kind: ItemKind::Impl,
+                        ..
+                    } => false,
+
                     EntityData::ItemName { id, .. } | EntityData::MemberName { id, .. } => {
                         id == name
                     }
@@ -26,11 +37,29 @@ crate fn resolve_name(
                     | EntityData::InputFile { .. } => false,
                 })
                 .next()
+                .or_else(|| {
+                    // The variants of an enum are in scope by their bare name.
+                    db.child_entities(scope)
+                        .iter()
+                        .filter(|entity| match entity.untern(db) {
+                            EntityData::ItemName {
+                                kind: ItemKind::Enum,
+                                ..
+                            } => true,
+                            _ => false,
+                        })
+                        .filter_map(|&enum_entity| {
+                            db.member_entity(enum_entity, MemberKind::Variant, name)
+                        })
+                        .next()
+                })
                 .or_else(|| {
                     // Implicit root scope:
                     let bool_id = "bool".intern(db);
                     let int_id = "int".intern(db);
                     let uint_id = "uint".intern(db);
+                    let float_id = "float".intern(db);
+                    let char_id = "char".intern(db);
                     let false_id = "false".intern(db);
                     let true_id = "true".intern(db);
                     let debug_id = "debug".intern(db);
@@ -42,6 +71,10 @@ crate fn resolve_name(
                         Some(EntityData::LangItem(LangItem::Int).intern(db))
                     } else if name == uint_id {
                         Some(EntityData::LangItem(LangItem::Uint).intern(db))
+                    } else if name == float_id {
+                        Some(EntityData::LangItem(LangItem::Float).intern(db))
+                    } else if name == char_id {
+                        Some(EntityData::LangItem(LangItem::Char).intern(db))
                     } else if name == false_id {
                         Some(EntityData::LangItem(LangItem::False).intern(db))
                     } else if name == true_id {
@@ -68,3 +101,70 @@ crate fn resolve_name(
         EntityData::Error(_) => Some(scope),
     }
 }
+
+/// Resolves a path like `geometry::Point` that appears in `scope`.
+/// The first component is resolved like any other name or, failing
+/// that, as the name of an input file (e.g., `geometry` for
+/// `geometry.lark`). Each later component names a child of the
+/// entity before it. On failure, returns the component that could
+/// not be resolved.
+crate fn resolve_path(
+    db: &dyn ParserDatabase,
+    scope: Entity,
+    path: &[Spanned<GlobalIdentifier, FileName>],
+) -> Result<Entity, Spanned<GlobalIdentifier, FileName>> {
+    let (first, rest) = path.split_first().expect("empty path");
+
+    let mut entity = match db.resolve_name(scope, first.value) {
+        Some(entity) => entity,
+        None if !rest.is_empty() => resolve_input_file(db, first.value).ok_or(*first)?,
+        None => return Err(*first),
+    };
+
+    for component in rest {
+        entity = resolve_child(db, entity, component.value).ok_or(*component)?;
+    }
+
+    Ok(entity)
+}
+
+fn resolve_input_file(db: &dyn ParserDatabase, name: GlobalIdentifier) -> Option<Entity> {
+    let name = name.untern(&db);
+    db.file_names()
+        .iter()
+        .find(|file_name| {
+            let path = file_name.id.untern(&db);
+            std::path::Path::new(&path[..]).file_stem() == Some(OsStr::new(&name[..]))
+        })
+        .map(|&file| EntityData::InputFile { file }.intern(&db))
+}
+
+fn resolve_child(
+    db: &dyn ParserDatabase,
+    entity: Entity,
+    name: GlobalIdentifier,
+) -> Option<Entity> {
+    match entity.untern(&db) {
+        EntityData::InputFile { .. } => db
+            .child_entities(entity)
+            .iter()
+            .cloned()
+            .find(|child| match child.untern(&db) {
+                EntityData::ItemName {
+                    kind: ItemKind::Impl,
+                    ..
+                } => false,
+                EntityData::ItemName { id, .. } => id == name,
+                _ => false,
+            }),
+
+        EntityData::ItemName { .. } => db
+            .members(entity)
+            .ok()?
+            .iter()
+            .find(|member| member.name == name)
+            .map(|member| member.entity),
+
+        EntityData::MemberName { .. } | EntityData::LangItem(_) | EntityData::Error(_) => None,
+    }
+}
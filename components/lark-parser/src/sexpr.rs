@@ -0,0 +1,530 @@
+//! Renders the entities of a file, along with the HIR of their fn
+//! bodies, as S-expressions. The output is deterministic and names
+//! are written out in full, which makes it convenient for golden-file
+//! tests and for debugging the parser.
+//!
+//! ```text
+//! (file
+//!   (struct Point (field x uint) (field y uint))
+//!   (def norm (param p Point) (returns uint) (* (. p x) (. p x))))
+//! ```
+
+use crate::ParserDatabase;
+use lark_entity::{Entity, EntityData, ItemKind, LangItem, MemberKind};
+use lark_hir as hir;
+use lark_intern::{Intern, Untern};
+use lark_span::FileName;
+use lark_ty::declaration::{Declaration, DeclaredPermKind};
+use lark_ty::{BaseKind, BoundVarOr, PermKind, Ty};
+
+/// Lists that render to at most this many characters are kept on
+/// one line; longer ones put each argument on its own line.
+const MAX_FLAT_WIDTH: usize = 60;
+
+/// Renders every entity in `file` as an S-expression. If `spans` is
+/// true, each HIR node is annotated with its byte range, as in
+/// `(call foo x @12..18)` or `x@16..17`.
+pub fn file_sexpr(db: &impl ParserDatabase, file: FileName, spans: bool) -> String {
+    let renderer = Renderer { db, spans };
+    let file_entity = EntityData::InputFile { file }.intern(db);
+
+    let mut elements = vec![SExpr::atom("file")];
+    elements.extend(
+        db.child_entities(file_entity)
+            .iter()
+            .map(|&entity| renderer.entity(entity)),
+    );
+
+    let mut output = String::new();
+    SExpr::List(elements).render(0, &mut output);
+    output
+}
+
+enum SExpr {
+    Atom(String),
+    List(Vec<SExpr>),
+}
+
+impl SExpr {
+    fn atom(text: impl Into<String>) -> SExpr {
+        SExpr::Atom(text.into())
+    }
+
+    fn list(head: impl Into<String>, tail: impl IntoIterator<Item = SExpr>) -> SExpr {
+        let mut elements = vec![SExpr::atom(head)];
+        elements.extend(tail);
+        SExpr::List(elements)
+    }
+
+    fn flat(&self) -> String {
+        match self {
+            SExpr::Atom(text) => text.clone(),
+            SExpr::List(elements) => {
+                let elements: Vec<_> = elements.iter().map(|e| e.flat()).collect();
+                format!("({})", elements.join(" "))
+            }
+        }
+    }
+
+    fn render(&self, indent: usize, output: &mut String) {
+        let flat = self.flat();
+        match self {
+            SExpr::List(elements) if indent + flat.len() > MAX_FLAT_WIDTH => {
+                // Keep the leading atoms (the head and, e.g., the name
+                // of a struct) on the first line.
+                let leading = elements
+                    .iter()
+                    .take_while(|e| match e {
+                        SExpr::Atom(_) => true,
+                        SExpr::List(_) => false,
+                    })
+                    .count();
+                let head: Vec<_> = elements[..leading].iter().map(|e| e.flat()).collect();
+                output.push('(');
+                output.push_str(&head.join(" "));
+                for element in &elements[leading..] {
+                    output.push('\n');
+                    output.push_str(&" ".repeat(indent + 2));
+                    element.render(indent + 2, output);
+                }
+                output.push(')');
+            }
+            _ => output.push_str(&flat),
+        }
+    }
+}
+
+struct Renderer<'db, DB: ParserDatabase> {
+    db: &'db DB,
+    spans: bool,
+}
+
+impl<DB: ParserDatabase> Renderer<'_, DB> {
+    fn entity(&self, entity: Entity) -> SExpr {
+        let db = self.db;
+        let (head, id) = match entity.untern(db) {
+            EntityData::ItemName { kind, id, .. } => (kind.keyword(), id),
+            EntityData::MemberName { kind, id, .. } => (
+                match kind {
+                    MemberKind::Field => "field",
+                    MemberKind::Variant => "variant",
+                    MemberKind::Method => "def",
+                    MemberKind::MethodSignature => "method-signature",
+                },
+                id,
+            ),
+            data @ EntityData::InputFile { .. }
+            | data @ EntityData::LangItem(_)
+            | data @ EntityData::Error(_) => {
+                return SExpr::atom(format!("{:?}", data));
+            }
+        };
+
+        let mut elements = vec![SExpr::atom(head), SExpr::atom(id.untern(db).to_string())];
+
+        match entity.untern(db) {
+            EntityData::MemberName {
+                kind: MemberKind::Field,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::TypeAlias,
+                ..
+            } => {
+                elements.push(self.ty(db.ty(entity).into_value()));
+            }
+
+            EntityData::MemberName {
+                kind: MemberKind::Variant,
+                ..
+            }
+            | EntityData::MemberName {
+                kind: MemberKind::MethodSignature,
+                ..
+            } => {
+                if let Ok(signature) = db.signature(entity).into_value() {
+                    elements.extend(
+                        signature
+                            .inputs
+                            .iter()
+                            .map(|&input| SExpr::list("param", vec![self.ty(input)])),
+                    );
+                    elements.push(SExpr::list("returns", vec![self.ty(signature.output)]));
+                }
+            }
+
+            _ => {}
+        }
+
+        if entity.untern(db).has_fn_body() {
+            let fn_body = db.fn_body(entity).into_value();
+            let cx = FnBodyRenderer {
+                renderer: self,
+                fn_body: &fn_body,
+            };
+
+            let signature = db.signature(entity).into_value();
+            if let (Ok(arguments), Ok(signature)) = (fn_body.arguments, &signature) {
+                elements.extend(arguments.iter(&fn_body).zip(signature.inputs.iter()).map(
+                    |(argument, &input)| {
+                        SExpr::list("param", vec![cx.variable(argument), self.ty(input)])
+                    },
+                ));
+                elements.push(SExpr::list("returns", vec![self.ty(signature.output)]));
+            }
+
+            elements.push(cx.expression(fn_body.root_expression));
+        }
+
+        elements.extend(
+            db.child_entities(entity)
+                .iter()
+                .map(|&child| self.entity(child)),
+        );
+
+        SExpr::List(elements)
+    }
+
+    fn ty(&self, ty: Ty<Declaration>) -> SExpr {
+        let base = self.base_ty(ty);
+        match ty.perm.untern(self.db) {
+            DeclaredPermKind::Own => base,
+            DeclaredPermKind::Share => SExpr::list("shared", vec![base]),
+            DeclaredPermKind::Borrow => SExpr::list("borrowed", vec![base]),
+        }
+    }
+
+    fn base_ty(&self, ty: Ty<Declaration>) -> SExpr {
+        let db = self.db;
+        let base_data = match ty.base.untern(db) {
+            BoundVarOr::BoundVar(var) => return SExpr::atom(format!("{:?}", var)),
+            BoundVarOr::Known(base_data) => base_data,
+        };
+
+        let entity = match base_data.kind {
+            BaseKind::Named(entity) => entity,
+            BaseKind::Placeholder(placeholder) => match placeholder {},
+            BaseKind::Error => return SExpr::atom("<error>"),
+        };
+
+        let generics: Vec<_> = base_data
+            .generics
+            .iter()
+            .map(|generic| self.ty(generic.assert_ty()))
+            .collect();
+        let name = self.entity_name(entity);
+        if generics.is_empty() {
+            SExpr::atom(name)
+        } else {
+            SExpr::list(name, generics)
+        }
+    }
+
+    fn entity_name(&self, entity: Entity) -> String {
+        let db = self.db;
+        match entity.untern(db) {
+            EntityData::LangItem(lang_item) => match lang_item {
+                LangItem::Boolean => "bool".to_string(),
+                LangItem::Int => "int".to_string(),
+                LangItem::Uint => "uint".to_string(),
+                LangItem::Float => "float".to_string(),
+                LangItem::Char => "char".to_string(),
+                LangItem::Tuple(0) => "()".to_string(),
+                LangItem::Tuple(_) => "tuple".to_string(),
+                LangItem::Range => "Range".to_string(),
+                LangItem::String => "String".to_string(),
+                LangItem::True => "true".to_string(),
+                LangItem::False => "false".to_string(),
+                LangItem::Debug => "debug".to_string(),
+            },
+            EntityData::ItemName { id, .. } | EntityData::MemberName { id, .. } => {
+                id.untern(db).to_string()
+            }
+            data @ EntityData::InputFile { .. } | data @ EntityData::Error(_) => {
+                format!("{:?}", data)
+            }
+        }
+    }
+}
+
+struct FnBodyRenderer<'me, 'db, DB: ParserDatabase> {
+    renderer: &'me Renderer<'db, DB>,
+    fn_body: &'me hir::FnBody,
+}
+
+impl<DB: ParserDatabase> FnBodyRenderer<'_, '_, DB> {
+    /// Annotates `sexpr` with the span of `index`, if spans were
+    /// requested (and `index` has one; synthesized nodes do not).
+    fn with_span(&self, sexpr: SExpr, index: impl Into<hir::MetaIndex>) -> SExpr {
+        let span = match self.fn_body.opt_span(index) {
+            Some(span) if self.renderer.spans => span,
+            _ => return sexpr,
+        };
+
+        let span = format!("@{}..{}", span.start().to_usize(), span.end().to_usize());
+        match sexpr {
+            SExpr::Atom(text) => SExpr::Atom(text + &span),
+            SExpr::List(mut elements) => {
+                elements.push(SExpr::Atom(span));
+                SExpr::List(elements)
+            }
+        }
+    }
+
+    fn text(&self, identifier: hir::Identifier) -> String {
+        self.fn_body.tables[identifier]
+            .text
+            .untern(self.renderer.db)
+            .to_string()
+    }
+
+    fn variable(&self, variable: hir::Variable) -> SExpr {
+        let name = self.text(self.fn_body.tables[variable].name);
+        self.with_span(SExpr::atom(name), variable)
+    }
+
+    fn expressions(&self, expressions: hir::List<hir::Expression>) -> Vec<SExpr> {
+        expressions
+            .iter(self.fn_body)
+            .map(|expression| self.expression(expression))
+            .collect()
+    }
+
+    fn expression(&self, expression: hir::Expression) -> SExpr {
+        let sexpr = match self.fn_body.tables[expression] {
+            hir::ExpressionData::Let {
+                variable,
+                ty,
+                initializer,
+                body,
+            } => {
+                let mut elements = vec![SExpr::atom("let"), self.variable(variable)];
+                if let Some(ty) = ty {
+                    elements.push(SExpr::list("type", vec![self.renderer.ty(ty)]));
+                }
+                if let Some(initializer) = initializer {
+                    elements.push(self.expression(initializer));
+                }
+                elements.push(self.expression(body));
+                SExpr::List(elements)
+            }
+
+            hir::ExpressionData::Place { place } => return self.place(place),
+
+            hir::ExpressionData::Assignment { place, value } => {
+                SExpr::list("=", vec![self.place(place), self.expression(value)])
+            }
+
+            hir::ExpressionData::CompoundAssignment {
+                operator,
+                place,
+                value,
+            } => SExpr::list(
+                format!("{}=", binary_operator(operator)),
+                vec![self.place(place), self.expression(value)],
+            ),
+
+            hir::ExpressionData::MethodCall { method, arguments } => {
+                let mut elements = vec![SExpr::atom("method-call"), SExpr::atom(self.text(method))];
+                elements.extend(self.expressions(arguments));
+                SExpr::List(elements)
+            }
+
+            hir::ExpressionData::Call {
+                function,
+                arguments,
+            } => {
+                let mut elements = vec![SExpr::atom("call"), self.expression(function)];
+                elements.extend(self.expressions(arguments));
+                SExpr::List(elements)
+            }
+
+            hir::ExpressionData::Sequence { first, second } => {
+                SExpr::list("seq", vec![self.expression(first), self.expression(second)])
+            }
+
+            hir::ExpressionData::If {
+                condition,
+                if_true,
+                if_false,
+            } => SExpr::list(
+                "if",
+                vec![
+                    self.expression(condition),
+                    self.expression(if_true),
+                    self.expression(if_false),
+                ],
+            ),
+
+            hir::ExpressionData::While { condition, body } => SExpr::list(
+                "while",
+                vec![self.expression(condition), self.expression(body)],
+            ),
+
+            hir::ExpressionData::For {
+                variable,
+                start,
+                end,
+                body,
+            } => SExpr::list(
+                "for",
+                vec![
+                    self.variable(variable),
+                    self.expression(start),
+                    self.expression(end),
+                    self.expression(body),
+                ],
+            ),
+
+            hir::ExpressionData::Match { scrutinee, arms } => {
+                let mut elements = vec![SExpr::atom("match"), self.expression(scrutinee)];
+                elements.extend(arms.iter_data(self.fn_body).map(|arm| {
+                    SExpr::list(
+                        "arm",
+                        vec![self.pattern(arm.pattern), self.expression(arm.body)],
+                    )
+                }));
+                SExpr::List(elements)
+            }
+
+            hir::ExpressionData::Loop { body } => SExpr::list("loop", vec![self.expression(body)]),
+
+            hir::ExpressionData::Break { value } => {
+                SExpr::list("break", vec![self.expression(value)])
+            }
+
+            hir::ExpressionData::Return { value } => {
+                SExpr::list("return", vec![self.expression(value)])
+            }
+
+            hir::ExpressionData::Binary {
+                operator,
+                left,
+                right,
+            } => SExpr::list(
+                binary_operator(operator),
+                vec![self.expression(left), self.expression(right)],
+            ),
+
+            hir::ExpressionData::Unary { operator, value } => SExpr::list(
+                match operator {
+                    hir::UnaryOperator::Not => "!",
+                    hir::UnaryOperator::Negate => "-",
+                },
+                vec![self.expression(value)],
+            ),
+
+            hir::ExpressionData::Range {
+                start,
+                end,
+                inclusive,
+            } => SExpr::list(
+                if inclusive { "..=" } else { ".." },
+                vec![
+                    start.map_or(SExpr::atom("_"), |start| self.expression(start)),
+                    end.map_or(SExpr::atom("_"), |end| self.expression(end)),
+                ],
+            ),
+
+            hir::ExpressionData::Reference { perm, place } => SExpr::list(
+                match perm {
+                    PermKind::Own => "own",
+                    PermKind::Share => "shared",
+                    PermKind::Borrow => "borrow",
+                },
+                vec![self.place(place)],
+            ),
+
+            hir::ExpressionData::Literal { data } => {
+                let value = data.value.untern(self.renderer.db);
+                SExpr::atom(match data.kind {
+                    hir::LiteralKind::String => format!("{:?}", &value[..]),
+                    hir::LiteralKind::Char => format!("'{}'", value),
+                    hir::LiteralKind::UnsignedInteger
+                    | hir::LiteralKind::Float
+                    | hir::LiteralKind::Boolean => value.to_string(),
+                })
+            }
+
+            hir::ExpressionData::Aggregate { entity, fields } => {
+                let mut elements = vec![
+                    SExpr::atom("new"),
+                    SExpr::atom(self.renderer.entity_name(entity)),
+                ];
+                elements.extend(fields.iter_data(self.fn_body).map(|field| {
+                    SExpr::list(
+                        self.text(field.identifier),
+                        vec![self.expression(field.expression)],
+                    )
+                }));
+                SExpr::List(elements)
+            }
+
+            hir::ExpressionData::Tuple { elements } => {
+                SExpr::list("tuple", self.expressions(elements))
+            }
+
+            hir::ExpressionData::Unit {} => SExpr::atom("()"),
+
+            hir::ExpressionData::Error { .. } => SExpr::atom("<error>"),
+        };
+
+        self.with_span(sexpr, expression)
+    }
+
+    fn place(&self, place: hir::Place) -> SExpr {
+        let sexpr = match self.fn_body.tables[place] {
+            hir::PlaceData::Variable(variable) => {
+                SExpr::atom(self.text(self.fn_body.tables[variable].name))
+            }
+            hir::PlaceData::Entity(entity) => SExpr::atom(self.renderer.entity_name(entity)),
+            hir::PlaceData::Temporary(expression) => return self.expression(expression),
+            hir::PlaceData::Field { owner, name } => {
+                SExpr::list(".", vec![self.place(owner), SExpr::atom(self.text(name))])
+            }
+            hir::PlaceData::TupleField { owner, index, .. } => {
+                SExpr::list(".", vec![self.place(owner), SExpr::atom(index.to_string())])
+            }
+        };
+
+        self.with_span(sexpr, place)
+    }
+
+    fn pattern(&self, pattern: hir::Pattern) -> SExpr {
+        let sexpr = match self.fn_body.tables[pattern] {
+            hir::PatternData::Wildcard => SExpr::atom("_"),
+            hir::PatternData::Binding(variable) => return self.variable(variable),
+            hir::PatternData::Literal(expression) => return self.expression(expression),
+            hir::PatternData::Struct { entity, fields } => {
+                let mut elements = vec![SExpr::atom(self.renderer.entity_name(entity))];
+                elements.extend(fields.iter_data(self.fn_body).map(|field| {
+                    SExpr::list(
+                        self.text(field.identifier),
+                        vec![self.pattern(field.pattern)],
+                    )
+                }));
+                SExpr::List(elements)
+            }
+        };
+
+        self.with_span(sexpr, pattern)
+    }
+}
+
+fn binary_operator(operator: hir::BinaryOperator) -> &'static str {
+    match operator {
+        hir::BinaryOperator::Add => "+",
+        hir::BinaryOperator::Subtract => "-",
+        hir::BinaryOperator::Multiply => "*",
+        hir::BinaryOperator::Divide => "/",
+        hir::BinaryOperator::Remainder => "%",
+        hir::BinaryOperator::Equals => "==",
+        hir::BinaryOperator::NotEquals => "!=",
+        hir::BinaryOperator::LessThan => "<",
+        hir::BinaryOperator::LessThanOrEqual => "<=",
+        hir::BinaryOperator::GreaterThan => ">",
+        hir::BinaryOperator::GreaterThanOrEqual => ">=",
+        hir::BinaryOperator::And => "&&",
+        hir::BinaryOperator::Or => "||",
+    }
+}
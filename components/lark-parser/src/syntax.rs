@@ -4,16 +4,19 @@ use crate::parser::Parser;
 use lark_debug_with::DebugWith;
 use lark_error::ErrorReported;
 
+pub mod attribute;
 pub mod delimited;
 pub mod entity;
 pub mod expression;
 pub mod fn_body;
 pub mod fn_signature;
+pub mod generics;
 pub mod guard;
 pub mod identifier;
 pub mod list;
 pub mod matched;
 pub mod member;
+pub mod path;
 pub mod sigil;
 pub mod skip_newline;
 pub mod type_reference;
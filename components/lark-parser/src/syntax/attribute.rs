@@ -0,0 +1,57 @@
+use crate::parser::Parser;
+use crate::syntax::delimited::Delimited;
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::list::CommaList;
+use crate::syntax::path::Path;
+use crate::syntax::sigil::{CloseSquare, OpenSquare, Parentheses, Pound};
+use crate::syntax::{NonEmptySyntax, Syntax};
+use lark_collections::Seq;
+use lark_debug_derive::DebugWith;
+use lark_error::ErrorReported;
+use lark_span::{FileName, Span, Spanned};
+use lark_string::GlobalIdentifier;
+
+/// An outer attribute like `#[inline]` or `#[allow(unused)]`.
+/// Attributes have no meaning (yet); any name is accepted.
+#[derive(DebugWith)]
+pub struct Attribute;
+
+#[derive(Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+pub struct ParsedAttribute {
+    /// The name of the attribute, e.g. `allow` in `#[allow(unused)]`.
+    pub path: Seq<Spanned<GlobalIdentifier, FileName>>,
+
+    /// The arguments, e.g. `unused` in `#[allow(unused)]`.
+    pub arguments: Seq<Spanned<GlobalIdentifier, FileName>>,
+
+    /// Span of the whole attribute, from `#` to `]`.
+    pub span: Span<FileName>,
+}
+
+impl Syntax<'parse> for Attribute {
+    type Data = ParsedAttribute;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(Pound)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        // Attribute = `#` `[` Path [ `(` Identifier { `,` Identifier } `)` ] `]`
+        let pound = parser.expect(Pound)?;
+        parser.expect(OpenSquare)?;
+        let path = parser.expect(Path)?;
+        let arguments = parser
+            .parse_if_present(Delimited(Parentheses, CommaList(SpannedGlobalIdentifier)))
+            .transpose()?
+            .unwrap_or_default();
+        parser.expect(CloseSquare)?;
+
+        Ok(ParsedAttribute {
+            path,
+            arguments,
+            span: pound.span.extended_until_end_of(parser.last_span()),
+        })
+    }
+}
+
+impl NonEmptySyntax<'parse> for Attribute {}
@@ -1,5 +1,7 @@
 use crate::parser::Parser;
+use crate::syntax::attribute::{Attribute, ParsedAttribute};
 use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::sigil::Pub;
 use crate::syntax::NonEmptySyntax;
 use crate::syntax::Syntax;
 use crate::ParserDatabase;
@@ -7,12 +9,16 @@ use lark_collections::Seq;
 use lark_debug_derive::DebugWith;
 use lark_debug_with::DebugWith;
 use lark_entity::Entity;
+use lark_entity::Visibility;
 use lark_error::ErrorReported;
 use lark_error::ErrorSentinel;
 use lark_error::WithError;
 use lark_hir as hir;
+use lark_intern::Intern;
 use lark_span::FileName;
 use lark_span::Span;
+use lark_span::Spanned;
+use lark_string::GlobalIdentifier;
 use lark_ty as ty;
 use lark_ty::declaration::Declaration;
 use std::sync::Arc;
@@ -32,7 +38,7 @@ impl Syntax<'parse> for EntitySyntax {
     type Data = ParsedEntity;
 
     fn test(&mut self, parser: &Parser<'_>) -> bool {
-        parser.test(SpannedGlobalIdentifier)
+        parser.test(SpannedGlobalIdentifier) || parser.test(Attribute)
     }
 
     fn expect(&mut self, parser: &mut Parser<'_>) -> Result<Self::Data, ErrorReported> {
@@ -45,6 +51,24 @@ impl Syntax<'parse> for EntitySyntax {
         // parsed by us
         // ```
 
+        let start_span = parser.peek_span();
+        let doc = parser
+            .preceding_doc_comment()
+            .map(|doc| doc.intern(&*parser));
+
+        // Any attributes, and then an optional `pub`, may precede the
+        // macro keyword.
+        let mut attributes = vec![];
+        while let Some(attribute) = parser.parse_if_present(Attribute) {
+            attributes.push(attribute?);
+            parser.skip_newlines();
+        }
+
+        let visibility = match parser.parse_if_present(Pub).transpose()? {
+            Some(_) => Visibility::Public,
+            None => Visibility::Private,
+        };
+
         let macro_name = parser.expect(SpannedGlobalIdentifier)?;
 
         log::debug!(
@@ -57,7 +81,15 @@ impl Syntax<'parse> for EntitySyntax {
             None => Err(parser.report_error("no macro with this name", macro_name.span))?,
         };
 
-        Ok(macro_definition.expect(parser, self.parent_entity, macro_name)?)
+        let parsed_entity = macro_definition.expect(parser, self.parent_entity, macro_name)?;
+
+        Ok(ParsedEntity {
+            full_span: start_span.extended_until_end_of(parsed_entity.full_span),
+            visibility,
+            doc,
+            attributes: Seq::from(attributes),
+            ..parsed_entity
+        })
     }
 }
 
@@ -79,6 +111,15 @@ pub struct ParsedEntity {
 
     /// Thunk to extract contents
     pub thunk: ParsedEntityThunk,
+
+    /// Whether the entity was declared `pub`.
+    pub visibility: Visibility,
+
+    /// The `///` comment preceding the entity, if any.
+    pub doc: Option<GlobalIdentifier>,
+
+    /// Attributes like `#[inline]` preceding the entity.
+    pub attributes: Seq<ParsedAttribute>,
 }
 
 impl ParsedEntity {
@@ -93,8 +134,16 @@ impl ParsedEntity {
             full_span,
             characteristic_span,
             thunk,
+            visibility: Visibility::default(),
+            doc: None,
+            attributes: Seq::default(),
         }
     }
+
+    /// Sets the visibility of this entity, which is otherwise private.
+    crate fn with_visibility(self, visibility: Visibility) -> Self {
+        Self { visibility, ..self }
+    }
 }
 
 /// The "parsed entity thunk" contains methods that will recursively
@@ -159,6 +208,28 @@ impl ParsedEntityThunk {
     ) -> WithError<hir::FnBody> {
         self.object.parse_fn_body(entity, db)
     }
+
+    /// See [`LazyParsedEntity::parse_impl_trait`]
+    crate fn parse_impl_trait(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<Option<Entity>> {
+        self.object.parse_impl_trait(entity, db)
+    }
+
+    /// See [`LazyParsedEntity::parse_alias_targets`]
+    crate fn parse_alias_targets(&self, entity: Entity, db: &dyn ParserDatabase) -> Seq<Entity> {
+        self.object.parse_alias_targets(entity, db)
+    }
+
+    /// See [`LazyParsedEntity::parse_parameter_names`]
+    crate fn parse_parameter_names(
+        &self,
+        db: &dyn ParserDatabase,
+    ) -> Seq<Spanned<GlobalIdentifier, FileName>> {
+        self.object.parse_parameter_names(db)
+    }
 }
 
 impl std::fmt::Debug for ParsedEntityThunk {
@@ -221,6 +292,33 @@ pub trait LazyParsedEntity {
     /// - `entity`: the entity id of self
     /// - `db`: the necessary bits/pieces of the parser database
     fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody>;
+
+    /// If this entity is an `impl Trait for Type`, resolves the trait
+    /// that it implements. Other entities implement no trait.
+    fn parse_impl_trait(
+        &self,
+        _entity: Entity,
+        _db: &dyn ParserDatabase,
+    ) -> WithError<Option<Entity>> {
+        WithError::ok(None)
+    }
+
+    /// If this entity is a type alias, returns the entities named
+    /// (anywhere) in its target type. Used to detect cyclic aliases
+    /// without computing their types.
+    fn parse_alias_targets(&self, _entity: Entity, _db: &dyn ParserDatabase) -> Seq<Entity> {
+        Seq::default()
+    }
+
+    /// If this entity is a fn or method, returns the names of its
+    /// parameters (including `self`, for methods), in order. Other
+    /// entities have no parameters.
+    fn parse_parameter_names(
+        &self,
+        _db: &dyn ParserDatabase,
+    ) -> Seq<Spanned<GlobalIdentifier, FileName>> {
+        Seq::default()
+    }
 }
 
 crate struct ErrorParsedEntity {
@@ -5,14 +5,20 @@ crate mod expr0_base;
 crate mod expr1_group;
 crate mod expr2_unary;
 crate mod expr34_math;
-crate mod expr5_eq;
+crate mod expr5_cmp;
+crate mod expr6_eq;
+crate mod expr78_logical;
+crate mod expr9_range;
 crate mod ident;
 crate mod literal;
+crate mod match_arm;
 crate mod member_access;
+crate mod pattern;
 crate mod scope;
 
 use crate::parser::Parser;
-use crate::syntax::expression::expr5_eq::Expression5;
+use crate::syntax::expression::binary::{BinaryOperator, COMPOUND_ASSIGNMENT_OPERATORS};
+use crate::syntax::expression::expr9_range::Expression9;
 use crate::syntax::expression::ident::HirIdentifier;
 use crate::syntax::expression::scope::ExpressionScope;
 use crate::syntax::sigil::{Colon, Equals};
@@ -24,6 +30,8 @@ use lark_error::ErrorReported;
 use lark_hir as hir;
 use lark_span::FileName;
 use lark_span::Span;
+use lark_ty::declaration::Declaration;
+use lark_ty::Ty;
 
 #[derive(Copy, Clone, DebugWith)]
 crate enum ParsedExpression {
@@ -53,6 +61,15 @@ impl ParsedExpression {
     }
 }
 
+impl From<ParsedExpression> for hir::MetaIndex {
+    fn from(parsed: ParsedExpression) -> hir::MetaIndex {
+        match parsed {
+            ParsedExpression::Place(p) => p.into(),
+            ParsedExpression::Expression(e) => e.into(),
+        }
+    }
+}
+
 impl hir::SpanIndex for ParsedExpression {
     fn span_from(self, tables: &hir::FnBodyTables) -> Span<FileName> {
         match self {
@@ -65,7 +82,12 @@ impl hir::SpanIndex for ParsedExpression {
 #[derive(Copy, Clone)]
 crate enum ParsedStatement {
     Expression(hir::Expression),
-    Let(Span<FileName>, hir::Variable, Option<hir::Expression>),
+    Let(
+        Span<FileName>,
+        hir::Variable,
+        Option<Ty<Declaration>>,
+        Option<hir::Expression>,
+    ),
 }
 
 #[derive(new, DebugWith)]
@@ -96,19 +118,21 @@ impl Syntax<'parse> for Expression<'me, 'parse> {
     type Data = ParsedExpression;
 
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
-        parser.test(Expression5::new(self.scope))
+        parser.test(Expression9::new(self.scope))
     }
 
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
-        // Parse `Expression5`
-        let expression = parser.expect(Expression5::new(self.scope))?;
+        // Parse `Expression9`
+        let expression = parser.expect(Expression9::new(self.scope))?;
 
-        // Check for `Expression5 = Expression5`
+        // Check for `Expression9 = Expression`. Note that the
+        // right-hand side may itself be an assignment, so `a = b = c`
+        // is parsed as `a = (b = c)`.
         if let Some(_operator) = parser.parse_if_present(Equals) {
             let place = expression.to_hir_place(self.scope);
 
             let value = parser
-                .expect(SkipNewline(Expression5::new(self.scope)))?
+                .expect(SkipNewline(Expression::new(self.scope)))?
                 .to_hir_expression(self.scope);
 
             let span = self
@@ -120,6 +144,30 @@ impl Syntax<'parse> for Expression<'me, 'parse> {
                 span,
                 hir::ExpressionData::Assignment { place, value },
             )))
+        } else if let Some(operator) =
+            parser.parse_if_present(BinaryOperator::new(COMPOUND_ASSIGNMENT_OPERATORS))
+        {
+            // Check for `Expression9 += Expression` and friends.
+            let operator = operator?;
+            let place = expression.to_hir_place(self.scope);
+
+            let value = parser
+                .expect(SkipNewline(Expression::new(self.scope)))?
+                .to_hir_expression(self.scope);
+
+            let span = self
+                .scope
+                .span(place)
+                .extended_until_end_of(parser.last_span());
+
+            Ok(ParsedExpression::Expression(self.scope.add(
+                span,
+                hir::ExpressionData::CompoundAssignment {
+                    operator,
+                    place,
+                    value,
+                },
+            )))
         } else {
             Ok(expression)
         }
@@ -129,6 +177,9 @@ impl Syntax<'parse> for Expression<'me, 'parse> {
 #[derive(new, DebugWith)]
 struct IdentifiedExpression<'me, 'parse> {
     scope: &'me mut ExpressionScope<'parse>,
+
+    /// If true, a bare `x` is accepted as shorthand for `x: x`.
+    shorthand: bool,
 }
 
 impl Syntax<'parse> for IdentifiedExpression<'me, 'parse> {
@@ -137,7 +188,7 @@ impl Syntax<'parse> for IdentifiedExpression<'me, 'parse> {
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
         let mut parser = parser.checkpoint();
         if let Some(_) = parser.parse_if_present(HirIdentifier::new(self.scope)) {
-            parser.test(Colon)
+            self.shorthand || parser.test(Colon)
         } else {
             false
         }
@@ -145,8 +196,12 @@ impl Syntax<'parse> for IdentifiedExpression<'me, 'parse> {
 
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
         let identifier = parser.expect(HirIdentifier::new(self.scope))?;
-        parser.expect(Colon)?;
-        let expression = parser.expect(SkipNewline(HirExpression::new(self.scope)))?;
+        let expression = if self.shorthand && !parser.test(Colon) {
+            self.shorthand_expression(identifier)
+        } else {
+            parser.expect(Colon)?;
+            parser.expect(SkipNewline(HirExpression::new(self.scope)))?
+        };
         let span = self
             .scope
             .span(identifier)
@@ -160,3 +215,21 @@ impl Syntax<'parse> for IdentifiedExpression<'me, 'parse> {
         ))
     }
 }
+
+impl IdentifiedExpression<'me, 'parse> {
+    /// Given the `x` in `Foo(x)`, returns an expression reading the
+    /// local variable `x`. Unlike an ordinary path, the shorthand
+    /// never refers to an item.
+    fn shorthand_expression(&mut self, identifier: hir::Identifier) -> hir::Expression {
+        let span = self.scope.span(identifier);
+        let text = self.scope[identifier].text;
+        match self.scope.variables.get(&text).cloned() {
+            Some(variable) => {
+                let place = self.scope.add(span, hir::PlaceData::Variable(variable));
+                self.scope.add(span, hir::ExpressionData::Place { place })
+            }
+
+            None => self.scope.unresolved_name_expression(span, text),
+        }
+    }
+}
@@ -43,6 +43,10 @@ impl Syntax<'parse> for CallArguments<'me, 'parse> {
 #[derive(new, DebugWith)]
 crate struct IdentifiedCallArguments<'me, 'parse> {
     scope: &'me mut ExpressionScope<'parse>,
+
+    /// True if we know that the callee is a struct, in which case
+    /// `Foo(x)` is shorthand for `Foo(x: x)`.
+    shorthand: bool,
 }
 
 impl Syntax<'parse> for IdentifiedCallArguments<'me, 'parse> {
@@ -51,7 +55,7 @@ impl Syntax<'parse> for IdentifiedCallArguments<'me, 'parse> {
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
         let mut parser = parser.checkpoint();
         if let Some(_) = parser.parse_if_present(OpenParenthesis) {
-            parser.test(IdentifiedExpression::new(self.scope))
+            parser.test(IdentifiedExpression::new(self.scope, self.shorthand))
         } else {
             false
         }
@@ -60,7 +64,7 @@ impl Syntax<'parse> for IdentifiedCallArguments<'me, 'parse> {
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
         let seq: Seq<hir::IdentifiedExpression> = parser.expect(Delimited(
             Parentheses,
-            CommaList(IdentifiedExpression::new(self.scope)),
+            CommaList(IdentifiedExpression::new(self.scope, self.shorthand)),
         ))?;
         Ok(hir::List::from_iterator(
             &mut self.scope.fn_body_tables,
@@ -64,15 +64,23 @@ where
                 );
 
                 match operator {
-                    hir::BinaryOperator::Equals | hir::BinaryOperator::NotEquals => {
-                        // Do not parse `a == b == c` etc
+                    hir::BinaryOperator::Equals
+                    | hir::BinaryOperator::NotEquals
+                    | hir::BinaryOperator::LessThan
+                    | hir::BinaryOperator::LessThanOrEqual
+                    | hir::BinaryOperator::GreaterThan
+                    | hir::BinaryOperator::GreaterThanOrEqual => {
+                        // Do not parse `a == b == c` or `a < b < c` etc
                         break;
                     }
 
                     hir::BinaryOperator::Add
                     | hir::BinaryOperator::Subtract
                     | hir::BinaryOperator::Multiply
-                    | hir::BinaryOperator::Divide => {
+                    | hir::BinaryOperator::Divide
+                    | hir::BinaryOperator::Remainder
+                    | hir::BinaryOperator::And
+                    | hir::BinaryOperator::Or => {
                         // `a + b + c` is ok
                     }
                 }
@@ -88,18 +96,42 @@ where
 crate const BINARY_OPERATORS_EXPR3: &[(&str, hir::BinaryOperator)] = &[
     ("*", hir::BinaryOperator::Multiply),
     ("/", hir::BinaryOperator::Divide),
+    ("%", hir::BinaryOperator::Remainder),
 ];
 
 crate const BINARY_OPERATORS_EXPR4: &[(&str, hir::BinaryOperator)] = &[
     ("+", hir::BinaryOperator::Add),
-    ("_", hir::BinaryOperator::Subtract),
+    ("-", hir::BinaryOperator::Subtract),
 ];
 
 crate const BINARY_OPERATORS_EXPR5: &[(&str, hir::BinaryOperator)] = &[
+    ("<", hir::BinaryOperator::LessThan),
+    ("<=", hir::BinaryOperator::LessThanOrEqual),
+    (">", hir::BinaryOperator::GreaterThan),
+    (">=", hir::BinaryOperator::GreaterThanOrEqual),
+];
+
+crate const BINARY_OPERATORS_EXPR6: &[(&str, hir::BinaryOperator)] = &[
     ("==", hir::BinaryOperator::Equals),
     ("!=", hir::BinaryOperator::NotEquals),
 ];
 
+crate const BINARY_OPERATORS_EXPR7: &[(&str, hir::BinaryOperator)] =
+    &[("&&", hir::BinaryOperator::And)];
+
+crate const BINARY_OPERATORS_EXPR8: &[(&str, hir::BinaryOperator)] =
+    &[("||", hir::BinaryOperator::Or)];
+
+/// Operators like `+=`, which combine a binary operation with an
+/// assignment to the left-hand side.
+crate const COMPOUND_ASSIGNMENT_OPERATORS: &[(&str, hir::BinaryOperator)] = &[
+    ("+=", hir::BinaryOperator::Add),
+    ("-=", hir::BinaryOperator::Subtract),
+    ("*=", hir::BinaryOperator::Multiply),
+    ("/=", hir::BinaryOperator::Divide),
+    ("%=", hir::BinaryOperator::Remainder),
+];
+
 #[derive(new, DebugWith)]
 crate struct BinaryOperator {
     operators: &'static [(&'static str, hir::BinaryOperator)],
@@ -3,7 +3,6 @@ use crate::syntax::delimited::Delimited;
 use crate::syntax::expression::scope::ExpressionScope;
 use crate::syntax::expression::ParsedStatement;
 use crate::syntax::fn_body::Statement;
-use crate::syntax::list::SeparatedList;
 use crate::syntax::sigil::{Curlies, Semicolon};
 use crate::syntax::Syntax;
 use derive_new::new;
@@ -17,13 +16,8 @@ crate struct Block<'me, 'parse> {
 }
 
 impl Block<'me, 'parse> {
-    fn definition(
-        &'a mut self,
-    ) -> Delimited<Curlies, SeparatedList<Statement<'a, 'parse>, Semicolon>> {
-        Delimited(
-            Curlies,
-            SeparatedList(Statement::new(self.scope), Semicolon),
-        )
+    fn definition(&'a mut self) -> Delimited<Curlies, Statements<'a, 'parse>> {
+        Delimited(Curlies, Statements::new(self.scope))
     }
 }
 
@@ -39,7 +33,14 @@ impl Syntax<'parse> for Block<'me, 'parse> {
         let variables_on_entry = self.scope.save_scope();
 
         let start_span = parser.peek_span();
-        let statements = parser.expect(self.definition())?;
+        let statements = parser.expect(self.definition());
+
+        // Restore the map of variables to what it used to be, so that
+        // bindings introduced in the block (even one that failed to
+        // parse) are not visible after it.
+        self.scope.restore_scope(variables_on_entry);
+
+        let (statements, trailing_semicolon) = statements?;
 
         if statements.is_empty() {
             // FIXME -- it'd be better if `Delimited` gave back a
@@ -50,11 +51,24 @@ impl Syntax<'parse> for Block<'me, 'parse> {
 
         // Convert a sequence of statements like `[a, b, c]` into a HIR tree
         // `[a, [b, c]]`.
-        let mut statements_iter = statements.into_iter().rev().cloned();
+        let mut statements_iter = statements.into_iter().rev();
 
         let mut result = match statements_iter.next().unwrap() {
+            // A trailing `;` discards the value of the final
+            // expression, so the block as a whole produces unit.
+            ParsedStatement::Expression(e) if trailing_semicolon => {
+                let unit = self.scope.unit_expression(parser.last_span());
+                let span = self.scope.span(e).extended_until_end_of(parser.last_span());
+                self.scope.add(
+                    span,
+                    hir::ExpressionData::Sequence {
+                        first: e,
+                        second: unit,
+                    },
+                )
+            }
             ParsedStatement::Expression(e) => e,
-            ParsedStatement::Let(span, variable, initializer) => {
+            ParsedStatement::Let(span, variable, ty, initializer) => {
                 // If a `let` appears as the last statement, then its associated
                 // value is just a unit expression.
                 let body = self.scope.unit_expression(parser.last_span());
@@ -62,6 +76,7 @@ impl Syntax<'parse> for Block<'me, 'parse> {
                     span,
                     hir::ExpressionData::Let {
                         variable,
+                        ty,
                         initializer,
                         body,
                     },
@@ -78,10 +93,11 @@ impl Syntax<'parse> for Block<'me, 'parse> {
                         second: result,
                     },
                 ),
-                ParsedStatement::Let(span, variable, initializer) => self.scope.add(
+                ParsedStatement::Let(span, variable, ty, initializer) => self.scope.add(
                     span,
                     hir::ExpressionData::Let {
                         variable,
+                        ty,
                         initializer,
                         body: result,
                     },
@@ -89,9 +105,49 @@ impl Syntax<'parse> for Block<'me, 'parse> {
             };
         }
 
-        // Restore the map of variables to what it used to be
-        self.scope.restore_scope(variables_on_entry);
-
         Ok(result)
     }
 }
+
+/// The statements within a block. This is like a
+/// `SeparatedList(Statement, Semicolon)`, except that we also report
+/// whether the final statement was followed by a `;`.
+#[derive(new, DebugWith)]
+struct Statements<'me, 'parse> {
+    scope: &'me mut ExpressionScope<'parse>,
+}
+
+impl Syntax<'parse> for Statements<'me, 'parse> {
+    type Data = (Vec<ParsedStatement>, bool);
+
+    fn test(&mut self, _parser: &Parser<'parse>) -> bool {
+        true // we never produce an error
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        let mut statements = vec![];
+        let mut trailing_semicolon = false;
+
+        parser.skip_newlines();
+        while let Some(statement) = parser.parse_if_present(Statement::new(self.scope)) {
+            statements.push(statement?);
+
+            trailing_semicolon = parser.parse_if_present(Semicolon).is_some();
+            let trailing_newline = parser.skip_newlines();
+            if !trailing_semicolon && !trailing_newline {
+                // Another statement on the same line, as in `let x = 1
+                // let y = 2`, is missing its `;`. Report it and carry
+                // on as if the `;` were there, rather than giving up
+                // on the rest of the block.
+                if parser.test(Statement::new(self.scope)) {
+                    parser.report_error("expected `;`", parser.elided_span());
+                    continue;
+                }
+
+                break;
+            }
+        }
+
+        Ok((statements, trailing_semicolon))
+    }
+}
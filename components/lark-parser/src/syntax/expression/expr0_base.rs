@@ -1,19 +1,27 @@
 use crate::parser::Parser;
 use crate::syntax::delimited::Delimited;
 use crate::syntax::expression::block::Block;
+use crate::syntax::expression::expr78_logical::Expression8;
+use crate::syntax::expression::ident::HirIdentifier;
 use crate::syntax::expression::literal::Literal;
+use crate::syntax::expression::match_arm::MatchArm;
 use crate::syntax::expression::scope::ExpressionScope;
 use crate::syntax::expression::ParsedExpression;
 use crate::syntax::expression::{Expression, HirExpression};
-use crate::syntax::identifier::SpannedLocalIdentifier;
-use crate::syntax::sigil::Parentheses;
+use crate::syntax::identifier::{SpannedGlobalIdentifier, SpannedLocalIdentifier};
+use crate::syntax::list::CommaList;
+use crate::syntax::sigil::{
+    CloseParenthesis, ColonColon, Comma, Curlies, DotDot, Else, If, In, OpenParenthesis,
+};
 use crate::syntax::skip_newline::SkipNewline;
 use crate::syntax::Syntax;
 use derive_new::new;
+use lark_collections::Seq;
 use lark_debug_derive::DebugWith;
 use lark_error::ErrorReported;
 use lark_hir as hir;
 use lark_intern::Intern;
+use lark_span::{FileName, Span, Spanned};
 
 #[derive(new, DebugWith)]
 crate struct Expression0<'me, 'parse> {
@@ -24,37 +32,167 @@ impl Syntax<'parse> for Expression0<'me, 'parse> {
     type Data = ParsedExpression;
 
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
-        SpannedLocalIdentifier.test(parser) || Literal::new(self.scope).test(parser)
+        SpannedLocalIdentifier.test(parser)
+            || Literal::new(self.scope).test(parser)
+            || OpenParenthesis.test(parser)
+            || Block::new(self.scope).test(parser)
     }
 
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
         // Expression0 = Identifier
+        // Expression0 = Identifier `::` Identifier { `::` Identifier }
         // Expression0 = "if" Expression Block [ "else" Block ]
+        // Expression0 = "if" Expression Block "else" Expression0(if)
+        // Expression0 = "while" Expression Block
+        // Expression0 = "loop" Block
+        // Expression0 = "for" Identifier "in" Expression8 ".." Expression8 Block
+        // Expression0 = "break" [ Expression ]
+        // Expression0 = "return" [ Expression ]
+        // Expression0 = "match" Expression `{` Comma(MatchArm) `}`
+        // Expression0 = "true" | "false"
         if parser.test(SpannedLocalIdentifier) {
             let text = parser.expect(SpannedLocalIdentifier)?;
 
+            if text.value == "true" || text.value == "false" {
+                let data = hir::LiteralData {
+                    kind: hir::LiteralKind::Boolean,
+                    value: text.value.intern(&self.scope.db),
+                };
+                let expression = self
+                    .scope
+                    .add(text.span, hir::ExpressionData::Literal { data });
+                return Ok(ParsedExpression::Expression(expression));
+            }
+
             // FIXME generalize this to any macro
             if text.value == "if" {
+                let expression = self.expect_if_tail(parser, text.span)?;
+                return Ok(ParsedExpression::Expression(expression));
+            }
+
+            if text.value == "while" {
                 let condition = parser.expect(HirExpression::new(self.scope))?;
-                let if_true = parser.expect(Block::new(self.scope))?;
-                let if_false = if let Some(b) = parser.parse_if_present(Block::new(self.scope)) {
-                    b?
-                } else {
-                    self.scope.unit_expression(parser.elided_span())
-                };
+                let body = self.expect_loop_body(parser)?;
+                let span = text.span.extended_until_end_of(parser.last_span());
+                let expression = self
+                    .scope
+                    .add(span, hir::ExpressionData::While { condition, body });
+                return Ok(ParsedExpression::Expression(expression));
+            }
+
+            if text.value == "for" {
+                let name = parser.expect(HirIdentifier::new(self.scope))?;
+                parser.expect(In)?;
+                // The endpoints are parsed below the level of range
+                // expressions, so that the `..` is left for us.
+                let start = parser
+                    .expect(Expression8::new(self.scope))?
+                    .to_hir_expression(self.scope);
+                parser.expect(DotDot)?;
+                let end = parser
+                    .expect(Expression8::new(self.scope))?
+                    .to_hir_expression(self.scope);
+
+                // The loop variable is only in scope within the body.
+                let name_span = self.scope.span(name);
+                let variable = self.scope.add(name_span, hir::VariableData { name });
+                let variables_on_entry = self.scope.save_scope();
+                self.scope.introduce_variable(variable);
+                let body = self.expect_loop_body(parser);
+                self.scope.restore_scope(variables_on_entry);
+                let body = body?;
 
+                let span = text.span.extended_until_end_of(parser.last_span());
                 let expression = self.scope.add(
-                    text.span,
-                    hir::ExpressionData::If {
-                        condition,
-                        if_true,
-                        if_false,
+                    span,
+                    hir::ExpressionData::For {
+                        variable,
+                        start,
+                        end,
+                        body,
                     },
                 );
+                return Ok(ParsedExpression::Expression(expression));
+            }
+
+            if text.value == "match" {
+                let scrutinee = parser.expect(HirExpression::new(self.scope))?;
+                let arms: Seq<hir::MatchArm> =
+                    parser.expect(Delimited(Curlies, CommaList(MatchArm::new(self.scope))))?;
+                let arms = hir::List::from_iterator(
+                    &mut self.scope.fn_body_tables,
+                    arms.iter().cloned(),
+                );
 
+                let span = text.span.extended_until_end_of(parser.last_span());
+                let expression = self
+                    .scope
+                    .add(span, hir::ExpressionData::Match { scrutinee, arms });
                 return Ok(ParsedExpression::Expression(expression));
             }
 
+            if text.value == "loop" {
+                let body = self.expect_loop_body(parser)?;
+                let span = text.span.extended_until_end_of(parser.last_span());
+                let expression = self.scope.add(span, hir::ExpressionData::Loop { body });
+                return Ok(ParsedExpression::Expression(expression));
+            }
+
+            if text.value == "break" {
+                let value = match parser.parse_if_present(HirExpression::new(self.scope)) {
+                    Some(value) => value?,
+                    None => self.scope.unit_expression(parser.elided_span()),
+                };
+                let span = text.span.extended_until_end_of(parser.last_span());
+
+                if self.scope.loop_depth == 0 {
+                    parser.report_error("`break` outside of a loop", span);
+                    let expression = self
+                        .scope
+                        .already_reported_error_expression(span, hir::ErrorData::Misc);
+                    return Ok(ParsedExpression::Expression(expression));
+                }
+
+                let expression = self.scope.add(span, hir::ExpressionData::Break { value });
+                return Ok(ParsedExpression::Expression(expression));
+            }
+
+            if text.value == "return" {
+                let value = match parser.parse_if_present(HirExpression::new(self.scope)) {
+                    Some(value) => value?,
+                    None => self.scope.unit_expression(parser.elided_span()),
+                };
+                let span = text.span.extended_until_end_of(parser.last_span());
+                let expression = self.scope.add(span, hir::ExpressionData::Return { value });
+                return Ok(ParsedExpression::Expression(expression));
+            }
+
+            // Expression0 = Identifier `::` Identifier { `::` Identifier }
+            if parser.test(ColonColon) {
+                let mut path = vec![Spanned {
+                    value: text.value.intern(&self.scope.db),
+                    span: text.span,
+                }];
+                while let Some(_) = parser.parse_if_present(ColonColon) {
+                    path.push(parser.expect(SpannedGlobalIdentifier)?);
+                }
+                let span = text.span.extended_until_end_of(parser.last_span());
+
+                match crate::scope::resolve_path(self.scope.db, self.scope.item_entity, &path) {
+                    Ok(entity) => {
+                        let place = self.scope.add(span, hir::PlaceData::Entity(entity));
+                        return Ok(ParsedExpression::Place(place));
+                    }
+
+                    Err(component) => {
+                        let error_expression = self
+                            .scope
+                            .unresolved_name_expression(component.span, component.value);
+                        return Ok(ParsedExpression::Expression(error_expression));
+                    }
+                }
+            }
+
             if let Some(variable) = self.scope.lookup_variable(text.value) {
                 let place = self
                     .scope
@@ -68,13 +206,7 @@ impl Syntax<'parse> for Expression0<'me, 'parse> {
                 return Ok(ParsedExpression::Place(place));
             }
 
-            let error_expression = self.scope.report_error_expression(
-                parser,
-                text.span,
-                hir::ErrorData::UnknownIdentifier {
-                    text: text.value.intern(&self.scope.db),
-                },
-            );
+            let error_expression = self.scope.unresolved_name_expression(text.span, id);
 
             return Ok(ParsedExpression::Expression(error_expression));
         }
@@ -85,11 +217,48 @@ impl Syntax<'parse> for Expression0<'me, 'parse> {
         }
 
         // Expression0 = `(` Expression ')'
-        if let Some(expr) = parser.parse_if_present(Delimited(
-            Parentheses,
-            SkipNewline(Expression::new(self.scope)),
-        )) {
-            return Ok(expr?);
+        if let Some(open) = parser.parse_if_present(OpenParenthesis) {
+            let open = open?;
+
+            // `()` is reserved for the unit value.
+            if parser.test(SkipNewline(CloseParenthesis)) {
+                let close = parser.expect(SkipNewline(CloseParenthesis))?;
+                let span = open.span.extended_until_end_of(close.span);
+                parser.report_error("expected an expression inside parentheses", span);
+                let error = self
+                    .scope
+                    .already_reported_error_expression(span, hir::ErrorData::Misc);
+                return Ok(ParsedExpression::Expression(error));
+            }
+
+            let expr = parser.expect(SkipNewline(Expression::new(self.scope)))?;
+
+            // Expression0 = `(` Expression `,` Comma(Expression) `)`
+            if parser.test(SkipNewline(Comma)) {
+                let mut elements = vec![expr.to_hir_expression(self.scope)];
+                while parser.parse_if_present(SkipNewline(Comma)).is_some() {
+                    match parser.parse_if_present(SkipNewline(HirExpression::new(self.scope))) {
+                        Some(element) => elements.push(element?),
+                        None => break,
+                    }
+                }
+                let close = parser.expect(SkipNewline(CloseParenthesis))?;
+
+                let span = open.span.extended_until_end_of(close.span);
+                let elements = hir::List::from_iterator(&mut self.scope.fn_body_tables, elements);
+                return Ok(ParsedExpression::Expression(
+                    self.scope.add(span, hir::ExpressionData::Tuple { elements }),
+                ));
+            }
+
+            let close = parser.expect(SkipNewline(CloseParenthesis))?;
+
+            // Extend the span of the inner expression to cover the
+            // parentheses, so that diagnostics point at the whole group.
+            let span = open.span.extended_until_end_of(close.span);
+            self.scope.set_span(expr, span);
+
+            return Ok(expr);
         }
 
         // Expression0 = `{` Block `}`
@@ -101,3 +270,49 @@ impl Syntax<'parse> for Expression0<'me, 'parse> {
         Err(parser.report_error("unrecognized start of expression", token.span))
     }
 }
+
+impl Expression0<'me, 'parse> {
+    /// Parses the rest of an `if` expression, once the `if` keyword
+    /// (found at `if_span`) has been consumed. An `if` with no `else`
+    /// gets an (elided) unit expression as its false branch.
+    fn expect_if_tail(
+        &mut self,
+        parser: &mut Parser<'parse>,
+        if_span: Span<FileName>,
+    ) -> Result<hir::Expression, ErrorReported> {
+        let condition = parser.expect(HirExpression::new(self.scope))?;
+        let if_true = parser.expect(Block::new(self.scope))?;
+        let if_false = if let Some(else_keyword) = parser.parse_if_present(Else) {
+            else_keyword?;
+            if let Some(if_keyword) = parser.parse_if_present(If) {
+                self.expect_if_tail(parser, if_keyword?.span)?
+            } else {
+                parser.expect(Block::new(self.scope))?
+            }
+        } else {
+            self.scope.unit_expression(parser.elided_span())
+        };
+
+        let span = if_span.extended_until_end_of(parser.last_span());
+        Ok(self.scope.add(
+            span,
+            hir::ExpressionData::If {
+                condition,
+                if_true,
+                if_false,
+            },
+        ))
+    }
+
+    /// Parses the body of a `while`, `for` or `loop`, within which `break`
+    /// is permitted.
+    fn expect_loop_body(
+        &mut self,
+        parser: &mut Parser<'parse>,
+    ) -> Result<hir::Expression, ErrorReported> {
+        self.scope.loop_depth += 1;
+        let body = parser.expect(Block::new(self.scope));
+        self.scope.loop_depth -= 1;
+        body
+    }
+}
@@ -7,8 +7,10 @@ use crate::syntax::expression::ParsedExpression;
 use crate::syntax::Syntax;
 use derive_new::new;
 use lark_debug_derive::DebugWith;
+use lark_entity::{EntityData, ItemKind};
 use lark_error::ErrorReported;
 use lark_hir as hir;
+use lark_intern::Untern;
 
 #[derive(new, DebugWith)]
 crate struct Expression1<'me, 'parse> {
@@ -26,10 +28,14 @@ impl Syntax<'parse> for Expression1<'me, 'parse> {
         let mut expr = parser.expect(Expression0::new(self.scope))?;
 
         // foo(f: a, g: b) -- struct construction
+        // foo(f, g) -- shorthand for `foo(f: f, g: g)`, if `foo` is a struct
         //
         // FIXME -- we probably want to support `foo.bar.baz(f: a, g:
         // b)`, too? Have to figure out the module system.
-        if let Some(fields) = parser.parse_if_present(IdentifiedCallArguments::new(self.scope)) {
+        let shorthand = self.names_struct(expr);
+        if let Some(fields) =
+            parser.parse_if_present(IdentifiedCallArguments::new(self.scope, shorthand))
+        {
             let fields = fields?;
 
             let place = expr.to_hir_place(self.scope);
@@ -79,11 +85,12 @@ impl Syntax<'parse> for Expression1<'me, 'parse> {
                     arguments,
                 },
             );
-            return Ok(ParsedExpression::Expression(expression));
+            expr = ParsedExpression::Expression(expression);
         }
 
         // foo.bar.baz
         // foo.bar.baz(a, b, c)
+        // foo(a).bar
         while let Some(member_access) = parser.parse_if_present(MemberAccess::new(expr, self.scope))
         {
             expr = member_access?;
@@ -92,3 +99,22 @@ impl Syntax<'parse> for Expression1<'me, 'parse> {
         Ok(expr)
     }
 }
+
+impl Expression1<'me, 'parse> {
+    /// True if `expr` is a path that names a struct.
+    fn names_struct(&self, expr: ParsedExpression) -> bool {
+        match expr {
+            ParsedExpression::Place(place) => match self.scope[place] {
+                hir::PlaceData::Entity(entity) => match entity.untern(&self.scope.db) {
+                    EntityData::ItemName {
+                        kind: ItemKind::Struct,
+                        ..
+                    } => true,
+                    _ => false,
+                },
+                _ => false,
+            },
+            ParsedExpression::Expression(_) => false,
+        }
+    }
+}
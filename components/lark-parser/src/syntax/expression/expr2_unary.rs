@@ -2,7 +2,7 @@ use crate::parser::Parser;
 use crate::syntax::expression::expr1_group::Expression1;
 use crate::syntax::expression::scope::ExpressionScope;
 use crate::syntax::expression::ParsedExpression;
-use crate::syntax::sigil::ExclamationPoint;
+use crate::syntax::sigil::{Borrow, ExclamationPoint, Minus, Shared};
 use crate::syntax::skip_newline::SkipNewline;
 use crate::syntax::Syntax;
 use derive_new::new;
@@ -10,6 +10,7 @@ use lark_debug_derive::DebugWith;
 use lark_error::ErrorReported;
 use lark_hir as hir;
 use lark_span::{Spanned, FileName};
+use lark_ty::PermKind;
 
 #[derive(new, DebugWith)]
 crate struct Expression2<'me, 'parse> {
@@ -22,14 +23,44 @@ impl AsMut<ExpressionScope<'parse>> for Expression2<'_, 'parse> {
     }
 }
 
+impl Expression2<'me, 'parse> {
+    /// True if we are at a reference expression like `shared x`. The
+    /// keyword must be followed by an operand; otherwise, as in
+    /// `shared + 1`, it is just the name of a variable.
+    fn test_reference(&mut self, parser: &Parser<'parse>) -> bool {
+        let mut parser = parser.checkpoint();
+        parser.parse_if_present(ReferencePerm).is_some()
+            && parser.test(Expression1::new(self.scope))
+    }
+}
+
 impl Syntax<'parse> for Expression2<'me, 'parse> {
     type Data = ParsedExpression;
 
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
-        parser.test(Expression1::new(self.scope)) || parser.test(UnaryOperator)
+        parser.test(Expression1::new(self.scope))
+            || parser.test(UnaryOperator)
+            || parser.test(ReferencePerm)
     }
 
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        // Expression2 = ( "shared" | "borrow" ) Expression2
+        if self.test_reference(parser) {
+            let perm = parser.expect(ReferencePerm)?;
+            let place = parser
+                .expect(SkipNewline(Expression2::new(self.scope)))?
+                .to_hir_place(self.scope);
+            let span = perm.span.extended_until_end_of(self.scope.span(place));
+            return Ok(ParsedExpression::Expression(self.scope.add(
+                span,
+                hir::ExpressionData::Reference {
+                    perm: perm.value,
+                    place,
+                },
+            )));
+        }
+
+        // Expression2 = UnaryOperator Expression2
         if let Some(operator) = parser.parse_if_present(UnaryOperator) {
             let operator = operator?;
             let value = parser
@@ -56,11 +87,36 @@ impl Syntax<'parse> for UnaryOperator {
     type Data = Spanned<hir::UnaryOperator, FileName>;
 
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
-        parser.test(ExclamationPoint)
+        parser.test(ExclamationPoint) || parser.test(Minus)
     }
 
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        if let Some(spanned) = parser.parse_if_present(Minus) {
+            return Ok(spanned?.map(|_| hir::UnaryOperator::Negate));
+        }
+
         let spanned = parser.expect(ExclamationPoint)?;
         Ok(spanned.map(|_| hir::UnaryOperator::Not))
     }
 }
+
+/// The keyword that begins a reference expression like `shared x`.
+#[derive(new, DebugWith)]
+struct ReferencePerm;
+
+impl Syntax<'parse> for ReferencePerm {
+    type Data = Spanned<PermKind, FileName>;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(Shared) || parser.test(Borrow)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        if let Some(spanned) = parser.parse_if_present(Shared) {
+            return Ok(spanned?.map(|_| PermKind::Share));
+        }
+
+        let spanned = parser.expect(Borrow)?;
+        Ok(spanned.map(|_| PermKind::Borrow))
+    }
+}
@@ -0,0 +1,31 @@
+use crate::parser::Parser;
+use crate::syntax::expression::binary::{
+    BinaryOperator, BinaryOperatorExpression, BINARY_OPERATORS_EXPR6,
+};
+use crate::syntax::expression::expr5_cmp::Expression5;
+use crate::syntax::expression::scope::ExpressionScope;
+use crate::syntax::expression::ParsedExpression;
+use crate::syntax::Syntax;
+use derive_new::new;
+use lark_debug_derive::DebugWith;
+use lark_error::ErrorReported;
+
+#[derive(new, DebugWith)]
+crate struct Expression6<'me, 'parse> {
+    scope: &'me mut ExpressionScope<'parse>,
+}
+
+impl Syntax<'parse> for Expression6<'me, 'parse> {
+    type Data = ParsedExpression;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(Expression5::new(self.scope))
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        parser.expect(BinaryOperatorExpression::new(
+            Expression5::new(self.scope),
+            BinaryOperator::new(BINARY_OPERATORS_EXPR6),
+        ))
+    }
+}
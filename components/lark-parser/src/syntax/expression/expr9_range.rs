@@ -0,0 +1,93 @@
+use crate::parser::Parser;
+use crate::syntax::expression::expr78_logical::Expression8;
+use crate::syntax::expression::scope::ExpressionScope;
+use crate::syntax::expression::ParsedExpression;
+use crate::syntax::sigil::{DotDot, DotDotEquals};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::Syntax;
+use derive_new::new;
+use lark_debug_derive::DebugWith;
+use lark_error::ErrorReported;
+use lark_hir as hir;
+use lark_span::{FileName, Spanned};
+
+/// Range expressions like `a..b`. These bind more loosely than any
+/// binary operator, so `a + 1..b` is `(a + 1)..b`.
+#[derive(new, DebugWith)]
+crate struct Expression9<'me, 'parse> {
+    scope: &'me mut ExpressionScope<'parse>,
+}
+
+impl Syntax<'parse> for Expression9<'me, 'parse> {
+    type Data = ParsedExpression;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(Expression8::new(self.scope)) || parser.test(RangeOperator)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        // Expression9 = Expression8
+        // Expression9 = [ Expression8 ] `..` [ Expression8 ]
+        // Expression9 = [ Expression8 ] `..=` Expression8
+        let start = match parser.parse_if_present(Expression8::new(self.scope)) {
+            Some(start) => {
+                let start = start?;
+                if !parser.test(RangeOperator) {
+                    return Ok(start);
+                }
+                Some(start.to_hir_expression(self.scope))
+            }
+            None => None,
+        };
+
+        let operator = parser.expect(RangeOperator)?;
+        let inclusive = operator.value;
+
+        // An inclusive range must have an end; for an exclusive one,
+        // it is optional.
+        let end = if inclusive {
+            let end = parser.expect(SkipNewline(Expression8::new(self.scope)))?;
+            Some(end.to_hir_expression(self.scope))
+        } else {
+            match parser.parse_if_present(Expression8::new(self.scope)) {
+                Some(end) => Some(end?.to_hir_expression(self.scope)),
+                None => None,
+            }
+        };
+
+        let start_span = match start {
+            Some(start) => self.scope.span(start),
+            None => operator.span,
+        };
+        let span = start_span.extended_until_end_of(parser.last_span());
+        Ok(ParsedExpression::Expression(self.scope.add(
+            span,
+            hir::ExpressionData::Range {
+                start,
+                end,
+                inclusive,
+            },
+        )))
+    }
+}
+
+/// `..` or `..=`; the data is true for the inclusive form.
+#[derive(new, DebugWith)]
+struct RangeOperator;
+
+impl Syntax<'parse> for RangeOperator {
+    type Data = Spanned<bool, FileName>;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(DotDot) || parser.test(DotDotEquals)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        if let Some(spanned) = parser.parse_if_present(DotDotEquals) {
+            return Ok(spanned?.map(|_| true));
+        }
+
+        let spanned = parser.expect(DotDot)?;
+        Ok(spanned.map(|_| false))
+    }
+}
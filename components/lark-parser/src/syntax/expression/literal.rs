@@ -7,6 +7,8 @@ use lark_debug_derive::DebugWith;
 use lark_error::ErrorReported;
 use lark_hir as hir;
 use lark_intern::Intern;
+use lark_span::Span;
+use std::ops::Range;
 
 #[derive(new, DebugWith)]
 crate struct Literal<'me, 'parse> {
@@ -17,21 +19,224 @@ impl Syntax<'parse> for Literal<'me, 'parse> {
     type Data = hir::Expression;
 
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
-        parser.is(LexToken::Integer) || parser.is(LexToken::String)
+        parser.is(LexToken::Integer)
+            || parser.is(LexToken::Float)
+            || parser.is(LexToken::String)
+            || parser.is(LexToken::Char)
+            || is_unterminated_string(parser)
     }
 
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        if is_unterminated_string(parser) {
+            let token = parser.shift();
+            return Err(parser.report_error("unterminated string literal", token.span));
+        }
+
         let text = parser.peek_str();
         let token = parser.shift();
-        let kind = match token.value {
-            LexToken::Integer => hir::LiteralKind::UnsignedInteger,
-            LexToken::String => hir::LiteralKind::String,
+        let data = match token.value {
+            LexToken::Integer => {
+                if has_leading_zero(text) {
+                    parser.report_error("integer literals cannot have leading zeros", token.span);
+                    return Ok(self
+                        .scope
+                        .already_reported_error_expression(token.span, hir::ErrorData::Misc));
+                }
+
+                // The literal is stored in decimal, without
+                // underscores, so later passes need not decode it.
+                match decode_integer(text) {
+                    Ok(value) => hir::LiteralData {
+                        kind: hir::LiteralKind::UnsignedInteger,
+                        value: value.to_string().intern(parser),
+                    },
+                    Err((message, range)) => {
+                        let start = token.span.start().to_usize();
+                        let span =
+                            Span::new(token.span.file(), start + range.start, start + range.end);
+                        parser.report_error(message, span);
+                        return Ok(self
+                            .scope
+                            .already_reported_error_expression(token.span, hir::ErrorData::Misc));
+                    }
+                }
+            }
+
+            LexToken::Float => hir::LiteralData {
+                kind: hir::LiteralKind::Float,
+                value: text.intern(parser),
+            },
+
+            // Backslashes in raw strings are not escapes.
+            LexToken::String if text.starts_with('r') => hir::LiteralData {
+                kind: hir::LiteralKind::String,
+                value: raw_string_contents(text).intern(parser),
+            },
+
+            LexToken::String => match unescape(&text[1..text.len() - 1]) {
+                Some(contents) => hir::LiteralData {
+                    kind: hir::LiteralKind::String,
+                    value: contents.intern(parser),
+                },
+                None => {
+                    parser.report_error("invalid escape sequence in string literal", token.span);
+                    return Ok(self
+                        .scope
+                        .already_reported_error_expression(token.span, hir::ErrorData::Misc));
+                }
+            },
+
+            LexToken::Char => match unescape(&text[1..text.len() - 1]) {
+                Some(contents) => {
+                    if contents.chars().count() != 1 {
+                        parser.report_error(
+                            "character literals must contain exactly one character",
+                            token.span,
+                        );
+                        return Ok(self
+                            .scope
+                            .already_reported_error_expression(token.span, hir::ErrorData::Misc));
+                    }
+
+                    hir::LiteralData {
+                        kind: hir::LiteralKind::Char,
+                        value: contents.intern(parser),
+                    }
+                }
+                None => {
+                    parser.report_error("invalid escape sequence in character literal", token.span);
+                    return Ok(self
+                        .scope
+                        .already_reported_error_expression(token.span, hir::ErrorData::Misc));
+                }
+            },
+
             _ => return Err(parser.report_error("expected a literal", token.span)),
         };
-        let value = text.intern(parser);
-        let data = hir::LiteralData { kind, value };
+
         Ok(self
             .scope
             .add(token.span, hir::ExpressionData::Literal { data }))
     }
 }
+
+/// The lexer produces an error token for a string literal that runs
+/// into the end of the file (for raw strings, covering just the
+/// opening delimiter); we claim it here so that we can give a better
+/// message than "unrecognized start of expression".
+fn is_unterminated_string(parser: &Parser<'_>) -> bool {
+    let text = parser.peek_str();
+    parser.is(LexToken::Error)
+        && (text.starts_with('"') || text.starts_with("r\"") || text.starts_with("r#"))
+}
+
+/// Strips the delimiters from a raw string literal like `r#"..."#`.
+fn raw_string_contents(text: &str) -> &str {
+    let hashes = text[1..].chars().take_while(|&c| c == '#').count();
+    &text[hashes + 2..text.len() - hashes - 1]
+}
+
+/// True for integer literals like `007`; `0` on its own is fine, as
+/// are radix prefixes like `0x`.
+fn has_leading_zero(text: &str) -> bool {
+    if text.len() > 1 && text[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return false;
+    }
+
+    let mut digits = text.chars().filter(|&c| c != '_');
+    match (digits.next(), digits.next()) {
+        (Some('0'), Some(_)) => true,
+        _ => false,
+    }
+}
+
+/// Decodes an integer literal like `1_000`, `0xFF`, `0o17` or
+/// `0b1010`. On failure, returns an error message along with the
+/// byte range within `text` that it applies to.
+fn decode_integer(text: &str) -> Result<u128, (String, Range<usize>)> {
+    let (radix, offset) = match text.get(..2) {
+        Some("0x") => (16, 2),
+        Some("0o") => (8, 2),
+        Some("0b") => (2, 2),
+        _ => (10, 0),
+    };
+
+    let digits = &text[offset..];
+    if digits.is_empty() {
+        return Err(("expected digits after radix prefix".to_string(), 0..offset));
+    }
+    if digits.starts_with('_') {
+        return Err((
+            "underscores cannot follow a radix prefix".to_string(),
+            offset..offset + 1,
+        ));
+    }
+
+    let mut value: u128 = 0;
+    for (index, c) in digits.char_indices() {
+        if c == '_' {
+            continue;
+        }
+
+        let digit = match c.to_digit(radix) {
+            Some(digit) => digit,
+            None => {
+                let start = offset + index;
+                return Err((
+                    format!("invalid digit for a base {} literal", radix),
+                    start..start + c.len_utf8(),
+                ));
+            }
+        };
+
+        value = match value
+            .checked_mul(u128::from(radix))
+            .and_then(|value| value.checked_add(u128::from(digit)))
+        {
+            Some(value) => value,
+            None => return Err(("integer literal is too large".to_string(), 0..text.len())),
+        };
+    }
+
+    Ok(value)
+}
+
+/// Decodes the escape sequences in the contents of a string or
+/// character literal (with the quotes already stripped). Returns
+/// `None` if an unknown or malformed escape sequence is found.
+fn unescape(text: &str) -> Option<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => result.push('\n'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            '"' => result.push('"'),
+            '\'' => result.push('\''),
+            '\\' => result.push('\\'),
+            'u' => {
+                // `\u{1F600}`
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let mut digits = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        c => digits.push(c),
+                    }
+                }
+                let value = u32::from_str_radix(&digits, 16).ok()?;
+                result.push(std::char::from_u32(value)?);
+            }
+            _ => return None,
+        }
+    }
+    Some(result)
+}
@@ -0,0 +1,45 @@
+use crate::parser::Parser;
+use crate::syntax::expression::pattern::HirPattern;
+use crate::syntax::expression::scope::ExpressionScope;
+use crate::syntax::expression::HirExpression;
+use crate::syntax::sigil::FatArrow;
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::Syntax;
+use derive_new::new;
+use lark_debug_derive::DebugWith;
+use lark_error::ErrorReported;
+use lark_hir as hir;
+
+/// `P => E`, within a `match`.
+#[derive(new, DebugWith)]
+crate struct MatchArm<'me, 'parse> {
+    scope: &'me mut ExpressionScope<'parse>,
+}
+
+impl Syntax<'parse> for MatchArm<'me, 'parse> {
+    type Data = hir::MatchArm;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(HirPattern::new(self.scope))
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        // The variables bound by the pattern are only in scope within
+        // this arm.
+        let variables_on_entry = self.scope.save_scope();
+        let arm: Result<_, ErrorReported> = try {
+            let pattern = parser.expect(HirPattern::new(self.scope))?;
+            parser.expect(FatArrow)?;
+            let body = parser.expect(SkipNewline(HirExpression::new(self.scope)))?;
+            (pattern, body)
+        };
+        self.scope.restore_scope(variables_on_entry);
+        let (pattern, body) = arm?;
+
+        let span = self
+            .scope
+            .span(pattern)
+            .extended_until_end_of(self.scope.span(body));
+        Ok(self.scope.add(span, hir::MatchArmData { pattern, body }))
+    }
+}
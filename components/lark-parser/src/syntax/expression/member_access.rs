@@ -1,3 +1,4 @@
+use crate::lexer::token::LexToken;
 use crate::parser::Parser;
 use crate::syntax::expression::args::CallArguments;
 use crate::syntax::expression::ident::HirIdentifier;
@@ -10,6 +11,7 @@ use derive_new::new;
 use lark_debug_derive::DebugWith;
 use lark_error::ErrorReported;
 use lark_hir as hir;
+use lark_intern::Intern;
 
 #[derive(new, DebugWith)]
 crate struct MemberAccess<'me, 'parse> {
@@ -36,6 +38,34 @@ impl Syntax<'parse> for MemberAccess<'me, 'parse> {
 
     fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
         parser.expect(SkipNewline(Dot))?;
+
+        // `<owner>.N` indexes into a tuple.
+        if parser.is(LexToken::Integer) {
+            let text = parser.peek_str();
+            let token = parser.shift();
+            let index = match text.parse::<usize>() {
+                Ok(index) => index,
+                Err(_) => return Err(parser.report_error("invalid tuple index", token.span)),
+            };
+
+            let index_name = self.scope.add(
+                token.span,
+                hir::IdentifierData {
+                    text: text.intern(parser),
+                },
+            );
+            let owner = self.owner.to_hir_place(self.scope);
+            let span = self.scope.span(owner).extended_until_end_of(token.span);
+            return Ok(ParsedExpression::Place(self.scope.add(
+                span,
+                hir::PlaceData::TupleField {
+                    owner,
+                    index,
+                    index_name,
+                },
+            )));
+        }
+
         let member_name = parser.expect(HirIdentifier::new(self.scope))?;
 
         if let Some(arguments) =
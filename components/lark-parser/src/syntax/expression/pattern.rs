@@ -0,0 +1,130 @@
+use crate::parser::Parser;
+use crate::syntax::delimited::Delimited;
+use crate::syntax::expression::ident::HirIdentifier;
+use crate::syntax::expression::literal::Literal;
+use crate::syntax::expression::scope::ExpressionScope;
+use crate::syntax::identifier::SpannedLocalIdentifier;
+use crate::syntax::list::CommaList;
+use crate::syntax::sigil::{Colon, OpenParenthesis, Parentheses};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::Syntax;
+use derive_new::new;
+use lark_collections::Seq;
+use lark_debug_derive::DebugWith;
+use lark_error::ErrorReported;
+use lark_hir as hir;
+use lark_intern::Intern;
+
+/// Parses a pattern, such as appears in a `match` arm. Any variables
+/// bound by the pattern are brought into scope as a side effect; it
+/// is up to the caller to save/restore the scope around it.
+#[derive(new, DebugWith)]
+crate struct HirPattern<'me, 'parse> {
+    scope: &'me mut ExpressionScope<'parse>,
+}
+
+impl Syntax<'parse> for HirPattern<'me, 'parse> {
+    type Data = hir::Pattern;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(SpannedLocalIdentifier) || parser.test(Literal::new(self.scope))
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        // Pattern = Literal
+        if let Some(literal) = parser.parse_if_present(Literal::new(self.scope)) {
+            let literal = literal?;
+            let span = self.scope.span(literal);
+            return Ok(self.scope.add(span, hir::PatternData::Literal(literal)));
+        }
+
+        let text = parser.expect(SpannedLocalIdentifier)?;
+
+        // Pattern = "_"
+        if text.value == "_" {
+            return Ok(self.scope.add(text.span, hir::PatternData::Wildcard));
+        }
+
+        // Pattern = "true" | "false"
+        if text.value == "true" || text.value == "false" {
+            let data = hir::LiteralData {
+                kind: hir::LiteralKind::Boolean,
+                value: text.value.intern(&self.scope.db),
+            };
+            let literal = self
+                .scope
+                .add(text.span, hir::ExpressionData::Literal { data });
+            return Ok(self.scope.add(text.span, hir::PatternData::Literal(literal)));
+        }
+
+        // Pattern = Identifier `(` IdentifiedPattern* `)`
+        if parser.test(OpenParenthesis) {
+            let id = text.value.intern(&self.scope.db);
+            let entity = match self.scope.db.resolve_name(self.scope.item_entity, id) {
+                Some(entity) => entity,
+                None => {
+                    return Err(parser.report_error(
+                        format!("unknown struct `{}`", text.value),
+                        text.span,
+                    ));
+                }
+            };
+
+            let fields: Seq<hir::IdentifiedPattern> = parser.expect(Delimited(
+                Parentheses,
+                CommaList(IdentifiedPattern::new(self.scope)),
+            ))?;
+            let fields = hir::List::from_iterator(
+                &mut self.scope.fn_body_tables,
+                fields.iter().cloned(),
+            );
+
+            let span = text.span.extended_until_end_of(parser.last_span());
+            return Ok(self
+                .scope
+                .add(span, hir::PatternData::Struct { entity, fields }));
+        }
+
+        // Pattern = Identifier
+        let name = self.scope.add(
+            text.span,
+            hir::IdentifierData {
+                text: text.value.intern(&self.scope.db),
+            },
+        );
+        let variable = self.scope.add(text.span, hir::VariableData { name });
+        self.scope.introduce_variable(variable);
+        Ok(self.scope.add(text.span, hir::PatternData::Binding(variable)))
+    }
+}
+
+/// `a: P`, within a struct pattern.
+#[derive(new, DebugWith)]
+struct IdentifiedPattern<'me, 'parse> {
+    scope: &'me mut ExpressionScope<'parse>,
+}
+
+impl Syntax<'parse> for IdentifiedPattern<'me, 'parse> {
+    type Data = hir::IdentifiedPattern;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(HirIdentifier::new(self.scope))
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        let identifier = parser.expect(HirIdentifier::new(self.scope))?;
+        parser.expect(Colon)?;
+        let pattern = parser.expect(SkipNewline(HirPattern::new(self.scope)))?;
+        let span = self
+            .scope
+            .span(identifier)
+            .extended_until_end_of(self.scope.span(pattern));
+        Ok(self.scope.add(
+            span,
+            hir::IdentifiedPatternData {
+                identifier,
+                pattern,
+            },
+        ))
+    }
+}
@@ -21,6 +21,10 @@ crate struct ExpressionScope<'parse> {
     crate variables: Rc<FxIndexMap<GlobalIdentifier, hir::Variable>>,
 
     crate fn_body_tables: hir::FnBodyTables,
+
+    /// Number of loops enclosing the expression being parsed; `break`
+    /// is only legal when this is non-zero.
+    crate loop_depth: usize,
 }
 
 impl ExpressionScope<'parse> {
@@ -28,6 +32,13 @@ impl ExpressionScope<'parse> {
         node.span_from(&self.fn_body_tables)
     }
 
+    /// Replaces the span recorded for `node`; used when we learn
+    /// that a node extends further than we thought (e.g., because it
+    /// is wrapped in parentheses).
+    crate fn set_span(&mut self, node: impl Into<hir::MetaIndex>, span: Span<FileName>) {
+        self.fn_body_tables.spans.insert(node.into(), span);
+    }
+
     crate fn save_scope(&self) -> Rc<FxIndexMap<GlobalIdentifier, hir::Variable>> {
         self.variables.clone()
     }
@@ -73,7 +84,7 @@ impl ExpressionScope<'parse> {
                 "can only supply named arguments when constructing structs".to_string()
             }
             hir::ErrorData::UnknownIdentifier { text } => {
-                format!("unknown identifier `{}`", text.untern(&self.db))
+                format!("cannot find `{}`", text.untern(&self.db))
             }
         };
 
@@ -91,6 +102,17 @@ impl ExpressionScope<'parse> {
         self.add(span, hir::ExpressionData::Error { error })
     }
 
+    /// Creates an error expression for `text`, a name that could not
+    /// be resolved. No error is reported here; the type checker
+    /// reports it when it encounters the expression.
+    crate fn unresolved_name_expression(
+        &mut self,
+        span: Span<FileName>,
+        text: GlobalIdentifier,
+    ) -> hir::Expression {
+        self.already_reported_error_expression(span, hir::ErrorData::UnknownIdentifier { text })
+    }
+
     crate fn unit_expression(&mut self, span: Span<FileName>) -> hir::Expression {
         self.add(span, hir::ExpressionData::Unit {})
     }
@@ -5,8 +5,9 @@ use crate::syntax::expression::ident::HirIdentifier;
 use crate::syntax::expression::scope::ExpressionScope;
 use crate::syntax::expression::{HirExpression, ParsedStatement};
 use crate::syntax::guard::Guard;
-use crate::syntax::sigil::{Equals, Let};
+use crate::syntax::sigil::{Colon, Equals, Let};
 use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::type_reference::TypeReference;
 use crate::syntax::Syntax;
 use crate::ParserDatabase;
 use derive_new::new;
@@ -40,14 +41,39 @@ use std::sync::Arc;
 // # Factored into "almost LL" form:
 //
 // Expression = {
+//   Expression9,
+//   Expression9 `=` Expression,
+//   Expression9 CompoundOp Expression,  // `+=`, `-=`, `*=`, `/=`, `%=`
+// }
+//
+// Expression9 = {
+//   Expression8,
+//   [ Expression8 ] `..` [ Expression8 ],
+//   [ Expression8 ] `..=` Expression8,
+// }
+//
+// Expression8 = {
+//   Expression7,
+//   Expression8 \n* `||` Expression7,
+// }
+//
+// Expression7 = {
+//   Expression6,
+//   Expression7 \n* `&&` Expression6,
+// }
+//
+// Expression6 = {
 //   Expression5,
-//   Expression5 `=` Expression5,
+//   Expression5 \n* `==` Expression5,
+//   Expression5 \n* `!=` Expression5,
 // }
 //
 // Expression5 = {
 //   Expression4,
-//   Expression4 \n* `==` Expression4,
-//   Expression4 \n* `!=` Expression4,
+//   Expression4 \n* `<` Expression4,
+//   Expression4 \n* `<=` Expression4,
+//   Expression4 \n* `>` Expression4,
+//   Expression4 \n* `>=` Expression4,
 // }
 //
 // Expression4 = {
@@ -60,11 +86,14 @@ use std::sync::Arc;
 //   Expression2,
 //   Expression3 \n* `*` Expression2,
 //   Expression3 \n* `/` Expression2,
+//   Expression3 \n* `%` Expression2,
 // }
 //
 // Expression2 = {
 //   Expression1,
 //   UnaryOp Expression0,
+//   "shared" Expression2,
+//   "borrow" Expression2,
 // }
 //
 // Expression1 = {
@@ -75,6 +104,7 @@ use std::sync::Arc;
 //
 // MemberAccess = {
 //   \n* `.` Identifier,
+//   \n* `.` Integer,
 //   `(` Comma(Expression) `)`,
 // }
 //
@@ -82,17 +112,36 @@ use std::sync::Arc;
 //   Literal
 //   Identifier,
 //   `(` \n* Expression \n* `)`,  // Should we allow newlines *anywhere* here?
+//   `(` Expression `,` Comma(Expression) `)`,
 //   Block,
 //   "if" Expression Block [ "else" Block ]
+//   "if" Expression Block "else" Expression0(if)
+//   "while" Expression Block
+//   "loop" Block
+//   "for" Identifier "in" Expression8 ".." Expression8 Block
+//   "break" [ Expression ]
+//   "return" [ Expression ]
+//   "match" Expression `{` Comma(MatchArm) `}`
+// }
+//
+// MatchArm = {
+//   Pattern `=>` \n* Expression
+// }
+//
+// Pattern = {
+//   `_`,
+//   Literal,
+//   Identifier,
+//   Identifier `(` Comma(Identifier `:` \n* Pattern) `)`,
 // }
 //
 // Block = {
-//   `{` Statement* \n* `}`
+//   `{` Statement* \n* `}`  // value of the final statement, unless it ends in `;`
 // }
 //
 // Statement = {
 //   \n* Expression Terminator,
-//   \n* `let` Identifier [`:` Ty ] `=` Expression Terminator,
+//   \n* `let` Identifier [`:` Ty ] [`=` Expression] Terminator,
 // }
 //
 // Terminator = {
@@ -118,6 +167,7 @@ crate fn parse_fn_body(
         item_entity,
         variables: Default::default(),
         fn_body_tables: Default::default(),
+        loop_depth: 0,
     };
 
     let arguments: Vec<_> = self_argument
@@ -197,6 +247,12 @@ impl Syntax<'parse> for LetStatement<'me, 'parse> {
         let let_keyword = parser.expect(Let)?;
         let name = parser.expect(HirIdentifier::new(self.scope))?;
 
+        let mut ty = None;
+        if let Some(type_reference) = parser.parse_if_present(Guard(Colon, TypeReference)) {
+            let declared_ty = type_reference?.parse_type(self.scope.item_entity, self.scope.db);
+            ty = Some(parser.accumulate_errors(declared_ty));
+        }
+
         let mut initializer = None;
         if let Some(expression) =
             parser.parse_if_present(Guard(Equals, SkipNewline(HirExpression::new(self.scope))))
@@ -204,6 +260,11 @@ impl Syntax<'parse> for LetStatement<'me, 'parse> {
             initializer = Some(expression?);
         }
 
+        if ty.is_none() && initializer.is_none() {
+            let span = let_keyword.span.extended_until_end_of(parser.last_span());
+            parser.report_error("`let` requires a type or an initializer", span);
+        }
+
         let span = let_keyword.span.extended_until_end_of(parser.peek_span());
 
         let name_span = self.scope.span(name);
@@ -215,6 +276,6 @@ impl Syntax<'parse> for LetStatement<'me, 'parse> {
         // the set of variable bindings.
         self.scope.introduce_variable(variable);
 
-        Ok(ParsedStatement::Let(span, variable, initializer))
+        Ok(ParsedStatement::Let(span, variable, ty, initializer))
     }
 }
@@ -3,16 +3,17 @@ use crate::syntax::delimited::Delimited;
 use crate::syntax::entity::ErrorParsedEntity;
 use crate::syntax::entity::LazyParsedEntity;
 use crate::syntax::fn_body;
+use crate::syntax::generics::{ParsedConstraint, WhereClause};
 use crate::syntax::guard::Guard;
 use crate::syntax::identifier::SpannedGlobalIdentifier;
 use crate::syntax::list::CommaList;
 use crate::syntax::matched::Matched;
 use crate::syntax::matched::ParsedMatch;
 use crate::syntax::member::{Field, ParsedField};
-use crate::syntax::sigil::{Curlies, Parentheses, RightArrow};
+use crate::syntax::sigil::{Curlies, Parentheses, RightArrow, SelfValue};
 use crate::syntax::skip_newline::SkipNewline;
 use crate::syntax::type_reference::ParsedTypeReference;
-use crate::syntax::type_reference::TypeReference;
+use crate::syntax::type_reference::{TypeMode, TypeReference};
 use crate::syntax::Syntax;
 use crate::ParserDatabase;
 use lark_collections::Seq;
@@ -22,20 +23,25 @@ use lark_error::ErrorReported;
 use lark_error::ResultExt;
 use lark_error::WithError;
 use lark_hir as hir;
-use lark_intern::Untern;
+use lark_intern::{Intern, Untern};
 use lark_span::FileName;
+use lark_span::Span;
 use lark_span::Spanned;
 use lark_string::GlobalIdentifier;
 use lark_ty as ty;
-use lark_ty::declaration::Declaration;
+use lark_ty::declaration::{Declaration, DeclaredPermKind};
 
 #[derive(DebugWith)]
 pub struct FunctionSignature;
 
 #[derive(Clone, DebugWith)]
 pub struct ParsedFunctionSignature {
+    /// An explicit `self` parameter, like `shared self`; only
+    /// permitted on methods.
+    pub self_parameter: Option<ParsedSelfParameter>,
     pub parameters: Seq<Spanned<ParsedField, FileName>>,
     pub return_type: ParsedTypeReference,
+    pub constraints: Seq<ParsedConstraint>,
     pub body: Result<Spanned<ParsedMatch, FileName>, ErrorReported>,
 }
 
@@ -47,9 +53,12 @@ impl Syntax<'parse> for FunctionSignature {
     }
 
     fn expect(&mut self, parser: &mut Parser<'_>) -> Result<Self::Data, ErrorReported> {
-        let parameters = parser
-            .expect(SkipNewline(Delimited(Parentheses, CommaList(Field))))
-            .unwrap_or_else(|ErrorReported(_)| Seq::default());
+        let ParsedParameters {
+            self_parameter,
+            parameters,
+        } = parser
+            .expect(SkipNewline(Parameters))
+            .unwrap_or_else(|ErrorReported(_)| ParsedParameters::default());
 
         let return_type = match parser
             .parse_if_present(SkipNewline(Guard(RightArrow, SkipNewline(TypeReference))))
@@ -58,11 +67,18 @@ impl Syntax<'parse> for FunctionSignature {
             None => ParsedTypeReference::Elided(parser.elided_span()),
         };
 
+        let constraints = parser
+            .parse_if_present(SkipNewline(WhereClause))
+            .transpose()?
+            .unwrap_or_default();
+
         let body = parser.expect(SkipNewline(Matched(Curlies)));
 
         Ok(ParsedFunctionSignature {
+            self_parameter,
             parameters,
             return_type,
+            constraints,
             body,
         })
     }
@@ -78,6 +94,7 @@ impl ParsedFunctionSignature {
         let mut errors = vec![];
 
         let inputs: Seq<_> = self_ty
+            .map(|self_ty| self_parameter_ty(self.self_parameter, self_ty, db))
             .into_iter()
             .chain(self.parameters.iter().map(|p| {
                 p.ty.parse_type(entity, db)
@@ -96,6 +113,18 @@ impl ParsedFunctionSignature {
         }
     }
 
+    /// The names of the parameters, preceded by `self_argument` (if
+    /// any).
+    pub fn parameter_names(
+        &self,
+        self_argument: Option<Spanned<GlobalIdentifier, FileName>>,
+    ) -> Seq<Spanned<GlobalIdentifier, FileName>> {
+        self_argument
+            .into_iter()
+            .chain(self.parameters.iter().map(|f| f.value.name))
+            .collect()
+    }
+
     pub fn parse_fn_body(
         &self,
         entity: Entity,
@@ -134,3 +163,118 @@ impl ParsedFunctionSignature {
         }
     }
 }
+
+/// An explicit `self` parameter, like `self` or `shared self`.
+#[derive(Copy, Clone, DebugWith)]
+pub struct ParsedSelfParameter {
+    pub mode: Option<Spanned<DeclaredPermKind, FileName>>,
+    pub span: Span<FileName>,
+}
+
+/// The type of the `self` parameter of a method on `self_ty`, taking
+/// into account the mode of an explicit `self` parameter, if any.
+crate fn self_parameter_ty(
+    self_parameter: Option<ParsedSelfParameter>,
+    mut self_ty: ty::Ty<Declaration>,
+    db: &dyn ParserDatabase,
+) -> ty::Ty<Declaration> {
+    if let Some(mode) = self_parameter.and_then(|p| p.mode) {
+        self_ty.perm = mode.value.intern(&db);
+    }
+    self_ty
+}
+
+#[derive(Clone, Default, DebugWith)]
+crate struct ParsedParameters {
+    crate self_parameter: Option<ParsedSelfParameter>,
+    crate parameters: Seq<Spanned<ParsedField, FileName>>,
+}
+
+/// The parenthesized parameter list of a function or method, which
+/// may begin with a `self` parameter.
+#[derive(DebugWith)]
+crate struct Parameters;
+
+impl Syntax<'parse> for Parameters {
+    type Data = ParsedParameters;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(Delimited(Parentheses, CommaList(Parameter)))
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        let mut result = ParsedParameters::default();
+        let mut parameters = vec![];
+        let list = parser.expect(Delimited(Parentheses, CommaList(Parameter)))?;
+        for (index, parameter) in list.iter().enumerate() {
+            match parameter {
+                ParsedParameter::SelfParameter(self_parameter) => {
+                    if index == 0 {
+                        result.self_parameter = Some(*self_parameter);
+                    } else {
+                        parser.report_error(
+                            "`self` must be the first parameter",
+                            self_parameter.span,
+                        );
+                    }
+                }
+                ParsedParameter::Field(field) => parameters.push(field.clone()),
+            }
+        }
+        result.parameters = Seq::from(parameters);
+        Ok(result)
+    }
+}
+
+#[derive(Clone, DebugWith)]
+enum ParsedParameter {
+    SelfParameter(ParsedSelfParameter),
+    Field(Spanned<ParsedField, FileName>),
+}
+
+#[derive(DebugWith)]
+struct Parameter;
+
+impl Syntax<'parse> for Parameter {
+    type Data = ParsedParameter;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(SelfParameter) || parser.test(Field)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        if parser.test(SelfParameter) {
+            Ok(ParsedParameter::SelfParameter(
+                parser.expect(SelfParameter)?,
+            ))
+        } else {
+            Ok(ParsedParameter::Field(parser.expect(Field)?))
+        }
+    }
+}
+
+/// Parameter = [ TypeMode ] `self`
+#[derive(DebugWith)]
+struct SelfParameter;
+
+impl Syntax<'parse> for SelfParameter {
+    type Data = ParsedSelfParameter;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        // A mode must be followed by `self`; otherwise, as in
+        // `shared: uint`, it is the name of an ordinary parameter.
+        let mut parser = parser.checkpoint();
+        let _ = parser.parse_if_present(TypeMode);
+        parser.test(SelfValue)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        let start_span = parser.peek_span();
+        let mode = parser.parse_if_present(TypeMode).transpose()?;
+        parser.expect(SelfValue)?;
+        Ok(ParsedSelfParameter {
+            mode,
+            span: start_span.extended_until_end_of(parser.last_span()),
+        })
+    }
+}
@@ -0,0 +1,155 @@
+use crate::parser::Parser;
+use crate::syntax::delimited::Delimited;
+use crate::syntax::guard::Guard;
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::list::CommaList;
+use crate::syntax::sigil::{Colon, OpenSquare, SquareBrackets, Where};
+use crate::syntax::skip_newline::SkipNewline;
+use crate::syntax::{NonEmptySyntax, Syntax};
+use crate::ParserDatabase;
+use lark_collections::Seq;
+use lark_debug_derive::DebugWith;
+use lark_entity::{Entity, EntityData, ItemKind};
+use lark_error::{ErrorReported, WithError};
+use lark_intern::Untern;
+use lark_span::{FileName, Spanned};
+use lark_string::GlobalIdentifier;
+use lark_ty as ty;
+use std::sync::Arc;
+
+/// `[T, U]` -- the generic type parameters declared by an item.
+#[derive(DebugWith)]
+pub struct GenericParameters;
+
+impl Syntax<'parse> for GenericParameters {
+    type Data = Seq<Spanned<GlobalIdentifier, FileName>>;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(OpenSquare)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        parser.expect(Delimited(SquareBrackets, CommaList(SpannedGlobalIdentifier)))
+    }
+}
+
+impl NonEmptySyntax<'parse> for GenericParameters {}
+
+/// `where T: Trait, U: Trait` -- constraints on the generic
+/// parameters declared by an item.
+#[derive(DebugWith)]
+pub struct WhereClause;
+
+impl Syntax<'parse> for WhereClause {
+    type Data = Seq<ParsedConstraint>;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(Where)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        parser.expect(Guard(Where, CommaList(Constraint)))
+    }
+}
+
+impl NonEmptySyntax<'parse> for WhereClause {}
+
+/// A single `T: Trait` constraint within a where clause.
+#[derive(DebugWith)]
+struct Constraint;
+
+impl Syntax<'parse> for Constraint {
+    type Data = ParsedConstraint;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(SpannedGlobalIdentifier)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        let parameter = parser.expect(SpannedGlobalIdentifier)?;
+        let trait_name = parser.expect(Guard(Colon, SkipNewline(SpannedGlobalIdentifier)))?;
+        Ok(ParsedConstraint {
+            parameter,
+            trait_name,
+        })
+    }
+}
+
+#[derive(Clone, DebugWith)]
+pub struct ParsedConstraint {
+    pub parameter: Spanned<GlobalIdentifier, FileName>,
+    pub trait_name: Spanned<GlobalIdentifier, FileName>,
+}
+
+/// Converts the generic parameters declared by `entity` (and the
+/// where clauses that constrain them) into its generic declarations.
+/// Within the item, the `i`th parameter is referenced as the bound
+/// variable `i`.
+crate fn generic_declarations(
+    entity: Entity,
+    db: &dyn ParserDatabase,
+    parameters: &[Spanned<GlobalIdentifier, FileName>],
+    constraints: &[ParsedConstraint],
+) -> WithError<Arc<ty::GenericDeclarations>> {
+    let mut errors = vec![];
+
+    let declarations: Vec<_> = parameters
+        .iter()
+        .map(|parameter| {
+            ty::GenericKind::Ty(ty::GenericTyDeclaration {
+                def_id: entity,
+                name: parameter.value,
+            })
+        })
+        .collect();
+
+    let constraints: Seq<_> = constraints
+        .iter()
+        .filter_map(|constraint| {
+            let position = parameters
+                .iter()
+                .position(|parameter| parameter.value == constraint.parameter.value);
+            let bound_var = match position {
+                Some(position) => ty::BoundVar::new(position),
+                None => {
+                    let msg = format!(
+                        "`{}` is not a generic parameter of this item",
+                        constraint.parameter.untern(&db),
+                    );
+                    errors.push(crate::diagnostic(msg, constraint.parameter.span));
+                    return None;
+                }
+            };
+
+            let trait_entity = db
+                .resolve_name(entity, constraint.trait_name.value)
+                .filter(|&trait_entity| match trait_entity.untern(&db) {
+                    EntityData::ItemName {
+                        kind: ItemKind::Trait,
+                        ..
+                    } => true,
+                    _ => false,
+                });
+            match trait_entity {
+                Some(trait_entity) => Some(ty::Constraint {
+                    bound_var,
+                    trait_entity,
+                }),
+                None => {
+                    let msg = format!("unknown trait: `{}`", constraint.trait_name.untern(&db));
+                    errors.push(crate::diagnostic(msg, constraint.trait_name.span));
+                    None
+                }
+            }
+        })
+        .collect();
+
+    WithError {
+        value: Arc::new(ty::GenericDeclarations {
+            parent_item: None,
+            declarations: declarations.into(),
+            constraints,
+        }),
+        errors,
+    }
+}
@@ -7,6 +7,7 @@ use lark_error::ErrorReported;
 use lark_intern::Intern;
 use lark_span::{FileName, Spanned};
 use lark_string::GlobalIdentifier;
+use unicode_normalization::UnicodeNormalization;
 
 #[derive(DebugWith)]
 pub struct SpannedGlobalIdentifier;
@@ -43,7 +44,7 @@ impl Syntax<'parse> for SpannedLocalIdentifier {
         if self.test(parser) {
             let Spanned { span, .. } = parser.shift();
             Ok(Spanned {
-                value: &parser.input()[span],
+                value: normalize(parser, &parser.input()[span]),
                 span: span,
             })
         } else {
@@ -53,3 +54,23 @@ impl Syntax<'parse> for SpannedLocalIdentifier {
 }
 
 impl NonEmptySyntax<'parse> for SpannedLocalIdentifier {}
+
+/// Identifiers are NFC-normalized, so that two spellings of the same
+/// name (e.g., `é` as one code point or as `e` plus a combining
+/// accent) are the same identifier. Text that is already normalized,
+/// which includes all ASCII, is returned as is; otherwise the
+/// normalized form is interned so that we can borrow it for `'parse`.
+fn normalize(parser: &Parser<'parse>, text: &'parse str) -> &'parse str {
+    if text.is_ascii() {
+        return text;
+    }
+
+    let normalized: String = text.nfc().collect();
+    if normalized == text {
+        return text;
+    }
+
+    let tables = parser.global_identifier_tables();
+    let id = normalized.intern(tables);
+    tables.untern_str(id)
+}
@@ -1,3 +1,4 @@
+use crate::lexer::token::LexToken;
 use crate::parser::Parser;
 use crate::syntax::sigil::Comma;
 use crate::syntax::Syntax;
@@ -77,8 +78,26 @@ where
         let mut result = vec![];
         parser.skip_newlines();
         loop {
+            let error_count = parser.error_count();
             if let Some(element) = parser.parse_if_present(&mut *element) {
-                result.push(element?);
+                // If the element is malformed, its error has been
+                // reported; we keep going so that we report all the
+                // bad elements at once and keep the good ones.
+                if let Ok(element) = element {
+                    result.push(element);
+                }
+
+                // Skip anything left over from the element (e.g., the
+                // `22` in `x: 22`) up to the next separator.
+                if !parser.test(&mut *delimiter)
+                    && !parser.is(LexToken::Newline)
+                    && !at_end_of_list(parser)
+                {
+                    if parser.error_count() == error_count {
+                        let _ = parser.expect(&mut *delimiter);
+                    }
+                    skip_to_separator(parser, &mut *delimiter);
+                }
 
                 if let Some(_) = parser.parse_if_present(&mut *delimiter) {
                     parser.skip_newlines();
@@ -96,3 +115,47 @@ where
         Ok(Seq::from(result))
     }
 }
+
+/// True if the current token ends a list: a closing sigil, the `{`
+/// that follows a `where` clause, or the end of the input.
+fn at_end_of_list(parser: &Parser<'parse>) -> bool {
+    if parser.is(LexToken::Sigil) {
+        match parser.peek_str() {
+            ")" | "]" | "}" | "{" => true,
+            _ => false,
+        }
+    } else {
+        parser.is(LexToken::EOF)
+    }
+}
+
+/// Skips the remaining tokens of a malformed list element, stopping
+/// at the next separator (a `delimiter` or newline) or at the end of
+/// the list. Anything nested within `()` or `[]` is skipped as a
+/// whole.
+fn skip_to_separator(parser: &mut Parser<'parse>, delimiter: &mut impl Syntax<'parse>) {
+    let mut depth = 0;
+    loop {
+        if parser.is(LexToken::EOF) {
+            return;
+        }
+
+        if depth == 0
+            && (parser.test(&mut *delimiter)
+                || parser.is(LexToken::Newline)
+                || at_end_of_list(parser))
+        {
+            return;
+        }
+
+        if parser.is(LexToken::Sigil) {
+            match parser.peek_str() {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" => depth -= 1,
+                _ => {}
+            }
+        }
+
+        parser.shift();
+    }
+}
@@ -4,9 +4,10 @@ use crate::syntax::entity::LazyParsedEntity;
 use crate::syntax::entity::ParsedEntity;
 use crate::syntax::fn_signature::FunctionSignature;
 use crate::syntax::fn_signature::ParsedFunctionSignature;
+use crate::syntax::generics;
 use crate::syntax::guard::Guard;
 use crate::syntax::identifier::SpannedGlobalIdentifier;
-use crate::syntax::sigil::Colon;
+use crate::syntax::sigil::{Colon, Pub};
 use crate::syntax::skip_newline::SkipNewline;
 use crate::syntax::type_reference::{ParsedTypeReference, TypeReference};
 use crate::syntax::Syntax;
@@ -14,6 +15,7 @@ use crate::ParserDatabase;
 use lark_collections::Seq;
 use lark_debug_derive::DebugWith;
 use lark_entity::Entity;
+use lark_entity::Visibility;
 use lark_error::ErrorReported;
 use lark_error::ErrorSentinel;
 use lark_error::ResultExt;
@@ -39,25 +41,39 @@ impl Syntax<'parse> for Member {
     }
 
     fn expect(&mut self, parser: &mut Parser<'_>) -> Result<Self::Data, ErrorReported> {
+        let start_span = parser.peek_span();
+        let visibility = match parser.parse_if_present(Pub).transpose()? {
+            Some(_) => Visibility::Public,
+            None => Visibility::Private,
+        };
+
         let name = parser.expect(SpannedGlobalIdentifier)?;
 
         if let Some(ty) =
             parser.parse_if_present(SkipNewline(Guard(Colon, SkipNewline(TypeReference))))
         {
-            let span = name.span.extended_until_end_of(parser.last_span());
+            let span = start_span.extended_until_end_of(parser.last_span());
             let ty = ty.unwrap_or_error_sentinel(&*parser);
 
             return Ok(Spanned {
-                value: ParsedMember::ParsedField(ParsedField { name, ty }),
+                value: ParsedMember::ParsedField(ParsedField {
+                    name,
+                    ty,
+                    visibility,
+                }),
                 span,
             });
         }
 
         let signature = parser.expect(FunctionSignature)?;
-        let span = name.span.extended_until_end_of(parser.last_span());
+        let span = start_span.extended_until_end_of(parser.last_span());
 
         return Ok(Spanned {
-            value: ParsedMember::ParsedMethod(ParsedMethod { name, signature }),
+            value: ParsedMember::ParsedMethod(ParsedMethod {
+                name,
+                signature,
+                visibility,
+            }),
             span,
         });
     }
@@ -83,7 +99,11 @@ impl Syntax<'parse> for Field {
         let span = name.span.extended_until_end_of(parser.last_span());
 
         return Ok(Spanned {
-            value: ParsedField { name, ty },
+            value: ParsedField {
+                name,
+                ty,
+                visibility: Visibility::Private,
+            },
             span,
         });
     }
@@ -99,6 +119,7 @@ pub enum ParsedMember {
 pub struct ParsedMethod {
     pub name: Spanned<GlobalIdentifier, FileName>,
     pub signature: ParsedFunctionSignature,
+    pub visibility: Visibility,
 }
 
 impl LazyParsedEntity for ParsedMethod {
@@ -112,10 +133,12 @@ impl LazyParsedEntity for ParsedMethod {
 
     fn parse_generic_declarations(
         &self,
-        _entity: Entity,
-        _db: &dyn ParserDatabase,
+        entity: Entity,
+        db: &dyn ParserDatabase,
     ) -> WithError<Result<Arc<ty::GenericDeclarations>, ErrorReported>> {
-        WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+        // Methods declare no generic parameters of their own, so any
+        // where clauses are reported as errors.
+        generics::generic_declarations(entity, db, &[], &self.signature.constraints).map(Ok)
     }
 
     fn parse_type(
@@ -152,21 +175,41 @@ impl LazyParsedEntity for ParsedMethod {
     }
 
     fn parse_fn_body(&self, entity: Entity, db: &dyn ParserDatabase) -> WithError<hir::FnBody> {
-        let self_argument: GlobalIdentifier = "self".intern(&db);
-        let spanned_self_argument = Spanned {
-            value: self_argument,
-            span: self.name.span,
-        };
         self.signature
-            .parse_fn_body(entity, db, Some(spanned_self_argument))
+            .parse_fn_body(entity, db, Some(self.self_argument(db)))
+    }
+
+    fn parse_parameter_names(
+        &self,
+        db: &dyn ParserDatabase,
+    ) -> Seq<Spanned<GlobalIdentifier, FileName>> {
+        self.signature.parameter_names(Some(self.self_argument(db)))
+    }
+}
+
+impl ParsedMethod {
+    /// Methods always take `self`, whether or not it is written
+    /// explicitly; when it is not, it is attributed to the method
+    /// name.
+    fn self_argument(&self, db: &dyn ParserDatabase) -> Spanned<GlobalIdentifier, FileName> {
+        Spanned {
+            value: "self".intern(&db),
+            span: match self.signature.self_parameter {
+                Some(self_parameter) => self_parameter.span,
+                None => self.name.span,
+            },
+        }
     }
 }
 
 /// Represents a parse of something like `foo: Type`
-#[derive(Copy, Clone, DebugWith)]
+#[derive(Clone, DebugWith)]
 pub struct ParsedField {
     pub name: Spanned<GlobalIdentifier, FileName>,
     pub ty: ParsedTypeReference,
+
+    /// Always private for fn parameters, which cannot be `pub`.
+    pub visibility: Visibility,
 }
 
 impl LazyParsedEntity for ParsedField {
@@ -0,0 +1,32 @@
+use crate::parser::Parser;
+use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::sigil::ColonColon;
+use crate::syntax::{NonEmptySyntax, Syntax};
+use lark_collections::Seq;
+use lark_debug_derive::DebugWith;
+use lark_error::ErrorReported;
+use lark_span::{FileName, Spanned};
+use lark_string::GlobalIdentifier;
+
+/// A path like `foo` or `geometry::Point`.
+#[derive(DebugWith)]
+pub struct Path;
+
+impl Syntax<'parse> for Path {
+    type Data = Seq<Spanned<GlobalIdentifier, FileName>>;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(SpannedGlobalIdentifier)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        // Path = Identifier { `::` Identifier }
+        let mut components = vec![parser.expect(SpannedGlobalIdentifier)?];
+        while let Some(_) = parser.parse_if_present(ColonColon) {
+            components.push(parser.expect(SpannedGlobalIdentifier)?);
+        }
+        Ok(Seq::from(components))
+    }
+}
+
+impl NonEmptySyntax<'parse> for Path {}
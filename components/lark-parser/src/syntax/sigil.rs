@@ -49,16 +49,33 @@ sigil_type! {
     pub struct OpenSquare = (LexToken::Sigil, "[");
     pub struct CloseSquare = (LexToken::Sigil, "]");
     pub struct Colon = (LexToken::Sigil, ":");
+    pub struct ColonColon = (LexToken::Sigil, "::");
     pub struct Semicolon = (LexToken::Sigil, ";");
     pub struct Comma = (LexToken::Sigil, ",");
     pub struct RightArrow = (LexToken::Sigil, "->");
+    pub struct FatArrow = (LexToken::Sigil, "=>");
     pub struct Dot = (LexToken::Sigil, ".");
+    pub struct DotDot = (LexToken::Sigil, "..");
+    pub struct DotDotEquals = (LexToken::Sigil, "..=");
     pub struct Let = (LexToken::Identifier, "let");
+    pub struct If = (LexToken::Identifier, "if");
+    pub struct Else = (LexToken::Identifier, "else");
+    pub struct In = (LexToken::Identifier, "in");
+    pub struct For = (LexToken::Identifier, "for");
+    pub struct Where = (LexToken::Identifier, "where");
+    pub struct Pub = (LexToken::Identifier, "pub");
+    pub struct Shared = (LexToken::Identifier, "shared");
+    pub struct Borrow = (LexToken::Identifier, "borrow");
+    pub struct Owned = (LexToken::Identifier, "owned");
+    pub struct Borrowed = (LexToken::Identifier, "borrowed");
+    pub struct SelfValue = (LexToken::Identifier, "self");
+    pub struct Pound = (LexToken::Sigil, "#");
     pub struct ExclamationPoint = (LexToken::Sigil, "!");
     pub struct Plus = (LexToken::Sigil, "+");
     pub struct Minus = (LexToken::Sigil, "-");
     pub struct Star = (LexToken::Sigil, "*");
     pub struct Slash = (LexToken::Sigil, "/");
+    pub struct Percent = (LexToken::Sigil, "%");
     pub struct Equals = (LexToken::Sigil, "=");
 }
 
@@ -93,3 +110,19 @@ impl Delimiter<'parse> for Parentheses {
         CloseParenthesis
     }
 }
+
+#[derive(DebugWith)]
+pub struct SquareBrackets;
+
+impl Delimiter<'parse> for SquareBrackets {
+    type Open = OpenSquare;
+    type Close = CloseSquare;
+
+    fn open_syntax(&self) -> Self::Open {
+        OpenSquare
+    }
+
+    fn close_syntax(&self) -> Self::Close {
+        CloseSquare
+    }
+}
@@ -1,15 +1,22 @@
 use crate::parser::Parser;
+use crate::syntax::delimited::Delimited;
 use crate::syntax::identifier::SpannedGlobalIdentifier;
+use crate::syntax::list::CommaList;
+use crate::syntax::path::Path;
+use crate::syntax::sigil::{Borrowed, Owned, Shared};
+use crate::syntax::sigil::{CloseParenthesis, Comma, OpenParenthesis, SquareBrackets};
+use crate::syntax::skip_newline::SkipNewline;
 use crate::syntax::Syntax;
 use crate::ParserDatabase;
+use lark_collections::Seq;
 use lark_debug_derive::DebugWith;
-use lark_entity::Entity;
+use lark_entity::{Entity, EntityData, ItemKind, LangItem};
 use lark_error::{ErrorReported, ErrorSentinel, WithError};
-use lark_intern::Untern;
+use lark_intern::{Intern, Untern};
 use lark_span::{FileName, Span, Spanned};
 use lark_string::GlobalIdentifier;
 use lark_ty as ty;
-use lark_ty::declaration::Declaration;
+use lark_ty::declaration::{Declaration, DeclaredPermKind};
 use lark_ty::TypeFamily;
 
 #[derive(DebugWith)]
@@ -19,24 +26,90 @@ impl Syntax<'parse> for TypeReference {
     type Data = ParsedTypeReference;
 
     fn test(&mut self, parser: &Parser<'parse>) -> bool {
-        parser.test(SpannedGlobalIdentifier)
+        parser.test(TypeMode)
+            || parser.test(SpannedGlobalIdentifier)
+            || parser.test(OpenParenthesis)
     }
 
     fn expect(
         &mut self,
         parser: &mut Parser<'parse>,
     ) -> Result<ParsedTypeReference, ErrorReported> {
-        let identifier = parser.expect(SpannedGlobalIdentifier)?;
+        // TypeReference = [ TypeMode ] ...
+        let mode = parser.parse_if_present(TypeMode).transpose()?;
+
+        // TypeReference = `(` [ TypeReference { `,` TypeReference } [ `,` ] ] `)`
+        if parser.test(OpenParenthesis) {
+            parser.expect(OpenParenthesis)?;
+
+            let mut elements = vec![];
+            let mut trailing_comma = false;
+            while let Some(element) = parser.parse_if_present(SkipNewline(TypeReference)) {
+                elements.push(element?);
+                trailing_comma = parser.parse_if_present(SkipNewline(Comma)).is_some();
+                if !trailing_comma {
+                    break;
+                }
+            }
+
+            parser.expect(SkipNewline(CloseParenthesis))?;
+
+            // `(T)` is just `T`; a one-element tuple is written `(T,)`.
+            if elements.len() == 1 && !trailing_comma && mode.is_none() {
+                return Ok(elements.pop().unwrap());
+            }
+
+            return Ok(ParsedTypeReference::Tuple(TupleTypeReference {
+                mode,
+                elements: Seq::from(elements),
+            }));
+        }
+
+        // TypeReference = Path [ `[` TypeReference { `,` TypeReference } `]` ]
+        let path = parser.expect(Path)?;
+        let arguments = parser
+            .parse_if_present(Delimited(SquareBrackets, CommaList(TypeReference)))
+            .transpose()?
+            .unwrap_or_default();
         Ok(ParsedTypeReference::Named(NamedTypeReference {
-            identifier,
+            mode,
+            path,
+            arguments,
         }))
     }
 }
 
+/// The permission keyword that may begin a type, like `shared` in
+/// `shared Point`.
+#[derive(DebugWith)]
+pub struct TypeMode;
+
+impl Syntax<'parse> for TypeMode {
+    type Data = Spanned<DeclaredPermKind, FileName>;
+
+    fn test(&mut self, parser: &Parser<'parse>) -> bool {
+        parser.test(Owned) || parser.test(Shared) || parser.test(Borrowed)
+    }
+
+    fn expect(&mut self, parser: &mut Parser<'parse>) -> Result<Self::Data, ErrorReported> {
+        if let Some(spanned) = parser.parse_if_present(Owned) {
+            return Ok(spanned?.map(|_| DeclaredPermKind::Own));
+        }
+
+        if let Some(spanned) = parser.parse_if_present(Shared) {
+            return Ok(spanned?.map(|_| DeclaredPermKind::Share));
+        }
+
+        let spanned = parser.expect(Borrowed)?;
+        Ok(spanned.map(|_| DeclaredPermKind::Borrow))
+    }
+}
+
 /// Parsed form of a type.
-#[derive(Copy, Clone, DebugWith)]
+#[derive(Clone, DebugWith)]
 pub enum ParsedTypeReference {
     Named(NamedTypeReference),
+    Tuple(TupleTypeReference),
     Elided(Span<FileName>),
     Error,
 }
@@ -49,12 +122,38 @@ impl ParsedTypeReference {
     ) -> WithError<ty::Ty<Declaration>> {
         match self {
             ParsedTypeReference::Named(named) => named.parse_type(entity, db),
+            ParsedTypeReference::Tuple(tuple) => tuple.parse_type(entity, db),
             ParsedTypeReference::Elided(_span) => {
                 WithError::ok(crate::type_conversion::unit_ty(db))
             }
             ParsedTypeReference::Error => WithError::ok(Declaration::error_type(&db)),
         }
     }
+
+    /// Pushes onto `output` each entity named by this type (including
+    /// those named in its generic arguments or tuple elements). Names
+    /// that do not resolve are ignored; `parse_type` reports them.
+    crate fn referenced_entities(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+        output: &mut Vec<Entity>,
+    ) {
+        match self {
+            ParsedTypeReference::Named(named) => {
+                output.extend(crate::scope::resolve_path(db, entity, &named.path).ok());
+                for argument in named.arguments.iter() {
+                    argument.referenced_entities(entity, db, output);
+                }
+            }
+            ParsedTypeReference::Tuple(tuple) => {
+                for element in tuple.elements.iter() {
+                    element.referenced_entities(entity, db, output);
+                }
+            }
+            ParsedTypeReference::Elided(_) | ParsedTypeReference::Error => {}
+        }
+    }
 }
 
 impl<Cx> ErrorSentinel<Cx> for ParsedTypeReference {
@@ -63,10 +162,13 @@ impl<Cx> ErrorSentinel<Cx> for ParsedTypeReference {
     }
 }
 
-/// Named type like `String` or (eventually) `Vec<u32>`
-#[derive(Copy, Clone, DebugWith)]
+/// Named type like `String`, `Vec[uint]`, or `geometry::Point`
+#[derive(Clone, DebugWith)]
 pub struct NamedTypeReference {
-    pub identifier: Spanned<GlobalIdentifier, FileName>,
+    /// The permission keyword, if any; `None` means `owned`.
+    pub mode: Option<Spanned<DeclaredPermKind, FileName>>,
+    pub path: Seq<Spanned<GlobalIdentifier, FileName>>,
+    pub arguments: Seq<ParsedTypeReference>,
 }
 
 impl NamedTypeReference {
@@ -75,27 +177,121 @@ impl NamedTypeReference {
         entity: Entity,
         db: &dyn ParserDatabase,
     ) -> WithError<ty::Ty<Declaration>> {
-        match db.resolve_name(entity, self.identifier.value) {
-            Some(entity) => {
+        // The final component names the type; e.g., `Point` in
+        // `geometry::Point`.
+        let name = self.path.last().unwrap();
+
+        // A reference to a generic parameter `T` that is in scope.
+        if self.path.len() == 1 && self.arguments.is_empty() {
+            if let Some(bv) =
+                crate::type_conversion::resolve_generic_parameter(db, entity, name.value)
+            {
+                let mut ty = crate::type_conversion::bound_var_ty(&db, bv);
+                ty.perm = perm_of(self.mode).intern(&db);
+                return WithError::ok(ty);
+            }
+        }
+
+        match crate::scope::resolve_path(db, entity, &self.path) {
+            Ok(named_entity) => {
+                let mut errors = vec![];
+                let generics: ty::Generics<Declaration> = self
+                    .arguments
+                    .iter()
+                    .map(|argument| {
+                        let ty = argument.parse_type(entity, db);
+                        ty::GenericKind::Ty(ty.accumulate_errors_into(&mut errors))
+                    })
+                    .collect();
+
+                if let Ok(generic_declarations) = db.generic_declarations(named_entity).into_value()
+                {
+                    let expected = generic_declarations.declarations.len();
+                    if generics.len() != expected {
+                        let msg = format!(
+                            "wrong number of generic arguments (expected {}, found {})",
+                            expected,
+                            generics.len(),
+                        );
+                        return WithError::report_error(&db, msg, name.span);
+                    }
+                }
+
+                // Aliases are transparent: `Id` is just another name
+                // for its target type. (Cycles are reported on the
+                // alias itself, which then has the error type.)
+                if let EntityData::ItemName {
+                    kind: ItemKind::TypeAlias,
+                    ..
+                } = named_entity.untern(&db)
+                {
+                    let mut value = db.ty(named_entity).into_value();
+                    if self.mode.is_some() {
+                        value.perm = perm_of(self.mode).intern(&db);
+                    }
+                    return WithError { value, errors };
+                }
+
                 // FIXME(ndm) -- eventually, we will want some way to
-                // represent types with other permissions/reprs. We'll
-                // need fields on `NamedTypeReference`, and we'll need
-                // to have methods for helping us adjust them
-                // post-parse, or else distinct parsing combinators
-                // (the former might be more convenient).
+                // represent types with other reprs.
                 let ty = crate::type_conversion::declaration_ty_named(
                     &db,
-                    entity,
-                    ty::declaration::DeclaredPermKind::Own,
+                    named_entity,
+                    perm_of(self.mode),
                     ty::ReprKind::Direct,
-                    ty::Generics::empty(),
+                    generics,
                 );
-                WithError::ok(ty)
+                WithError { value: ty, errors }
             }
-            None => {
-                let msg = format!("unknown type: `{}`", self.identifier.untern(&db));
-                WithError::report_error(&db, msg, self.identifier.span)
+            Err(component) => {
+                let msg = if component.span == name.span {
+                    format!("unknown type: `{}`", component.untern(&db))
+                } else {
+                    format!("unresolved path component: `{}`", component.untern(&db))
+                };
+                WithError::report_error(&db, msg, component.span)
             }
         }
     }
 }
+
+/// Tuple type like `(uint, bool)`
+#[derive(Clone, DebugWith)]
+pub struct TupleTypeReference {
+    /// The permission keyword, if any; `None` means `owned`.
+    pub mode: Option<Spanned<DeclaredPermKind, FileName>>,
+    pub elements: Seq<ParsedTypeReference>,
+}
+
+impl TupleTypeReference {
+    pub fn parse_type(
+        &self,
+        entity: Entity,
+        db: &dyn ParserDatabase,
+    ) -> WithError<ty::Ty<Declaration>> {
+        let mut errors = vec![];
+        let generics: ty::Generics<Declaration> = self
+            .elements
+            .iter()
+            .map(|element| {
+                let ty = element.parse_type(entity, db);
+                ty::GenericKind::Ty(ty.accumulate_errors_into(&mut errors))
+            })
+            .collect();
+        let arity = self.elements.len();
+        let tuple_entity = EntityData::LangItem(LangItem::Tuple(arity)).intern(&db);
+        let ty = crate::type_conversion::declaration_ty_named(
+            &db,
+            tuple_entity,
+            perm_of(self.mode),
+            ty::ReprKind::Direct,
+            generics,
+        );
+        WithError { value: ty, errors }
+    }
+}
+
+/// The permission for a type written with `mode`.
+fn perm_of(mode: Option<Spanned<DeclaredPermKind, FileName>>) -> DeclaredPermKind {
+    mode.map(|mode| mode.value).unwrap_or(DeclaredPermKind::Own)
+}
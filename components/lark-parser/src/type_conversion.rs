@@ -1,8 +1,10 @@
 use crate::ParserDatabase;
+use lark_collections::Seq;
 use lark_debug_with::DebugWith;
 use lark_entity::{Entity, EntityData, LangItem};
 use lark_error::{ErrorReported, ErrorSentinel, WithError};
 use lark_intern::{Intern, Untern};
+use lark_string::GlobalIdentifier;
 use lark_ty as ty;
 use lark_ty::declaration::Declaration;
 use lark_ty::declaration::DeclarationTables;
@@ -20,6 +22,8 @@ crate fn generic_declarations(
         | EntityData::LangItem(LangItem::String)
         | EntityData::LangItem(LangItem::Int)
         | EntityData::LangItem(LangItem::Uint)
+        | EntityData::LangItem(LangItem::Float)
+        | EntityData::LangItem(LangItem::Char)
         | EntityData::LangItem(LangItem::False)
         | EntityData::LangItem(LangItem::True)
         | EntityData::LangItem(LangItem::Debug) => {
@@ -27,10 +31,33 @@ crate fn generic_declarations(
         }
 
         EntityData::LangItem(LangItem::Tuple(arity)) => {
-            if arity != 0 {
-                unimplemented!("non-zero arity tuples");
-            }
-            WithError::ok(Ok(ty::GenericDeclarations::empty(None)))
+            // A tuple of arity N has one type parameter per element;
+            // see `ty` below, which references them as bound vars.
+            let declarations: Vec<_> = (0..arity)
+                .map(|i| {
+                    ty::GenericKind::Ty(ty::GenericTyDeclaration {
+                        def_id: entity,
+                        name: format!("T{}", i).intern(db),
+                    })
+                })
+                .collect();
+            WithError::ok(Ok(Arc::new(ty::GenericDeclarations {
+                parent_item: None,
+                declarations: declarations.into(),
+                constraints: Seq::default(),
+            })))
+        }
+
+        EntityData::LangItem(LangItem::Range) => {
+            let declarations = vec![ty::GenericKind::Ty(ty::GenericTyDeclaration {
+                def_id: entity,
+                name: "T".intern(db),
+            })];
+            WithError::ok(Ok(Arc::new(ty::GenericDeclarations {
+                parent_item: None,
+                declarations: declarations.into(),
+                constraints: Seq::default(),
+            })))
         }
 
         EntityData::ItemName { .. } | EntityData::MemberName { .. } => db
@@ -53,6 +80,8 @@ crate fn ty(db: &impl ParserDatabase, entity: Entity) -> WithError<ty::Ty<Declar
         | EntityData::LangItem(LangItem::String)
         | EntityData::LangItem(LangItem::Int)
         | EntityData::LangItem(LangItem::Uint)
+        | EntityData::LangItem(LangItem::Float)
+        | EntityData::LangItem(LangItem::Char)
         | EntityData::LangItem(LangItem::Debug) => WithError::ok(declaration_ty_named(
             db,
             entity,
@@ -66,24 +95,21 @@ crate fn ty(db: &impl ParserDatabase, entity: Entity) -> WithError<ty::Ty<Declar
             ty(db, boolean_entity)
         }
 
-        EntityData::LangItem(LangItem::Tuple(arity)) => {
-            let generics: ty::Generics<Declaration> = (0..arity)
-                .map(|i| ty::BoundVar::new(i))
-                .map(|bv| ty::Ty {
-                    base: Declaration::intern_bound_var(db, bv),
-                    repr: ty::ReprKind::Direct,
-                    perm: Declaration::own_perm(db),
-                })
-                .map(|ty| ty::GenericKind::Ty(ty))
-                .collect();
-            WithError::ok(declaration_ty_named(
-                db,
-                entity,
-                ty::declaration::DeclaredPermKind::Own,
-                ty::ReprKind::Direct,
-                generics,
-            ))
-        }
+        EntityData::LangItem(LangItem::Range) => WithError::ok(declaration_ty_named(
+            db,
+            entity,
+            ty::declaration::DeclaredPermKind::Own,
+            ty::ReprKind::Direct,
+            bound_var_generics(db, 1),
+        )),
+
+        EntityData::LangItem(LangItem::Tuple(arity)) => WithError::ok(declaration_ty_named(
+            db,
+            entity,
+            ty::declaration::DeclaredPermKind::Own,
+            ty::ReprKind::Direct,
+            bound_var_generics(db, arity),
+        )),
 
         EntityData::ItemName { .. } | EntityData::MemberName { .. } => {
             db.parsed_entity(entity).thunk.parse_type(entity, db)
@@ -107,8 +133,11 @@ crate fn signature(
         | EntityData::LangItem(LangItem::String)
         | EntityData::LangItem(LangItem::Int)
         | EntityData::LangItem(LangItem::Uint)
+        | EntityData::LangItem(LangItem::Float)
+        | EntityData::LangItem(LangItem::Char)
         | EntityData::LangItem(LangItem::False)
         | EntityData::LangItem(LangItem::Tuple(_))
+        | EntityData::LangItem(LangItem::Range)
         | EntityData::LangItem(LangItem::Debug)
         | EntityData::LangItem(LangItem::True) => {
             panic!("cannot invoke `signature` of `{:?}`", entity.untern(db))
@@ -132,6 +161,76 @@ crate fn unit_ty(db: &dyn ParserDatabase) -> ty::Ty<Declaration> {
     )
 }
 
+/// Generics that reference the bound variables `0..count` in turn.
+/// This is how an item with generic parameters refers to itself
+/// from within its own declaration: e.g., the type of the struct
+/// `Foo[T]` is `Foo[T]`.
+crate fn bound_var_generics(
+    db: &dyn AsRef<DeclarationTables>,
+    count: usize,
+) -> ty::Generics<Declaration> {
+    (0..count)
+        .map(|i| ty::GenericKind::Ty(bound_var_ty(db, ty::BoundVar::new(i))))
+        .collect()
+}
+
+crate fn bound_var_ty(db: &dyn AsRef<DeclarationTables>, bv: ty::BoundVar) -> ty::Ty<Declaration> {
+    ty::Ty {
+        base: Declaration::intern_bound_var(db, bv),
+        repr: ty::ReprKind::Direct,
+        perm: Declaration::own_perm(db),
+    }
+}
+
+/// If `name` is a generic parameter that is in scope at `entity` --
+/// that is, one declared by `entity` itself or by an item that it
+/// is nested within -- returns the bound variable that refers to it.
+crate fn resolve_generic_parameter(
+    db: &dyn ParserDatabase,
+    entity: Entity,
+    name: GlobalIdentifier,
+) -> Option<ty::BoundVar> {
+    let entity_data = entity.untern(&db);
+    match entity_data {
+        EntityData::ItemName { .. } | EntityData::MemberName { .. } => {}
+        _ => return None,
+    }
+
+    if let Ok(generic_declarations) = db.generic_declarations(entity).into_value() {
+        let position = generic_declarations
+            .declarations
+            .iter()
+            .position(|declaration| match declaration {
+                ty::GenericKind::Ty(declaration) => declaration.name == name,
+            });
+        if let Some(position) = position {
+            // Our own generics come after those of our parent item (if any).
+            let offset = match generic_declarations.parent_item {
+                Some(parent_item) => generic_count(db, parent_item),
+                None => 0,
+            };
+            return Some(ty::BoundVar::new(offset + position));
+        }
+    }
+
+    resolve_generic_parameter(db, entity_data.parent()?, name)
+}
+
+/// Total number of generics for `entity`, including those of its
+/// parent item.
+fn generic_count(db: &dyn ParserDatabase, entity: Entity) -> usize {
+    match db.generic_declarations(entity).into_value() {
+        Ok(generic_declarations) => {
+            let parent_count = match generic_declarations.parent_item {
+                Some(parent_item) => generic_count(db, parent_item),
+                None => 0,
+            };
+            parent_count + generic_declarations.declarations.len()
+        }
+        Err(ErrorReported(_)) => 0,
+    }
+}
+
 crate fn declaration_ty_named(
     db: &dyn AsRef<DeclarationTables>,
     entity: Entity,
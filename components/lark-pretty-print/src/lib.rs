@@ -3,7 +3,7 @@ use lark_intern::Untern;
 use lark_parser::ParserDatabase;
 use lark_ty::declaration::{Declaration, DeclaredPermKind};
 use lark_ty::full_inferred::{FullInferred, FullInferredTables};
-use lark_ty::{BaseData, BaseKind, BoundVarOr, PermKind, Ty, TypeFamily};
+use lark_ty::{BaseData, BaseKind, BoundVar, BoundVarOr, PermKind, Ty, TypeFamily};
 
 pub trait PrettyPrintDatabase: ParserDatabase + AsRef<FullInferredTables> {}
 
@@ -13,19 +13,34 @@ pub trait PrettyPrint {
 
 impl PrettyPrint for Ty<Declaration> {
     fn pretty_print(&self, db: &(impl PrettyPrintDatabase + ?Sized)) -> String {
-        format!(
-            "{}{}",
-            match self.perm.untern(&db) {
-                DeclaredPermKind::Own => "",
-            },
-            match self.base.untern(&db) {
-                BoundVarOr::BoundVar(var) => format!("{:?}", var),
-                BoundVarOr::Known(base_data) => base_data.pretty_print(db),
-            }
-        )
+        pretty_print_declared_ty(self, db, &|var| format!("{:?}", var))
     }
 }
 
+/// Like `Ty<Declaration>::pretty_print`, but uses `bound_var_name`
+/// to print references to generic parameters (e.g., to print them
+/// with the names they were declared with).
+pub fn pretty_print_declared_ty(
+    ty: &Ty<Declaration>,
+    db: &(impl PrettyPrintDatabase + ?Sized),
+    bound_var_name: &dyn Fn(BoundVar) -> String,
+) -> String {
+    format!(
+        "{}{}",
+        match ty.perm.untern(&db) {
+            DeclaredPermKind::Own => "",
+            DeclaredPermKind::Share => "shared ",
+            DeclaredPermKind::Borrow => "borrowed ",
+        },
+        match ty.base.untern(&db) {
+            BoundVarOr::BoundVar(var) => bound_var_name(var),
+            BoundVarOr::Known(base_data) => pretty_print_base_data(&base_data, db, &|ty| {
+                pretty_print_declared_ty(&ty, db, bound_var_name)
+            }),
+        }
+    )
+}
+
 impl PrettyPrint for Ty<FullInferred> {
     fn pretty_print(&self, db: &(impl PrettyPrintDatabase + ?Sized)) -> String {
         format!(
@@ -35,11 +50,40 @@ impl PrettyPrint for Ty<FullInferred> {
                 PermKind::Share => "shared ",
                 PermKind::Borrow => "borrowed ",
             },
-            self.base.untern(&db).pretty_print(db),
+            pretty_print_base_data(&self.base.untern(&db), db, &|ty| ty.pretty_print(db)),
         )
     }
 }
 
+/// Like `BaseData::pretty_print`, but also prints the generic
+/// arguments (using `print_ty`), as in `(uint, bool)` or
+/// `Range[uint]`.
+fn pretty_print_base_data<T: TypeFamily>(
+    base_data: &BaseData<T>,
+    db: &(impl PrettyPrintDatabase + ?Sized),
+    print_ty: &dyn Fn(Ty<T>) -> String,
+) -> String {
+    if let BaseKind::Named(entity) = base_data.kind {
+        let elements: Vec<_> = base_data
+            .generics
+            .iter()
+            .map(|generic| print_ty(generic.assert_ty()))
+            .collect();
+        match entity.untern(&db) {
+            EntityData::LangItem(LangItem::Tuple(arity)) if arity != 0 => {
+                let trailing_comma = if arity == 1 { "," } else { "" };
+                return format!("({}{})", elements.join(", "), trailing_comma);
+            }
+            _ if !elements.is_empty() => {
+                return format!("{}[{}]", entity.pretty_print(db), elements.join(", "));
+            }
+            _ => {}
+        }
+    }
+
+    base_data.pretty_print(db)
+}
+
 impl<T: TypeFamily> PrettyPrint for BaseData<T> {
     fn pretty_print(&self, db: &(impl PrettyPrintDatabase + ?Sized)) -> String {
         self.kind.pretty_print(db)
@@ -52,8 +96,12 @@ impl PrettyPrint for Entity {
             EntityData::LangItem(LangItem::Boolean) => "bool".into(),
             EntityData::LangItem(LangItem::Uint) => "uint".into(),
             EntityData::LangItem(LangItem::Int) => "int".into(),
+            EntityData::LangItem(LangItem::Float) => "float".into(),
+            EntityData::LangItem(LangItem::Char) => "char".into(),
             EntityData::LangItem(LangItem::String) => "String".into(),
             EntityData::LangItem(LangItem::Tuple(0)) => "void".into(),
+            EntityData::LangItem(LangItem::Tuple(arity)) => format!("{}-tuple", arity),
+            EntityData::LangItem(LangItem::Range) => "Range".into(),
             EntityData::LangItem(LangItem::Debug) => "<debug>".into(),
             EntityData::MemberName {
                 kind: MemberKind::Field,
@@ -66,6 +114,11 @@ impl PrettyPrint for Entity {
                 kind: MemberKind::Method,
                 id,
                 ..
+            }
+            | EntityData::MemberName {
+                kind: MemberKind::MethodSignature,
+                id,
+                ..
             } => {
                 let mut output_sig = "(".to_string();
                 let mut first = true;
@@ -87,10 +140,49 @@ impl PrettyPrint for Entity {
 
                 format!("{}{}", id.untern(&db).to_string(), output_sig)
             }
+            EntityData::MemberName {
+                kind: MemberKind::Variant,
+                id,
+                ..
+            } => {
+                let sig = db.signature(*self).value.unwrap();
+
+                if sig.inputs.is_empty() {
+                    format!("{}", id.untern(&db))
+                } else {
+                    let inputs: Vec<_> = sig.inputs.iter().map(|i| i.pretty_print(db)).collect();
+                    format!("{}({})", id.untern(&db), inputs.join(", "))
+                }
+            }
             EntityData::ItemName {
                 kind: ItemKind::Struct,
                 id,
                 ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Enum,
+                id,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Trait,
+                id,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Impl,
+                id,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::Const,
+                id,
+                ..
+            }
+            | EntityData::ItemName {
+                kind: ItemKind::TypeAlias,
+                id,
+                ..
             } => format!("{}", id.untern(&db)),
             EntityData::ItemName {
                 kind: ItemKind::Function,
@@ -1,11 +1,15 @@
 use language_reporting as l_r;
 use lark_actor::{Actor, LspResponse, QueryRequest};
-use lark_entity::EntityTables;
+use lark_entity::{Entity, EntityTables};
+use lark_error::WithError;
 use lark_intern::{Intern, Untern};
 use lark_parser::{ParserDatabase, ParserDatabaseExt};
 use lark_pretty_print::PrettyPrintDatabase;
 use lark_span::{ByteIndex, FileName, Span};
-use lark_string::{GlobalIdentifier, GlobalIdentifierTables, Text};
+use lark_string::{GlobalIdentifier, GlobalIdentifierTables, InternerStats, Text};
+use lark_ty::full_inferred::FullInferred;
+use lark_type_check::{TypeCheckDatabase, TypeCheckResults};
+use rayon::prelude::*;
 use salsa::{Database, ParallelDatabase, Snapshot};
 use std::collections::VecDeque;
 use std::sync::mpsc::Sender;
@@ -38,9 +42,47 @@ impl LarkDatabase {
         s.intern(self)
     }
 
+    pub fn intern_strings<S>(&self, items: impl IntoIterator<Item = S>) -> Vec<GlobalIdentifier>
+    where
+        S: AsRef<str>,
+    {
+        self.global_id_tables.intern_strings(items)
+    }
+
     pub fn untern_string(&self, id: GlobalIdentifier) -> Text {
         id.untern(self)
     }
+
+    pub fn untern_str(&self, id: GlobalIdentifier) -> &str {
+        self.global_id_tables.untern_str(id)
+    }
+
+    pub fn interner_stats(&self) -> InternerStats {
+        self.global_id_tables.stats()
+    }
+
+    /// Type-checks every fn body in the project, spreading the work
+    /// across rayon's thread pool. Each body is checked against its
+    /// own snapshot of the database, so the results are memoized just
+    /// as if they had been computed one at a time (and later queries,
+    /// like `all_diagnostics`, reuse them). The results are in the
+    /// order of `all_entities`, no matter which thread finishes first.
+    pub fn type_check_all_in_parallel(
+        &self,
+    ) -> Vec<(Entity, WithError<Arc<TypeCheckResults<FullInferred>>>)> {
+        // The database itself cannot be shared between threads, so
+        // take the snapshots up front.
+        let work: Vec<_> = self
+            .all_entities(())
+            .iter()
+            .filter(|entity| entity.untern(self).has_fn_body())
+            .map(|&entity| (entity, self.snapshot()))
+            .collect();
+
+        work.into_par_iter()
+            .map(|(entity, db)| (entity, db.full_type_check(entity)))
+            .collect()
+    }
 }
 
 impl Default for LarkDatabase {
@@ -147,12 +189,14 @@ impl l_r::ReportingFiles for &LarkDatabase {
     }
 
     fn line_span(&self, file: Self::FileId, lineno: usize) -> Option<Self::Span> {
-        let line_offsets = self.line_offsets(file);
-        let line_start = line_offsets[lineno];
-        let next_line_start = line_offsets[lineno + 1];
+        let line_index = self.line_index(file);
+        if lineno >= line_index.line_count() {
+            return None;
+        }
 
-        // This includes the `\n` from `lineno`, is that ok?
-        Some(Span::new(file, line_start, next_line_start))
+        // This includes the line terminator from `lineno`, is that ok?
+        let range = line_index.line_range(lineno);
+        Some(Span::new(file, range.start, range.end))
     }
 
     fn source(&self, span: Self::Span) -> Option<String> {
@@ -218,18 +262,13 @@ impl QuerySystem {
             let db = self.lark_db.snapshot();
             let send_channel = self.send_channel.clone();
             move || {
-                match db.errors_for_project() {
+                match db.lsp_errors_for_project() {
                     Ok(errors) => {
                         // loop over hashmap and send messages
                         for (key, value) in errors {
                             let send_channel = send_channel.clone();
                             let url = Url::parse(&key).unwrap();
-                            let ranges_with_default =
-                                value.iter().map(|x| (x.range, x.label.clone())).collect();
-                            send(
-                                send_channel,
-                                LspResponse::Diagnostics(url, ranges_with_default),
-                            );
+                            send(send_channel, LspResponse::Diagnostics(url, value));
                         }
                     }
                     Err(Cancelled) => {
@@ -255,7 +294,7 @@ impl QuerySystem {
 
                 // Process sets on the same thread -- this not only gives them priority,
                 // it ensures an overall ordering to edits.
-                self.lark_db.add_file(url.as_str(), text);
+                self.lark_db.set_file(url.as_str(), text);
             }
 
             QueryRequest::EditFile(url, changes) => {
@@ -264,36 +303,30 @@ impl QuerySystem {
                 let path_id = self.lark_db.intern_string(url.as_str());
                 let file_name = FileName { id: path_id };
 
-                let text = self.lark_db.file_text(file_name);
-                let mut current_contents = text.to_string();
-
                 for change in changes {
+                    // LSP columns count UTF-16 code units.
+                    let line_index = self.lark_db.line_index(file_name);
+
                     let start_position = change.0.start;
-                    let start_offset = self.lark_db.byte_index(
-                        file_name,
-                        start_position.line,
-                        start_position.character,
+                    let start_offset = line_index.utf16_line_col_to_offset(
+                        start_position.line as usize,
+                        start_position.character as usize,
                     );
 
                     let end_position = change.0.end;
-                    let end_offset = self.lark_db.byte_index(
-                        file_name,
-                        end_position.line,
-                        end_position.character,
+                    let end_offset = line_index.utf16_line_col_to_offset(
+                        end_position.line as usize,
+                        end_position.character as usize,
                     );
 
-                    unsafe {
-                        let vec = current_contents.as_mut_vec();
-                        vec.drain(start_offset.to_usize()..end_offset.to_usize());
-                    }
-
-                    current_contents.insert_str(start_offset.to_usize(), &change.1);
+                    // A stale range may even be reversed.
+                    let span = Span::new(
+                        file_name,
+                        start_offset.min(end_offset),
+                        start_offset.max(end_offset),
+                    );
+                    self.lark_db.edit_file(span, &change.1);
                 }
-
-                let text = Text::from(current_contents);
-                self.lark_db
-                    .query_mut(lark_parser::FileTextQuery)
-                    .set(file_name, text);
             }
             QueryRequest::RenameAtPosition(task_id, url, position, new_name) => {
                 std::thread::spawn({
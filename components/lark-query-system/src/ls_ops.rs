@@ -4,13 +4,12 @@
 //! (e.g. `&uri`) that wouldn't be possible otherwise, which is
 //! convenient.
 
-use languageserver_types::{Position, Range};
-use lark_entity::{Entity, EntityData, ItemKind, MemberKind};
-use lark_error::Diagnostic;
+use languageserver_types::{DiagnosticSeverity, Position, Range};
+use lark_entity::{Entity, EntityData};
+use lark_error::{Diagnostic, Severity};
 use lark_intern::{Intern, Untern};
 use lark_parser::HoverTargetKind;
-use lark_pretty_print::PrettyPrint;
-use lark_span::{ByteIndex, FileName, IntoFileName, Span};
+use lark_span::{ByteIndex, FileName, IntoFileName, LineIndex, Span};
 use std::collections::HashMap;
 
 #[derive(Debug)]
@@ -25,6 +24,43 @@ impl RangedDiagnostic {
     }
 }
 
+/// The LSP range of `span`, which must be in the file that
+/// `line_index` was built from. Like spans, LSP ranges are half-open:
+/// the end position is just past the last character. As the LSP
+/// requires, columns count UTF-16 code units.
+fn lsp_range(line_index: &LineIndex, span: Span<FileName>) -> Range {
+    let lsp_position = |index| {
+        let (line, column) = line_index.offset_to_utf16_line_col(index);
+        Position::new(line as u64, column as u64)
+    };
+    Range::new(lsp_position(span.start()), lsp_position(span.end()))
+}
+
+/// Converts `diagnostics`, which must all be in the file that
+/// `line_index` was built from, into a JSON array of LSP
+/// `Diagnostic` objects, with ranges as given by `lsp_range`.
+pub fn diagnostics_to_lsp(diagnostics: &[Diagnostic], line_index: &LineIndex) -> serde_json::Value {
+    let lsp_diagnostics: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let severity = match diagnostic.severity {
+                Severity::Error => DiagnosticSeverity::Error,
+                Severity::Warning => DiagnosticSeverity::Warning,
+            };
+            languageserver_types::Diagnostic::new(
+                lsp_range(line_index, diagnostic.span),
+                Some(severity),
+                None,
+                None,
+                diagnostic.label.clone(),
+                None,
+            )
+        })
+        .collect();
+
+    serde_json::to_value(lsp_diagnostics).unwrap()
+}
+
 pub struct Cancelled;
 
 pub type Cancelable<T> = Result<T, Cancelled>;
@@ -38,6 +74,16 @@ pub trait LsDatabase: lark_type_check::TypeCheckDatabase + salsa::Database {
         }
     }
 
+    /// The errors in `file` that are reported to the editor.
+    fn file_errors(&self, file: FileName) -> Vec<Diagnostic> {
+        // Warnings are not (yet) reported to the editor.
+        self.file_diagnostics(file)
+            .iter()
+            .filter(|x| x.severity == Severity::Error)
+            .cloned()
+            .collect()
+    }
+
     fn errors_for_project(&self) -> Cancelable<HashMap<String, Vec<RangedDiagnostic>>> {
         let input_files = self.file_names();
         let mut file_errors = HashMap::new();
@@ -45,21 +91,11 @@ pub trait LsDatabase: lark_type_check::TypeCheckDatabase + salsa::Database {
         for &input_file in &*input_files {
             self.check_for_cancellation()?;
 
-            // Check file for syntax errors
-            let mut errors = vec![];
-            let _ = self
-                .parsed_file(input_file)
-                .accumulate_errors_into(&mut errors);
-
-            // Next, check entities in file for type-safety
-            let file_entity = EntityData::InputFile { file: input_file }.intern(self);
-            for &entity in self.descendant_entities(file_entity).iter() {
-                self.accumulate_errors_for_entity(entity, &mut errors)?;
-            }
-
-            let error_ranges = errors
-                .iter()
-                .map(|x| RangedDiagnostic::new(x.label.clone(), self.range(x.span)))
+            let line_index = self.line_index(input_file);
+            let error_ranges = self
+                .file_errors(input_file)
+                .into_iter()
+                .map(|x| RangedDiagnostic::new(x.label, lsp_range(&line_index, x.span)))
                 .collect();
 
             file_errors.insert(input_file.id.untern(self).to_string(), error_ranges);
@@ -68,92 +104,41 @@ pub trait LsDatabase: lark_type_check::TypeCheckDatabase + salsa::Database {
         Ok(file_errors)
     }
 
-    fn range(&self, span: Span<FileName>) -> languageserver_types::Range {
-        let left = self.location(span.file(), span.start()).as_position();
-        let right = self.location(span.file(), span.end()).as_position();
-        languageserver_types::Range::new(left, right)
-    }
+    /// Like `errors_for_project`, but gives each file's errors as the
+    /// LSP JSON produced by `diagnostics_to_lsp`, ready to publish to
+    /// the editor.
+    fn lsp_errors_for_project(&self) -> Cancelable<HashMap<String, serde_json::Value>> {
+        let input_files = self.file_names();
+        let mut file_errors = HashMap::new();
 
-    fn accumulate_errors_for_entity(
-        &self,
-        entity: Entity,
-        errors: &mut Vec<Diagnostic>,
-    ) -> Cancelable<()> {
-        self.check_for_cancellation()?;
+        for &input_file in &*input_files {
+            self.check_for_cancellation()?;
 
-        match entity.untern(self) {
-            EntityData::InputFile { .. } => {}
-            EntityData::LangItem(_) => {}
-            EntityData::Error(_) => {}
-            EntityData::ItemName {
-                kind: ItemKind::Struct,
-                ..
-            } => {
-                let _ = self
-                    .generic_declarations(entity)
-                    .accumulate_errors_into(errors);
-                let _ = self.ty(entity).accumulate_errors_into(errors);
-            }
-            EntityData::MemberName {
-                kind: MemberKind::Field,
-                ..
-            } => {
-                let _ = self
-                    .generic_declarations(entity)
-                    .accumulate_errors_into(errors);
-                let _ = self.ty(entity).accumulate_errors_into(errors);
-            }
-            EntityData::ItemName {
-                kind: ItemKind::Function,
-                ..
-            } => {
-                let _ = self
-                    .generic_declarations(entity)
-                    .accumulate_errors_into(errors);
-                let _ = self.ty(entity).accumulate_errors_into(errors);
-                let _ = self.signature(entity).accumulate_errors_into(errors);
-                let _ = self.fn_body(entity).accumulate_errors_into(errors);
-                let _ = self.full_type_check(entity).accumulate_errors_into(errors);
-            }
-            EntityData::MemberName {
-                kind: MemberKind::Method,
-                ..
-            } => {
-                let _ = self
-                    .generic_declarations(entity)
-                    .accumulate_errors_into(errors);
-                let _ = self.ty(entity).accumulate_errors_into(errors);
-                let _ = self.signature(entity).accumulate_errors_into(errors);
-                let _ = self.fn_body(entity).accumulate_errors_into(errors);
-                let _ = self.full_type_check(entity).accumulate_errors_into(errors);
-            }
+            let errors = self.file_errors(input_file);
+            let lsp_errors = diagnostics_to_lsp(&errors, &self.line_index(input_file));
+
+            file_errors.insert(input_file.id.untern(self).to_string(), lsp_errors);
         }
 
-        Ok(())
+        Ok(file_errors)
+    }
+
+    fn range(&self, span: Span<FileName>) -> languageserver_types::Range {
+        lsp_range(&self.line_index(span.file()), span)
     }
 
     fn find_all_references_to_definition(&self, definition_entity: Entity) -> Vec<(String, Range)> {
         let input_files = self.file_names();
         let mut uses = vec![];
 
-        let p = lark_hir::PlaceData::Entity(definition_entity);
-
         for &input_file in &*input_files {
-            let _ = self.parsed_file(input_file);
-
-            let file_entity = EntityData::InputFile { file: input_file }.intern(self);
-            for &entity in self.descendant_entities(file_entity).iter() {
-                if entity.untern(self).has_fn_body() {
-                    let fn_body = self.fn_body(entity).into_value();
-                    for (key, value) in fn_body.tables.places.iter_enumerated() {
-                        if *value == p {
-                            let span = fn_body.span(key);
-                            let range = self.range(span);
-                            let filename = span.file().id.untern(self).to_string();
-                            uses.push((filename, range));
-                        }
-                    }
-                }
+            for &span in self
+                .references_in_file(input_file, definition_entity)
+                .iter()
+            {
+                let range = self.range(span);
+                let filename = span.file().id.untern(self).to_string();
+                uses.push((filename, range));
             }
         }
 
@@ -181,58 +166,6 @@ pub trait LsDatabase: lark_type_check::TypeCheckDatabase + salsa::Database {
         uses
     }
 
-    fn find_all_references_to_field(&self, field_entity: Entity) -> Vec<(String, Range)> {
-        let input_files = self.file_names();
-        let mut uses = vec![];
-
-        for &input_file in &*input_files {
-            let _ = self.parsed_file(input_file);
-
-            let file_entity = EntityData::InputFile { file: input_file }.intern(self);
-            for &entity in self.descendant_entities(file_entity).iter() {
-                if entity.untern(self).has_fn_body() {
-                    let fn_body = self.fn_body(entity).into_value();
-                    let possible_match_types = &self.full_type_check(entity).into_value();
-
-                    for value in fn_body.tables.places.iter() {
-                        match value {
-                            lark_hir::PlaceData::Field {
-                                name: value_name, ..
-                            } => {
-                                if possible_match_types.entities[&(*value_name).into()]
-                                    == field_entity
-                                {
-                                    let span = fn_body.span(*value_name);
-                                    let range = self.range(span);
-                                    let filename = span.file().id.untern(self).to_string();
-                                    uses.push((filename, range));
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-
-                    for identified_expression in fn_body.tables.identified_expressions.iter() {
-                        match &identified_expression {
-                            lark_hir::IdentifiedExpressionData { identifier, .. } => {
-                                if possible_match_types.entities[&(*identifier).into()]
-                                    == field_entity
-                                {
-                                    let span = fn_body.span(*identifier);
-                                    let range = self.range(span);
-                                    let filename = span.file().id.untern(self).to_string();
-                                    uses.push((filename, range));
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        uses
-    }
-
     fn rename_all_references_at_position(
         &self,
         url: &str,
@@ -267,13 +200,9 @@ pub trait LsDatabase: lark_type_check::TypeCheckDatabase + salsa::Database {
             .iter()
             .rev()
             .filter_map(|target| match target.kind {
-                HoverTargetKind::Entity(hovered_entity) => match hovered_entity.untern(self) {
-                    EntityData::MemberName {
-                        kind: MemberKind::Field,
-                        ..
-                    } => Some(self.find_all_references_to_field(hovered_entity)),
-                    _ => Some(self.find_all_references_to_definition(hovered_entity)),
-                },
+                HoverTargetKind::Entity(hovered_entity) => {
+                    Some(self.find_all_references_to_definition(hovered_entity))
+                }
                 HoverTargetKind::MetaIndex(entity, mi) => match mi {
                     lark_hir::MetaIndex::Variable(variable) => {
                         let fn_body = self.fn_body(entity).into_value();
@@ -294,7 +223,7 @@ pub trait LsDatabase: lark_type_check::TypeCheckDatabase + salsa::Database {
                                 let source_types = &self.full_type_check(entity).into_value();
                                 let hovered_entity = source_types.entities[&name.into()];
 
-                                Some(self.find_all_references_to_field(hovered_entity))
+                                Some(self.find_all_references_to_definition(hovered_entity))
                             }
                             _ => None,
                         }
@@ -445,59 +374,20 @@ pub trait LsDatabase: lark_type_check::TypeCheckDatabase + salsa::Database {
     fn hover_text_at_position(&self, url: &str, position: Position) -> Cancelable<Option<String>> {
         let url_file_name = url.into_file_name(self);
         let byte_index = self.position_to_byte_index(url, position);
-        let targets = self.hover_targets(url_file_name, byte_index);
+        let hover_info = self.hover_at(url_file_name, byte_index);
         self.check_for_cancellation()?;
 
-        Ok(targets
-            .iter()
-            .rev()
-            .filter_map(|target| match target.kind {
-                HoverTargetKind::Entity(entity) => match entity.untern(self) {
-                    EntityData::InputFile { .. }
-                    | EntityData::LangItem(_)
-                    | EntityData::Error(_) => None,
-                    EntityData::ItemName {
-                        kind: ItemKind::Struct,
-                        ..
-                    } => Some(format!("struct {}", entity.pretty_print(self))),
-                    EntityData::ItemName {
-                        kind: ItemKind::Function,
-                        ..
-                    } => Some(format!("def {}", entity.pretty_print(self))),
-                    _ => Some(entity.pretty_print(self)),
-                },
-
-                HoverTargetKind::MetaIndex(entity, mi) => {
-                    let fn_body_types = self.full_type_check(entity).into_value();
-
-                    match mi {
-                        lark_hir::MetaIndex::Identifier(identifier) => {
-                            if let Some(target_entity) =
-                                fn_body_types.entities.get(&identifier.into())
-                            {
-                                Some(format!(
-                                    "{}",
-                                    self.ty(*target_entity).value.pretty_print(self),
-                                ))
-                            } else {
-                                None
-                            }
-                        }
-                        _ => {
-                            if let Some(ty) = fn_body_types.opt_ty(mi) {
-                                Some(format!("{}", ty.pretty_print(self),))
-                            } else {
-                                None
-                            }
-                        }
-                    }
-                }
-            })
-            .next())
+        Ok(hover_info.map(|info| match info.doc {
+            Some(doc) => format!("{}\n\n{}", info.text, doc),
+            None => info.text,
+        }))
     }
 
+    /// The byte index of the LSP `position`, whose column counts
+    /// UTF-16 code units.
     fn position_to_byte_index(&self, url: &str, position: Position) -> ByteIndex {
         let url_id = url.intern(self);
-        self.byte_index(FileName { id: url_id }, position.line, position.character)
+        self.line_index(FileName { id: url_id })
+            .utf16_line_col_to_offset(position.line as usize, position.character as usize)
     }
 }
@@ -2,6 +2,8 @@ use crate::Span;
 use lark_debug_derive::DebugWith;
 use lark_intern::{Intern, Untern};
 use lark_string::{GlobalIdentifier, GlobalIdentifierTables, Text};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 pub trait SpanFile: Copy + Debug + Eq + Ord {}
@@ -17,6 +19,7 @@ impl<File: SpanFile> std::ops::Index<Span<File>> for Text {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FileName {
     pub id: GlobalIdentifier,
 }
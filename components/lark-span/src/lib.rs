@@ -3,11 +3,13 @@
 #![feature(in_band_lifetimes)]
 
 mod file;
+mod line_index;
 mod location;
 mod span;
 mod spanned;
 
 pub use self::file::*;
+pub use self::line_index::*;
 pub use self::location::*;
 pub use self::span::*;
 pub use self::spanned::*;
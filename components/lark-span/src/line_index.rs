@@ -0,0 +1,174 @@
+use crate::ByteIndex;
+use crate::Location;
+use lark_string::Text;
+use std::ops::Range;
+
+mod test;
+
+/// Maps between byte offsets into a file's text and (line, column)
+/// positions. The text is scanned once, when the index is built, to
+/// find where each line starts; after that, finding the line for an
+/// offset is a binary search, and only the column is counted within
+/// the line.
+///
+/// Lines are terminated by `\n`, `\r\n`, or a lone `\r`. As in
+/// `Location`, lines and columns are 0-based and columns count
+/// UTF-8 characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineIndex {
+    text: Text,
+
+    /// The byte offset at which each line starts. The first line
+    /// always starts at 0; text that ends with a line terminator has
+    /// a final, empty line starting at the end of the text.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: Text) -> Self {
+        let mut line_starts = vec![0];
+        let bytes = text.as_bytes();
+        let mut index = 0;
+        while index < bytes.len() {
+            match bytes[index] {
+                b'\r' if bytes.get(index + 1) == Some(&b'\n') => {
+                    index += 2;
+                    line_starts.push(index);
+                }
+                b'\r' | b'\n' => {
+                    index += 1;
+                    line_starts.push(index);
+                }
+                _ => index += 1,
+            }
+        }
+
+        LineIndex { text, line_starts }
+    }
+
+    /// The number of lines; always at least one, even for empty text.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The byte range of `line`, including its terminator (if any).
+    pub fn line_range(&self, line: usize) -> Range<usize> {
+        let start = self.line_starts[line];
+        let end = match self.line_starts.get(line + 1) {
+            Some(&next_start) => next_start,
+            None => self.text.len(),
+        };
+        start..end
+    }
+
+    /// The line and column of `offset`. An offset at the end of the
+    /// text is on the last line, just after its final character. An
+    /// offset past the end of the text is clamped to the end, and one
+    /// inside a character is rounded down to the start of that
+    /// character; the returned location carries the adjusted offset.
+    pub fn offset_to_line_col(&self, offset: ByteIndex) -> Location {
+        let offset = self.clamp_offset(offset.to_usize());
+        let (line, line_text) = self.line_up_to(offset);
+        let column = line_text.chars().count();
+        Location::new(line, column, ByteIndex::from(offset))
+    }
+
+    /// Like `offset_to_line_col`, but the column counts UTF-16 code
+    /// units, as positions in the Language Server Protocol do.
+    pub fn offset_to_utf16_line_col(&self, offset: ByteIndex) -> (usize, usize) {
+        let (line, line_text) = self.line_up_to(self.clamp_offset(offset.to_usize()));
+        let column = line_text.chars().map(char::len_utf16).sum();
+        (line, column)
+    }
+
+    /// Clamps `offset` to the text and rounds it down to a character
+    /// boundary.
+    fn clamp_offset(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// The line containing `offset` (which must be a character
+    /// boundary within the text), along with the text of that line up
+    /// to `offset`.
+    fn line_up_to(&self, offset: usize) -> (usize, &str) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let text: &str = &self.text;
+        (line, &text[line_start..offset])
+    }
+
+    /// The byte offset of the given line and column, where the column
+    /// counts UTF-8 characters (as in `offset_to_line_col`). Positions
+    /// past the end of a line are clamped to the end of that line
+    /// (before its terminator), and lines past the end of the text to
+    /// the end of the text.
+    pub fn line_col_to_offset(&self, line: usize, column: usize) -> ByteIndex {
+        self.offset_in_line(line, |contents| {
+            contents
+                .char_indices()
+                .nth(column)
+                .map(|(column_offset, _)| column_offset)
+        })
+    }
+
+    /// Like `line_col_to_offset`, but the column counts bytes rather
+    /// than characters. A column inside a character is rounded down to
+    /// the start of that character.
+    pub fn line_byte_col_to_offset(&self, line: usize, column: usize) -> ByteIndex {
+        self.offset_in_line(line, |contents| {
+            if column <= contents.len() {
+                let mut column = column;
+                while !contents.is_char_boundary(column) {
+                    column -= 1;
+                }
+                Some(column)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Like `line_col_to_offset`, but the column counts UTF-16 code
+    /// units (as in `offset_to_utf16_line_col`). A column in the
+    /// middle of a character that takes two code units is rounded
+    /// down to the start of that character.
+    pub fn utf16_line_col_to_offset(&self, line: usize, column: usize) -> ByteIndex {
+        self.offset_in_line(line, |contents| {
+            let mut units = 0;
+            for (column_offset, c) in contents.char_indices() {
+                units += c.len_utf16();
+                if units > column {
+                    return Some(column_offset);
+                }
+            }
+            None
+        })
+    }
+
+    /// Finds the start of `line` and adds the offset that
+    /// `column_offset` picks within the line's contents (excluding
+    /// its terminator), clamping as described on
+    /// `line_col_to_offset`.
+    fn offset_in_line(
+        &self,
+        line: usize,
+        column_offset: impl FnOnce(&str) -> Option<usize>,
+    ) -> ByteIndex {
+        if line >= self.line_count() {
+            return ByteIndex::from(self.text.len());
+        }
+
+        let range = self.line_range(line);
+        let text: &str = &self.text;
+        let contents = text[range.clone()].trim_end_matches(|c| c == '\n' || c == '\r');
+        let column_offset = column_offset(contents).unwrap_or(contents.len());
+        ByteIndex::from(range.start + column_offset)
+    }
+}
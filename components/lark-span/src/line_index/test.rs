@@ -0,0 +1,144 @@
+#![cfg(test)]
+
+use crate::{ByteIndex, LineIndex};
+use lark_string::Text;
+
+fn line_col(index: &LineIndex, offset: usize) -> (usize, usize) {
+    let location = index.offset_to_line_col(ByteIndex::from(offset));
+    assert_eq!(location.byte, ByteIndex::from(offset));
+    (location.line, location.column)
+}
+
+#[test]
+fn mixed_line_endings() {
+    let text = "ab\ncd\r\nef\rg";
+    let index = LineIndex::new(Text::from(text));
+    assert_eq!(index.line_count(), 4);
+
+    let expected = [
+        (0, 0),
+        (0, 1),
+        (0, 2), // `\n`
+        (1, 0),
+        (1, 1),
+        (1, 2), // `\r`
+        (1, 3), // `\n`
+        (2, 0),
+        (2, 1),
+        (2, 2), // lone `\r`
+        (3, 0),
+        (3, 1), // end of text
+    ];
+    for (offset, &position) in expected.iter().enumerate() {
+        assert_eq!(line_col(&index, offset), position, "offset {}", offset);
+    }
+
+    assert_eq!(index.line_range(0), 0..3);
+    assert_eq!(index.line_range(1), 3..7);
+    assert_eq!(index.line_range(2), 7..10);
+    assert_eq!(index.line_range(3), 10..11);
+}
+
+#[test]
+fn round_trip() {
+    let text = "ab\ncd\r\nef\rg\n\r\nh";
+    let index = LineIndex::new(Text::from(text));
+    for offset in 0..=text.len() {
+        let (line, column) = line_col(&index, offset);
+        let expected = if text[..offset].ends_with('\r') && text[offset..].starts_with('\n') {
+            // The middle of a `\r\n` maps to the end of its line.
+            offset - 1
+        } else {
+            offset
+        };
+        assert_eq!(
+            index.line_col_to_offset(line, column),
+            ByteIndex::from(expected),
+            "offset {}",
+            offset
+        );
+    }
+}
+
+#[test]
+fn trailing_newline_starts_empty_line() {
+    let index = LineIndex::new(Text::from("a\n"));
+    assert_eq!(index.line_count(), 2);
+    assert_eq!(line_col(&index, 2), (1, 0));
+
+    let index = LineIndex::new(Text::from(""));
+    assert_eq!(index.line_count(), 1);
+    assert_eq!(line_col(&index, 0), (0, 0));
+}
+
+#[test]
+fn columns_count_characters() {
+    // `é` and `α` are two bytes each.
+    let text = "é = α\nβ";
+    let index = LineIndex::new(Text::from(text));
+    assert_eq!(line_col(&index, 2), (0, 1));
+    assert_eq!(line_col(&index, 7), (0, 5));
+    assert_eq!(line_col(&index, 10), (1, 1));
+    assert_eq!(index.line_col_to_offset(0, 4), ByteIndex::from(5));
+    assert_eq!(index.line_col_to_offset(1, 1), ByteIndex::from(10));
+
+    // Byte columns are taken as-is.
+    assert_eq!(index.line_byte_col_to_offset(0, 4), ByteIndex::from(4));
+    assert_eq!(index.line_byte_col_to_offset(1, 2), ByteIndex::from(10));
+}
+
+#[test]
+fn utf16_columns() {
+    // `é` is one UTF-16 code unit; `😀` is two.
+    let text = "é😀x\nb";
+    let index = LineIndex::new(Text::from(text));
+    assert_eq!(index.offset_to_utf16_line_col(ByteIndex::from(2)), (0, 1));
+    assert_eq!(index.offset_to_utf16_line_col(ByteIndex::from(6)), (0, 3));
+    assert_eq!(index.offset_to_utf16_line_col(ByteIndex::from(7)), (0, 4));
+    assert_eq!(index.offset_to_utf16_line_col(ByteIndex::from(9)), (1, 1));
+    assert_eq!(index.offset_to_line_col(ByteIndex::from(7)).column, 3);
+
+    assert_eq!(index.utf16_line_col_to_offset(0, 1), ByteIndex::from(2));
+    assert_eq!(index.utf16_line_col_to_offset(0, 3), ByteIndex::from(6));
+    assert_eq!(index.utf16_line_col_to_offset(1, 1), ByteIndex::from(9));
+
+    // The middle of `😀` rounds down to its start; columns past the
+    // end of the line are clamped.
+    assert_eq!(index.utf16_line_col_to_offset(0, 2), ByteIndex::from(2));
+    assert_eq!(index.utf16_line_col_to_offset(0, 10), ByteIndex::from(7));
+}
+
+#[test]
+fn out_of_range_positions_are_clamped() {
+    let text = "abc\r\nd";
+    let index = LineIndex::new(Text::from(text));
+    assert_eq!(index.line_col_to_offset(0, 10), ByteIndex::from(3));
+    assert_eq!(index.line_col_to_offset(1, 10), ByteIndex::from(6));
+    assert_eq!(index.line_col_to_offset(5, 0), ByteIndex::from(6));
+    assert_eq!(index.line_byte_col_to_offset(0, 10), ByteIndex::from(3));
+}
+
+#[test]
+fn offsets_past_the_end_are_clamped() {
+    let index = LineIndex::new(Text::from("ab\nc"));
+    let location = index.offset_to_line_col(ByteIndex::from(10));
+    assert_eq!((location.line, location.column), (1, 1));
+    assert_eq!(location.byte, ByteIndex::from(4));
+    assert_eq!(index.offset_to_utf16_line_col(ByteIndex::from(10)), (1, 1));
+}
+
+#[test]
+fn offsets_inside_characters_round_down() {
+    // `é` is two bytes; `😀` is four.
+    let text = "é😀";
+    let index = LineIndex::new(Text::from(text));
+    let location = index.offset_to_line_col(ByteIndex::from(1));
+    assert_eq!((location.line, location.column), (0, 0));
+    assert_eq!(location.byte, ByteIndex::from(0));
+    assert_eq!(index.offset_to_utf16_line_col(ByteIndex::from(4)), (0, 1));
+
+    // Byte columns are rounded down too, so the offsets they give can
+    // be mapped back.
+    assert_eq!(index.line_byte_col_to_offset(0, 1), ByteIndex::from(0));
+    assert_eq!(index.line_byte_col_to_offset(0, 5), ByteIndex::from(2));
+}
@@ -3,9 +3,12 @@ use crate::{FileName, SpanFile};
 use language_reporting as l_r;
 use lark_debug_derive::DebugWith;
 use lark_string::Text;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::ops::Index;
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ByteIndex(crate usize);
 
 impl ByteIndex {
@@ -32,6 +35,7 @@ impl<File: SpanFile> Index<Span<File>> for str {
 }
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Span<File: SpanFile> {
     file: File,
     crate start: ByteIndex,
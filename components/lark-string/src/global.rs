@@ -1,8 +1,15 @@
 //! Global string interning.
 
 use crate::text::Text;
+use lark_collections::{FxHasher, FxIndexMap, U32Index};
 use lark_debug_with::FmtWithSpecialized;
 use lark_intern::{Intern, Untern};
+use parking_lot::RwLock;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod test;
 
 lark_collections::index_type! {
     /// A "global ident" is an identifier that is valid across files
@@ -13,19 +20,171 @@ lark_collections::index_type! {
 
 lark_debug_with::debug_fallback_impl!(GlobalIdentifier);
 
-lark_intern::intern_tables! {
-    pub struct GlobalIdentifierTables {
-        struct GlobalIdentifierTablesData {
-            strings: map(GlobalIdentifier, Text),
+/// Number of shards in the map from strings to ids. Each shard has
+/// its own lock, so threads interning different strings rarely
+/// contend with one another.
+const SHARDS: usize = 16;
+
+/// The global string interner; clones share the same tables.
+///
+/// To intern a string, we look it up in one shard of a sharded map,
+/// chosen by the string's hash. A string that is not yet present is
+/// added while holding that shard's write lock, so equal strings
+/// always get equal ids. The strings themselves are kept in a
+/// separate append-only list, indexed by id, which is only locked for
+/// writing to push a new string.
+#[derive(Clone, Default)]
+pub struct GlobalIdentifierTables {
+    data: Arc<GlobalIdentifierTablesData>,
+}
+
+#[derive(Default)]
+struct GlobalIdentifierTablesData {
+    shards: [RwLock<FxIndexMap<Text, GlobalIdentifier>>; SHARDS],
+    strings: RwLock<Vec<Text>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl AsRef<GlobalIdentifierTables> for GlobalIdentifierTables {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+/// Summary of the global string interner, for diagnosing memory use
+/// and redundant interning.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InternerStats {
+    /// Number of distinct strings interned.
+    pub strings: usize,
+
+    /// Total length, in bytes, of the distinct strings.
+    pub bytes: usize,
+
+    /// Number of interning requests for an already-interned string.
+    pub hits: usize,
+
+    /// Number of interning requests that added a new string.
+    pub misses: usize,
+}
+
+impl GlobalIdentifierTables {
+    pub fn stats(&self) -> InternerStats {
+        let strings = self.data.strings.read();
+        InternerStats {
+            strings: strings.len(),
+            bytes: strings.iter().map(|text| text.len()).sum(),
+            hits: self.data.hits.load(Ordering::Relaxed),
+            misses: self.data.misses.load(Ordering::Relaxed),
         }
     }
+
+    /// Returns the string for `id`, borrowed from the interner rather
+    /// than cloned out of it as `untern` does. Prefer this on hot
+    /// paths that only need to inspect the text.
+    ///
+    /// The read lock is released before returning, so the result may
+    /// be held across further interning without deadlocking.
+    pub fn untern_str(&self, id: GlobalIdentifier) -> &str {
+        let strings = self.data.strings.read();
+        let text: *const str = &strings[id.as_usize()][..];
+
+        // Safe because the list is append-only: entries are never
+        // removed or mutated, and each `Text` points into an
+        // immutable, heap-allocated `Arc<String>`. Growing the list
+        // may move the `Text` handles but never the string bytes, and
+        // the list lives as long as `self`.
+        unsafe { &*text }
+    }
+
+    /// Interns each of `items`, returning their ids in order.
+    ///
+    /// The items are grouped by shard first, so each shard's write
+    /// lock is taken at most once for the whole batch.
+    pub fn intern_strings<S>(&self, items: impl IntoIterator<Item = S>) -> Vec<GlobalIdentifier>
+    where
+        S: AsRef<str>,
+    {
+        let items: Vec<S> = items.into_iter().collect();
+
+        let mut by_shard: Vec<Vec<usize>> = vec![vec![]; SHARDS];
+        for (index, item) in items.iter().enumerate() {
+            by_shard[shard_index(item.as_ref())].push(index);
+        }
+
+        let mut ids = vec![None; items.len()];
+        for (shard, indices) in self.data.shards.iter().zip(by_shard) {
+            if indices.is_empty() {
+                continue;
+            }
+
+            let mut shard = shard.write();
+            for index in indices {
+                let item = items[index].as_ref();
+                ids[index] = Some(self.intern_locked(&mut shard, item, Text::from));
+            }
+        }
+
+        ids.into_iter().map(|id| id.unwrap()).collect()
+    }
+
+    /// Interns `data`, converting it with `to_text` only if it has
+    /// not been interned before.
+    fn intern_with<D>(&self, data: D, to_text: impl FnOnce(D) -> Text) -> GlobalIdentifier
+    where
+        D: AsRef<str>,
+    {
+        let shard = &self.data.shards[shard_index(data.as_ref())];
+        if let Some(&id) = shard.read().get(data.as_ref()) {
+            self.data.hits.fetch_add(1, Ordering::Relaxed);
+            return id;
+        }
+
+        // Another thread may have added the string since we looked,
+        // so `intern_locked` checks again under the write lock.
+        self.intern_locked(&mut shard.write(), data, to_text)
+    }
+
+    /// Interns `data` into `shard`, whose write lock the caller holds.
+    fn intern_locked<D>(
+        &self,
+        shard: &mut FxIndexMap<Text, GlobalIdentifier>,
+        data: D,
+        to_text: impl FnOnce(D) -> Text,
+    ) -> GlobalIdentifier
+    where
+        D: AsRef<str>,
+    {
+        if let Some(&id) = shard.get(data.as_ref()) {
+            self.data.hits.fetch_add(1, Ordering::Relaxed);
+            return id;
+        }
+
+        let text = to_text(data);
+        let id = {
+            let mut strings = self.data.strings.write();
+            strings.push(text.clone());
+            GlobalIdentifier::from_usize(strings.len() - 1)
+        };
+        shard.insert(text, id);
+        self.data.misses.fetch_add(1, Ordering::Relaxed);
+        id
+    }
+}
+
+/// Picks the shard of the string-to-id map that holds `string`.
+fn shard_index(string: &str) -> usize {
+    let mut hasher = FxHasher::default();
+    string.hash(&mut hasher);
+    hasher.finish() as usize % SHARDS
 }
 
 impl Intern<GlobalIdentifierTables> for &str {
     type Key = GlobalIdentifier;
 
     fn intern(self, interner: &dyn AsRef<GlobalIdentifierTables>) -> Self::Key {
-        lark_intern::intern_impl(self, interner, |d| &d[..], |d| Text::from(d))
+        interner.as_ref().intern_with(self, Text::from)
     }
 }
 
@@ -33,7 +192,24 @@ impl Intern<GlobalIdentifierTables> for String {
     type Key = GlobalIdentifier;
 
     fn intern(self, interner: &dyn AsRef<GlobalIdentifierTables>) -> Self::Key {
-        lark_intern::intern_impl(self, interner, |d| &d[..], |d| Text::from(d))
+        interner.as_ref().intern_with(self, Text::from)
+    }
+}
+
+impl Intern<GlobalIdentifierTables> for Text {
+    type Key = GlobalIdentifier;
+
+    fn intern(self, interner: &dyn AsRef<GlobalIdentifierTables>) -> Self::Key {
+        interner.as_ref().intern_with(self, |text| text)
+    }
+}
+
+impl Untern<GlobalIdentifierTables> for GlobalIdentifier {
+    type Data = Text;
+
+    fn untern(self, interner: &dyn AsRef<GlobalIdentifierTables>) -> Text {
+        let tables: &GlobalIdentifierTables = interner.as_ref();
+        tables.data.strings.read()[self.as_usize()].clone()
     }
 }
 
@@ -41,8 +217,36 @@ impl<Cx> FmtWithSpecialized<Cx> for GlobalIdentifier
 where
     Cx: AsRef<GlobalIdentifierTables>,
 {
+    /// Renders as the string itself, e.g. `"foo"`. Contexts without
+    /// access to the interner fall back to the plain `Debug` impl,
+    /// which shows only the index.
     fn fmt_with_specialized(&self, cx: &Cx, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let data = self.untern(cx);
-        write!(fmt, "{:?}", &data[..])
+        let cx: &GlobalIdentifierTables = cx.as_ref();
+        write!(fmt, "{:?}", cx.untern_str(*self))
+    }
+}
+
+impl GlobalIdentifier {
+    /// Displays the string along with its index, e.g. `"foo"#12`;
+    /// handy when debugging the interner itself.
+    pub fn display_with_index(self, tables: &GlobalIdentifierTables) -> DisplayWithIndex<'_> {
+        DisplayWithIndex { id: self, tables }
+    }
+}
+
+/// See `GlobalIdentifier::display_with_index`.
+pub struct DisplayWithIndex<'me> {
+    id: GlobalIdentifier,
+    tables: &'me GlobalIdentifierTables,
+}
+
+impl std::fmt::Display for DisplayWithIndex<'_> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "{:?}#{}",
+            self.tables.untern_str(self.id),
+            self.id.as_usize()
+        )
     }
 }
@@ -0,0 +1,149 @@
+#![cfg(test)]
+
+use crate::global::{GlobalIdentifier, GlobalIdentifierTables, InternerStats};
+use lark_debug_with::DebugWith;
+use lark_intern::{Intern, Untern};
+
+#[test]
+fn untern_str_matches_untern() {
+    let tables = GlobalIdentifierTables::default();
+    let ids: Vec<GlobalIdentifier> = (0..100)
+        .map(|i| format!("s{}", i).intern(&tables))
+        .collect();
+    for (i, &id) in ids.iter().enumerate() {
+        assert_eq!(tables.untern_str(id), format!("s{}", i));
+        assert_eq!(tables.untern_str(id), &id.untern(&tables)[..]);
+    }
+}
+
+#[test]
+fn untern_str_does_not_copy() {
+    let tables = GlobalIdentifierTables::default();
+    let id = "foo".intern(&tables);
+    let ptr = tables.untern_str(id).as_ptr();
+    for _ in 0..10_000 {
+        assert_eq!(tables.untern_str(id).as_ptr(), ptr);
+    }
+}
+
+#[test]
+fn untern_str_survives_interning() {
+    let tables = GlobalIdentifierTables::default();
+    let id = "foo".intern(&tables);
+    let foo = tables.untern_str(id);
+
+    // Interning while `foo` is borrowed must neither deadlock nor
+    // invalidate it, even though the table grows several times.
+    for i in 0..10_000 {
+        format!("bar{}", i).intern(&tables);
+    }
+
+    assert_eq!(foo, "foo");
+    assert_eq!("foo".intern(&tables), id);
+}
+
+#[test]
+fn intern_strings_matches_intern() {
+    let tables = GlobalIdentifierTables::default();
+    let bar = "bar".intern(&tables);
+
+    let first = tables.intern_strings(&["foo", "bar", "baz", "foo"]);
+    let second = tables.intern_strings(vec!["foo".to_string(), "bar".to_string()]);
+
+    assert_eq!(first[0], first[3]);
+    assert_eq!(first[1], bar);
+    assert_eq!(&first[..2], &second[..]);
+    for (&id, s) in first.iter().zip(&["foo", "bar", "baz", "foo"]) {
+        assert_eq!(id, s.intern(&tables));
+        assert_eq!(tables.untern_str(id), *s);
+    }
+}
+
+#[test]
+fn stats() {
+    let tables = GlobalIdentifierTables::default();
+    assert_eq!(tables.stats(), InternerStats::default());
+
+    for s in &["a", "bb", "ccc"] {
+        s.intern(&tables);
+    }
+    for s in &["a", "ccc", "a", "bb"] {
+        s.intern(&tables);
+    }
+    tables.intern_strings(&["bb", "dddd"]);
+
+    assert_eq!(
+        tables.stats(),
+        InternerStats {
+            strings: 4,
+            bytes: 10,
+            hits: 5,
+            misses: 4,
+        }
+    );
+}
+
+#[test]
+fn debug_with_interner() {
+    let tables = GlobalIdentifierTables::default();
+    let foo = "foo".intern(&tables);
+    assert_eq!(foo.debug_with(&tables).to_string(), r#""foo""#);
+}
+
+#[test]
+fn display_with_index() {
+    let tables = GlobalIdentifierTables::default();
+    "bar".intern(&tables);
+    let foo = "foo".intern(&tables);
+    assert_eq!(foo.display_with_index(&tables).to_string(), r##""foo"#1"##);
+}
+
+#[test]
+fn debug_with_without_interner() {
+    let tables = GlobalIdentifierTables::default();
+    let foo = "foo".intern(&tables);
+    assert_eq!(foo.debug_with(&()).to_string(), format!("{:?}", foo));
+}
+
+#[test]
+fn concurrent_interning_agrees() {
+    let tables = GlobalIdentifierTables::default();
+
+    // Each thread interns an overlapping window of the same strings,
+    // in a different order.
+    let threads: Vec<_> = (0..8)
+        .map(|t| {
+            let tables = tables.clone();
+            std::thread::spawn(move || {
+                let mut ids: Vec<(String, GlobalIdentifier)> = (0..1000)
+                    .map(|i| (t * 100 + i * 7 % 1000) % 1500)
+                    .map(|i| {
+                        let s = format!("s{}", i);
+                        let id = s.as_str().intern(&tables);
+                        (s, id)
+                    })
+                    .collect();
+                ids.reverse();
+                ids
+            })
+        })
+        .collect();
+
+    let mut seen: std::collections::HashMap<String, GlobalIdentifier> = Default::default();
+    for thread in threads {
+        for (s, id) in thread.join().unwrap() {
+            assert_eq!(*seen.entry(s.clone()).or_insert(id), id, "{}", s);
+            assert_eq!(tables.untern_str(id), s);
+        }
+    }
+
+    // Every distinct string got a distinct id, and nothing else was
+    // interned.
+    let mut ids: Vec<_> = seen.values().cloned().collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), seen.len());
+    assert_eq!(tables.stats().strings, seen.len());
+    assert_eq!(tables.stats().misses, seen.len());
+    assert_eq!(tables.stats().hits, 8 * 1000 - seen.len());
+}
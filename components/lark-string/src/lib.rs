@@ -4,5 +4,5 @@
 mod global;
 mod text;
 
-pub use self::global::{GlobalIdentifier, GlobalIdentifierTables};
+pub use self::global::{DisplayWithIndex, GlobalIdentifier, GlobalIdentifierTables, InternerStats};
 pub use self::text::Text;
@@ -117,6 +117,34 @@ pub fn lark_parser_db(text: impl AsRef<str>) -> (FileName, LarkDatabase) {
     (path1, db)
 }
 
+/// Generates the text of a file with a `Point` struct followed by
+/// `fn_count` fns that use it; each fn body exercises `let`,
+/// arithmetic, `if`, `while` and `match`. For tests and benchmarks
+/// that want a large input.
+pub fn synthetic_source(fn_count: usize) -> String {
+    let mut text = String::from("struct Point {\n    x: uint,\n    y: uint,\n}\n");
+    for i in 0..fn_count {
+        text.push_str(&format!(
+            "
+def f{i}(p: Point, n: uint) -> uint {{
+    let a = p.x * n + p.y
+    let b = if a > {i} {{ a - {i} }} else {{ {i} }}
+    let c = 0
+    while c < n {{
+        c += 1
+    }}
+    match b {{
+        0 => c,
+        other => other + c,
+    }}
+}}
+",
+            i = i
+        ));
+    }
+    text
+}
+
 /// Test that two values are equal, with a better error than `assert_eq`
 pub fn assert_equal<Cx, A>(cx: &Cx, expected_value: &A, actual_value: &A)
 where
@@ -5,11 +5,14 @@
 use crate::BaseData;
 use crate::BoundVar;
 use crate::BoundVarOr;
+use crate::PermKind;
 use crate::ReprKind;
 use crate::TypeFamily;
 use lark_debug_derive::DebugWith;
 use lark_debug_with::{DebugWith, FmtWithSpecialized};
 use lark_intern::{Intern, Untern};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
@@ -75,10 +78,24 @@ where
     }
 }
 
-/// For now, we only support `own T` in declarations.
+/// The permission written on a declared type: `owned T` (the
+/// default), `shared T` or `borrowed T`.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeclaredPermKind {
     Own,
+    Share,
+    Borrow,
+}
+
+impl DeclaredPermKind {
+    pub fn perm_kind(self) -> PermKind {
+        match self {
+            DeclaredPermKind::Own => PermKind::Own,
+            DeclaredPermKind::Share => PermKind::Share,
+            DeclaredPermKind::Borrow => PermKind::Borrow,
+        }
+    }
 }
 
 lark_intern::intern_tables! {
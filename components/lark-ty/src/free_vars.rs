@@ -0,0 +1,140 @@
+//! A visitor that collects the free variables (inference variables,
+//! placeholders and permission variables) that appear in a value.
+
+use crate::declaration;
+use crate::map_family::Visit;
+use crate::map_family::Visitor;
+use crate::BaseData;
+use crate::Erased;
+use crate::InferVarOr;
+use crate::PermKind;
+use crate::Ty;
+use crate::TypeFamily;
+use lark_collections::FxIndexSet;
+use lark_debug_derive::DebugWith;
+use lark_intern::Untern;
+use lark_unify::InferVar;
+
+mod test;
+
+/// A free variable found by the `FreeVarCollector`.
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+pub enum FreeVar<F: TypeFamily> {
+    InferVar(InferVar),
+    Placeholder(F::Placeholder),
+
+    /// A permission that is yet to be inferred.
+    Perm(F::Perm),
+}
+
+/// Implemented by the data that the `Base` of a type family unterns
+/// to; tells the `FreeVarCollector` whether a base is an inference
+/// variable.
+pub trait UnternedBase<F: TypeFamily> {
+    fn into_base_data(self) -> Result<BaseData<F>, InferVar>;
+}
+
+impl<F: TypeFamily> UnternedBase<F> for BaseData<F> {
+    fn into_base_data(self) -> Result<BaseData<F>, InferVar> {
+        Ok(self)
+    }
+}
+
+impl<F: TypeFamily> UnternedBase<F> for InferVarOr<BaseData<F>> {
+    fn into_base_data(self) -> Result<BaseData<F>, InferVar> {
+        match self {
+            InferVarOr::InferVar(var) => Err(var),
+            InferVarOr::Known(base_data) => Ok(base_data),
+        }
+    }
+}
+
+/// Implemented by the `Perm` of a type family; tells the
+/// `FreeVarCollector` whether a permission is a free variable.
+pub trait FreePerm<F: TypeFamily> {
+    fn free_var(self, tables: &dyn AsRef<F::InternTables>) -> Option<FreeVar<F>>;
+}
+
+impl<F: TypeFamily> FreePerm<F> for Erased {
+    fn free_var(self, _tables: &dyn AsRef<F::InternTables>) -> Option<FreeVar<F>> {
+        None
+    }
+}
+
+impl<F: TypeFamily> FreePerm<F> for PermKind {
+    fn free_var(self, _tables: &dyn AsRef<F::InternTables>) -> Option<FreeVar<F>> {
+        None
+    }
+}
+
+impl<F: TypeFamily> FreePerm<F> for declaration::Perm {
+    fn free_var(self, _tables: &dyn AsRef<F::InternTables>) -> Option<FreeVar<F>> {
+        None
+    }
+}
+
+/// Records every inference variable, placeholder and permission
+/// variable that it encounters.
+pub struct FreeVarCollector<'me, F: TypeFamily> {
+    tables: &'me dyn AsRef<F::InternTables>,
+    free_vars: FxIndexSet<FreeVar<F>>,
+}
+
+impl<F: TypeFamily> FreeVarCollector<'me, F> {
+    pub fn new(tables: &'me dyn AsRef<F::InternTables>) -> Self {
+        FreeVarCollector {
+            tables,
+            free_vars: FxIndexSet::default(),
+        }
+    }
+
+    /// The free variables seen so far, in the order they were first
+    /// encountered.
+    pub fn into_free_vars(self) -> FxIndexSet<FreeVar<F>> {
+        self.free_vars
+    }
+}
+
+impl<F> Visitor<F> for FreeVarCollector<'_, F>
+where
+    F: TypeFamily,
+    F::Base: Untern<F::InternTables>,
+    <F::Base as Untern<F::InternTables>>::Data: UnternedBase<F>,
+    F::Perm: FreePerm<F>,
+{
+    fn visit_ty(&mut self, ty: Ty<F>) {
+        self.visit_perm(ty.perm);
+
+        match ty.base.untern(self.tables).into_base_data() {
+            Ok(base_data) => base_data.visit_with(self),
+
+            Err(var) => {
+                self.free_vars.insert(FreeVar::InferVar(var));
+            }
+        }
+    }
+
+    fn visit_placeholder(&mut self, placeholder: F::Placeholder) {
+        self.free_vars.insert(FreeVar::Placeholder(placeholder));
+    }
+
+    fn visit_perm(&mut self, perm: F::Perm) {
+        if let Some(free_var) = perm.free_var(self.tables) {
+            self.free_vars.insert(free_var);
+        }
+    }
+}
+
+/// Returns the free variables that appear in `value`.
+pub fn free_vars<F, M>(value: &M, tables: &dyn AsRef<F::InternTables>) -> FxIndexSet<FreeVar<F>>
+where
+    F: TypeFamily,
+    F::Base: Untern<F::InternTables>,
+    <F::Base as Untern<F::InternTables>>::Data: UnternedBase<F>,
+    F::Perm: FreePerm<F>,
+    M: Visit<F>,
+{
+    let mut collector = FreeVarCollector::new(tables);
+    value.visit_with(&mut collector);
+    collector.into_free_vars()
+}
@@ -0,0 +1,75 @@
+#![cfg(test)]
+
+use crate::base_inferred::BaseInferred;
+use crate::base_inferred::BaseInferredTables;
+use crate::free_vars::free_vars;
+use crate::free_vars::FreeVar;
+use crate::BaseData;
+use crate::BaseKind;
+use crate::BoundVar;
+use crate::Erased;
+use crate::GenericKind;
+use crate::Generics;
+use crate::Placeholder;
+use crate::Ty;
+use crate::Universe;
+use lark_intern::Intern;
+
+fn placeholder(index: usize) -> Placeholder {
+    Placeholder {
+        universe: Universe::ROOT,
+        bound_var: BoundVar::new(index),
+    }
+}
+
+/// Creates the type for the placeholder `index`, applied to `generics`.
+fn placeholder_ty(
+    tables: &BaseInferredTables,
+    index: usize,
+    generics: Vec<Ty<BaseInferred>>,
+) -> Ty<BaseInferred> {
+    let base_data: BaseData<BaseInferred> = BaseData {
+        kind: BaseKind::Placeholder(placeholder(index)),
+        generics: generics.into_iter().map(GenericKind::Ty).collect(),
+    };
+    Ty {
+        repr: Erased,
+        perm: Erased,
+        base: base_data.intern(tables),
+    }
+}
+
+#[test]
+fn nested_placeholders() {
+    let tables = &BaseInferredTables::default();
+    let p0 = placeholder_ty(tables, 0, vec![]);
+    let p1 = placeholder_ty(tables, 1, vec![p0]);
+    let ty = placeholder_ty(tables, 0, vec![p1, p0]);
+
+    let vars: Vec<_> = free_vars::<BaseInferred, _>(&ty, tables)
+        .into_iter()
+        .collect();
+    assert_eq!(
+        vars,
+        vec![
+            FreeVar::Placeholder(placeholder(0)),
+            FreeVar::Placeholder(placeholder(1)),
+        ]
+    );
+}
+
+#[test]
+fn no_free_vars() {
+    let tables = &BaseInferredTables::default();
+    let error_ty: Ty<BaseInferred> = Ty {
+        repr: Erased,
+        perm: Erased,
+        base: BaseData {
+            kind: BaseKind::Error,
+            generics: Generics::empty(),
+        }
+        .intern(tables),
+    };
+
+    assert!(free_vars::<BaseInferred, _>(&(error_ty, error_ty), tables).is_empty());
+}
@@ -12,6 +12,8 @@ use lark_error::ErrorReported;
 use lark_error::ErrorSentinel;
 use lark_string::GlobalIdentifier;
 use lark_unify::InferVar;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug};
 use std::hash::Hash;
 use std::iter::IntoIterator;
@@ -19,9 +21,14 @@ use std::sync::Arc;
 
 pub mod base_inferred;
 pub mod declaration;
+pub mod free_vars;
 pub mod full_inferred;
 pub mod identity;
 pub mod map_family;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod substitution;
+mod test;
 
 pub trait TypeFamily: Copy + Clone + Debug + DebugWith + Eq + Hash + 'static {
     type InternTables: AsRef<Self::InternTables>;
@@ -66,6 +73,14 @@ pub trait TypeFamily: Copy + Clone + Debug + DebugWith + Eq + Hash + 'static {
 
 /// A type is the combination of a *permission* and a *base type*.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "F::Repr: Serialize, F::Perm: Serialize, F::Base: Serialize",
+        deserialize = "F::Repr: Deserialize<'de>, F::Perm: Deserialize<'de>, F::Base: Deserialize<'de>"
+    ))
+)]
 pub struct Ty<F: TypeFamily> {
     pub repr: F::Repr,
     pub perm: F::Perm,
@@ -84,6 +99,7 @@ where
 
 /// Indicates something that we've opted not to track statically.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Erased;
 
 /// The "base data" for a type.
@@ -139,6 +155,7 @@ impl<T> InferVarOr<T> {
 /// when we are type-checking a function like `fn foo<T>`, the `T` is represented by
 /// a placeholder.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Placeholder {
     pub universe: Universe,
     pub bound_var: BoundVar,
@@ -188,6 +205,14 @@ lark_debug_with::debug_fallback_impl!(BoundVar);
 /// A set of generic arguments; e.g., in a type like `Vec<i32>`, this
 /// would be `[i32]`.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "Ty<F>: Serialize",
+        deserialize = "Ty<F>: Deserialize<'de>"
+    ))
+)]
 pub struct Generics<F: TypeFamily> {
     elements: Seq<Generic<F>>,
 }
@@ -289,6 +314,7 @@ pub type Generic<F: TypeFamily> = GenericKind<Ty<F>>;
 /// An enum that lists out the various "kinds" of generic arguments
 /// (currently only types) and a distinct type of value for each kind.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GenericKind<T> {
     Ty(T),
 }
@@ -328,6 +354,10 @@ impl<F: TypeFamily> Signature<F> {
 pub struct GenericDeclarations {
     pub parent_item: Option<Entity>,
     pub declarations: IndexVec<BoundVar, GenericKind<GenericTyDeclaration>>,
+
+    /// The `where` clauses that constrain the generic parameters;
+    /// these must be satisfied whenever the item is instantiated.
+    pub constraints: Seq<Constraint>,
 }
 
 impl GenericDeclarations {
@@ -335,6 +365,7 @@ impl GenericDeclarations {
         Arc::new(GenericDeclarations {
             parent_item,
             declarations: IndexVec::default(),
+            constraints: Seq::default(),
         })
     }
 
@@ -350,15 +381,51 @@ pub struct GenericTyDeclaration {
     pub name: GlobalIdentifier,
 }
 
+/// A `where` clause like `T: Trait`, requiring that the generic
+/// parameter `bound_var` implement the trait `trait_entity`.
+#[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+pub struct Constraint {
+    pub bound_var: BoundVar,
+    pub trait_entity: Entity,
+}
+
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PermKind {
     Own,
     Share,
     Borrow,
 }
 
+impl PermKind {
+    /// The least upper bound of `self` and `other`, where permissions
+    /// are ordered `Share < Borrow < Own`.
+    pub fn join(self, other: PermKind) -> PermKind {
+        if self.at_least(other) {
+            self
+        } else {
+            other
+        }
+    }
+
+    /// True if `self` permits everything that `other` does -- that
+    /// is, if `other <= self` in the ordering `Share < Borrow < Own`.
+    pub fn at_least(self, other: PermKind) -> bool {
+        self.rank() >= other.rank()
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            PermKind::Share => 0,
+            PermKind::Borrow => 1,
+            PermKind::Own => 2,
+        }
+    }
+}
+
 /// Encodes whether we reach the data through pointer indirection or not.
 #[derive(Copy, Clone, Debug, DebugWith, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ReprKind {
     /// Store the fields in place.
     Direct,
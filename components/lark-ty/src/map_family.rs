@@ -13,6 +13,8 @@ use std::collections::BTreeMap;
 use std::hash::Hash;
 use std::sync::Arc;
 
+mod test;
+
 pub trait Map<S: TypeFamily, T: TypeFamily>: Clone {
     type Output;
 
@@ -54,6 +56,20 @@ where
     }
 }
 
+impl<S, T, V> Map<S, T> for Box<V>
+where
+    S: TypeFamily,
+    T: TypeFamily,
+    V: Map<S, T>,
+{
+    type Output = Box<V::Output>;
+
+    fn map(&self, mapper: &mut impl FamilyMapper<S, T>) -> Self::Output {
+        let this: &V = self;
+        Box::new(this.map(mapper))
+    }
+}
+
 impl<S, T, V> Map<S, T> for Option<V>
 where
     S: TypeFamily,
@@ -70,6 +86,37 @@ where
     }
 }
 
+impl<S, T, A, B> Map<S, T> for (A, B)
+where
+    S: TypeFamily,
+    T: TypeFamily,
+    A: Map<S, T>,
+    B: Map<S, T>,
+{
+    type Output = (A::Output, B::Output);
+
+    fn map(&self, mapper: &mut impl FamilyMapper<S, T>) -> Self::Output {
+        let (a, b) = self;
+        (a.map(mapper), b.map(mapper))
+    }
+}
+
+impl<S, T, A, B, C> Map<S, T> for (A, B, C)
+where
+    S: TypeFamily,
+    T: TypeFamily,
+    A: Map<S, T>,
+    B: Map<S, T>,
+    C: Map<S, T>,
+{
+    type Output = (A::Output, B::Output, C::Output);
+
+    fn map(&self, mapper: &mut impl FamilyMapper<S, T>) -> Self::Output {
+        let (a, b, c) = self;
+        (a.map(mapper), b.map(mapper), c.map(mapper))
+    }
+}
+
 impl<S, T, V> Map<S, T> for Vec<V>
 where
     S: TypeFamily,
@@ -258,3 +305,231 @@ where
         *self
     }
 }
+
+/// Like `Map`, but walks the value without rebuilding it -- so no
+/// interning is done. Read-only analyses (like collecting free
+/// variables) should prefer this to a `FamilyMapper` that returns its
+/// input unchanged.
+pub trait Visit<F: TypeFamily> {
+    fn visit_with(&self, visitor: &mut impl Visitor<F>);
+}
+
+pub trait Visitor<F: TypeFamily> {
+    fn visit_ty(&mut self, ty: Ty<F>);
+
+    fn visit_placeholder(&mut self, placeholder: F::Placeholder);
+
+    fn visit_perm(&mut self, perm: F::Perm);
+}
+
+impl<F, V> Visit<F> for &V
+where
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        <V as Visit<F>>::visit_with(self, visitor)
+    }
+}
+
+impl<F, V> Visit<F> for Arc<V>
+where
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        let this: &V = self;
+        this.visit_with(visitor)
+    }
+}
+
+impl<F, V> Visit<F> for Box<V>
+where
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        let this: &V = self;
+        this.visit_with(visitor)
+    }
+}
+
+impl<F, V> Visit<F> for Option<V>
+where
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        if let Some(v) = self {
+            v.visit_with(visitor);
+        }
+    }
+}
+
+impl<F, A, B> Visit<F> for (A, B)
+where
+    F: TypeFamily,
+    A: Visit<F>,
+    B: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        let (a, b) = self;
+        a.visit_with(visitor);
+        b.visit_with(visitor);
+    }
+}
+
+impl<F, A, B, C> Visit<F> for (A, B, C)
+where
+    F: TypeFamily,
+    A: Visit<F>,
+    B: Visit<F>,
+    C: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        let (a, b, c) = self;
+        a.visit_with(visitor);
+        b.visit_with(visitor);
+        c.visit_with(visitor);
+    }
+}
+
+impl<F, V> Visit<F> for Vec<V>
+where
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        for e in self {
+            e.visit_with(visitor);
+        }
+    }
+}
+
+impl<F, V> Visit<F> for Seq<V>
+where
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        for e in self.iter() {
+            e.visit_with(visitor);
+        }
+    }
+}
+
+impl<K, F, V> Visit<F> for BTreeMap<K, V>
+where
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        for v in self.values() {
+            v.visit_with(visitor);
+        }
+    }
+}
+
+impl<K, F, V> Visit<F> for FxIndexMap<K, V>
+where
+    K: Eq + Hash,
+    F: TypeFamily,
+    V: Visit<F>,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        for v in self.values() {
+            v.visit_with(visitor);
+        }
+    }
+}
+
+impl<F, V> Visit<F> for FxIndexSet<V>
+where
+    F: TypeFamily,
+    V: Visit<F> + Eq + Hash,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        for v in self {
+            v.visit_with(visitor);
+        }
+    }
+}
+
+impl<F> Visit<F> for Ty<F>
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        visitor.visit_ty(*self)
+    }
+}
+
+impl<F> Visit<F> for BaseData<F>
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        let BaseData { kind, generics } = self;
+        kind.visit_with(visitor);
+        generics.visit_with(visitor);
+    }
+}
+
+impl<F> Visit<F> for Entity
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, _visitor: &mut impl Visitor<F>) {}
+}
+
+impl<F> Visit<F> for BaseKind<F>
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        match self {
+            BaseKind::Named(_) | BaseKind::Error => {}
+
+            BaseKind::Placeholder(placeholder) => visitor.visit_placeholder(*placeholder),
+        }
+    }
+}
+
+impl<F> Visit<F> for Generics<F>
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        let Generics { elements } = self;
+        elements.visit_with(visitor);
+    }
+}
+
+impl<F> Visit<F> for Generic<F>
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        match self {
+            GenericKind::Ty(ty) => ty.visit_with(visitor),
+        }
+    }
+}
+
+impl<F> Visit<F> for Signature<F>
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, visitor: &mut impl Visitor<F>) {
+        let Signature { inputs, output } = self;
+        inputs.visit_with(visitor);
+        output.visit_with(visitor);
+    }
+}
+
+impl<F> Visit<F> for Erased
+where
+    F: TypeFamily,
+{
+    fn visit_with(&self, _visitor: &mut impl Visitor<F>) {}
+}
@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use crate::base_inferred::BaseInferred;
+use crate::base_inferred::BaseInferredTables;
+use crate::identity::Identity;
+use crate::map_family::Map;
+use crate::map_family::Visit;
+use crate::map_family::Visitor;
+use crate::substitution::Substitution;
+use crate::BaseData;
+use crate::BaseKind;
+use crate::BoundVar;
+use crate::Erased;
+use crate::GenericKind;
+use crate::Placeholder;
+use crate::Ty;
+use crate::Universe;
+use lark_collections::{FxIndexMap, U32Index};
+use lark_intern::{Intern, Untern};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn placeholder(index: usize) -> Placeholder {
+    Placeholder {
+        universe: Universe::ROOT,
+        bound_var: BoundVar::new(index),
+    }
+}
+
+/// Creates the type for the placeholder `index`, applied to `generics`.
+fn placeholder_ty(
+    tables: &BaseInferredTables,
+    index: usize,
+    generics: Vec<Ty<BaseInferred>>,
+) -> Ty<BaseInferred> {
+    let base_data: BaseData<BaseInferred> = BaseData {
+        kind: BaseKind::Placeholder(placeholder(index)),
+        generics: generics.into_iter().map(GenericKind::Ty).collect(),
+    };
+    Ty {
+        repr: Erased,
+        perm: Erased,
+        base: base_data.intern(tables),
+    }
+}
+
+/// Creates a substitution replacing the placeholder `from` with `to`.
+fn replace(
+    tables: &'me BaseInferredTables,
+    from: usize,
+    to: Ty<BaseInferred>,
+) -> Substitution<'me, BaseInferred> {
+    let tys = vec![(placeholder(from), to)].into_iter().collect();
+    Substitution::new(tables, tys, FxIndexMap::default())
+}
+
+#[test]
+fn map_pair() {
+    let tables = &BaseInferredTables::default();
+    let p0 = placeholder_ty(tables, 0, vec![]);
+    let p1 = placeholder_ty(tables, 1, vec![]);
+    let p2 = placeholder_ty(tables, 2, vec![]);
+    let pair = (p0, placeholder_ty(tables, 1, vec![p0]));
+
+    assert_eq!(pair.map(&mut Identity::new(tables)), pair);
+
+    let mut substitution = replace(tables, 0, p2);
+    assert_eq!(
+        pair.map(&mut substitution),
+        (p2, placeholder_ty(tables, 1, vec![p2]))
+    );
+    assert_eq!((p1, p1).map(&mut substitution), (p1, p1));
+}
+
+#[test]
+fn map_triple() {
+    let tables = &BaseInferredTables::default();
+    let p0 = placeholder_ty(tables, 0, vec![]);
+    let p1 = placeholder_ty(tables, 1, vec![]);
+    let p2 = placeholder_ty(tables, 2, vec![]);
+
+    let mut substitution = replace(tables, 0, p2);
+    assert_eq!((p0, p1, p0).map(&mut substitution), (p2, p1, p2));
+}
+
+#[test]
+fn map_btree_map() {
+    let tables = &BaseInferredTables::default();
+    let p0 = placeholder_ty(tables, 0, vec![]);
+    let p1 = placeholder_ty(tables, 1, vec![]);
+    let p2 = placeholder_ty(tables, 2, vec![]);
+    let tys: BTreeMap<u32, Ty<BaseInferred>> =
+        vec![(22, p0), (44, p1), (66, p0)].into_iter().collect();
+
+    let mut substitution = replace(tables, 0, p2);
+    let expected: BTreeMap<u32, Ty<BaseInferred>> =
+        vec![(22, p2), (44, p1), (66, p2)].into_iter().collect();
+    assert_eq!(tys.map(&mut substitution), expected);
+}
+
+#[test]
+fn map_box() {
+    let tables = &BaseInferredTables::default();
+    let p0 = placeholder_ty(tables, 0, vec![]);
+    let p2 = placeholder_ty(tables, 2, vec![]);
+
+    let boxed = Box::new(placeholder_ty(tables, 1, vec![p0]));
+    let mut substitution = replace(tables, 0, p2);
+    assert_eq!(
+        boxed.map(&mut substitution),
+        Box::new(placeholder_ty(tables, 1, vec![p2]))
+    );
+}
+
+#[test]
+fn map_arc() {
+    let tables = &BaseInferredTables::default();
+    let p0 = placeholder_ty(tables, 0, vec![]);
+    let p2 = placeholder_ty(tables, 2, vec![]);
+
+    let shared = Arc::new(placeholder_ty(tables, 1, vec![p0]));
+    let mut substitution = replace(tables, 0, p2);
+    let mapped = shared.map(&mut substitution);
+    assert_eq!(*mapped, placeholder_ty(tables, 1, vec![p2]));
+
+    // The original is left alone, and the identity mapping still
+    // produces a fresh `Arc`.
+    assert_eq!(*shared, placeholder_ty(tables, 1, vec![p0]));
+    let identity = shared.map(&mut Identity::new(tables));
+    assert!(!Arc::ptr_eq(&identity, &shared));
+}
+
+/// Counts the types it visits, including those in generics.
+struct CountTys<'me> {
+    tables: &'me BaseInferredTables,
+    count: usize,
+}
+
+impl Visitor<BaseInferred> for CountTys<'_> {
+    fn visit_ty(&mut self, ty: Ty<BaseInferred>) {
+        self.count += 1;
+        ty.base.untern(self.tables).visit_with(self);
+    }
+
+    fn visit_placeholder(&mut self, _placeholder: Placeholder) {}
+
+    fn visit_perm(&mut self, _perm: Erased) {}
+}
+
+#[test]
+fn visit_does_not_intern() {
+    let tables = &BaseInferredTables::default();
+    let p0 = placeholder_ty(tables, 0, vec![]);
+    let p2 = placeholder_ty(tables, 2, vec![p0]);
+    let ty = placeholder_ty(tables, 1, vec![p0, p2]);
+
+    // Newly interned bases get consecutive indices, so long as
+    // nothing else was interned in between.
+    let before = placeholder_ty(tables, 3, vec![]).base.as_usize();
+    let mut count_tys = CountTys { tables, count: 0 };
+    (ty, vec![ty, p0]).visit_with(&mut count_tys);
+    let after = placeholder_ty(tables, 4, vec![]).base.as_usize();
+
+    assert_eq!(count_tys.count, 9);
+    assert_eq!(after, before + 1);
+}
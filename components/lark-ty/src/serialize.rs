@@ -0,0 +1,438 @@
+//! Support for serializing types with serde (enabled by the `serde`
+//! feature).
+//!
+//! A `Ty` is serialized as the interned ids of its permission and
+//! base, and those ids only mean something to the database that
+//! interned them. To read types back into another database,
+//! `SideTableBuilder` records the data behind every id that a value
+//! mentions -- identifiers, entities, permissions and bases -- into a
+//! `SideTable`. Later, `SideTable::reintern` interns that data into
+//! the new database, giving a `Reinterner` that maps each old id to
+//! its new one.
+
+use crate::base_inferred::{self, BaseInferred};
+use crate::declaration::{self, Declaration, DeclaredPermKind};
+use crate::full_inferred::{self, FullInferred};
+use crate::BaseData;
+use crate::BaseKind;
+use crate::BoundVar;
+use crate::BoundVarOr;
+use crate::Erased;
+use crate::GenericKind;
+use crate::Generics;
+use crate::PermKind;
+use crate::Placeholder;
+use crate::Ty;
+use crate::TypeFamily;
+use lark_collections::FxIndexMap;
+use lark_entity::{Entity, EntityData, EntityTables};
+use lark_error::ErrorReported;
+use lark_intern::{Intern, Untern};
+use lark_span::{FileName, Span};
+use lark_string::{GlobalIdentifier, GlobalIdentifierTables};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A type family whose interned permissions and bases can be
+/// recorded in a `SideTable`.
+pub trait SerializeFamily: TypeFamily {
+    /// The data behind a permission.
+    type PermData: Clone + Debug + Eq;
+
+    fn untern_perm(tables: &dyn AsRef<Self::InternTables>, perm: Self::Perm) -> Self::PermData;
+
+    fn intern_perm(tables: &dyn AsRef<Self::InternTables>, data: Self::PermData) -> Self::Perm;
+
+    fn untern_base(
+        tables: &dyn AsRef<Self::InternTables>,
+        base: Self::Base,
+    ) -> SerializedBase<Self>;
+
+    fn intern_base(
+        tables: &dyn AsRef<Self::InternTables>,
+        data: SerializedBase<Self>,
+    ) -> Self::Base;
+}
+
+/// The data behind an interned base. The generic arguments of a
+/// named type still use the ids of the database that interned them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "Generics<F>: Serialize",
+    deserialize = "Generics<F>: Deserialize<'de>"
+))]
+pub enum SerializedBase<F: TypeFamily> {
+    /// A reference to a generic parameter (only in declared types).
+    BoundVar(BoundVar),
+
+    Named { entity: Entity, generics: Generics<F> },
+
+    /// An instantiated generic parameter (only in inferred types).
+    Placeholder(Placeholder),
+
+    Error,
+}
+
+impl<F: TypeFamily> SerializedBase<F> {
+    fn from_base_data(
+        data: BaseData<F>,
+        placeholder: impl FnOnce(F::Placeholder) -> Placeholder,
+    ) -> Self {
+        let BaseData { kind, generics } = data;
+        match kind {
+            BaseKind::Named(entity) => SerializedBase::Named { entity, generics },
+            BaseKind::Placeholder(p) => SerializedBase::Placeholder(placeholder(p)),
+            BaseKind::Error => SerializedBase::Error,
+        }
+    }
+
+    fn into_base_data(
+        self,
+        placeholder: impl FnOnce(Placeholder) -> F::Placeholder,
+    ) -> BaseData<F> {
+        match self {
+            SerializedBase::BoundVar(_) => panic!("only declared types refer to bound variables"),
+            SerializedBase::Named { entity, generics } => BaseData {
+                kind: BaseKind::Named(entity),
+                generics,
+            },
+            SerializedBase::Placeholder(p) => BaseData::from_placeholder(placeholder(p)),
+            SerializedBase::Error => BaseData {
+                kind: BaseKind::Error,
+                generics: Generics::empty(),
+            },
+        }
+    }
+}
+
+impl SerializeFamily for Declaration {
+    type PermData = DeclaredPermKind;
+
+    fn untern_perm(
+        tables: &dyn AsRef<declaration::DeclarationTables>,
+        perm: declaration::Perm,
+    ) -> DeclaredPermKind {
+        perm.untern(tables)
+    }
+
+    fn intern_perm(
+        tables: &dyn AsRef<declaration::DeclarationTables>,
+        data: DeclaredPermKind,
+    ) -> declaration::Perm {
+        data.intern(tables)
+    }
+
+    fn untern_base(
+        tables: &dyn AsRef<declaration::DeclarationTables>,
+        base: declaration::Base,
+    ) -> SerializedBase<Self> {
+        match base.untern(tables) {
+            BoundVarOr::BoundVar(bound_var) => SerializedBase::BoundVar(bound_var),
+            BoundVarOr::Known(data) => SerializedBase::from_base_data(data, |p| match p {}),
+        }
+    }
+
+    fn intern_base(
+        tables: &dyn AsRef<declaration::DeclarationTables>,
+        data: SerializedBase<Self>,
+    ) -> declaration::Base {
+        match data {
+            SerializedBase::BoundVar(bound_var) => Declaration::intern_bound_var(tables, bound_var),
+            data => {
+                let data = data.into_base_data(|_| panic!("declared types have no placeholders"));
+                Declaration::intern_base_data(tables, data)
+            }
+        }
+    }
+}
+
+impl SerializeFamily for BaseInferred {
+    type PermData = Erased;
+
+    fn untern_perm(
+        _tables: &dyn AsRef<base_inferred::BaseInferredTables>,
+        perm: Erased,
+    ) -> Erased {
+        perm
+    }
+
+    fn intern_perm(
+        _tables: &dyn AsRef<base_inferred::BaseInferredTables>,
+        data: Erased,
+    ) -> Erased {
+        data
+    }
+
+    fn untern_base(
+        tables: &dyn AsRef<base_inferred::BaseInferredTables>,
+        base: base_inferred::Base,
+    ) -> SerializedBase<Self> {
+        SerializedBase::from_base_data(base.untern(tables), |p| p)
+    }
+
+    fn intern_base(
+        tables: &dyn AsRef<base_inferred::BaseInferredTables>,
+        data: SerializedBase<Self>,
+    ) -> base_inferred::Base {
+        BaseInferred::intern_base_data(tables, data.into_base_data(|p| p))
+    }
+}
+
+impl SerializeFamily for FullInferred {
+    type PermData = PermKind;
+
+    fn untern_perm(
+        _tables: &dyn AsRef<full_inferred::FullInferredTables>,
+        perm: PermKind,
+    ) -> PermKind {
+        perm
+    }
+
+    fn intern_perm(
+        _tables: &dyn AsRef<full_inferred::FullInferredTables>,
+        data: PermKind,
+    ) -> PermKind {
+        data
+    }
+
+    fn untern_base(
+        tables: &dyn AsRef<full_inferred::FullInferredTables>,
+        base: full_inferred::Base,
+    ) -> SerializedBase<Self> {
+        SerializedBase::from_base_data(base.untern(tables), |p| p)
+    }
+
+    fn intern_base(
+        tables: &dyn AsRef<full_inferred::FullInferredTables>,
+        data: SerializedBase<Self>,
+    ) -> full_inferred::Base {
+        FullInferred::intern_base_data(tables, data.into_base_data(|p| p))
+    }
+}
+
+/// The data behind each interned id that a serialized value mentions.
+/// Every entry comes after the entries that it refers to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F::Repr: Serialize, F::Perm: Serialize, F::Base: Serialize, \
+                 F::PermData: Serialize",
+    deserialize = "F::Repr: Deserialize<'de>, F::Perm: Deserialize<'de>, \
+                   F::Base: Deserialize<'de>, F::PermData: Deserialize<'de>"
+))]
+pub struct SideTable<F: SerializeFamily> {
+    pub strings: Vec<(GlobalIdentifier, String)>,
+    pub entities: Vec<(Entity, EntityData)>,
+    pub perms: Vec<(F::Perm, F::PermData)>,
+    pub bases: Vec<(F::Base, SerializedBase<F>)>,
+}
+
+impl<F: SerializeFamily> SideTable<F> {
+    /// Interns the data in this table into `db`, returning the
+    /// mapping from the ids in the table to the new ids.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry refers to an id that comes later in the
+    /// table (or not at all); tables made by `SideTableBuilder` never
+    /// do.
+    pub fn reintern<DB>(&self, db: &DB) -> Reinterner<F>
+    where
+        DB: AsRef<GlobalIdentifierTables> + AsRef<EntityTables> + AsRef<F::InternTables>,
+    {
+        let mut reinterner = Reinterner {
+            strings: FxIndexMap::default(),
+            entities: FxIndexMap::default(),
+            perms: FxIndexMap::default(),
+            bases: FxIndexMap::default(),
+        };
+
+        for (old, text) in &self.strings {
+            let new = text.as_str().intern(db);
+            reinterner.strings.insert(*old, new);
+        }
+
+        for (old, data) in &self.entities {
+            let data = match data {
+                EntityData::Error(ErrorReported(span)) => {
+                    EntityData::Error(ErrorReported(reinterner.span(*span)))
+                }
+                EntityData::LangItem(lang_item) => EntityData::LangItem(*lang_item),
+                EntityData::InputFile { file } => EntityData::InputFile {
+                    file: reinterner.file_name(*file),
+                },
+                EntityData::ItemName { base, kind, id } => EntityData::ItemName {
+                    base: reinterner.entity(*base),
+                    kind: *kind,
+                    id: reinterner.string(*id),
+                },
+                EntityData::MemberName { base, kind, id } => EntityData::MemberName {
+                    base: reinterner.entity(*base),
+                    kind: *kind,
+                    id: reinterner.string(*id),
+                },
+            };
+            let new = data.intern(db);
+            reinterner.entities.insert(*old, new);
+        }
+
+        for (old, data) in &self.perms {
+            let new = F::intern_perm(db, data.clone());
+            reinterner.perms.insert(*old, new);
+        }
+
+        for (old, data) in &self.bases {
+            let data = match data {
+                SerializedBase::Named { entity, generics } => SerializedBase::Named {
+                    entity: reinterner.entity(*entity),
+                    generics: reinterner.generics(generics),
+                },
+                data => data.clone(),
+            };
+            let new = F::intern_base(db, data);
+            reinterner.bases.insert(*old, new);
+        }
+
+        reinterner
+    }
+}
+
+/// Records the data behind interned ids, building up a `SideTable`.
+pub struct SideTableBuilder<'db, DB, F: SerializeFamily> {
+    db: &'db DB,
+    strings: FxIndexMap<GlobalIdentifier, String>,
+    entities: FxIndexMap<Entity, EntityData>,
+    perms: FxIndexMap<F::Perm, F::PermData>,
+    bases: FxIndexMap<F::Base, SerializedBase<F>>,
+}
+
+impl<DB, F> SideTableBuilder<'db, DB, F>
+where
+    DB: AsRef<GlobalIdentifierTables> + AsRef<EntityTables> + AsRef<F::InternTables>,
+    F: SerializeFamily,
+{
+    pub fn new(db: &'db DB) -> Self {
+        SideTableBuilder {
+            db,
+            strings: FxIndexMap::default(),
+            entities: FxIndexMap::default(),
+            perms: FxIndexMap::default(),
+            bases: FxIndexMap::default(),
+        }
+    }
+
+    pub fn record_string(&mut self, id: GlobalIdentifier) {
+        if !self.strings.contains_key(&id) {
+            let text = id.untern(self.db).to_string();
+            self.strings.insert(id, text);
+        }
+    }
+
+    pub fn record_span(&mut self, span: Span<FileName>) {
+        self.record_string(span.file().id);
+    }
+
+    pub fn record_entity(&mut self, entity: Entity) {
+        if self.entities.contains_key(&entity) {
+            return;
+        }
+
+        let data = entity.untern(self.db);
+        match &data {
+            EntityData::Error(error) => self.record_span(error.span()),
+            EntityData::LangItem(_) => {}
+            EntityData::InputFile { file } => self.record_string(file.id),
+            EntityData::ItemName { base, id, .. } | EntityData::MemberName { base, id, .. } => {
+                self.record_entity(*base);
+                self.record_string(*id);
+            }
+        }
+        self.entities.insert(entity, data);
+    }
+
+    pub fn record_perm(&mut self, perm: F::Perm) {
+        if !self.perms.contains_key(&perm) {
+            let data = F::untern_perm(self.db, perm);
+            self.perms.insert(perm, data);
+        }
+    }
+
+    pub fn record_ty(&mut self, ty: Ty<F>) {
+        self.record_perm(ty.perm);
+        if !self.bases.contains_key(&ty.base) {
+            let data = F::untern_base(self.db, ty.base);
+            if let SerializedBase::Named { entity, generics } = &data {
+                self.record_entity(*entity);
+                self.record_generics(generics);
+            }
+            self.bases.insert(ty.base, data);
+        }
+    }
+
+    pub fn record_generics(&mut self, generics: &Generics<F>) {
+        for generic in generics {
+            match generic {
+                GenericKind::Ty(ty) => self.record_ty(ty),
+            }
+        }
+    }
+
+    pub fn finish(self) -> SideTable<F> {
+        SideTable {
+            strings: self.strings.into_iter().collect(),
+            entities: self.entities.into_iter().collect(),
+            perms: self.perms.into_iter().collect(),
+            bases: self.bases.into_iter().collect(),
+        }
+    }
+}
+
+/// Maps the ids recorded in a `SideTable` to the ids of the same data
+/// in another database. Each method panics if given an id that is not
+/// in the table.
+pub struct Reinterner<F: SerializeFamily> {
+    strings: FxIndexMap<GlobalIdentifier, GlobalIdentifier>,
+    entities: FxIndexMap<Entity, Entity>,
+    perms: FxIndexMap<F::Perm, F::Perm>,
+    bases: FxIndexMap<F::Base, F::Base>,
+}
+
+impl<F: SerializeFamily> Reinterner<F> {
+    pub fn string(&self, id: GlobalIdentifier) -> GlobalIdentifier {
+        self.strings[&id]
+    }
+
+    pub fn file_name(&self, file: FileName) -> FileName {
+        FileName {
+            id: self.string(file.id),
+        }
+    }
+
+    pub fn span(&self, span: Span<FileName>) -> Span<FileName> {
+        span.in_file_named(self.file_name(span.file()))
+    }
+
+    pub fn entity(&self, entity: Entity) -> Entity {
+        self.entities[&entity]
+    }
+
+    pub fn perm(&self, perm: F::Perm) -> F::Perm {
+        self.perms[&perm]
+    }
+
+    pub fn ty(&self, ty: Ty<F>) -> Ty<F> {
+        Ty {
+            repr: ty.repr,
+            perm: self.perm(ty.perm),
+            base: self.bases[&ty.base],
+        }
+    }
+
+    pub fn generics(&self, generics: &Generics<F>) -> Generics<F> {
+        generics
+            .iter()
+            .map(|generic| match generic {
+                GenericKind::Ty(ty) => GenericKind::Ty(self.ty(ty)),
+            })
+            .collect()
+    }
+}
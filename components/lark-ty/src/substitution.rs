@@ -0,0 +1,167 @@
+//! Mappers that substitute types: `Substitution` replaces
+//! placeholders with types (and permissions with other permissions),
+//! leaving everything else untouched; `BoundVarSubstitution`
+//! instantiates the bound variables of a declaration with generics.
+
+use crate::declaration;
+use crate::declaration::Declaration;
+use crate::declaration::DeclarationTables;
+use crate::free_vars::UnternedBase;
+use crate::map_family::FamilyMapper;
+use crate::map_family::Map;
+use crate::BaseKind;
+use crate::BoundVar;
+use crate::BoundVarOr;
+use crate::Generic;
+use crate::ReprKind;
+use crate::Ty;
+use crate::TypeFamily;
+use lark_collections::FxIndexMap;
+use lark_intern::Untern;
+
+mod test;
+
+pub struct Substitution<'me, F: TypeFamily> {
+    tables: &'me dyn AsRef<F::InternTables>,
+
+    /// Each placeholder in this map is replaced with the given type;
+    /// the replacement is used as is (e.g., the permission on the
+    /// placeholder itself is dropped).
+    tys: FxIndexMap<F::Placeholder, Ty<F>>,
+
+    /// Each permission in this map is replaced with the given one.
+    perms: FxIndexMap<F::Perm, F::Perm>,
+}
+
+impl<F: TypeFamily> Substitution<'me, F> {
+    pub fn new(
+        tables: &'me dyn AsRef<F::InternTables>,
+        tys: FxIndexMap<F::Placeholder, Ty<F>>,
+        perms: FxIndexMap<F::Perm, F::Perm>,
+    ) -> Self {
+        Substitution { tables, tys, perms }
+    }
+}
+
+impl<F> FamilyMapper<F, F> for Substitution<'_, F>
+where
+    F: TypeFamily,
+    F::Base: Untern<F::InternTables>,
+    <F::Base as Untern<F::InternTables>>::Data: UnternedBase<F>,
+{
+    fn map_ty(&mut self, ty: Ty<F>) -> Ty<F> {
+        let Ty { repr, perm, base } = ty;
+        let perm = self.map_perm(perm);
+
+        match base.untern(self.tables).into_base_data() {
+            Ok(base_data) => {
+                if let BaseKind::Placeholder(placeholder) = base_data.kind {
+                    if let Some(&ty) = self.tys.get(&placeholder) {
+                        return ty;
+                    }
+                }
+
+                let base_data = base_data.map(self);
+                Ty {
+                    repr,
+                    perm,
+                    base: F::intern_base_data(self.tables, base_data),
+                }
+            }
+
+            // Inference variables are left alone.
+            Err(_) => Ty { repr, perm, base },
+        }
+    }
+
+    fn map_placeholder(&mut self, placeholder: F::Placeholder) -> F::Placeholder {
+        placeholder
+    }
+
+    fn map_perm(&mut self, perm: F::Perm) -> F::Perm {
+        self.perms.get(&perm).cloned().unwrap_or(perm)
+    }
+}
+
+pub struct BoundVarSubstitution<'me, F, V>
+where
+    F: TypeFamily,
+    V: std::ops::Index<BoundVar, Output = Generic<F>>,
+{
+    delegate: &'me mut dyn SubstitutionDelegate<F>,
+    values: &'me V,
+}
+
+pub trait SubstitutionDelegate<F: TypeFamily>: AsRef<DeclarationTables> {
+    // FIXME(rust-lang/rust#56229) -- can't use `AsRef` supertrait here due to ICE
+    fn as_f_tables(&self) -> &F::InternTables;
+
+    /// Map the repr/perm from a case where the "base type" was not
+    /// substituted; e.g. if the user declared `own String`, this
+    /// would map the `own` from that declaration into the family F.
+    fn map_repr_perm(&mut self, repr: ReprKind, perm: declaration::Perm) -> (F::Repr, F::Perm);
+
+    fn map_perm(&mut self, perm: declaration::Perm) -> F::Perm;
+
+    /// Map the repr/perm from a case where the "base type" is
+    /// substituted to `ty`; e.g. if the user declared `own T`, and
+    /// `T` maps to `ty`, then this function applies the `own` from
+    /// that declaration to `ty`.
+    fn apply_repr_perm(&mut self, repr: ReprKind, perm: declaration::Perm, ty: Ty<F>) -> Ty<F>;
+}
+
+impl<F, V> BoundVarSubstitution<'me, F, V>
+where
+    F: TypeFamily,
+    V: std::ops::Index<BoundVar, Output = Generic<F>>,
+{
+    pub fn new(delegate: &'me mut dyn SubstitutionDelegate<F>, values: &'me V) -> Self {
+        BoundVarSubstitution { delegate, values }
+    }
+}
+
+impl<F, V> AsRef<DeclarationTables> for BoundVarSubstitution<'me, F, V>
+where
+    F: TypeFamily,
+    V: std::ops::Index<BoundVar, Output = Generic<F>>,
+{
+    fn as_ref(&self) -> &DeclarationTables {
+        &self.delegate.as_ref()
+    }
+}
+
+impl<F, V> FamilyMapper<Declaration, F> for BoundVarSubstitution<'me, F, V>
+where
+    F: TypeFamily,
+    V: std::ops::Index<BoundVar, Output = Generic<F>>,
+{
+    fn map_ty(&mut self, ty: Ty<Declaration>) -> Ty<F> {
+        let Ty { repr, perm, base } = ty;
+
+        match base.untern(self) {
+            BoundVarOr::BoundVar(var) => {
+                // This corresponds to something like `own T`.
+                let g = self.values[var].assert_ty();
+                self.delegate.apply_repr_perm(repr, perm, g)
+            }
+
+            BoundVarOr::Known(base_data) => {
+                let base_data1 = base_data.map(self);
+                let (repr1, perm1) = self.delegate.map_repr_perm(repr, perm);
+                Ty {
+                    repr: repr1,
+                    perm: perm1,
+                    base: F::intern_base_data(self.delegate.as_f_tables(), base_data1),
+                }
+            }
+        }
+    }
+
+    fn map_placeholder(&mut self, placeholder: !) -> F::Placeholder {
+        placeholder
+    }
+
+    fn map_perm(&mut self, perm: declaration::Perm) -> F::Perm {
+        self.delegate.map_perm(perm)
+    }
+}
@@ -0,0 +1,82 @@
+#![cfg(test)]
+
+use crate::full_inferred::FullInferred;
+use crate::full_inferred::FullInferredTables;
+use crate::map_family::Map;
+use crate::substitution::Substitution;
+use crate::BaseData;
+use crate::BaseKind;
+use crate::BoundVar;
+use crate::Erased;
+use crate::GenericKind;
+use crate::Generics;
+use crate::PermKind;
+use crate::Placeholder;
+use crate::Ty;
+use crate::Universe;
+use lark_collections::FxIndexMap;
+use lark_intern::Intern;
+
+fn placeholder(index: usize) -> Placeholder {
+    Placeholder {
+        universe: Universe::ROOT,
+        bound_var: BoundVar::new(index),
+    }
+}
+
+fn ty(
+    tables: &FullInferredTables,
+    perm: PermKind,
+    kind: BaseKind<FullInferred>,
+) -> Ty<FullInferred> {
+    let base_data = BaseData {
+        kind,
+        generics: Generics::empty(),
+    };
+    Ty {
+        repr: Erased,
+        perm,
+        base: base_data.intern(tables),
+    }
+}
+
+#[test]
+fn substitute_in_generics() {
+    let tables = &FullInferredTables::default();
+    let p0 = ty(tables, PermKind::Own, BaseKind::Placeholder(placeholder(0)));
+    let p1 = ty(tables, PermKind::Own, BaseKind::Placeholder(placeholder(1)));
+    let error = ty(tables, PermKind::Share, BaseKind::Error);
+
+    let generics: Generics<FullInferred> =
+        vec![p0, p1, p0].into_iter().map(GenericKind::Ty).collect();
+
+    let tys: FxIndexMap<_, _> = vec![(placeholder(0), error)].into_iter().collect();
+    let mut substitution = Substitution::<FullInferred>::new(tables, tys, FxIndexMap::default());
+
+    let expected: Generics<FullInferred> = vec![error, p1, error]
+        .into_iter()
+        .map(GenericKind::Ty)
+        .collect();
+    assert_eq!(generics.map(&mut substitution), expected);
+}
+
+#[test]
+fn substitute_perm() {
+    let tables = &FullInferredTables::default();
+    let p0 = ty(tables, PermKind::Own, BaseKind::Placeholder(placeholder(0)));
+    let p0_shared = ty(
+        tables,
+        PermKind::Share,
+        BaseKind::Placeholder(placeholder(0)),
+    );
+    let p1 = ty(
+        tables,
+        PermKind::Borrow,
+        BaseKind::Placeholder(placeholder(1)),
+    );
+
+    let perms: FxIndexMap<_, _> = vec![(PermKind::Own, PermKind::Share)].into_iter().collect();
+    let mut substitution = Substitution::<FullInferred>::new(tables, FxIndexMap::default(), perms);
+
+    assert_eq!((p0, p1).map(&mut substitution), (p0_shared, p1));
+}
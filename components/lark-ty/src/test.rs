@@ -0,0 +1,46 @@
+#![cfg(test)]
+
+use crate::PermKind::{self, Borrow, Own, Share};
+
+#[test]
+fn join_share() {
+    assert_eq!(Share.join(Share), Share);
+    assert_eq!(Share.join(Borrow), Borrow);
+    assert_eq!(Share.join(Own), Own);
+}
+
+#[test]
+fn join_borrow() {
+    assert_eq!(Borrow.join(Share), Borrow);
+    assert_eq!(Borrow.join(Borrow), Borrow);
+    assert_eq!(Borrow.join(Own), Own);
+}
+
+#[test]
+fn join_own() {
+    assert_eq!(Own.join(Share), Own);
+    assert_eq!(Own.join(Borrow), Own);
+    assert_eq!(Own.join(Own), Own);
+}
+
+#[test]
+fn at_least() {
+    let kinds = [Share, Borrow, Own];
+    for (i, &a) in kinds.iter().enumerate() {
+        for (j, &b) in kinds.iter().enumerate() {
+            assert_eq!(a.at_least(b), i >= j, "{:?}.at_least({:?})", a, b);
+        }
+    }
+}
+
+#[test]
+fn join_is_at_least_both() {
+    let kinds: [PermKind; 3] = [Share, Borrow, Own];
+    for &a in &kinds {
+        for &b in &kinds {
+            let j = a.join(b);
+            assert!(j.at_least(a) && j.at_least(b));
+            assert_eq!(j, b.join(a));
+        }
+    }
+}
@@ -3,8 +3,6 @@
 //! representations and focus only on the base types.
 
 use crate::results::TypeCheckResults;
-use crate::substitute::Substitution;
-use crate::substitute::SubstitutionDelegate;
 use crate::HirLocation;
 use crate::TypeChecker;
 use crate::TypeCheckerFamilyDependentExt;
@@ -19,12 +17,15 @@ use lark_pretty_print::PrettyPrint;
 use lark_ty::declaration;
 use lark_ty::declaration::Declaration;
 use lark_ty::map_family::Map;
+use lark_ty::substitution::BoundVarSubstitution;
+use lark_ty::substitution::SubstitutionDelegate;
 use lark_ty::BaseData;
 use lark_ty::BaseKind;
 use lark_ty::Erased;
 use lark_ty::GenericKind;
 use lark_ty::Generics;
 use lark_ty::InferVarOr;
+use lark_ty::PermKind;
 use lark_ty::Placeholder;
 use lark_ty::ReprKind;
 use lark_ty::Ty;
@@ -123,7 +124,7 @@ impl TypeCheckerFamilyDependentExt<BaseInference>
     where
         M: Map<Declaration, BaseInference>,
     {
-        value.map(&mut Substitution::new(self, generics))
+        value.map(&mut BoundVarSubstitution::new(self, generics))
     }
 
     fn apply_owner_perm(
@@ -136,6 +137,16 @@ impl TypeCheckerFamilyDependentExt<BaseInference>
         field_ty
     }
 
+    fn apply_user_perm(
+        &mut self,
+        _cause: impl Into<hir::MetaIndex>,
+        _location: impl Into<HirLocation>,
+        _perm: PermKind,
+        place_ty: Ty<BaseInference>,
+    ) -> Ty<BaseInference> {
+        place_ty
+    }
+
     fn record_variable_ty(&mut self, var: hir::Variable, ty: Ty<BaseInference>) {
         self.storage.record_max_ty(var, ty);
     }
@@ -157,7 +168,7 @@ impl TypeCheckerFamilyDependentExt<BaseInference>
     }
 
     fn request_variable_ty(&mut self, var: hir::Variable) -> Ty<BaseInference> {
-        self.storage.opt_ty(var).unwrap_or_else(|| {
+        self.storage.try_ty(var).unwrap_or_else(|| {
             let ty = self.new_variable();
             self.storage.record_max_ty(var, ty);
             ty
@@ -177,6 +188,7 @@ impl TypeCheckerFamilyDependentExt<BaseInference>
         self.storage.record_entity(index, entity);
         let generics = self.inference_variables_for(entity);
         self.storage.record_generics(index, &generics);
+        self.check_generic_constraints(index, entity, &generics);
         generics
     }
 }
@@ -231,11 +243,10 @@ impl TypeCheckerVariableExt<BaseInference, Ty<BaseInference>>
 
                 if data1.kind != data2.kind {
                     self.record_error(
-                        format!(
-                            "mismatched types ({} vs {})",
-                            data1.kind.pretty_print(self.db),
-                            data2.kind.pretty_print(self.db)
-                        ),
+                        ErrorKind::Mismatch {
+                            expected: data2.kind.pretty_print(self.db),
+                            actual: data1.kind.pretty_print(self.db),
+                        },
                         cause,
                     );
                     return;
@@ -1,16 +1,22 @@
 use crate::base_inference::resolve_to_base_inferred::ResolveToBaseInferred;
 use crate::base_inference::{BaseInference, BaseInferenceTables};
 use crate::results::TypeCheckResults;
+use crate::Severity;
 use crate::TypeCheckDatabase;
 use crate::TypeChecker;
 use crate::UniverseBinder;
 use generational_arena::Arena;
-use lark_collections::{FxIndexMap, IndexVec};
+use lark_collections::{FxIndexMap, FxIndexSet, IndexVec};
 use lark_entity::Entity;
 use lark_error::Diagnostic;
 use lark_error::WithError;
+use lark_hir as hir;
+use lark_intern::Untern;
+use lark_span::{FileName, Span};
 use lark_ty::base_inferred::BaseInferred;
 use lark_ty::map_family::Map;
+use lark_ty::Generics;
+use lark_ty::Ty;
 use lark_unify::UnificationTable;
 use std::sync::Arc;
 
@@ -30,14 +36,20 @@ crate fn base_type_check(
         unify: UnificationTable::new(interners.clone()),
         storage: TypeCheckResults::default(),
         universe_binders: IndexVec::from(vec![UniverseBinder::Root]),
+        fn_generics: Generics::empty(),
+        return_ty: None,
         errors: vec![],
+        enclosing_loops: vec![],
+        integer_literals: vec![],
+        negated_literals: FxIndexSet::default(),
+        diverging_variables: vec![],
     };
 
     let mut unresolved_variables = base_type_checker.check_fn_body();
 
     // Record the final results. If any unresolved type variables are
     // encountered, report an error.
-    let inferred_results = base_type_checker
+    let mut inferred_results = base_type_checker
         .storage
         .map(&mut ResolveToBaseInferred::new(
             &mut base_type_checker.unify,
@@ -45,7 +57,13 @@ crate fn base_type_check(
             &mut unresolved_variables,
         ));
 
-    let mut errors = base_type_checker.errors;
+    let mut errors: Vec<Diagnostic> = base_type_checker
+        .errors
+        .iter()
+        .filter(|error| error.severity == Severity::Error)
+        .map(|error| error.to_diagnostic(&fn_body))
+        .collect();
+    inferred_results.errors = base_type_checker.errors;
     for _ in unresolved_variables {
         // FIXME: Decent diagnostics for unresolved inference
         // variables.
@@ -60,3 +78,32 @@ crate fn base_type_check(
         errors,
     }
 }
+
+crate fn ty_of_expression(
+    db: &impl TypeCheckDatabase,
+    fn_entity: Entity,
+    expression: hir::Expression,
+) -> Option<Ty<BaseInferred>> {
+    if !fn_entity.untern(db).has_fn_body() {
+        return None;
+    }
+
+    db.base_type_check(fn_entity)
+        .into_value()
+        .try_ty(expression)
+}
+
+crate fn error_spans(db: &impl TypeCheckDatabase, fn_entity: Entity) -> Vec<Span<FileName>> {
+    if !fn_entity.untern(db).has_fn_body() {
+        return vec![];
+    }
+
+    let fn_body = db.fn_body(fn_entity).into_value();
+    db.base_type_check(fn_entity)
+        .into_value()
+        .errors
+        .iter()
+        .filter(|error| error.severity == Severity::Error)
+        .map(|error| error.span(&fn_body))
+        .collect()
+}
@@ -0,0 +1,127 @@
+use crate::TypeCheckDatabase;
+use lark_entity::{Entity, EntityData, ItemKind, MemberKind};
+use lark_error::Diagnostic;
+use lark_error::Severity;
+use lark_intern::{Intern, Untern};
+use lark_span::FileName;
+use std::sync::Arc;
+
+crate fn all_diagnostics(db: &impl TypeCheckDatabase, (): ()) -> Arc<Vec<Diagnostic>> {
+    let mut diagnostics = vec![];
+    for &file in db.file_names().iter() {
+        diagnostics.extend(db.file_diagnostics(file).iter().cloned());
+    }
+    Arc::new(diagnostics)
+}
+
+crate fn file_diagnostics(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+) -> Arc<Vec<Diagnostic>> {
+    // Check file for syntax errors
+    let mut diagnostics = vec![];
+    let _ = db
+        .parsed_file(file)
+        .accumulate_errors_into(&mut diagnostics);
+
+    // Next, check entities in file for type-safety
+    let file_entity = EntityData::InputFile { file }.intern(db);
+    for &entity in db.descendant_entities(file_entity).iter() {
+        accumulate_entity_diagnostics(db, entity, &mut diagnostics);
+    }
+
+    Arc::new(diagnostics)
+}
+
+fn accumulate_entity_diagnostics(
+    db: &impl TypeCheckDatabase,
+    entity: Entity,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match entity.untern(db) {
+        EntityData::InputFile { .. } => {}
+        EntityData::LangItem(_) => {}
+        EntityData::Error(_) => {}
+        EntityData::ItemName {
+            kind: ItemKind::Struct,
+            ..
+        }
+        | EntityData::ItemName {
+            kind: ItemKind::Enum,
+            ..
+        }
+        | EntityData::ItemName {
+            kind: ItemKind::Trait,
+            ..
+        }
+        | EntityData::ItemName {
+            kind: ItemKind::TypeAlias,
+            ..
+        }
+        | EntityData::MemberName {
+            kind: MemberKind::Field,
+            ..
+        } => {
+            let _ = db
+                .generic_declarations(entity)
+                .accumulate_errors_into(diagnostics);
+            let _ = db.ty(entity).accumulate_errors_into(diagnostics);
+        }
+        EntityData::ItemName {
+            kind: ItemKind::Impl,
+            ..
+        } => {
+            let _ = db
+                .generic_declarations(entity)
+                .accumulate_errors_into(diagnostics);
+            let _ = db.ty(entity).accumulate_errors_into(diagnostics);
+            let _ = db.impl_trait(entity).accumulate_errors_into(diagnostics);
+        }
+        EntityData::MemberName {
+            kind: MemberKind::Variant,
+            ..
+        }
+        | EntityData::MemberName {
+            kind: MemberKind::MethodSignature,
+            ..
+        } => {
+            let _ = db
+                .generic_declarations(entity)
+                .accumulate_errors_into(diagnostics);
+            let _ = db.ty(entity).accumulate_errors_into(diagnostics);
+            let _ = db.signature(entity).accumulate_errors_into(diagnostics);
+        }
+        EntityData::ItemName {
+            kind: ItemKind::Function,
+            ..
+        }
+        | EntityData::ItemName {
+            kind: ItemKind::Const,
+            ..
+        }
+        | EntityData::MemberName {
+            kind: MemberKind::Method,
+            ..
+        } => {
+            let _ = db
+                .generic_declarations(entity)
+                .accumulate_errors_into(diagnostics);
+            let _ = db.ty(entity).accumulate_errors_into(diagnostics);
+            let _ = db.signature(entity).accumulate_errors_into(diagnostics);
+            let fn_body = db.fn_body(entity).accumulate_errors_into(diagnostics);
+            let results = db
+                .full_type_check(entity)
+                .accumulate_errors_into(diagnostics);
+
+            // The errors were reported above; only the warnings are
+            // left to report.
+            diagnostics.extend(
+                results
+                    .errors
+                    .iter()
+                    .filter(|error| error.severity == Severity::Warning)
+                    .map(|error| error.to_diagnostic(&fn_body)),
+            );
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use lark_entity::MemberKind;
+use lark_error::Diagnostic;
+use lark_hir as hir;
+use lark_span::{FileName, Span};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use lark_error::Severity;
+
+/// An error found while type-checking a fn body.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Error {
+    /// The HIR node that the error is reported against.
+    pub location: hir::MetaIndex,
+
+    /// What went wrong.
+    pub kind: ErrorKind,
+
+    /// Whether this is a hard error or only a warning.
+    pub severity: Severity,
+}
+
+impl Error {
+    /// The source span of the error, given the `fn_body` that was
+    /// type-checked. If the HIR node it was reported against has no
+    /// span of its own (i.e., it was synthesized), falls back to the
+    /// span of the body as a whole.
+    pub fn span(&self, fn_body: &hir::FnBody) -> Span<FileName> {
+        fn_body
+            .opt_span(self.location)
+            .unwrap_or_else(|| fn_body.span(fn_body.root_expression))
+    }
+
+    /// Converts to a diagnostic, using `fn_body` (the body that was
+    /// type-checked) to find the span.
+    crate fn to_diagnostic(&self, fn_body: &hir::FnBody) -> Diagnostic {
+        Diagnostic {
+            span: self.span(fn_body),
+            label: self.kind.message(),
+            severity: self.severity,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ErrorKind {
+    /// Two types that must agree did not. The types are given as
+    /// they are printed for the user.
+    Mismatch { expected: String, actual: String },
+
+    /// A name in an expression resolved to neither a local variable
+    /// nor an entity.
+    UnresolvedName { name: String },
+
+    /// A member (e.g., a field or method) could not be resolved. The
+    /// owner is the type it was looked up on, as printed for the user.
+    UnresolvedMember {
+        member: String,
+        owner: String,
+        kind: MemberKind,
+    },
+
+    /// A struct construction or pattern named a field that the
+    /// struct does not declare.
+    UnknownField { field: String, owner: String },
+
+    /// Something that is not a place (e.g., the result of a call)
+    /// was assigned to or borrowed.
+    NotAPlace { expression_kind: PlaceExpressionKind },
+
+    /// A call supplied the wrong number of arguments.
+    ArityMismatch,
+
+    /// A `let` binding that is never referenced.
+    UnusedVariable { name: String },
+
+    /// An entity that is not a value (e.g., a struct) was used as one.
+    NotAValue,
+
+    /// A tuple field index was not less than the arity of the tuple.
+    TupleIndexOutOfRange { index: usize, arity: usize },
+
+    /// A tuple field was accessed on something that is not a tuple.
+    NotATuple,
+
+    /// Something that is not a fn, method or variant was called.
+    NotCallable,
+
+    /// A struct construction named something that cannot be
+    /// constructed that way.
+    NotConstructible,
+
+    /// A struct pattern was matched against something that is not a
+    /// struct.
+    NotAStruct,
+
+    /// A struct construction did not supply a value for a field.
+    MissingMember { name: String },
+
+    /// The bounds of a range were not integers.
+    RangeBoundsNotInteger,
+
+    /// An operator was applied to an operand of a type it does not
+    /// support. The type is given as it is printed for the user.
+    UnsupportedOperand { op: Operator, ty: String },
+
+    /// The place assigned by a compound assignment (e.g., `+=`) was
+    /// not numeric.
+    NonNumericCompoundAssignment { ty: String },
+
+    /// An integer literal does not fit in its type.
+    IntegerOutOfRange { ty: String },
+
+    /// A type does not implement a trait that a where-clause requires.
+    TraitNotImplemented { trait_name: String, ty: String },
+
+    /// An operation that is not (yet) supported on generic types.
+    UnsupportedOnGeneric(GenericOperation),
+}
+
+/// The operator of an `ErrorKind::UnsupportedOperand`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operator {
+    Binary(hir::BinaryOperator),
+    Unary(hir::UnaryOperator),
+}
+
+/// The kind of expression that required a place, for an
+/// `ErrorKind::NotAPlace`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PlaceExpressionKind {
+    /// An assignment, including compound assignments like `+=`.
+    Assignment,
+
+    /// A `borrow` expression.
+    Borrow,
+}
+
+/// The operation of an `ErrorKind::UnsupportedOnGeneric`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GenericOperation {
+    FieldAccess,
+    Call,
+    MethodCall,
+    Operator,
+}
+
+impl ErrorKind {
+    /// A human-readable description of the error.
+    pub fn message(&self) -> String {
+        match self {
+            ErrorKind::Mismatch { expected, actual } => {
+                format!("mismatched types ({} vs {})", actual, expected)
+            }
+            ErrorKind::UnresolvedName { name } => format!("cannot find `{}`", name),
+            ErrorKind::UnknownField { field, owner } => {
+                format!("no field `{}` on `{}`", field, owner)
+            }
+            ErrorKind::ArityMismatch => "mismatched argument count".to_string(),
+            ErrorKind::UnusedVariable { name } => format!("unused variable `{}`", name),
+            ErrorKind::UnresolvedMember {
+                member,
+                owner,
+                kind,
+            } => {
+                let kind = match kind {
+                    MemberKind::Field => "field",
+                    MemberKind::Method | MemberKind::MethodSignature => "method",
+                    MemberKind::Variant => "variant",
+                };
+                format!("no {} `{}` on `{}`", kind, member, owner)
+            }
+            ErrorKind::NotAPlace { expression_kind } => match expression_kind {
+                PlaceExpressionKind::Assignment => "cannot assign to this expression".to_string(),
+                PlaceExpressionKind::Borrow => "cannot borrow this expression".to_string(),
+            },
+            ErrorKind::NotAValue => "cannot access as a value".to_string(),
+            ErrorKind::TupleIndexOutOfRange { .. } => "tuple index out of range".to_string(),
+            ErrorKind::NotATuple => "cannot index into a non-tuple type".to_string(),
+            ErrorKind::NotCallable => "cannot call value of this type".to_string(),
+            ErrorKind::NotConstructible => "disallowed expression type".to_string(),
+            ErrorKind::NotAStruct => "can only match against structs".to_string(),
+            ErrorKind::MissingMember { name } => format!("missing member `{}`", name),
+            ErrorKind::RangeBoundsNotInteger => "range bounds must be integers".to_string(),
+            ErrorKind::UnsupportedOperand { op, ty } => match op {
+                Operator::Binary(_) => format!("type {} does not support this operation", ty),
+                Operator::Unary(hir::UnaryOperator::Not) => {
+                    "incompatible type for 'not' operator".to_string()
+                }
+                Operator::Unary(hir::UnaryOperator::Negate) => {
+                    "incompatible type for negation".to_string()
+                }
+            },
+            ErrorKind::NonNumericCompoundAssignment { ty } => format!(
+                "operand of compound assignment must be numeric, found `{}`",
+                ty
+            ),
+            ErrorKind::IntegerOutOfRange { ty } => {
+                format!("integer literal is out of range for `{}`", ty)
+            }
+            ErrorKind::TraitNotImplemented { trait_name, ty } => format!(
+                "the trait `{}` is not implemented for `{}`",
+                trait_name, ty
+            ),
+            ErrorKind::UnsupportedOnGeneric(operation) => match operation {
+                GenericOperation::FieldAccess => {
+                    "cannot access fields from generic types(yet)".to_string()
+                }
+                GenericOperation::Call => "cannot call a generic type (yet)".to_string(),
+                GenericOperation::MethodCall => {
+                    "cannot invoke methods on generic types(yet)".to_string()
+                }
+                GenericOperation::Operator => "unknown expression for operator".to_string(),
+            },
+        }
+    }
+}
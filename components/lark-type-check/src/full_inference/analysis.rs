@@ -11,15 +11,18 @@ use lark_debug_derive::DebugWith;
 use lark_entity::Entity;
 use lark_error::Diagnostic;
 use lark_hir as hir;
+use lark_span::{FileName, Span};
 use lark_string::GlobalIdentifier;
 use lark_ty::PermKind;
 use lark_unify::UnificationTable;
 
+mod borrow_check;
 mod builder;
 mod dump;
 mod initialization;
 mod kind_inference;
 
+use borrow_check::BorrowCheck;
 use dump::DumpCx;
 use initialization::Initialization;
 use kind_inference::KindInference;
@@ -142,6 +145,13 @@ impl AnalysisIr {
 
         let initialization = Initialization::new(cx, &self, &kind_inference);
 
+        let borrow_check = BorrowCheck::new(&self, &kind_inference, tables);
+        cx.dump_facts(
+            "error_borrow_conflict",
+            borrow_check.error_borrow_conflict.iter(),
+        )
+        .unwrap();
+
         let perm_kinds = kind_inference.to_kind_map(tables);
 
         let mut errors = vec![];
@@ -168,9 +178,30 @@ impl AnalysisIr {
             ));
         }
 
+        for &(node, _loan_perm, _access_perm) in borrow_check.error_borrow_conflict.iter() {
+            errors.push(Diagnostic::new(
+                "access conflicts with a live borrow".to_string(),
+                self.node_span(node, fn_body),
+            ));
+        }
+
         AnalysisResults { perm_kinds, errors }
     }
 
+    /// The span of the HIR that `node` represents; nodes without HIR
+    /// of their own (e.g., the start and return nodes) use the span
+    /// of the whole fn body.
+    fn node_span(&self, node: Node, fn_body: &hir::FnBody) -> Span<FileName> {
+        match self.node_datas[node] {
+            HirLocation::Expression(e) | HirLocation::AfterExpression(e) => fn_body.span(e),
+            HirLocation::Place(p) => fn_body.span(p),
+            HirLocation::Pattern(p) => fn_body.span(p),
+            HirLocation::Start | HirLocation::Return | HirLocation::Error => {
+                fn_body.span(fn_body.root_expression)
+            }
+        }
+    }
+
     crate fn lookup_node(&self, data: impl Into<HirLocation>) -> Node {
         let data: HirLocation = data.into();
         Node::from_usize(match self.reverse_node_datas.get_full(&data) {
@@ -224,6 +255,9 @@ crate enum PathData {
     /// A path like `owner.name`
     Field { owner: Path, name: GlobalIdentifier },
 
+    /// A path like `owner.0`
+    TupleField { owner: Path, index: usize },
+
     /// A path like `owner[_]`.
     Index { owner: Path },
 }
@@ -232,14 +266,18 @@ impl PathData {
     fn owner(self) -> Option<Path> {
         match self {
             PathData::Entity(_) | PathData::Temporary(_) | PathData::Variable(_) => None,
-            PathData::Field { owner, name: _ } | PathData::Index { owner } => Some(owner),
+            PathData::Field { owner, name: _ }
+            | PathData::TupleField { owner, index: _ }
+            | PathData::Index { owner } => Some(owner),
         }
     }
 
     fn precise(self, path_datas: &IndexVec<Path, PathData>) -> bool {
         match self {
             PathData::Entity(_) | PathData::Temporary(_) | PathData::Variable(_) => true,
-            PathData::Field { owner, name: _ } => path_datas[owner].precise(path_datas),
+            PathData::Field { owner, name: _ } | PathData::TupleField { owner, index: _ } => {
+                path_datas[owner].precise(path_datas)
+            }
             PathData::Index { owner: _ } => false,
         }
     }
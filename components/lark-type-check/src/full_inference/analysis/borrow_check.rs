@@ -0,0 +1,280 @@
+use crate::full_inference::analysis::initialization;
+use crate::full_inference::analysis::kind_inference::KindInference;
+use crate::full_inference::analysis::AnalysisIr;
+use crate::full_inference::analysis::Node;
+use crate::full_inference::analysis::Path;
+use crate::full_inference::perm::PermData;
+use crate::full_inference::FullInferenceTables;
+use crate::full_inference::Perm;
+use datafrog::Iteration;
+use datafrog::Relation;
+use datafrog::RelationLeaper;
+use lark_intern::Untern;
+
+mod test;
+
+crate struct BorrowCheck {
+    /// Contains `(Node, Perm1, Perm2)` if, at `Node`, a path is
+    /// accessed with the borrow or own permission `Perm2` while a
+    /// loan of an overlapping path with permission `Perm1` is live.
+    crate error_borrow_conflict: Relation<(Node, Perm, Perm)>,
+}
+
+impl BorrowCheck {
+    /// Executes the **borrow check**.
+    ///
+    /// TL;DR:
+    ///
+    /// - An access to a path `Path` with a permission that is borrow
+    ///   (but not own) creates a **loan** of `Path`.
+    /// - A loan is **live** at a node if its permission -- or some
+    ///   inferred permission that must permit it, per `perm_less` -- is
+    ///   used at that node or at some node reachable from it.
+    /// - Loans are propagated across CFG edges, starting from the node
+    ///   that created them, for as long as they are live.
+    /// - Accessing a path that overlaps with the path of a live loan
+    ///   (that is, the same path, a parent, or a child) is an error if
+    ///   the access is borrow or own. Shared accesses never conflict.
+    crate fn new(
+        analysis_ir: &AnalysisIr,
+        kind_inference: &KindInference,
+        tables: &impl AsRef<FullInferenceTables>,
+    ) -> Self {
+        let borrow = &kind_inference.borrow;
+        let owned = &kind_inference.owned;
+        let is_borrow = |perm: Perm| borrow.elements.binary_search(&(perm, ())).is_ok();
+        let is_owned = |perm: Perm| owned.elements.binary_search(&(perm, ())).is_ok();
+
+        ///////////////////////////////////////////////////////////////////////////
+        // Round 0: Compute `loan` and `overlapping_path`
+
+        // .decl loan(Perm:perm, Path:path, Node:node)
+        //
+        // The path `Path` is lent out with the permission `Perm` at `Node`.
+        //
+        // loan(Perm, Path, Node) :-
+        //   access(Perm, Path, Node),
+        //   borrow(Perm),
+        //   !owned(Perm).
+        let loan: Relation<(Perm, Path, Node)> = analysis_ir
+            .access
+            .iter()
+            .filter(|&&(perm, _, _)| is_borrow(perm) && !is_owned(perm))
+            .collect();
+
+        // .decl mutable_access(Path:path, Node:node, Perm:perm)
+        //
+        // The path `Path` is accessed with the permission `Perm` at
+        // `Node`, and `Perm` permits mutation (i.e., it is borrow or own).
+        //
+        // mutable_access(Path, Node, Perm) :-
+        //   access(Perm, Path, Node),
+        //   borrow(Perm).
+        let mutable_access: Relation<((Path, Node), Perm)> = analysis_ir
+            .access
+            .iter()
+            .filter(|&&(perm, _, _)| is_borrow(perm))
+            .map(|&(perm, path, node)| ((path, node), perm))
+            .collect();
+
+        let owner_path: Relation<_> = analysis_ir.owner_path.iter().collect();
+        let transitive_owner_path = initialization::transitive_owner_path(&owner_path);
+
+        // .decl overlapping_path(Path1:path, Path2:path)
+        //
+        // overlapping_path(Path, Path) :- access(_, Path, _).
+        // overlapping_path(Path1, Path2) :- transitive_owner_path(Path1, Path2).
+        // overlapping_path(Path2, Path1) :- transitive_owner_path(Path1, Path2).
+        let overlapping_path: Relation<(Path, Path)> = analysis_ir
+            .access
+            .iter()
+            .map(|&(_, path, _)| (path, path))
+            .chain(transitive_owner_path.iter().cloned())
+            .chain(
+                transitive_owner_path
+                    .iter()
+                    .map(|&(path1, path2)| (path2, path1)),
+            )
+            .collect();
+
+        ///////////////////////////////////////////////////////////////////////////
+        // Round 1: Compute `perm_reaches`
+
+        // .decl inferred_perm_less(Perm1:perm, Perm2:perm)
+        //
+        // Like `perm_less`, but only where `Perm2` is inferred; known
+        // permissions like `own` are shared by unrelated values, so we
+        // must not look past them.
+        let inferred_perm_less: Relation<(Perm, Perm)> = kind_inference
+            .perm_less
+            .iter()
+            .filter(|&&(_, perm2)| match perm2.untern(tables) {
+                PermData::Inferred(_) => true,
+                PermData::Known(_) | PermData::Placeholder(_) => false,
+            })
+            .cloned()
+            .collect();
+
+        // .decl perm_reaches(Perm1:perm, Perm2:perm)
+        //
+        // `Perm1` is the permission of some loan and `Perm2` must
+        // permit `Perm1`; that is, a value with permission `Perm2` may
+        // be relying on the loan.
+        let perm_reaches: Relation<(Perm, Perm)> = {
+            let mut iteration = Iteration::new();
+
+            let perm_reaches = iteration.variable::<(Perm, Perm)>("perm_reaches");
+
+            // perm_reaches(Perm, Perm) :- loan(Perm, _, _).
+            perm_reaches.extend(loan.iter().map(|&(perm, _, _)| (perm, perm)));
+
+            while iteration.changed() {
+                // perm_reaches(Perm1, Perm3) :-
+                //   perm_reaches(Perm1, Perm2),
+                //   inferred_perm_less(Perm2, Perm3).
+                perm_reaches.from_leapjoin(
+                    &perm_reaches,
+                    inferred_perm_less.extend_with(|&(_, perm2)| perm2),
+                    |&(perm1, _), &perm3| (perm1, perm3),
+                );
+            }
+
+            perm_reaches.complete()
+        };
+
+        ///////////////////////////////////////////////////////////////////////////
+        // Round 2: Compute `perm_live`
+
+        // Variant of `cfg_edge` keyed by the target node
+        let cfg_edge_by_target: Relation<(Node, Node)> = analysis_ir
+            .cfg_edge
+            .iter()
+            .map(|&(node1, node2)| (node2, node1))
+            .collect();
+
+        // .decl perm_live(Perm:perm, Node:node)
+        //
+        // The permission `Perm` is used at `Node` or at some node
+        // reachable from `Node`.
+        let perm_live: Relation<(Perm, Node)> = {
+            let mut iteration = Iteration::new();
+
+            let perm_live = iteration.variable::<(Perm, Node)>("perm_live");
+
+            // perm_live(Perm, Node) :- used(Perm, Node).
+            perm_live.extend(analysis_ir.used.iter().cloned());
+
+            while iteration.changed() {
+                // perm_live(Perm, Node1) :-
+                //   perm_live(Perm, Node2),
+                //   cfg_edge(Node1, Node2).
+                perm_live.from_leapjoin(
+                    &perm_live,
+                    cfg_edge_by_target.extend_with(|&(_, node2)| node2),
+                    |&(perm, _), &node1| (perm, node1),
+                );
+            }
+
+            perm_live.complete()
+        };
+
+        // .decl loan_live(Perm:perm, Node:node)
+        //
+        // The loan with permission `Perm` is live at `Node`.
+        //
+        // loan_live(Perm1, Node) :-
+        //   perm_reaches(Perm1, Perm2),
+        //   perm_live(Perm2, Node).
+        let perm_reaches_by_target: Relation<(Perm, Perm)> = perm_reaches
+            .iter()
+            .map(|&(perm1, perm2)| (perm2, perm1))
+            .collect();
+        let loan_live: Relation<((Perm, Node), ())> =
+            Relation::from_join(&perm_reaches_by_target, &perm_live, |_, &perm1, &node| {
+                ((perm1, node), ())
+            });
+
+        ///////////////////////////////////////////////////////////////////////////
+        // Round 3: Compute `loan_in_scope`
+
+        // .decl cfg_edge(Node1:node, Node2:node)
+        // .input cfg_edge
+        let cfg_edge: Relation<_> = analysis_ir.cfg_edge.iter().collect();
+
+        let mut iteration = Iteration::new();
+
+        // .decl loan_in_scope(Perm:perm, Path:path, Node:node)
+        //
+        // The loan of `Path` with permission `Perm` reaches `Node`.
+        let loan_in_scope = iteration.variable::<(Perm, Path, Node)>("loan_in_scope");
+
+        // loan_in_scope(Perm, Path, Node2) :-
+        //   loan(Perm, Path, Node1),
+        //   cfg_edge(Node1, Node2).
+        loan_in_scope.insert(Relation::from_leapjoin(
+            &loan,
+            cfg_edge.extend_with(|&(_, _, node1)| node1),
+            |&(perm, path, _), &node2| (perm, path, node2),
+        ));
+
+        while iteration.changed() {
+            // loan_in_scope(Perm, Path, Node2) :-
+            //   loan_in_scope(Perm, Path, Node1),
+            //   loan_live(Perm, Node1),
+            //   cfg_edge(Node1, Node2).
+            loan_in_scope.from_leapjoin(
+                &loan_in_scope,
+                (
+                    loan_live.filter_with(|&(perm, _, node1)| ((perm, node1), ())),
+                    cfg_edge.extend_with(|&(_, _, node1)| node1),
+                ),
+                |&(perm, path, _), &node2| (perm, path, node2),
+            );
+        }
+
+        let loan_in_scope = loan_in_scope.complete();
+
+        ///////////////////////////////////////////////////////////////////////////
+        // Round 4: Compute `error_borrow_conflict`
+
+        // .decl live_loan_path(Path:path, Perm:perm, Node:node)
+        //
+        // live_loan_path(Path, Perm, Node) :-
+        //   loan_in_scope(Perm, Path, Node),
+        //   loan_live(Perm, Node).
+        let live_loan_path: Relation<(Path, (Perm, Node))> = Relation::from_leapjoin(
+            &loan_in_scope,
+            loan_live.filter_with(|&(perm, _, node)| ((perm, node), ())),
+            |&(perm, path, node), &()| (path, (perm, node)),
+        );
+
+        // .decl live_loan_overlapping(Path2:path, Node:node, Perm1:perm)
+        //
+        // live_loan_overlapping(Path2, Node, Perm1) :-
+        //   live_loan_path(Path1, Perm1, Node),
+        //   overlapping_path(Path1, Path2).
+        let live_loan_overlapping: Relation<((Path, Node), Perm)> = Relation::from_join(
+            &live_loan_path,
+            &overlapping_path,
+            |_, &(perm1, node), &path2| ((path2, node), perm1),
+        );
+
+        // error_borrow_conflict(Node, Perm1, Perm2) :-
+        //   live_loan_overlapping(Path, Node, Perm1),
+        //   mutable_access(Path, Node, Perm2),
+        //   Perm1 != Perm2.
+        let error_borrow_conflict: Relation<(Node, Perm, Perm)> = Relation::from_join(
+            &live_loan_overlapping,
+            &mutable_access,
+            |&(_, node), &perm1, &perm2| (node, perm1, perm2),
+        )
+        .iter()
+        .filter(|&&(_, perm1, perm2)| perm1 != perm2)
+        .cloned()
+        .collect();
+
+        BorrowCheck {
+            error_borrow_conflict,
+        }
+    }
+}
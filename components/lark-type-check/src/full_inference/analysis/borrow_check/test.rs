@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+use crate::full_inference::analysis::borrow_check::BorrowCheck;
+use crate::full_inference::analysis::kind_inference::KindInference;
+use crate::full_inference::analysis::AnalysisIr;
+use crate::full_inference::analysis::Node;
+use crate::full_inference::analysis::Path;
+use crate::full_inference::perm::Perm;
+use crate::full_inference::perm::PermData;
+use crate::full_inference::perm::PermVar;
+use crate::full_inference::FullInferenceTables;
+use lark_intern::Intern;
+use lark_ty::PermKind;
+
+fn var(tables: &FullInferenceTables, index: usize) -> Perm {
+    PermData::Inferred(PermVar::new(index)).intern(tables)
+}
+
+/// Creates an analysis IR whose CFG is the straight line `N0 -> N1
+/// -> ... -> N4`.
+fn straight_line() -> AnalysisIr {
+    let mut analysis_ir = AnalysisIr::default();
+    analysis_ir.cfg_edge = (0..4).map(|i| (Node::new(i), Node::new(i + 1))).collect();
+    analysis_ir
+}
+
+#[test]
+fn borrow_then_move() {
+    let tables = &FullInferenceTables::default();
+    let borrow: Perm = PermKind::Borrow.intern(tables);
+    let own: Perm = PermKind::Own.intern(tables);
+    let (p0, p1, p2) = (var(tables, 0), var(tables, 1), var(tables, 2));
+    let b = Path::new(0);
+
+    // let y = borrow b   // N1: P0 is borrow
+    // take(b)            // N2: P1 is own
+    // use(y)             // N3: uses P2, which must permit P0
+    let mut analysis_ir = straight_line();
+    analysis_ir.access = vec![(p0, b, Node::new(1)), (p1, b, Node::new(2))];
+    analysis_ir.used = vec![(p2, Node::new(3))];
+    analysis_ir.perm_less_base = vec![
+        (borrow, p0, Node::new(1)),
+        (own, p1, Node::new(2)),
+        (p0, p2, Node::new(1)),
+    ];
+
+    let kind_inference = KindInference::new(tables, &analysis_ir.perm_less_base, &[]);
+    let borrow_check = BorrowCheck::new(&analysis_ir, &kind_inference, tables);
+
+    assert_eq!(
+        borrow_check.error_borrow_conflict.elements,
+        vec![(Node::new(2), p0, p1)]
+    );
+}
+
+#[test]
+fn borrow_of_field_then_move_owner() {
+    let tables = &FullInferenceTables::default();
+    let borrow: Perm = PermKind::Borrow.intern(tables);
+    let own: Perm = PermKind::Own.intern(tables);
+    let (p0, p1) = (var(tables, 0), var(tables, 1));
+    let (a, a_b) = (Path::new(0), Path::new(1));
+
+    // let y = borrow a.b   // N1: P0 is borrow
+    // take(a)              // N2: P1 is own
+    // use(y)               // N3: uses P0
+    let mut analysis_ir = straight_line();
+    analysis_ir.owner_path = vec![(a, a_b)];
+    analysis_ir.access = vec![(p0, a_b, Node::new(1)), (p1, a, Node::new(2))];
+    analysis_ir.used = vec![(p0, Node::new(3))];
+    analysis_ir.perm_less_base = vec![(borrow, p0, Node::new(1)), (own, p1, Node::new(2))];
+
+    let kind_inference = KindInference::new(tables, &analysis_ir.perm_less_base, &[]);
+    let borrow_check = BorrowCheck::new(&analysis_ir, &kind_inference, tables);
+
+    assert_eq!(
+        borrow_check.error_borrow_conflict.elements,
+        vec![(Node::new(2), p0, p1)]
+    );
+}
+
+#[test]
+fn shared_then_shared() {
+    let tables = &FullInferenceTables::default();
+    let (p0, p1) = (var(tables, 0), var(tables, 1));
+    let b = Path::new(0);
+
+    // let y = shared b   // N1: P0 is share
+    // let z = shared b   // N2: P1 is share
+    // use(y, z)          // N3: uses P0 and P1
+    let mut analysis_ir = straight_line();
+    analysis_ir.access = vec![(p0, b, Node::new(1)), (p1, b, Node::new(2))];
+    analysis_ir.used = vec![(p0, Node::new(3)), (p1, Node::new(3))];
+
+    let kind_inference = KindInference::new(tables, &analysis_ir.perm_less_base, &[]);
+    let borrow_check = BorrowCheck::new(&analysis_ir, &kind_inference, tables);
+
+    assert!(borrow_check.error_borrow_conflict.elements.is_empty());
+}
+
+#[test]
+fn borrow_no_longer_used() {
+    let tables = &FullInferenceTables::default();
+    let borrow: Perm = PermKind::Borrow.intern(tables);
+    let own: Perm = PermKind::Own.intern(tables);
+    let (p0, p1) = (var(tables, 0), var(tables, 1));
+    let b = Path::new(0);
+
+    // let y = borrow b   // N1: P0 is borrow
+    // use(y)             // N2: uses P0
+    // take(b)            // N3: P1 is own
+    let mut analysis_ir = straight_line();
+    analysis_ir.access = vec![(p0, b, Node::new(1)), (p1, b, Node::new(3))];
+    analysis_ir.used = vec![(p0, Node::new(2))];
+    analysis_ir.perm_less_base = vec![(borrow, p0, Node::new(1)), (own, p1, Node::new(3))];
+
+    let kind_inference = KindInference::new(tables, &analysis_ir.perm_less_base, &[]);
+    let borrow_check = BorrowCheck::new(&analysis_ir, &kind_inference, tables);
+
+    assert!(borrow_check.error_borrow_conflict.elements.is_empty());
+}
+
+#[test]
+fn borrow_of_other_path() {
+    let tables = &FullInferenceTables::default();
+    let borrow: Perm = PermKind::Borrow.intern(tables);
+    let own: Perm = PermKind::Own.intern(tables);
+    let (p0, p1) = (var(tables, 0), var(tables, 1));
+    let (a, b) = (Path::new(0), Path::new(1));
+
+    // let y = borrow a   // N1: P0 is borrow
+    // take(b)            // N2: P1 is own
+    // use(y)             // N3: uses P0
+    let mut analysis_ir = straight_line();
+    analysis_ir.access = vec![(p0, a, Node::new(1)), (p1, b, Node::new(2))];
+    analysis_ir.used = vec![(p0, Node::new(3))];
+    analysis_ir.perm_less_base = vec![(borrow, p0, Node::new(1)), (own, p1, Node::new(2))];
+
+    let kind_inference = KindInference::new(tables, &analysis_ir.perm_less_base, &[]);
+    let borrow_check = BorrowCheck::new(&analysis_ir, &kind_inference, tables);
+
+    assert!(borrow_check.error_borrow_conflict.elements.is_empty());
+}
@@ -24,6 +24,14 @@ crate struct AnalysisBuilder<'me> {
     results: &'me TypeCheckResults<FullInference>,
     unify: &'me mut UnificationTable<FullInferenceTables, hir::MetaIndex>,
     reverse_path_datas: FxIndexMap<PathData, ()>,
+
+    /// For each loop enclosing the expression being built (innermost
+    /// last), the node that a `break` from that loop jumps to.
+    loop_exits: Vec<Node>,
+
+    /// Nodes for the `return` expressions seen so far; each gets an
+    /// edge to the function's return node once that is created.
+    returns: Vec<Node>,
 }
 
 impl AnalysisBuilder<'_> {
@@ -40,6 +48,8 @@ impl AnalysisBuilder<'_> {
             constraints,
             unify,
             reverse_path_datas: Default::default(),
+            loop_exits: vec![],
+            returns: vec![],
         };
 
         let start_node = builder.push_node(HirLocation::Start);
@@ -52,7 +62,10 @@ impl AnalysisBuilder<'_> {
         }
 
         let root_node = builder.build_node(start_node, fn_body.root_expression);
-        let _return_node = builder.push_node_edge(root_node, HirLocation::Return);
+        let return_node = builder.push_node_edge(root_node, HirLocation::Return);
+        for return_expression_node in std::mem::replace(&mut builder.returns, vec![]) {
+            builder.push_edge(return_expression_node, return_node);
+        }
 
         let _error_node = builder.push_node(HirLocation::Error);
 
@@ -170,6 +183,10 @@ impl AnalysisBuilder<'_> {
                 }
                 self.intern_path(PathData::Field { owner, name })
             }
+            hir::PlaceData::TupleField { owner, index, .. } => {
+                let owner = self.path(owner);
+                self.intern_path(PathData::TupleField { owner, index })
+            }
         }
     }
 
@@ -201,7 +218,10 @@ impl AnalysisBuilder<'_> {
                     self.analysis.local_path.push(path);
                 }
 
-                PathData::Entity(_) | PathData::Field { .. } | PathData::Index { .. } => {
+                PathData::Entity(_)
+                | PathData::Field { .. }
+                | PathData::TupleField { .. }
+                | PathData::Index { .. } => {
                     // These paths are either initialized from a base
                     // path, or do not need to be initialized (e.g., a
                     // global).
@@ -285,6 +305,7 @@ impl BuildCfgNode for hir::Expression {
         match &builder.fn_body[self] {
             hir::ExpressionData::Let {
                 variable,
+                ty: _,
                 initializer,
                 body,
             } => {
@@ -321,7 +342,25 @@ impl BuildCfgNode for hir::Expression {
                 self_node
             }
 
-            hir::ExpressionData::Assignment { place, value } => {
+            hir::ExpressionData::Reference { place, .. } => {
+                let place_node = builder.build_node(start_node, place);
+                let self_node = builder.push_node_edge(place_node, self.into());
+
+                // The place is accessed with the permission that the
+                // user asked for, which is the permission of the
+                // resulting type.
+                let perm = builder.results.ty(self).perm;
+                let path = builder.path(*place);
+                builder.access(perm, path, self_node);
+
+                self_node
+            }
+
+            // A compound assignment like `x += 1` also reads from
+            // `x`, but only numeric types support those operators, so
+            // there are no permissions to track for that read.
+            hir::ExpressionData::Assignment { place, value }
+            | hir::ExpressionData::CompoundAssignment { place, value, .. } => {
                 let place_node = builder.build_node(start_node, place);
                 let value_node = builder.build_node(place_node, value);
                 let self_node = builder.push_node_edge(value_node, self.into());
@@ -381,6 +420,118 @@ impl BuildCfgNode for hir::Expression {
                 join_node
             }
 
+            hir::ExpressionData::While { condition, body } => {
+                // The `while` itself is the loop head, which we
+                // return to after each execution of the body:
+                let head_node = builder.push_node_edge(start_node, self.into());
+                let condition_node = builder.build_node(head_node, condition);
+
+                // The condition is tested after it is evaluated; the
+                // loop exits from there.
+                let test_node =
+                    builder.push_node_edge(condition_node, HirLocation::AfterExpression(self));
+                builder.use_result_of(test_node, *condition);
+
+                // A `break` also exits via the test node. This is
+                // imprecise (it suggests that the body may execute
+                // again after a `break`), but conservative.
+                builder.loop_exits.push(test_node);
+                let body_node = builder.build_node(test_node, body);
+                builder.loop_exits.pop();
+                builder.push_edge(body_node, head_node);
+
+                test_node
+            }
+
+            hir::ExpressionData::For {
+                variable,
+                start,
+                end,
+                body,
+            } => {
+                let start_node = builder.build_node(start_node, start);
+                let end_node = builder.build_node(start_node, end);
+
+                // As with `while`, the `for` itself is the loop head,
+                // where the loop variable is assigned its next value:
+                let head_node = builder.push_node_edge(end_node, self.into());
+                builder.use_result_of(head_node, *start);
+                builder.use_result_of(head_node, *end);
+                let variable_path = builder.variable_path(*variable);
+                builder.generate_assignment_facts(variable_path, head_node);
+
+                // The loop exits from the head, or via `break`.
+                let exit_node =
+                    builder.push_node_edge(head_node, HirLocation::AfterExpression(self));
+                builder.loop_exits.push(exit_node);
+                let body_node = builder.build_node(head_node, body);
+                builder.loop_exits.pop();
+                builder.push_edge(body_node, head_node);
+
+                exit_node
+            }
+
+            hir::ExpressionData::Match { scrutinee, arms } => {
+                let scrutinee_node = builder.build_node(start_node, scrutinee);
+
+                // We say that a `match` "executes" when the scrutinee
+                // is inspected; the arms come afterwards.
+                let self_node = builder.push_node_edge(scrutinee_node, self.into());
+                builder.use_result_of(self_node, *scrutinee);
+
+                // Create a node to rejoin the control-flows:
+                let join_node = builder.push_node(HirLocation::AfterExpression(self));
+                if arms.is_empty() {
+                    builder.push_edge(self_node, join_node);
+                }
+
+                for arm in arms.iter(builder.fn_body) {
+                    let arm_node = builder.build_node(self_node, arm);
+                    builder.push_edge(arm_node, join_node);
+                }
+
+                join_node
+            }
+
+            hir::ExpressionData::Loop { body } => {
+                let head_node = builder.push_node_edge(start_node, self.into());
+
+                // The only way out of a `loop` is via `break`, which
+                // jumps to the exit node.
+                let exit_node = builder.push_node(HirLocation::AfterExpression(self));
+                builder.loop_exits.push(exit_node);
+                let body_node = builder.build_node(head_node, body);
+                builder.loop_exits.pop();
+                builder.push_edge(body_node, head_node);
+
+                exit_node
+            }
+
+            hir::ExpressionData::Break { value } => {
+                let value_node = builder.build_node(start_node, value);
+                let self_node = builder.push_node_edge(value_node, self.into());
+                builder.use_result_of(self_node, *value);
+
+                if let Some(&exit_node) = builder.loop_exits.last() {
+                    builder.push_edge(self_node, exit_node);
+                }
+
+                // Whatever comes after the `break` is unreachable, so
+                // we give it a node with no incoming edges.
+                builder.push_node(HirLocation::AfterExpression(self))
+            }
+
+            hir::ExpressionData::Return { value } => {
+                let value_node = builder.build_node(start_node, value);
+                let self_node = builder.push_node_edge(value_node, self.into());
+                builder.use_result_of(self_node, *value);
+                builder.returns.push(self_node);
+
+                // As with `break`, whatever comes after the `return`
+                // is unreachable.
+                builder.push_node(HirLocation::AfterExpression(self))
+            }
+
             hir::ExpressionData::Binary { left, right, .. } => {
                 let left_node = builder.build_node(start_node, left);
                 let right_node = builder.build_node(left_node, right);
@@ -412,6 +563,25 @@ impl BuildCfgNode for hir::Expression {
                 self_node
             }
 
+            hir::ExpressionData::Range { start, end, .. } => {
+                let endpoints_node = builder.build_node(start_node, start);
+                let endpoints_node = builder.build_node(endpoints_node, end);
+                let self_node = builder.push_node_edge(endpoints_node, self.into());
+                for &endpoint in start.iter().chain(end) {
+                    builder.use_result_of(self_node, endpoint);
+                }
+                self_node
+            }
+
+            hir::ExpressionData::Tuple { elements } => {
+                let elements_node = builder.build_node(start_node, elements);
+                let self_node = builder.push_node_edge(elements_node, self.into());
+                for element in elements.iter(builder.fn_body) {
+                    builder.use_result_of(self_node, element);
+                }
+                self_node
+            }
+
             hir::ExpressionData::Sequence { first, second } => {
                 let first_node = builder.build_node(start_node, first);
                 let self_node = builder.push_node_edge(first_node, self.into());
@@ -427,6 +597,40 @@ impl BuildCfgNode for hir::IdentifiedExpression {
     }
 }
 
+impl BuildCfgNode for hir::MatchArm {
+    fn build_cfg_node(self, start_node: Node, builder: &mut AnalysisBuilder<'_>) -> Node {
+        let hir::MatchArmData { pattern, body } = builder.fn_body[self];
+        let pattern_node = builder.build_node(start_node, pattern);
+        builder.build_node(pattern_node, body)
+    }
+}
+
+impl BuildCfgNode for hir::Pattern {
+    fn build_cfg_node(self, start_node: Node, builder: &mut AnalysisBuilder<'_>) -> Node {
+        match builder.fn_body[self] {
+            hir::PatternData::Wildcard => start_node,
+
+            hir::PatternData::Binding(variable) => {
+                // The matched value is assigned into the variable.
+                let self_node = builder.push_node_edge(start_node, self.into());
+                let variable_path = builder.variable_path(variable);
+                builder.generate_assignment_facts(variable_path, self_node);
+                self_node
+            }
+
+            hir::PatternData::Literal(literal) => builder.build_node(start_node, literal),
+
+            hir::PatternData::Struct { fields, .. } => builder.build_node(start_node, fields),
+        }
+    }
+}
+
+impl BuildCfgNode for hir::IdentifiedPattern {
+    fn build_cfg_node(self, start_node: Node, builder: &mut AnalysisBuilder<'_>) -> Node {
+        builder.build_node(start_node, builder.fn_body[self].pattern)
+    }
+}
+
 impl BuildCfgNode for hir::Place {
     fn build_cfg_node(self, start_node: Node, builder: &mut AnalysisBuilder<'_>) -> Node {
         match &builder.fn_body[self] {
@@ -442,7 +646,7 @@ impl BuildCfgNode for hir::Place {
                 self_node
             }
 
-            hir::PlaceData::Field { owner, .. } => {
+            hir::PlaceData::Field { owner, .. } | hir::PlaceData::TupleField { owner, .. } => {
                 let owner_node = builder.build_node(start_node, owner);
 
                 // We need a control-flow node for "field" places,
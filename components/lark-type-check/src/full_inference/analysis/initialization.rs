@@ -46,32 +46,10 @@ impl Initialization {
 
         let owner_path: Relation<_> = analysis_ir.owner_path.iter().collect();
 
-        // .decl transitive_owner_path(Path1:path, Path2:node)
+        // .decl transitive_owner_path(Path1:path, Path2:path)
         //
         // Transitive version of `owner_path`.
-        let transitive_owner_path: Relation<(Path, Path)> = {
-            let mut iteration = Iteration::new();
-
-            // .decl transitive_owner_path(Path, Path)
-            let transitive_owner_path = iteration.variable::<(Path, Path)>("transitive_owner_path");
-
-            // transitive_owner_path(Path1, Path2) :-
-            //   owner_path(Path1, Path2).
-            transitive_owner_path.insert(owner_path.clone());
-
-            while iteration.changed() {
-                // transitive_owner_path(Path1, Path3) :-
-                //   transitive_owner_path(Path1, Path2),
-                //   owner_path(Path2, Path3).
-                transitive_owner_path.from_leapjoin(
-                    &transitive_owner_path,
-                    owner_path.extend_with(|&(path1, _)| path1),
-                    |&(path1, _), &path3| (path1, path3),
-                );
-            }
-
-            transitive_owner_path.complete()
-        };
+        let transitive_owner_path = transitive_owner_path(&owner_path);
 
         ///////////////////////////////////////////////////////////////////////////
         // Round 1: Compute `transitive_overwritten`
@@ -316,3 +294,29 @@ impl Initialization {
         }
     }
 }
+
+/// Computes the transitive version of `owner_path`: contains
+/// `(Path1, Path2)` if `Path1` is any parent path of `Path2`.
+crate fn transitive_owner_path(owner_path: &Relation<(Path, Path)>) -> Relation<(Path, Path)> {
+    let mut iteration = Iteration::new();
+
+    // .decl transitive_owner_path(Path, Path)
+    let transitive_owner_path = iteration.variable::<(Path, Path)>("transitive_owner_path");
+
+    // transitive_owner_path(Path1, Path2) :-
+    //   owner_path(Path1, Path2).
+    transitive_owner_path.insert(owner_path.clone());
+
+    while iteration.changed() {
+        // transitive_owner_path(Path1, Path3) :-
+        //   transitive_owner_path(Path1, Path2),
+        //   owner_path(Path2, Path3).
+        transitive_owner_path.from_leapjoin(
+            &transitive_owner_path,
+            owner_path.extend_with(|&(path1, _)| path1),
+            |&(path1, _), &path3| (path1, path3),
+        );
+    }
+
+    transitive_owner_path.complete()
+}
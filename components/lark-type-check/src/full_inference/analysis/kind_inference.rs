@@ -10,6 +10,8 @@ use lark_intern::Intern;
 use lark_intern::Untern;
 use lark_ty::PermKind;
 
+mod test;
+
 /// **Kind inference:** The role of *kind inference* is to decide, for
 /// each permission variable `P`, whether it is "share" or "borrow" or
 /// "owned". This is a flow-insensitive analysis -- it does not need
@@ -17,8 +19,12 @@ use lark_ty::PermKind;
 /// relations between permissions: if we have that `Pa: Pb` and `Pb`
 /// is borrow (resp. own), then `Pa` must be borrow (resp. own).
 crate struct KindInference {
+    crate perm_less: Relation<(Perm, Perm)>,
     crate borrow: Relation<(Perm, ())>,
     crate owned: Relation<(Perm, ())>,
+
+    /// Every permission that appears in the constraints.
+    perms: Vec<Perm>,
 }
 
 impl KindInference {
@@ -27,6 +33,16 @@ impl KindInference {
         perm_less_base: &[(Perm, Perm, Node)],
         perm_less_if_base: &[(Perm, Perm, Perm, Node)],
     ) -> Self {
+        let perms: Vec<Perm> = perm_less_base
+            .iter()
+            .flat_map(|&(a, b, _n)| vec![a, b])
+            .chain(
+                perm_less_if_base
+                    .iter()
+                    .flat_map(|&(c, a, b, _n)| vec![c, a, b]),
+            )
+            .collect();
+
         let mut iteration = Iteration::new();
 
         // .decl perm_less(Pa, Pb)
@@ -51,7 +67,7 @@ impl KindInference {
         // .decl owned(Pa)
         //
         // True if `Pa` is at least own.
-        let owned = iteration.variable::<(Perm, ())>("borrow");
+        let owned = iteration.variable::<(Perm, ())>("owned");
         owned.extend(std::iter::once((perm_own, ())));
 
         while iteration.changed() {
@@ -67,43 +83,60 @@ impl KindInference {
             owned.from_join(&perm_less, &owned, |&_p_a, &p_b, &()| (p_b, ()));
         }
 
+        let perm_less = perm_less.complete();
         let borrow = borrow.complete();
         let owned = owned.complete();
 
-        Self { borrow, owned }
+        // Owned is strictly stronger than borrow: everything owned must
+        // also be borrow, but the `borrow` permission itself must never
+        // show up as owned (which would mean the two sets got mixed up).
+        debug_assert!(owned
+            .elements
+            .iter()
+            .all(|o| borrow.elements.binary_search(o).is_ok()));
+        debug_assert!(owned.elements.binary_search(&(perm_borrow, ())).is_err());
+
+        Self {
+            perm_less,
+            borrow,
+            owned,
+            perms,
+        }
     }
 
-    /// Returns a map that specifies whether each `PermVar` is either
-    /// borrow or owned. If there is no entry for a given `PermVar`,
-    /// then it is shared.
+    /// Returns a map that specifies whether each `PermVar` that
+    /// appears in the constraints is share, borrow, or owned.
     crate fn to_kind_map(
         &self,
         tables: &impl AsRef<FullInferenceTables>,
     ) -> FxIndexMap<PermVar, PermKind> {
         let mut set: FxIndexMap<PermVar, PermKind> = FxIndexMap::default();
 
-        // Insert all things in `borrow` set into the map with `PermKind = Borrow`
-        set.extend(
-            self.borrow
-                .elements
-                .iter()
-                .filter_map(|&(v, ())| match v.untern(tables) {
-                    PermData::Inferred(v) => Some((v, PermKind::Borrow)),
-                    PermData::Known(_) | PermData::Placeholder(_) => None,
-                }),
-        );
-
-        // Insert all things in `own` set into the map with `PermKind = Own`,
-        // in some cases overwriting things inserted previously with `PermKind = Borrow`
-        set.extend(
-            self.owned
-                .elements
-                .iter()
-                .filter_map(|&(v, ())| match v.untern(tables) {
-                    PermData::Inferred(v) => Some((v, PermKind::Own)),
-                    PermData::Known(_) | PermData::Placeholder(_) => None,
-                }),
-        );
+        let perms = self.perms.iter().map(|&v| (v, PermKind::Share));
+        let borrow = self
+            .borrow
+            .elements
+            .iter()
+            .map(|&(v, ())| (v, PermKind::Borrow));
+        let owned = self
+            .owned
+            .elements
+            .iter()
+            .map(|&(v, ())| (v, PermKind::Own));
+
+        // Every variable starts out as `PermKind = Share`, and things in
+        // the `borrow` and `owned` sets are then raised to `Borrow` or
+        // `Own`. Using `join` means the order we visit them in doesn't
+        // matter: `Own` always wins over `Borrow`.
+        for (v, kind) in perms.chain(borrow).chain(owned) {
+            match v.untern(tables) {
+                PermData::Inferred(v) => {
+                    let entry = set.entry(v).or_insert(PermKind::Share);
+                    *entry = entry.join(kind);
+                }
+                PermData::Known(_) | PermData::Placeholder(_) => {}
+            }
+        }
 
         set
     }
@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+use crate::full_inference::analysis::kind_inference::KindInference;
+use crate::full_inference::analysis::Node;
+use crate::full_inference::perm::Perm;
+use crate::full_inference::perm::PermData;
+use crate::full_inference::perm::PermVar;
+use crate::full_inference::FullInferenceTables;
+use lark_intern::Intern;
+use lark_ty::PermKind;
+
+fn var(tables: &FullInferenceTables, index: usize) -> Perm {
+    PermData::Inferred(PermVar::new(index)).intern(tables)
+}
+
+#[test]
+fn mixed_constraints() {
+    let tables = &FullInferenceTables::default();
+    let node = Node::new(0);
+    let share: Perm = PermKind::Share.intern(tables);
+    let borrow: Perm = PermKind::Borrow.intern(tables);
+    let own: Perm = PermKind::Own.intern(tables);
+    let p: Vec<Perm> = (0..9).map(|i| var(tables, i)).collect();
+
+    let perm_less_base = vec![
+        // P0 is forced to own, and P1 is at least P0.
+        (own, p[0], node),
+        (p[0], p[1], node),
+        // P2 is forced to borrow.
+        (borrow, p[2], node),
+        // P3 and P4 are related, but neither is forced, so both
+        // default to share.
+        (p[3], p[4], node),
+        // Share forces nothing.
+        (share, p[5], node),
+    ];
+
+    let perm_less_if_base = vec![
+        // P2 is borrow, so the condition holds and P6 is at least P0.
+        (p[2], p[0], p[6], node),
+        // P3 is share, so the condition never holds and P7 stays share.
+        (p[3], p[0], p[7], node),
+        // P8 only gets borrow through the conditional on P6.
+        (p[6], borrow, p[8], node),
+    ];
+
+    let kind_map =
+        KindInference::new(tables, &perm_less_base, &perm_less_if_base).to_kind_map(tables);
+
+    let kind = |index: usize| kind_map[&PermVar::new(index)];
+    assert_eq!(kind(0), PermKind::Own);
+    assert_eq!(kind(1), PermKind::Own);
+    assert_eq!(kind(2), PermKind::Borrow);
+    assert_eq!(kind(3), PermKind::Share);
+    assert_eq!(kind(4), PermKind::Share);
+    assert_eq!(kind(5), PermKind::Share);
+    assert_eq!(kind(6), PermKind::Own);
+    assert_eq!(kind(7), PermKind::Share);
+    assert_eq!(kind(8), PermKind::Borrow);
+}
+
+#[test]
+fn unconstrained_defaults_to_share() {
+    let tables = &FullInferenceTables::default();
+    let node = Node::new(0);
+    let p0 = var(tables, 0);
+    let p1 = var(tables, 1);
+
+    let kind_map = KindInference::new(tables, &[(p0, p1, node)], &[]).to_kind_map(tables);
+
+    assert_eq!(kind_map.len(), 2);
+    assert_eq!(kind_map[&PermVar::new(0)], PermKind::Share);
+    assert_eq!(kind_map[&PermVar::new(1)], PermKind::Share);
+}
@@ -1,11 +1,17 @@
 //! The representation of Permissions when doing full inference.
 
+use crate::full_inference::FullInference;
 use crate::full_inference::FullInferenceTables;
 use lark_debug_derive::DebugWith;
 use lark_intern::Intern;
+use lark_intern::Untern;
+use lark_ty::free_vars::FreePerm;
+use lark_ty::free_vars::FreeVar;
 use lark_ty::PermKind;
 use lark_ty::Placeholder;
 
+mod test;
+
 lark_collections::index_type! {
     /// An intern'd permission.
     crate struct Perm { .. }
@@ -14,7 +20,9 @@ lark_collections::index_type! {
 lark_debug_with::debug_fallback_impl!(Perm);
 
 lark_collections::index_type! {
-    crate struct PermVar { .. }
+    /// A permission variable, whose kind is decided by kind
+    /// inference (see the `perm_kinds` query).
+    pub struct PermVar { .. }
 }
 
 lark_debug_with::debug_fallback_impl!(PermVar);
@@ -40,3 +48,13 @@ impl Intern<FullInferenceTables> for PermKind {
         PermData::Known(self).intern(interner)
     }
 }
+
+impl FreePerm<FullInference> for Perm {
+    fn free_var(self, tables: &dyn AsRef<FullInferenceTables>) -> Option<FreeVar<FullInference>> {
+        match self.untern(tables) {
+            PermData::Known(_) => None,
+            PermData::Placeholder(placeholder) => Some(FreeVar::Placeholder(placeholder)),
+            PermData::Inferred(_) => Some(FreeVar::Perm(self)),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(test)]
+
+use crate::full_inference::perm::Perm;
+use crate::full_inference::perm::PermData;
+use crate::full_inference::perm::PermVar;
+use crate::full_inference::FullInference;
+use crate::full_inference::FullInferenceTables;
+use lark_intern::Intern;
+use lark_ty::free_vars::free_vars;
+use lark_ty::free_vars::FreeVar;
+use lark_ty::BaseData;
+use lark_ty::BaseKind;
+use lark_ty::Erased;
+use lark_ty::GenericKind;
+use lark_ty::PermKind;
+use lark_ty::Ty;
+use lark_ty::TypeFamily;
+
+fn var(tables: &FullInferenceTables, index: usize) -> Perm {
+    PermData::Inferred(PermVar::new(index)).intern(tables)
+}
+
+/// Creates an error type with the permission `perm`, applied to
+/// `generics`.
+fn error_ty(
+    tables: &FullInferenceTables,
+    perm: Perm,
+    generics: Vec<Ty<FullInference>>,
+) -> Ty<FullInference> {
+    let base_data: BaseData<FullInference> = BaseData {
+        kind: BaseKind::Error,
+        generics: generics.into_iter().map(GenericKind::Ty).collect(),
+    };
+    Ty {
+        repr: Erased,
+        perm,
+        base: FullInference::intern_base_data(tables, base_data),
+    }
+}
+
+#[test]
+fn free_perm_vars() {
+    let tables = &FullInferenceTables::default();
+    let own: Perm = PermKind::Own.intern(tables);
+    let (p0, p1) = (var(tables, 0), var(tables, 1));
+
+    // `P1 Error<own Error, P0 Error<>>`
+    let inner = error_ty(tables, p0, vec![]);
+    let ty = error_ty(tables, p1, vec![error_ty(tables, own, vec![]), inner]);
+
+    let vars: Vec<_> = free_vars::<FullInference, _>(&ty, tables)
+        .into_iter()
+        .collect();
+    assert_eq!(vars, vec![FreeVar::Perm(p1), FreeVar::Perm(p0)]);
+}
+
+#[test]
+fn known_perms_are_not_free() {
+    let tables = &FullInferenceTables::default();
+    let share: Perm = PermKind::Share.intern(tables);
+    let ty = error_ty(tables, share, vec![]);
+
+    assert!(free_vars::<FullInference, _>(&ty, tables).is_empty());
+}
@@ -1,20 +1,24 @@
 use crate::full_inference::analysis::AnalysisIr;
 use crate::full_inference::analysis::AnalysisResults;
+use crate::full_inference::perm::PermVar;
 use crate::full_inference::resolve_to_full_inferred::ResolveToFullInferred;
 use crate::full_inference::type_checker::FullInferenceStorage;
 use crate::full_inference::FullInference;
 use crate::full_inference::FullInferenceTables;
 use crate::results::TypeCheckResults;
+use crate::Severity;
 use crate::TypeCheckDatabase;
 use crate::TypeChecker;
 use crate::UniverseBinder;
 use generational_arena::Arena;
-use lark_collections::{FxIndexMap, IndexVec};
+use lark_collections::{FxIndexMap, FxIndexSet, IndexVec};
 use lark_entity::Entity;
 use lark_error::Diagnostic;
 use lark_error::WithError;
 use lark_ty::full_inferred::FullInferred;
 use lark_ty::map_family::Map;
+use lark_ty::Generics;
+use lark_ty::PermKind;
 use lark_unify::UnificationTable;
 use std::sync::Arc;
 
@@ -22,37 +26,34 @@ crate fn full_type_check(
     db: &impl TypeCheckDatabase,
     fn_entity: Entity,
 ) -> WithError<Arc<TypeCheckResults<FullInferred>>> {
-    let fn_body = db.fn_body(fn_entity).into_value();
-    let interners = FullInferenceTables::default();
-    let mut type_checker: TypeChecker<'_, FullInference, _> = TypeChecker {
-        db,
-        fn_entity,
-        f_tables: interners.clone(),
-        hir: fn_body.clone(),
-        ops_arena: Arena::new(),
-        ops_blocked: FxIndexMap::default(),
-        unify: UnificationTable::new(interners.clone()),
-        storage: FullInferenceStorage::default(),
-        universe_binders: IndexVec::from(vec![UniverseBinder::Root]),
-        errors: vec![],
-    };
+    db.full_inference(fn_entity).map(|(results, _)| results)
+}
 
-    type_checker.check_fn_body();
+crate fn perm_kinds(
+    db: &impl TypeCheckDatabase,
+    fn_entity: Entity,
+) -> Arc<FxIndexMap<PermVar, PermKind>> {
+    db.full_inference(fn_entity).into_value().1
+}
 
-    let analysis = AnalysisIr::new(
-        &fn_body,
-        &type_checker.storage.results,
-        &type_checker.storage.constraints,
-        &mut type_checker.unify,
-    );
+crate fn full_inference(
+    db: &impl TypeCheckDatabase,
+    fn_entity: Entity,
+) -> WithError<(
+    Arc<TypeCheckResults<FullInferred>>,
+    Arc<FxIndexMap<PermVar, PermKind>>,
+)> {
+    let (mut type_checker, analysis_results) = check_and_analyze(db, fn_entity);
+    let fn_body = type_checker.hir.clone();
+    let interners = type_checker.f_tables.clone();
 
     let AnalysisResults {
         perm_kinds,
         mut errors,
-    } = analysis.infer(fn_entity, db, &fn_body, &type_checker);
+    } = analysis_results;
 
     let mut unresolved_variables = vec![];
-    let inferred_results = type_checker
+    let mut inferred_results = type_checker
         .storage
         .results
         .map(&mut ResolveToFullInferred::new(
@@ -63,7 +64,14 @@ crate fn full_type_check(
             &perm_kinds,
         ));
 
-    errors.extend(type_checker.errors);
+    errors.extend(
+        type_checker
+            .errors
+            .iter()
+            .filter(|error| error.severity == Severity::Error)
+            .map(|error| error.to_diagnostic(&fn_body)),
+    );
+    inferred_results.errors = type_checker.errors;
     for _ in unresolved_variables {
         // FIXME: Decent diagnostics for unresolved inference
         // variables.
@@ -74,7 +82,52 @@ crate fn full_type_check(
     }
 
     WithError {
-        value: Arc::new(inferred_results),
+        value: (Arc::new(inferred_results), Arc::new(perm_kinds)),
         errors,
     }
 }
+
+/// Type-checks the fn body of `fn_entity` and then runs the safety
+/// analysis on the result. The type checker is returned as well so
+/// that its inferred types can be resolved.
+fn check_and_analyze(
+    db: &'db impl TypeCheckDatabase,
+    fn_entity: Entity,
+) -> (
+    TypeChecker<'db, FullInference, FullInferenceStorage>,
+    AnalysisResults,
+) {
+    let fn_body = db.fn_body(fn_entity).into_value();
+    let interners = FullInferenceTables::default();
+    let mut type_checker: TypeChecker<'_, FullInference, _> = TypeChecker {
+        db,
+        fn_entity,
+        f_tables: interners.clone(),
+        hir: fn_body.clone(),
+        ops_arena: Arena::new(),
+        ops_blocked: FxIndexMap::default(),
+        unify: UnificationTable::new(interners.clone()),
+        storage: FullInferenceStorage::default(),
+        universe_binders: IndexVec::from(vec![UniverseBinder::Root]),
+        fn_generics: Generics::empty(),
+        return_ty: None,
+        errors: vec![],
+        enclosing_loops: vec![],
+        integer_literals: vec![],
+        negated_literals: FxIndexSet::default(),
+        diverging_variables: vec![],
+    };
+
+    type_checker.check_fn_body();
+
+    let analysis = AnalysisIr::new(
+        &fn_body,
+        &type_checker.storage.results,
+        &type_checker.storage.constraints,
+        &mut type_checker.unify,
+    );
+
+    let analysis_results = analysis.infer(fn_entity, db, &fn_body, &type_checker);
+
+    (type_checker, analysis_results)
+}
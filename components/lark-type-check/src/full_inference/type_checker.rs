@@ -7,8 +7,6 @@ use crate::full_inference::perm::PermVar;
 use crate::full_inference::FullInference;
 use crate::full_inference::FullInferenceTables;
 use crate::results::TypeCheckResults;
-use crate::substitute::Substitution;
-use crate::substitute::SubstitutionDelegate;
 use crate::HirLocation;
 use crate::TypeChecker;
 use crate::TypeCheckerFamilyDependentExt;
@@ -23,6 +21,8 @@ use lark_ty::declaration;
 use lark_ty::declaration::Declaration;
 use lark_ty::declaration::DeclaredPermKind;
 use lark_ty::map_family::Map;
+use lark_ty::substitution::BoundVarSubstitution;
+use lark_ty::substitution::SubstitutionDelegate;
 use lark_ty::BaseKind;
 use lark_ty::Erased;
 use lark_ty::GenericKind;
@@ -77,7 +77,7 @@ impl TypeCheckerFamilyDependentExt<FullInference>
     where
         M: Map<Declaration, FullInference>,
     {
-        value.map(&mut Substitution::new(self, generics))
+        value.map(&mut BoundVarSubstitution::new(self, generics))
     }
 
     fn apply_owner_perm(
@@ -90,6 +90,17 @@ impl TypeCheckerFamilyDependentExt<FullInference>
         self.apply_access_perm(cause.into(), location.into(), owner_perm, field_ty)
     }
 
+    fn apply_user_perm(
+        &mut self,
+        cause: impl Into<hir::MetaIndex>,
+        location: impl Into<HirLocation>,
+        perm: PermKind,
+        place_ty: Ty<FullInference>,
+    ) -> Ty<FullInference> {
+        let perm = perm.intern(self);
+        self.apply_access_perm(cause.into(), location.into(), perm, place_ty)
+    }
+
     fn record_variable_ty(&mut self, var: hir::Variable, ty: Ty<FullInference>) {
         self.storage.results.record_max_ty(var, ty);
     }
@@ -122,7 +133,7 @@ impl TypeCheckerFamilyDependentExt<FullInference>
     }
 
     fn request_variable_ty(&mut self, var: hir::Variable) -> Ty<FullInference> {
-        self.storage.results.opt_ty(var).unwrap_or_else(|| {
+        self.storage.results.try_ty(var).unwrap_or_else(|| {
             let ty = self.new_variable();
             self.storage.results.record_max_ty(var, ty);
             ty
@@ -142,6 +153,7 @@ impl TypeCheckerFamilyDependentExt<FullInference>
         self.storage.results.record_entity(index, entity);
         let generics = self.inference_variables_for(entity);
         self.storage.results.record_generics(index, &generics);
+        self.check_generic_constraints(index, entity, &generics);
         generics
     }
 }
@@ -202,11 +214,10 @@ impl TypeCheckerVariableExt<FullInference, Ty<FullInference>>
 
                 if data1.kind != data2.kind {
                     self.record_error(
-                        format!(
-                            "mismatched types ({} vs {})",
-                            data1.kind.pretty_print(self.db),
-                            data2.kind.pretty_print(self.db)
-                        ),
+                        ErrorKind::Mismatch {
+                            expected: data2.kind.pretty_print(self.db),
+                            actual: data1.kind.pretty_print(self.db),
+                        },
                         cause,
                     );
                     return;
@@ -242,9 +253,8 @@ impl SubstitutionDelegate<FullInference> for TypeChecker<'me, FullInference, Ful
     }
 
     fn map_perm(&mut self, perm: declaration::Perm) -> Perm {
-        match perm.untern(self) {
-            DeclaredPermKind::Own => PermData::Known(PermKind::Own).intern(self),
-        }
+        let perm: DeclaredPermKind = perm.untern(self);
+        PermData::Known(perm.perm_kind()).intern(self)
     }
 
     fn apply_repr_perm(
@@ -259,6 +269,24 @@ impl SubstitutionDelegate<FullInference> for TypeChecker<'me, FullInference, Ful
                 // the result is just `U`.
                 ty
             }
+
+            // If you have `shared T` and substitute `U`, the result
+            // has the lesser of the two permissions.
+            //
+            // FIXME -- if `U`'s permission is still being inferred,
+            // we should relate it to the declared one with a
+            // constraint; for now, the declared permission wins.
+            declared => {
+                let declared = declared.perm_kind();
+                let perm = match ty.perm.untern(self) {
+                    PermData::Known(known) if declared.at_least(known) => known,
+                    _ => declared,
+                };
+                Ty {
+                    perm: PermData::Known(perm).intern(self),
+                    ..ty
+                }
+            }
         }
     }
 }
@@ -1,4 +1,9 @@
+use crate::EnclosingLoop;
+use crate::ErrorKind;
+use crate::GenericOperation;
 use crate::HirLocation;
+use crate::Operator;
+use crate::PlaceExpressionKind;
 use crate::TypeChecker;
 use crate::TypeCheckerFamily;
 use crate::TypeCheckerFamilyDependentExt;
@@ -12,10 +17,12 @@ use lark_error::ErrorSentinel;
 use lark_hir as hir;
 use lark_intern::Untern;
 use lark_pretty_print::PrettyPrint;
+use lark_string::GlobalIdentifier;
 use lark_ty::declaration::Declaration;
+use lark_ty::PermKind;
 use lark_ty::Signature;
 use lark_ty::Ty;
-use lark_ty::{BaseData, BaseKind};
+use lark_ty::{BaseData, BaseKind, BoundVar};
 use lark_unify::InferVar;
 use lark_unify::Inferable;
 
@@ -41,7 +48,8 @@ where
             .unwrap_or_else(|ErrorReported(_)| {
                 <Signature<Declaration>>::error_sentinel(self, hir_arguments_len)
             });
-        let placeholders = self.placeholders_for(self.fn_entity);
+        self.fn_generics = self.placeholders_for(self.fn_entity);
+        let placeholders = self.fn_generics.clone();
         let signature = self.substitute(
             self.hir.root_expression,
             &placeholders,
@@ -54,12 +62,38 @@ where
                 self.record_variable_ty(argument, input);
             }
         }
+        self.return_ty = Some(signature.output);
         self.check_expression(
             CheckType(signature.output, HirLocation::Return),
             self.hir.root_expression,
         );
+        self.check_unused_variables();
 
-        // Complete all deferred type operations; run to steady state.
+        self.run_deferred_ops();
+
+        // Integer literals whose type was not otherwise inferred
+        // default to `int`; that may in turn unblock further
+        // operations.
+        self.default_integer_literals();
+        self.run_deferred_ops();
+
+        // Likewise, the type of a diverging expression that nothing
+        // else constrained defaults to unit.
+        self.default_diverging_variables();
+        self.run_deferred_ops();
+
+        let mut unresolved_variables = vec![];
+
+        // Look for any deferred operations that never executed. Those
+        // variables that they are blocked on must not be resolved; record
+        // as an error.
+        self.untriggered_ops(&mut unresolved_variables);
+
+        unresolved_variables
+    }
+
+    /// Complete all deferred type operations; run to steady state.
+    fn run_deferred_ops(&mut self) {
         loop {
             let vars: Vec<InferVar> = self.unify.drain_events().collect();
             if vars.is_empty() {
@@ -69,15 +103,36 @@ where
                 self.trigger_ops(var);
             }
         }
+    }
 
-        let mut unresolved_variables = vec![];
-
-        // Look for any deferred operations that never executed. Those
-        // variables that they are blocked on must not be resolved; record
-        // as an error.
-        self.untriggered_ops(&mut unresolved_variables);
+    /// Unifies the type of each integer literal that is still an
+    /// inference variable with `int` (cf. Rust's `i32` fallback).
+    fn default_integer_literals(&mut self) {
+        let int_type = self.int_type();
+        for (expression, ty) in std::mem::replace(&mut self.integer_literals, vec![]) {
+            if self.unify.shallow_resolve_data(ty.base).is_err() {
+                self.equate(
+                    expression,
+                    expression,
+                    ty,
+                    Ty {
+                        base: int_type.base,
+                        ..ty
+                    },
+                );
+            }
+        }
+    }
 
-        unresolved_variables
+    /// Unifies each type from `never_type` that is still an inference
+    /// variable with unit (cf. Rust's fallback for `!`).
+    fn default_diverging_variables(&mut self) {
+        let unit_type = self.unit_type();
+        for (expression, ty) in std::mem::replace(&mut self.diverging_variables, vec![]) {
+            if self.unify.shallow_resolve_data(ty.base).is_err() {
+                self.equate(expression, expression, ty, unit_type);
+            }
+        }
     }
 
     /// Type-check the expression `expression` in the given mode
@@ -104,6 +159,55 @@ where
         }
     }
 
+    /// The type of `expression`, which never produces a value (like
+    /// `return`). This "never" type is the bottom element: it adopts
+    /// whatever type the context expects, so that when branches are
+    /// combined, `lub(never, T) = T`. With nothing expected, it is a
+    /// fresh inference variable, which unifies with anything and
+    /// falls back to unit if nothing constrains it, as in Rust.
+    fn never_type(&mut self, expression: hir::Expression, mode: Mode<F>) -> Ty<F> {
+        match mode {
+            Synthesize => {
+                let ty = self.new_variable();
+                self.diverging_variables.push((expression, ty));
+                ty
+            }
+            CheckType(expected_ty, _) => expected_ty,
+        }
+    }
+
+    /// True if evaluating `expression` always diverges (via `return`
+    /// or `break`) rather than producing a value.
+    fn diverges(&self, expression: hir::Expression) -> bool {
+        match self.hir[expression] {
+            hir::ExpressionData::Return { .. } | hir::ExpressionData::Break { .. } => true,
+
+            hir::ExpressionData::Sequence { first, second } => {
+                self.diverges(first) || self.diverges(second)
+            }
+
+            hir::ExpressionData::Let {
+                initializer, body, ..
+            } => initializer.map_or(false, |i| self.diverges(i)) || self.diverges(body),
+
+            hir::ExpressionData::If {
+                condition,
+                if_true,
+                if_false,
+            } => self.diverges(condition) || (self.diverges(if_true) && self.diverges(if_false)),
+
+            hir::ExpressionData::Match { scrutinee, arms } => {
+                self.diverges(scrutinee)
+                    || (!arms.is_empty()
+                        && arms
+                            .iter_data(&self.hir)
+                            .all(|arm_data| self.diverges(arm_data.body)))
+            }
+
+            _ => false,
+        }
+    }
+
     /// Common helper for checking and synthesizing the type of an expression.
     ///
     /// If `expected_ty` is `None`, this will synthesize. Otherwise, it will consider
@@ -117,10 +221,16 @@ where
         match expression_data {
             hir::ExpressionData::Let {
                 variable,
+                ty,
                 initializer,
                 body,
             } => {
                 let variable_ty = self.request_variable_ty(variable);
+                if let Some(declared_ty) = ty {
+                    let generics = self.fn_generics.clone();
+                    let declared_ty = self.substitute(expression, &generics, declared_ty);
+                    self.equate(expression, expression, variable_ty, declared_ty);
+                }
                 if let Some(initializer) = initializer {
                     self.check_expression(CheckType(variable_ty, expression.into()), initializer);
                 }
@@ -130,11 +240,38 @@ where
             hir::ExpressionData::Place { place } => self.check_place(place),
 
             hir::ExpressionData::Assignment { place, value } => {
+                self.check_mutable_place(place, PlaceExpressionKind::Assignment);
                 let place_ty = self.check_place(place);
                 self.check_expression(CheckType(place_ty, expression.into()), value);
                 self.unit_type()
             }
 
+            hir::ExpressionData::CompoundAssignment {
+                operator,
+                place,
+                value,
+            } => {
+                // `x += y` is checked like `x = x + y`, except that
+                // `x` must be numeric.
+                self.check_mutable_place(place, PlaceExpressionKind::Assignment);
+                let place_ty = self.check_place(place);
+                let value_ty = self.check_expression(Synthesize, value);
+                self.with_base_data_equate(
+                    place_ty.base,
+                    move |this, place_base_data| {
+                        this.check_compound_assignment(
+                            expression,
+                            operator,
+                            place_ty,
+                            value_ty,
+                            place_base_data,
+                        )
+                    },
+                    |_, ()| (),
+                );
+                self.unit_type()
+            }
+
             hir::ExpressionData::MethodCall { method, arguments } => {
                 let owner_expression = arguments.first(&self.hir).unwrap();
                 let owner_ty = self.check_expression(Mode::Synthesize, owner_expression);
@@ -153,8 +290,33 @@ where
                 self.check_aggregate(expression, entity, fields)
             }
 
+            hir::ExpressionData::Tuple { elements } => {
+                let hir = &self.hir.clone();
+                let element_tys = elements
+                    .iter(hir)
+                    .map(|element| self.check_expression(Synthesize, element))
+                    .collect();
+                self.tuple_type(element_tys)
+            }
+
+            // The value of `first` is discarded, as with a statement
+            // like `foo();` in Rust, so the sequence has the type of
+            // `second`. A block whose final statement ends in `;` is
+            // represented with a unit expression for `second`.
+            //
+            // Since a newline separates statements just as `;` does,
+            // we do not require `first` to be unit: `foo()` on a line
+            // of its own is fine even if `foo` returns something.
             hir::ExpressionData::Sequence { first, second } => {
-                self.check_expression(CheckType(self.unit_type(), expression.into()), first);
+                self.check_expression(Synthesize, first);
+
+                // Anything after a diverging expression is
+                // unreachable, so its type does not matter.
+                if self.diverges(first) {
+                    self.check_expression(Synthesize, second);
+                    return self.never_type(expression, mode);
+                }
+
                 self.check_expression(mode, second)
             }
 
@@ -165,6 +327,14 @@ where
             } => {
                 self.check_expression(CheckType(self.boolean_type(), expression.into()), condition);
 
+                // A branch that diverges adopts the type of the other
+                // (see `never_type`); if both do, so does the `if`.
+                if self.diverges(if_true) && self.diverges(if_false) {
+                    self.check_expression(Synthesize, if_true);
+                    self.check_expression(Synthesize, if_false);
+                    return self.never_type(expression, mode);
+                }
+
                 let ty = self.type_or_infer_variable(mode);
                 self.check_expression(
                     CheckType(ty, HirLocation::AfterExpression(expression)),
@@ -178,14 +348,173 @@ where
                 ty
             }
 
+            hir::ExpressionData::While { condition, body } => {
+                self.check_expression(CheckType(self.boolean_type(), expression.into()), condition);
+
+                // The value of the body, if any, is discarded. A
+                // `break` within a `while` cannot carry a value.
+                self.enclosing_loops.push(EnclosingLoop {
+                    break_ty: self.unit_type(),
+                    has_break: false,
+                });
+                self.check_expression(Synthesize, body);
+                self.enclosing_loops.pop();
+
+                self.unit_type()
+            }
+
+            hir::ExpressionData::For {
+                variable,
+                start,
+                end,
+                body,
+            } => {
+                // The loop variable takes on each value in `start..end`.
+                let variable_ty = self.request_variable_ty(variable);
+                self.check_expression(CheckType(variable_ty, expression.into()), start);
+                self.check_expression(CheckType(variable_ty, expression.into()), end);
+
+                self.enclosing_loops.push(EnclosingLoop {
+                    break_ty: self.unit_type(),
+                    has_break: false,
+                });
+                self.check_expression(Synthesize, body);
+                self.enclosing_loops.pop();
+
+                let unit_ty = self.unit_type();
+                self.with_base_data(
+                    expression,
+                    expression,
+                    variable_ty.base,
+                    move |this, base_data| this.check_range_bounds(start, base_data, unit_ty),
+                )
+            }
+
+            hir::ExpressionData::Range {
+                start,
+                end,
+                inclusive: _,
+            } => {
+                // The endpoints (if any) must agree on an integer type,
+                // which becomes the element type of the range. For `..`
+                // there is nothing to infer it from, so we use `int`, the
+                // default for integer literals.
+                let first_endpoint = start.or(end);
+                let element_ty = match first_endpoint {
+                    Some(_) => self.new_variable(),
+                    None => self.int_type(),
+                };
+                for endpoint in start.into_iter().chain(end) {
+                    self.check_expression(CheckType(element_ty, expression.into()), endpoint);
+                }
+
+                let range_ty = self.range_type(element_ty);
+                match first_endpoint {
+                    Some(first_endpoint) => self.with_base_data(
+                        expression,
+                        expression,
+                        element_ty.base,
+                        move |this, base_data| {
+                            this.check_range_bounds(first_endpoint, base_data, range_ty)
+                        },
+                    ),
+                    None => range_ty,
+                }
+            }
+
+            hir::ExpressionData::Match { scrutinee, arms } => {
+                let scrutinee_ty = self.check_expression(Synthesize, scrutinee);
+
+                // FIXME -- we do not check that the arms are exhaustive.
+                let ty = self.type_or_infer_variable(mode);
+                if arms.is_empty() {
+                    self.equate(expression, expression, ty, self.unit_type());
+                }
+
+                let hir = &self.hir.clone();
+                for arm_data in arms.iter_data(hir) {
+                    self.check_pattern(expression, scrutinee_ty, arm_data.pattern);
+                    self.check_expression(
+                        CheckType(ty, HirLocation::AfterExpression(expression)),
+                        arm_data.body,
+                    );
+                }
+
+                ty
+            }
+
+            hir::ExpressionData::Loop { body } => {
+                let break_ty = self.type_or_infer_variable(mode);
+                self.enclosing_loops.push(EnclosingLoop {
+                    break_ty,
+                    has_break: false,
+                });
+                self.check_expression(Synthesize, body);
+                let enclosing_loop = self.enclosing_loops.pop().unwrap();
+
+                // A loop that is never broken out of never produces a
+                // value, so its type is never (see `never_type`).
+                if !enclosing_loop.has_break {
+                    self.diverging_variables.push((expression, break_ty));
+                }
+
+                break_ty
+            }
+
+            hir::ExpressionData::Break { value } => {
+                let break_ty = self.enclosing_loops.last_mut().map(|enclosing_loop| {
+                    enclosing_loop.has_break = true;
+                    enclosing_loop.break_ty
+                });
+
+                match break_ty {
+                    Some(break_ty) => {
+                        self.check_expression(CheckType(break_ty, expression.into()), value);
+                    }
+
+                    // The parser rejects a `break` outside of a loop.
+                    None => {
+                        self.check_expression(Synthesize, value);
+                    }
+                }
+
+                self.never_type(expression, mode)
+            }
+
+            hir::ExpressionData::Return { value } => {
+                // Mismatches are reported against the `return` as a
+                // whole, rather than just its value.
+                // (Every body, even that of a `const`, has a declared
+                // return type, which `check_fn_body` sets up first.)
+                let value_ty = self.check_expression(Synthesize, value);
+                let return_ty = self.return_ty.unwrap();
+                self.equate(expression, expression, value_ty, return_ty);
+
+                self.never_type(expression, mode)
+            }
+
             hir::ExpressionData::Literal { data } => match data.kind {
                 hir::LiteralKind::String => self.string_type(),
-                hir::LiteralKind::UnsignedInteger => self.uint_type(),
+                hir::LiteralKind::Float => self.float_type(),
+                hir::LiteralKind::Char => self.char_type(),
+                hir::LiteralKind::Boolean => self.boolean_type(),
+                // The parser has already rejected literals that are
+                // too large to decode; `check_integer_literal` checks
+                // the range once the type is known.
+                hir::LiteralKind::UnsignedInteger => self.integer_literal_type(expression),
             },
 
             hir::ExpressionData::Unit {} => self.unit_type(),
 
-            hir::ExpressionData::Error { error: _ } => self.error_type(),
+            hir::ExpressionData::Error { error } => {
+                // Unresolved names are left for us to report; other
+                // errors were reported when the HIR was constructed.
+                if let hir::ErrorData::UnknownIdentifier { text } = self.hir[error] {
+                    let name = text.untern(&self.db).to_string();
+                    self.record_error(ErrorKind::UnresolvedName { name }, expression);
+                }
+                self.error_type()
+            }
 
             hir::ExpressionData::Binary {
                 operator,
@@ -196,6 +525,47 @@ where
             hir::ExpressionData::Unary { operator, value } => {
                 self.check_unary(expression, operator, value)
             }
+
+            // Anything can be shared, but only a place that could be
+            // assigned to can be borrowed.
+            hir::ExpressionData::Reference { perm, place } => {
+                if let PermKind::Borrow = perm {
+                    self.check_mutable_place(place, PlaceExpressionKind::Borrow);
+                }
+                let place_ty = self.check_place(place);
+                self.apply_user_perm(expression, expression, perm, place_ty)
+            }
+        }
+    }
+
+    /// Warns about each `let`-bound variable that is never referenced
+    /// (unless its name starts with `_`).
+    fn check_unused_variables(&mut self) {
+        let hir = self.hir.clone();
+        let used: FxIndexSet<hir::Variable> = hir
+            .tables
+            .places
+            .iter()
+            .filter_map(|place_data| match *place_data {
+                hir::PlaceData::Variable(variable) => Some(variable),
+                _ => None,
+            })
+            .collect();
+
+        for expression_data in hir.tables.expressions.iter() {
+            let variable = match *expression_data {
+                hir::ExpressionData::Let { variable, .. } => variable,
+                _ => continue,
+            };
+            if used.contains(&variable) {
+                continue;
+            }
+
+            let name = hir[hir[variable].name].text.untern(&self.db);
+            if !name.starts_with('_') {
+                let name = name.to_string();
+                self.record_warning(ErrorKind::UnusedVariable { name }, variable);
+            }
         }
     }
 
@@ -206,6 +576,22 @@ where
         self.record_place_ty(place, ty)
     }
 
+    /// Reports an error if `place` (used by an expression of the given
+    /// kind) is not something that can be assigned to or borrowed:
+    /// only local variables and fields (including tuple fields)
+    /// qualify, not (for example) literals or the results of calls.
+    fn check_mutable_place(&mut self, place: hir::Place, expression_kind: PlaceExpressionKind) {
+        match self.hir[place] {
+            hir::PlaceData::Variable(_)
+            | hir::PlaceData::Field { .. }
+            | hir::PlaceData::TupleField { .. } => {}
+
+            hir::PlaceData::Entity(_) | hir::PlaceData::Temporary(_) => {
+                self.record_error(ErrorKind::NotAPlace { expression_kind }, place);
+            }
+        }
+    }
+
     /// Helper for `check_place`.
     fn compute_place_ty(&mut self, place: hir::Place) -> Ty<F> {
         let place_data = self.hir[place];
@@ -214,7 +600,7 @@ where
 
             hir::PlaceData::Entity(entity) => {
                 if !entity.untern(self).is_value() {
-                    self.record_error("cannot access as a value", place);
+                    self.record_error(ErrorKind::NotAValue, place);
                     return self.error_type();
                 }
 
@@ -242,7 +628,9 @@ where
                                 }
 
                                 None => {
-                                    this.record_error("field not found", name);
+                                    let error =
+                                        this.unresolved_member(def_id, MemberKind::Field, text);
+                                    this.record_error(error, name);
                                     this.error_type()
                                 }
                             }
@@ -250,7 +638,49 @@ where
 
                         BaseKind::Placeholder(_placeholder) => {
                             // Cannot presently access fields from generic types.
-                            this.record_error("cannot access fields from generic types(yet)", name);
+                            this.record_error(
+                                ErrorKind::UnsupportedOnGeneric(GenericOperation::FieldAccess),
+                                name,
+                            );
+                            this.error_type()
+                        }
+
+                        BaseKind::Error => this.error_type(),
+                    }
+                })
+            }
+
+            hir::PlaceData::TupleField {
+                owner,
+                index,
+                index_name,
+            } => {
+                let owner_ty = self.check_place(owner);
+                self.with_base_data(place, place, owner_ty.base, move |this, base_data| {
+                    let BaseData { kind, generics } = base_data;
+                    match kind {
+                        BaseKind::Named(def_id) => match def_id.untern(this) {
+                            EntityData::LangItem(LangItem::Tuple(arity)) => {
+                                if index >= arity {
+                                    this.record_error(
+                                        ErrorKind::TupleIndexOutOfRange { index, arity },
+                                        index_name,
+                                    );
+                                    return this.error_type();
+                                }
+
+                                let element_ty = generics[BoundVar::new(index)].assert_ty();
+                                this.apply_owner_perm(place, place, owner_ty.perm, element_ty)
+                            }
+
+                            _ => {
+                                this.record_error(ErrorKind::NotATuple, place);
+                                this.error_type()
+                            }
+                        },
+
+                        BaseKind::Placeholder(_placeholder) => {
+                            this.record_error(ErrorKind::NotATuple, place);
                             this.error_type()
                         }
 
@@ -292,8 +722,13 @@ where
                     EntityData::ItemName {
                         kind: ItemKind::Function,
                         ..
+                    }
+                    | EntityData::MemberName {
+                        kind: MemberKind::Variant,
+                        ..
                     } => {
-                        // You can call this
+                        // You can call this; calling an enum variant
+                        // constructs a value of the enum.
                     }
 
                     EntityData::LangItem(LangItem::Debug) => {
@@ -302,7 +737,7 @@ where
                     }
 
                     _ => {
-                        self.record_error("cannot call value of this type", expression);
+                        self.record_error(ErrorKind::NotCallable, expression);
                         return self.check_arguments_in_case_of_error(arguments, 0);
                     }
                 }
@@ -327,7 +762,10 @@ where
 
             BaseKind::Placeholder(_placeholder) => {
                 // Cannot presently invoke generic types.
-                self.record_error("cannot call a generic type (yet)", expression);
+                self.record_error(
+                    ErrorKind::UnsupportedOnGeneric(GenericOperation::Call),
+                    expression,
+                );
                 return self.check_arguments_in_case_of_error(arguments, 0);
             }
 
@@ -376,8 +814,9 @@ where
                 let method_entity = match self.db.member_entity(def_id, MemberKind::Method, text) {
                     Some(def_id) => def_id,
                     None => {
-                        self.record_error("method not found", expression);
-                        return self.error_type();
+                        let error = self.unresolved_member(def_id, MemberKind::Method, text);
+                        self.record_error(error, method_name);
+                        return self.check_arguments_in_case_of_error(arguments, 1);
                     }
                 };
 
@@ -406,11 +845,15 @@ where
 
             BaseKind::Placeholder(_placeholder) => {
                 // Cannot presently invoke methods on generic types.
-                self.record_error("cannot invoke methods on generic types(yet)", method_name);
-                return self.check_arguments_in_case_of_error(arguments, 0);
+                self.record_error(
+                    ErrorKind::UnsupportedOnGeneric(GenericOperation::MethodCall),
+                    method_name,
+                );
+                return self.check_arguments_in_case_of_error(arguments, 1);
             }
 
-            BaseKind::Error => self.error_type(),
+            // The receiver (argument 0) was already checked by our caller.
+            BaseKind::Error => self.check_arguments_in_case_of_error(arguments, 1),
         }
     }
 
@@ -433,7 +876,7 @@ where
             arguments.debug_with(self),
         );
         if inputs.len() != arguments.len() {
-            self.record_error("mismatched argument count", cause);
+            self.record_error(ErrorKind::ArityMismatch, cause);
             return self.check_arguments_in_case_of_error(arguments, skip);
         }
 
@@ -462,6 +905,50 @@ where
         self.error_type()
     }
 
+    /// Checks the field values of an aggregate whose type is not
+    /// known, so that they still get types recorded.
+    fn check_fields_in_case_of_error(
+        &mut self,
+        fields: hir::List<hir::IdentifiedExpression>,
+    ) -> Ty<F> {
+        let hir = &self.hir.clone();
+        for field_data in fields.iter_data(hir) {
+            self.check_expression(
+                CheckType(self.error_type(), HirLocation::Error),
+                field_data.expression,
+            );
+        }
+        self.error_type()
+    }
+
+    /// The error for a reference to the member `member` (of the given
+    /// kind), which the type `owner` does not have.
+    fn unresolved_member(
+        &self,
+        owner: Entity,
+        kind: MemberKind,
+        member: GlobalIdentifier,
+    ) -> ErrorKind {
+        ErrorKind::UnresolvedMember {
+            member: member.untern(&self.db).to_string(),
+            owner: owner.pretty_print(self.db),
+            kind,
+        }
+    }
+
+    /// The error for a reference to `field`, which is not declared
+    /// by the struct `owner`.
+    fn unknown_field(&self, owner: Entity, field: GlobalIdentifier) -> ErrorKind {
+        let owner = match owner.untern(self) {
+            EntityData::ItemName { id, .. } => id.untern(&self.db).to_string(),
+            data => panic!("unknown field on non-item {:?}", data),
+        };
+        ErrorKind::UnknownField {
+            field: field.untern(&self.db).to_string(),
+            owner,
+        }
+    }
+
     fn check_aggregate(
         &mut self,
         expression: hir::Expression,
@@ -486,24 +973,18 @@ where
                     generics.is_empty(),
                     "generics should be empty, no need to propagate error"
                 );
-                let error_type = self.error_type();
                 let hir = &self.hir.clone();
-                for field in fields.iter(hir) {
-                    let field_data = self.hir[field];
+                for field_data in fields.iter_data(hir) {
                     self.record_entity(field_data.identifier, entity);
-                    self.check_expression(
-                        CheckType(error_type, HirLocation::Error),
-                        field_data.expression,
-                    );
                 }
-                return error_type;
+                return self.check_fields_in_case_of_error(fields);
             }
 
             // Something like `def foo() { .. } foo { .. }` is just not legal.
             _ => {
-                self.record_error("disallowed expression type", expression);
+                self.record_error(ErrorKind::NotConstructible, expression);
                 self.propagate_error(expression, &generics);
-                return self.error_type();
+                return self.check_fields_in_case_of_error(fields);
             }
         };
 
@@ -513,11 +994,11 @@ where
                 .iter()
                 .filter(|f| match f.kind {
                     MemberKind::Field => true,
-                    MemberKind::Method => false,
+                    MemberKind::Method | MemberKind::MethodSignature | MemberKind::Variant => false,
                 })
                 .map(|m| m.entity)
                 .collect(),
-            Err(err) => return Ty::error_sentinel(self, err),
+            Err(ErrorReported(_)) => return self.check_fields_in_case_of_error(fields),
         };
 
         // Find the entity for each of the field names that the user gave us.
@@ -535,7 +1016,8 @@ where
                 }
 
                 None => {
-                    self.record_error("unknown field", field_data.identifier);
+                    let error = self.unknown_field(entity, field_name);
+                    self.record_error(error, field_data.identifier);
                     self.error_type()
                 }
             };
@@ -548,8 +1030,12 @@ where
         }
 
         // If we are missing any members, that's an error.
-        for _missing_member in missing_members {
-            self.record_error("missing member", expression);
+        for missing_member in missing_members {
+            let name = match missing_member.untern(self) {
+                EntityData::MemberName { id, .. } => id.untern(&self.db).to_string(),
+                data => panic!("missing member is not a member {:?}", data),
+            };
+            self.record_error(ErrorKind::MissingMember { name }, expression);
 
             // Propagate this error to the generics, since they may be
             // underconstrained as a result.
@@ -569,12 +1055,63 @@ where
         left: hir::Expression,
         right: hir::Expression,
     ) -> Ty<F> {
+        match operator {
+            hir::BinaryOperator::And | hir::BinaryOperator::Or => {
+                // The logical operators only apply to booleans, so we
+                // can check both sides against `bool` directly. (Both
+                // sides are always type-checked, even though at
+                // runtime the right-hand side may not be evaluated.)
+                let boolean_type = self.boolean_type();
+                self.check_expression(CheckType(boolean_type, expression.into()), left);
+                self.check_expression(CheckType(boolean_type, expression.into()), right);
+                return boolean_type;
+            }
+
+            _ => {}
+        }
+
+        let left_ty = self.check_expression(Synthesize, left);
+        let right_ty = self.check_expression(Synthesize, right);
+        self.compute_binary_ty(expression, operator, left_ty, right_ty)
+    }
+
+    /// Computes the result type of applying the (non-logical) binary
+    /// operator `operator` to values of type `left_ty` and `right_ty`.
+    /// Shared between binary expressions and compound assignments.
+    fn compute_binary_ty(
+        &mut self,
+        expression: hir::Expression,
+        operator: hir::BinaryOperator,
+        left_ty: Ty<F>,
+        right_ty: Ty<F>,
+    ) -> Ty<F> {
+        // The operands must have the same type, so an operand whose
+        // type is not yet known (e.g., an integer literal) can take it
+        // from an integer on the other side, as in `x + 1`. (Only the
+        // base types are equated, not the permissions.)
+        let left_data = self.unify.shallow_resolve_data(left_ty.base).ok();
+        let right_data = self.unify.shallow_resolve_data(right_ty.base).ok();
+        let infer_operand = match (&left_data, &right_data) {
+            (None, None) => true,
+            (Some(data), None) | (None, Some(data)) => self.is_integer(data),
+            (Some(_), Some(_)) => false,
+        };
+        if infer_operand {
+            self.equate(
+                expression,
+                expression,
+                left_ty,
+                Ty {
+                    base: right_ty.base,
+                    ..left_ty
+                },
+            );
+        }
+
         // For (most) binary operators, we need to know the type of
         // left + right before we can say anything about the result
         // type. So use `with_base_data` to get a callback once that is
         // known.
-        let left_ty = self.check_expression(Synthesize, left);
-        let right_ty = self.check_expression(Synthesize, right);
         let result_ty = self.with_base_data(
             expression,
             expression,
@@ -597,8 +1134,13 @@ where
         );
 
         match operator {
-            hir::BinaryOperator::Equals | hir::BinaryOperator::NotEquals => {
-                // For the `==` and `!=` operators, we know the result
+            hir::BinaryOperator::Equals
+            | hir::BinaryOperator::NotEquals
+            | hir::BinaryOperator::LessThan
+            | hir::BinaryOperator::LessThanOrEqual
+            | hir::BinaryOperator::GreaterThan
+            | hir::BinaryOperator::GreaterThanOrEqual => {
+                // For comparison operators like `==`, we know the result
                 // will be boolean, so even if `result_ty` is an
                 // inference variable, we can unify it *now* rather
                 // than wait until the input types are known.
@@ -610,7 +1152,10 @@ where
             hir::BinaryOperator::Add
             | hir::BinaryOperator::Subtract
             | hir::BinaryOperator::Multiply
-            | hir::BinaryOperator::Divide => result_ty,
+            | hir::BinaryOperator::Divide
+            | hir::BinaryOperator::Remainder => result_ty,
+
+            hir::BinaryOperator::And | hir::BinaryOperator::Or => unreachable!(),
         }
     }
 
@@ -625,26 +1170,29 @@ where
     ) -> Ty<F> {
         let int_type = self.int_type();
         let uint_type = self.uint_type();
+        let float_type = self.float_type();
         let boolean_type = self.boolean_type();
 
         match operator {
             hir::BinaryOperator::Add
             | hir::BinaryOperator::Subtract
             | hir::BinaryOperator::Multiply
-            | hir::BinaryOperator::Divide => match (&left_base_data.kind, &right_base_data.kind) {
+            | hir::BinaryOperator::Divide
+            | hir::BinaryOperator::Remainder => match (&left_base_data.kind, &right_base_data.kind) {
                 (BaseKind::Named(entity), BaseKind::Named(right_entity))
                     if entity == right_entity =>
                 {
                     match entity.untern(self) {
                         EntityData::LangItem(LangItem::Int) => int_type,
                         EntityData::LangItem(LangItem::Uint) => uint_type,
+                        EntityData::LangItem(LangItem::Float) => float_type,
                         EntityData::Error(_) => self.error_type(),
                         _ => {
                             self.record_error(
-                                format!(
-                                    "type {:?} does not support this operation",
-                                    self.error_type()
-                                ),
+                                ErrorKind::UnsupportedOperand {
+                                    op: Operator::Binary(operator),
+                                    ty: left_base_data.pretty_print(self.db),
+                                },
                                 expression,
                             );
                             self.error_type()
@@ -656,27 +1204,30 @@ where
 
                 (BaseKind::Named(_), _) | (BaseKind::Placeholder(_), _) => {
                     self.record_error(
-                        format!(
-                            "mismatched types ({} vs {})",
-                            left_base_data.pretty_print(self.db),
-                            right_base_data.pretty_print(self.db)
-                        ),
+                        ErrorKind::Mismatch {
+                            expected: right_base_data.pretty_print(self.db),
+                            actual: left_base_data.pretty_print(self.db),
+                        },
                         expression,
                     );
                     self.error_type()
                 }
             },
 
-            hir::BinaryOperator::Equals | hir::BinaryOperator::NotEquals => {
+            hir::BinaryOperator::Equals
+            | hir::BinaryOperator::NotEquals
+            | hir::BinaryOperator::LessThan
+            | hir::BinaryOperator::LessThanOrEqual
+            | hir::BinaryOperator::GreaterThan
+            | hir::BinaryOperator::GreaterThanOrEqual => {
                 // Unclear what rule will eventually be... for now, require
                 // that the two types are the same?
                 if left_base_data != right_base_data {
                     self.record_error(
-                        format!(
-                            "mismatched types ({} vs {})",
-                            left_base_data.pretty_print(self.db),
-                            right_base_data.pretty_print(self.db)
-                        ),
+                        ErrorKind::Mismatch {
+                            expected: right_base_data.pretty_print(self.db),
+                            actual: left_base_data.pretty_print(self.db),
+                        },
                         expression,
                     );
                 }
@@ -684,6 +1235,227 @@ where
                 // Either way, yields a boolean
                 boolean_type
             }
+
+            hir::BinaryOperator::And | hir::BinaryOperator::Or => unreachable!(),
+        }
+    }
+
+    /// Checks the compound assignment `expression` once the type of
+    /// the place being assigned to is known.
+    fn check_compound_assignment(
+        &mut self,
+        expression: hir::Expression,
+        operator: hir::BinaryOperator,
+        place_ty: Ty<F>,
+        value_ty: Ty<F>,
+        place_base_data: BaseData<F>,
+    ) {
+        let is_numeric = match place_base_data.kind {
+            BaseKind::Named(entity) => match entity.untern(self) {
+                EntityData::LangItem(LangItem::Int)
+                | EntityData::LangItem(LangItem::Uint)
+                | EntityData::LangItem(LangItem::Float)
+                | EntityData::Error(_) => true,
+                _ => false,
+            },
+            BaseKind::Error => true,
+            BaseKind::Placeholder(_) => false,
+        };
+
+        if !is_numeric {
+            self.record_error(
+                ErrorKind::NonNumericCompoundAssignment {
+                    ty: place_base_data.pretty_print(self.db),
+                },
+                expression,
+            );
+            return;
+        }
+
+        let result_ty = self.compute_binary_ty(expression, operator, place_ty, value_ty);
+        self.equate(expression, expression, result_ty, place_ty);
+    }
+
+    /// Checks that `pattern` (part of the `match` expression `owner`)
+    /// can match values of type `ty`, giving types to any variables
+    /// that it binds.
+    fn check_pattern(&mut self, owner: hir::Expression, ty: Ty<F>, pattern: hir::Pattern) {
+        match self.hir[pattern] {
+            hir::PatternData::Wildcard => {}
+
+            hir::PatternData::Binding(variable) => {
+                let variable_ty = self.request_variable_ty(variable);
+                self.equate(pattern, pattern, variable_ty, ty);
+            }
+
+            hir::PatternData::Literal(literal) => {
+                self.check_expression(CheckType(ty, owner.into()), literal);
+            }
+
+            hir::PatternData::Struct { entity, fields } => {
+                let generics = self.record_entity_and_get_generics(pattern, entity);
+
+                match entity.untern(self) {
+                    EntityData::ItemName {
+                        kind: ItemKind::Struct,
+                        ..
+                    } => {}
+
+                    EntityData::Error(_) => {
+                        let hir = &self.hir.clone();
+                        for field_data in fields.iter_data(hir) {
+                            self.record_entity(field_data.identifier, entity);
+                            self.check_pattern(owner, self.error_type(), field_data.pattern);
+                        }
+                        return;
+                    }
+
+                    _ => {
+                        self.record_error(ErrorKind::NotAStruct, pattern);
+                        self.propagate_error(pattern, &generics);
+                        return;
+                    }
+                }
+
+                let entity_ty = self.db.ty(entity).into_value();
+                let struct_ty = self.substitute(owner, &generics, entity_ty);
+                self.equate(pattern, owner, struct_ty, ty);
+
+                let hir = &self.hir.clone();
+                for field_data in fields.iter_data(hir) {
+                    let field_name = hir[field_data.identifier].text;
+                    let field_ty =
+                        match self.db.member_entity(entity, MemberKind::Field, field_name) {
+                            Some(field_entity) => {
+                                self.record_entity(field_data.identifier, field_entity);
+                                let field_decl_ty = self.db.ty(field_entity).into_value();
+                                let field_ty = self.substitute(owner, &generics, field_decl_ty);
+                                self.apply_owner_perm(pattern, owner, ty.perm, field_ty)
+                            }
+
+                            None => {
+                                let error = self.unknown_field(entity, field_name);
+                                self.record_error(error, field_data.identifier);
+                                self.error_type()
+                            }
+                        };
+
+                    self.check_pattern(owner, field_ty, field_data.pattern);
+                }
+            }
+        }
+    }
+
+    /// The type of the integer literal `expression`: an inference
+    /// variable, so that (e.g.) `x + 1` works whether `x` is an `int`
+    /// or a `uint`. It must turn out to be one of those two; if
+    /// nothing constrains it, it defaults to `int` (see
+    /// `default_integer_literals`).
+    fn integer_literal_type(&mut self, expression: hir::Expression) -> Ty<F> {
+        let ty = self.new_variable();
+        self.integer_literals.push((expression, ty));
+        self.with_base_data_equate(
+            ty.base,
+            move |this, base_data| this.check_integer_literal(expression, base_data),
+            |_, ()| (),
+        );
+        ty
+    }
+
+    fn check_integer_literal(&mut self, expression: hir::Expression, base_data: BaseData<F>) {
+        let is_error = match base_data.kind {
+            BaseKind::Named(entity) => match entity.untern(self) {
+                EntityData::Error(_) => true,
+                _ => false,
+            },
+            BaseKind::Error => true,
+            BaseKind::Placeholder(_) => false,
+        };
+
+        if !is_error && !self.is_integer(&base_data) {
+            self.record_error(
+                ErrorKind::Mismatch {
+                    expected: base_data.pretty_print(self.db),
+                    actual: "int".to_string(),
+                },
+                expression,
+            );
+            return;
+        }
+
+        // `uint` and `int` are 32 bits wide (cf. `u32` and `i32` in
+        // the generated Rust); the parser has already stored the
+        // literal in decimal. A negated `int` literal may be as large
+        // as the magnitude of `i32::MIN`.
+        let max = match base_data.kind {
+            BaseKind::Named(entity) => match entity.untern(self) {
+                EntityData::LangItem(LangItem::Uint) => u128::from(std::u32::MAX),
+                EntityData::LangItem(LangItem::Int) => {
+                    if self.negated_literals.contains(&expression) {
+                        std::i32::MAX as u128 + 1
+                    } else {
+                        std::i32::MAX as u128
+                    }
+                }
+                _ => return,
+            },
+            BaseKind::Error | BaseKind::Placeholder(_) => return,
+        };
+        let in_range = match self.hir[expression] {
+            hir::ExpressionData::Literal { data } => data
+                .value
+                .untern(&self.db)
+                .parse::<u128>()
+                .map_or(false, |value| value <= max),
+            _ => true,
+        };
+        if !in_range {
+            self.record_error(
+                ErrorKind::IntegerOutOfRange {
+                    ty: base_data.pretty_print(self.db),
+                },
+                expression,
+            );
+        }
+    }
+
+    /// True if `base_data` is `int` or `uint`.
+    fn is_integer(&self, base_data: &BaseData<F>) -> bool {
+        match base_data.kind {
+            BaseKind::Named(entity) => match entity.untern(self) {
+                EntityData::LangItem(LangItem::Uint) | EntityData::LangItem(LangItem::Int) => true,
+                _ => false,
+            },
+            BaseKind::Error | BaseKind::Placeholder(_) => false,
+        }
+    }
+
+    /// Checks that the bounds of a range (the first of which is
+    /// `start`) are integers, returning `ok_ty` if so.
+    fn check_range_bounds(
+        &mut self,
+        start: hir::Expression,
+        base_data: BaseData<F>,
+        ok_ty: Ty<F>,
+    ) -> Ty<F> {
+        match &base_data.kind {
+            BaseKind::Named(entity) => match entity.untern(self) {
+                EntityData::LangItem(LangItem::Uint) | EntityData::LangItem(LangItem::Int) => ok_ty,
+
+                EntityData::Error(_) => self.error_type(),
+
+                _ => {
+                    self.record_error(ErrorKind::RangeBoundsNotInteger, start);
+                    self.error_type()
+                }
+            },
+
+            BaseKind::Error => self.error_type(),
+
+            BaseKind::Placeholder(_) => {
+                self.record_error(ErrorKind::RangeBoundsNotInteger, start);
+                self.error_type()
+            }
         }
     }
 
@@ -693,6 +1465,12 @@ where
         operator: hir::UnaryOperator,
         value: hir::Expression,
     ) -> Ty<F> {
+        if let hir::UnaryOperator::Negate = operator {
+            if let hir::ExpressionData::Literal { .. } = self.hir[value] {
+                self.negated_literals.insert(value);
+            }
+        }
+
         // We may want to add overloading later. So make sure we know
         // the type of the expression before we determine the type of
         // the output.
@@ -721,7 +1499,43 @@ where
                     EntityData::Error(_) => self.error_type(),
 
                     _ => {
-                        self.record_error("incompatible type for 'not' operator", expression);
+                        self.record_error(
+                            ErrorKind::UnsupportedOperand {
+                                op: Operator::Unary(operator),
+                                ty: value_base_data.pretty_print(self.db),
+                            },
+                            expression,
+                        );
+                        self.error_type()
+                    }
+                },
+
+                BaseKind::Error => self.error_type(),
+
+                BaseKind::Placeholder(_) => {
+                    self.record_error(
+                        ErrorKind::UnsupportedOnGeneric(GenericOperation::Operator),
+                        expression,
+                    );
+                    self.error_type()
+                }
+            },
+
+            hir::UnaryOperator::Negate => match &value_base_data.kind {
+                BaseKind::Named(entity) => match entity.untern(self) {
+                    EntityData::LangItem(LangItem::Int) => self.int_type(),
+                    EntityData::LangItem(LangItem::Float) => self.float_type(),
+
+                    EntityData::Error(_) => self.error_type(),
+
+                    _ => {
+                        self.record_error(
+                            ErrorKind::UnsupportedOperand {
+                                op: Operator::Unary(operator),
+                                ty: value_base_data.pretty_print(self.db),
+                            },
+                            expression,
+                        );
                         self.error_type()
                     }
                 },
@@ -729,7 +1543,10 @@ where
                 BaseKind::Error => self.error_type(),
 
                 BaseKind::Placeholder(_) => {
-                    self.record_error("unknown expression for operator", expression);
+                    self.record_error(
+                        ErrorKind::UnsupportedOnGeneric(GenericOperation::Operator),
+                        expression,
+                    );
                     self.error_type()
                 }
             },
@@ -0,0 +1,595 @@
+//! Queries that answer questions an editor asks about a position in
+//! a file, built atop `hover_targets` and the type-check results.
+
+use crate::TypeCheckDatabase;
+use lark_collections::FxIndexMap;
+use lark_entity::{Entity, EntityData, ItemKind, LangItem, MemberKind};
+use lark_hir as hir;
+use lark_intern::{Intern, Untern};
+use lark_parser::{HoverTargetKind, LexToken};
+use lark_pretty_print::{pretty_print_declared_ty, PrettyPrint};
+use lark_span::{ByteIndex, FileName, Span};
+use lark_ty::{BaseKind, BoundVar, BoundVarOr, GenericKind};
+use std::sync::Arc;
+
+/// Something that could be typed at the cursor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+
+    /// The entity the completion refers to; `None` for locals.
+    pub entity: Option<Entity>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompletionKind {
+    Field,
+    Method,
+    Variable,
+    Item(ItemKind),
+}
+
+/// The classification of a token, for semantic highlighting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub span: Span<FileName>,
+    pub kind: SemanticTokenKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SemanticTokenKind {
+    Keyword,
+
+    /// A name that refers to a type, like `Point` or `uint`.
+    Type,
+
+    /// A name that refers to a fn or method.
+    Function,
+
+    /// A local variable or parameter.
+    Local,
+
+    /// A name that refers to a `const` item.
+    Constant,
+
+    Field,
+    Literal,
+    Operator,
+}
+
+/// What to show when hovering over some part of a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HoverInfo {
+    /// The type of the expression under the cursor or, for a
+    /// reference to an item (or the item itself), its declaration.
+    pub text: String,
+
+    /// The doc comment of the entity under the cursor, if any.
+    pub doc: Option<String>,
+}
+
+crate fn definition_at(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+    index: ByteIndex,
+) -> Option<(Entity, Span<FileName>)> {
+    // Targets come outermost-to-innermost; we want the innermost
+    // reference. The entities themselves (i.e., their headers and
+    // bodies as a whole) are not references, so we skip them --
+    // otherwise whitespace in a fn body would resolve to the fn.
+    db.hover_targets(file, index)
+        .iter()
+        .rev()
+        .filter_map(|target| match target.kind {
+            HoverTargetKind::Entity(_) => None,
+            HoverTargetKind::MetaIndex(fn_entity, mi) => referenced_entity(db, fn_entity, mi),
+        })
+        .filter(|entity| entity.input_file(db).is_some())
+        .map(|entity| (entity, db.entity_span(entity)))
+        .next()
+}
+
+/// If `mi` (in the fn body of `fn_entity`) refers to some entity,
+/// returns it.
+fn referenced_entity(
+    db: &impl TypeCheckDatabase,
+    fn_entity: Entity,
+    mi: hir::MetaIndex,
+) -> Option<Entity> {
+    let results = db.full_type_check(fn_entity).into_value();
+    if let Some(&entity) = results.entities.get(&mi) {
+        return Some(entity);
+    }
+
+    match mi {
+        hir::MetaIndex::Place(place) => match db.fn_body(fn_entity).into_value().tables[place] {
+            hir::PlaceData::Entity(entity) => Some(entity),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+crate fn hover_at(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+    index: ByteIndex,
+) -> Option<HoverInfo> {
+    db.hover_targets(file, index)
+        .iter()
+        .rev()
+        .filter_map(|target| match target.kind {
+            HoverTargetKind::Entity(entity) => hover_entity(db, entity),
+
+            HoverTargetKind::MetaIndex(fn_entity, mi) => {
+                if let Some(info) =
+                    referenced_entity(db, fn_entity, mi).and_then(|entity| hover_entity(db, entity))
+                {
+                    return Some(info);
+                }
+
+                let results = db.full_type_check(fn_entity).into_value();
+                results.try_ty(mi).map(|ty| HoverInfo {
+                    text: ty.pretty_print(db),
+                    doc: None,
+                })
+            }
+        })
+        .next()
+}
+
+fn hover_entity(db: &impl TypeCheckDatabase, entity: Entity) -> Option<HoverInfo> {
+    let text = match entity.untern(db) {
+        EntityData::InputFile { .. } | EntityData::LangItem(_) | EntityData::Error(_) => {
+            return None;
+        }
+        EntityData::ItemName {
+            kind: ItemKind::TypeAlias,
+            ..
+        } => format!(
+            "type {} = {}",
+            entity.pretty_print(db),
+            db.ty(entity).value.pretty_print(db),
+        ),
+        EntityData::ItemName {
+            kind: ItemKind::Const,
+            ..
+        } => format!(
+            "const {}: {}",
+            entity.pretty_print(db),
+            db.ty(entity).value.pretty_print(db),
+        ),
+        EntityData::ItemName { kind, .. } => {
+            format!("{} {}", kind.keyword(), entity.pretty_print(db))
+        }
+        EntityData::MemberName { .. } => entity.pretty_print(db),
+    };
+
+    let doc = db
+        .doc_of_entity(entity)
+        .map(|doc| doc.untern(db).to_string());
+
+    Some(HoverInfo { text, doc })
+}
+
+crate fn signature_string(db: &impl TypeCheckDatabase, entity: Entity) -> Option<String> {
+    let (keyword, id) = match entity.untern(db) {
+        EntityData::ItemName {
+            kind: ItemKind::Function,
+            id,
+            ..
+        } => ("def ", id),
+        EntityData::MemberName {
+            kind: MemberKind::Method,
+            id,
+            ..
+        } => ("", id),
+        _ => return None,
+    };
+
+    let signature = db.signature(entity).into_value().ok()?;
+    let fn_body = db.fn_body(entity).into_value();
+
+    // Bound variables are numbered across the generics of the
+    // enclosing items (outermost first) and then those of `entity`.
+    let mut generic_names = vec![];
+    let mut own_generics = 0;
+    let mut item = Some(entity);
+    while let Some(def_id) = item {
+        let declarations = db.generic_declarations(def_id).into_value().ok()?;
+        let names: Vec<_> = declarations
+            .declarations
+            .iter()
+            .map(|declaration| match declaration {
+                GenericKind::Ty(declaration) => declaration.name.untern(db).to_string(),
+            })
+            .collect();
+        if def_id == entity {
+            own_generics = names.len();
+        }
+        generic_names.splice(0..0, names);
+        item = declarations.parent_item;
+    }
+
+    let bound_var_name = |var: BoundVar| match generic_names.get(var.as_usize()) {
+        Some(name) => name.clone(),
+        None => format!("{:?}", var),
+    };
+
+    let mut text = format!("{}{}", keyword, id.untern(db));
+
+    if own_generics != 0 {
+        let own = &generic_names[generic_names.len() - own_generics..];
+        text.push_str(&format!("[{}]", own.join(", ")));
+    }
+
+    let arguments = fn_body.arguments.unwrap_or_default();
+    let parameters: Vec<_> = arguments
+        .iter(&fn_body)
+        .zip(signature.inputs.iter())
+        .map(|(variable, ty)| {
+            let name = fn_body.tables[fn_body.tables[variable].name].text;
+            format!(
+                "{}: {}",
+                name.untern(db),
+                pretty_print_declared_ty(ty, db, &bound_var_name),
+            )
+        })
+        .collect();
+    text.push_str(&format!("({})", parameters.join(", ")));
+
+    // Like in the source, a `void` return type is left implicit.
+    let void = EntityData::LangItem(LangItem::Tuple(0)).intern(db);
+    let returns_void = match signature.output.base.untern(db) {
+        BoundVarOr::Known(base_data) => base_data.kind == BaseKind::Named(void),
+        BoundVarOr::BoundVar(_) => false,
+    };
+    if !returns_void {
+        text.push_str(&format!(
+            " -> {}",
+            pretty_print_declared_ty(&signature.output, db, &bound_var_name),
+        ));
+    }
+
+    Some(text)
+}
+
+crate fn references_to(
+    db: &impl TypeCheckDatabase,
+    entity: Entity,
+) -> Arc<Vec<Span<FileName>>> {
+    let mut spans = vec![];
+
+    if entity.input_file(db).is_some() {
+        spans.push(db.characteristic_entity_span(entity));
+    }
+
+    for &file in db.file_names().iter() {
+        spans.extend(db.references_in_file(file, entity).iter().cloned());
+    }
+
+    Arc::new(spans)
+}
+
+crate fn references_in_file(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+    entity: Entity,
+) -> Arc<Vec<Span<FileName>>> {
+    let mut spans = vec![];
+
+    let file_entity = EntityData::InputFile { file }.intern(db);
+    for &fn_entity in db.descendant_entities(file_entity).iter() {
+        if !fn_entity.untern(db).has_fn_body() {
+            continue;
+        }
+
+        let fn_body = db.fn_body(fn_entity).into_value();
+        let results = db.full_type_check(fn_entity).into_value();
+
+        // Type-relative references, like the field in `foo.bar`.
+        spans.extend(
+            results
+                .entities
+                .iter()
+                .filter(|&(_, &target)| target == entity)
+                .map(|(&mi, _)| fn_body.span(mi)),
+        );
+
+        // References by name, like the fn in `foo(..)`.
+        spans.extend(
+            fn_body
+                .tables
+                .places
+                .iter_enumerated()
+                .filter(|&(_, &data)| data == hir::PlaceData::Entity(entity))
+                .map(|(place, _)| fn_body.span(place)),
+        );
+    }
+
+    spans.sort_by_key(|span| (span.start(), span.end()));
+    spans.dedup();
+    Arc::new(spans)
+}
+
+crate fn completions_at(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+    index: ByteIndex,
+) -> Arc<Vec<Completion>> {
+    // Skip back over the part of the name typed so far, if any. An
+    // index past the end of the text or inside a character (e.g.,
+    // from a stale editor position) has no completions.
+    let text = db.file_text(file);
+    let prefix = match text.get(..index.to_usize()) {
+        Some(prefix) => prefix,
+        None => return Arc::new(vec![]),
+    };
+    let start = prefix
+        .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+        .len();
+
+    let completions = if prefix[..start].ends_with('.') {
+        member_completions(db, file, ByteIndex::from(start - 1))
+    } else {
+        name_completions(db, file, index)
+    };
+
+    Arc::new(completions)
+}
+
+/// Completions for `recv.`, where the `.` is found at `dot`: the
+/// fields and methods of the type of `recv`.
+fn member_completions(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+    dot: ByteIndex,
+) -> Vec<Completion> {
+    if dot.to_usize() == 0 {
+        return vec![];
+    }
+
+    // The receiver is the outermost expression (or place) that ends
+    // right at the `.`.
+    let receiver_ty = db
+        .hover_targets(file, ByteIndex::from(dot.to_usize() - 1))
+        .iter()
+        .filter(|target| target.span.end() == dot)
+        .filter_map(|target| match target.kind {
+            HoverTargetKind::Entity(_) => None,
+            HoverTargetKind::MetaIndex(fn_entity, mi) => {
+                db.full_type_check(fn_entity).into_value().try_ty(mi)
+            }
+        })
+        .next();
+
+    let struct_entity = match receiver_ty.map(|ty| ty.base.untern(db).kind) {
+        Some(BaseKind::Named(entity)) => match entity.untern(db) {
+            EntityData::ItemName {
+                kind: ItemKind::Struct,
+                ..
+            } => entity,
+            _ => return vec![],
+        },
+        _ => return vec![],
+    };
+
+    db.members(struct_entity)
+        .unwrap_or_default()
+        .iter()
+        .map(|member| Completion {
+            label: member.name.untern(db).to_string(),
+            kind: match member.kind {
+                MemberKind::Field => CompletionKind::Field,
+                MemberKind::Method | MemberKind::MethodSignature | MemberKind::Variant => {
+                    CompletionKind::Method
+                }
+            },
+            entity: Some(member.entity),
+        })
+        .collect()
+}
+
+/// Completions for a bare name at `index`: the locals in scope there
+/// (if it is in a fn body), followed by the items of the file.
+fn name_completions(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+    index: ByteIndex,
+) -> Vec<Completion> {
+    let mut completions: Vec<Completion> = vec![];
+
+    let file_entity = EntityData::InputFile { file }.intern(db);
+    let fn_entity = db
+        .descendant_entities(file_entity)
+        .iter()
+        .cloned()
+        .filter(|entity| entity.untern(db).has_fn_body())
+        .find(|&entity| db.entity_span(entity).contains_index(index));
+
+    if let Some(fn_entity) = fn_entity {
+        let fn_body = db.fn_body(fn_entity).into_value();
+
+        // The arguments, and the variables of each `let` whose body
+        // contains `index`.
+        let arguments = fn_body.arguments.unwrap_or_default();
+        let lets = fn_body
+            .tables
+            .expressions
+            .iter()
+            .filter_map(|data| match *data {
+                hir::ExpressionData::Let { variable, body, .. }
+                    if fn_body.span(body).contains_index(index) =>
+                {
+                    Some(variable)
+                }
+                _ => None,
+            });
+
+        for variable in arguments.iter(&fn_body).chain(lets) {
+            let name = fn_body.tables[fn_body.tables[variable].name].text;
+            let label = name.untern(db).to_string();
+            if completions
+                .iter()
+                .all(|completion| completion.label != label)
+            {
+                completions.push(Completion {
+                    label,
+                    kind: CompletionKind::Variable,
+                    entity: None,
+                });
+            }
+        }
+    }
+
+    completions.extend(db.child_entities(file_entity).iter().filter_map(|&entity| {
+        match entity.untern(db) {
+            // An impl is named after its struct, which is
+            // already listed.
+            EntityData::ItemName {
+                kind: ItemKind::Impl,
+                ..
+            } => None,
+            EntityData::ItemName { kind, id, .. } => Some(Completion {
+                label: id.untern(db).to_string(),
+                kind: CompletionKind::Item(kind),
+                entity: Some(entity),
+            }),
+            _ => None,
+        }
+    }));
+
+    completions
+}
+
+/// Identifiers that are keywords, rather than names, wherever they
+/// appear.
+const KEYWORDS: &[&str] = &[
+    "struct", "def", "enum", "trait", "impl", "const", "type", "let", "if", "else", "while",
+    "loop", "break", "match", "return", "in", "for", "where", "pub", "shared", "borrow", "owned",
+    "borrowed",
+];
+
+crate fn semantic_tokens(
+    db: &impl TypeCheckDatabase,
+    file: FileName,
+) -> Arc<Vec<SemanticToken>> {
+    let text = db.file_text(file);
+    let file_entity = EntityData::InputFile { file }.intern(db);
+    let entities = db.descendant_entities(file_entity);
+
+    // The classification of each span that names something, gathered
+    // from the declarations in the file and from their fn bodies.
+    let mut resolved: FxIndexMap<Span<FileName>, SemanticTokenKind> = FxIndexMap::default();
+    for &entity in entities.iter() {
+        if let Some(kind) = entity_token_kind(db, entity) {
+            resolved.insert(db.characteristic_entity_span(entity), kind);
+        }
+
+        if !entity.untern(db).has_fn_body() {
+            continue;
+        }
+
+        let fn_body = db.fn_body(entity).into_value();
+        let results = db.full_type_check(entity).into_value();
+
+        for data in fn_body.tables.variables.iter() {
+            if let Some(span) = fn_body.opt_span(data.name) {
+                resolved.insert(span, SemanticTokenKind::Local);
+            }
+        }
+
+        for (place, data) in fn_body.tables.places.iter_enumerated() {
+            let kind = match *data {
+                hir::PlaceData::Variable(_) => Some(SemanticTokenKind::Local),
+                hir::PlaceData::Entity(entity) => entity_token_kind(db, entity),
+                _ => None,
+            };
+            if let (Some(kind), Some(span)) = (kind, fn_body.opt_span(place)) {
+                resolved.insert(span, kind);
+            }
+        }
+
+        // Type-relative references, like the field in `foo.bar`.
+        for (&mi, &entity) in results.entities.iter() {
+            let kind = entity_token_kind(db, entity);
+            if let (Some(kind), Some(span)) = (kind, fn_body.opt_span(mi)) {
+                resolved.insert(span, kind);
+            }
+        }
+    }
+
+    let tokens = db
+        .file_tokens(file)
+        .into_value()
+        .iter()
+        .filter_map(|token| {
+            let kind = match token.value {
+                LexToken::Integer | LexToken::Float | LexToken::String | LexToken::Char => {
+                    SemanticTokenKind::Literal
+                }
+                LexToken::Sigil => SemanticTokenKind::Operator,
+                LexToken::Identifier => {
+                    let name = &text[token.span];
+                    if let Some(&kind) = resolved.get(&token.span) {
+                        kind
+                    } else if KEYWORDS.contains(&name) {
+                        SemanticTokenKind::Keyword
+                    } else {
+                        // Names outside of fn bodies, like the types
+                        // in a signature, are resolved in the scope of
+                        // the innermost entity that contains them.
+                        let scope = entities
+                            .iter()
+                            .rev()
+                            .cloned()
+                            .find(|&entity| {
+                                db.entity_span(entity).contains_index(token.span.start())
+                            })
+                            .unwrap_or(file_entity);
+                        db.resolve_name(scope, name.intern(db))
+                            .and_then(|entity| entity_token_kind(db, entity))?
+                    }
+                }
+                LexToken::Whitespace
+                | LexToken::Comment
+                | LexToken::Newline
+                | LexToken::EOF
+                | LexToken::Error => return None,
+            };
+            Some(SemanticToken {
+                span: token.span,
+                kind,
+            })
+        })
+        .collect();
+
+    Arc::new(tokens)
+}
+
+/// How to highlight a name that refers to `entity`.
+fn entity_token_kind(db: &impl TypeCheckDatabase, entity: Entity) -> Option<SemanticTokenKind> {
+    match entity.untern(db) {
+        EntityData::ItemName { kind, .. } => match kind {
+            ItemKind::Struct
+            | ItemKind::Enum
+            | ItemKind::Trait
+            | ItemKind::Impl
+            | ItemKind::TypeAlias => Some(SemanticTokenKind::Type),
+            ItemKind::Function => Some(SemanticTokenKind::Function),
+            ItemKind::Const => Some(SemanticTokenKind::Constant),
+        },
+        EntityData::MemberName { kind, .. } => match kind {
+            MemberKind::Field => Some(SemanticTokenKind::Field),
+            MemberKind::Method | MemberKind::MethodSignature => Some(SemanticTokenKind::Function),
+            MemberKind::Variant => Some(SemanticTokenKind::Type),
+        },
+        EntityData::LangItem(LangItem::True) | EntityData::LangItem(LangItem::False) => {
+            Some(SemanticTokenKind::Literal)
+        }
+        EntityData::LangItem(LangItem::Debug) => Some(SemanticTokenKind::Function),
+        EntityData::LangItem(_) => Some(SemanticTokenKind::Type),
+        EntityData::InputFile { .. } | EntityData::Error(_) => None,
+    }
+}
@@ -6,13 +6,15 @@
 #![feature(trait_alias)]
 
 use generational_arena::Arena;
-use lark_collections::{FxIndexMap, IndexVec};
+use lark_collections::{FxIndexMap, FxIndexSet, IndexVec};
 use lark_debug_derive::DebugWith;
 use lark_entity::{Entity, EntityTables};
-use lark_error::{Diagnostic, WithError};
+use lark_error::Diagnostic;
+use lark_error::WithError;
 use lark_hir as hir;
 use lark_parser::ParserDatabase;
 use lark_pretty_print::PrettyPrintDatabase;
+use lark_span::{ByteIndex, FileName, Span};
 use lark_ty::base_inferred::BaseInferred;
 use lark_ty::base_inferred::BaseInferredTables;
 use lark_ty::declaration::Declaration;
@@ -22,6 +24,7 @@ use lark_ty::full_inferred::FullInferredTables;
 use lark_ty::map_family::Map;
 use lark_ty::BaseData;
 use lark_ty::Generics;
+use lark_ty::PermKind;
 use lark_ty::Placeholder;
 use lark_ty::Ty;
 use lark_ty::TypeFamily;
@@ -32,11 +35,15 @@ use lark_unify::UnificationTable;
 use std::sync::Arc;
 
 mod base_inference;
+mod diagnostics;
+mod error;
 mod full_inference;
 mod hir_typeck;
+mod ide;
 mod ops;
 mod results;
-mod substitute;
+#[cfg(feature = "serde")]
+mod serialize;
 
 #[salsa::query_group(TypeCheckStorage)]
 pub trait TypeCheckDatabase:
@@ -51,9 +58,106 @@ pub trait TypeCheckDatabase:
     /// This is the type information excluding permissions.
     #[salsa::invoke(full_inference::query_definition::full_type_check)]
     fn full_type_check(&self, key: Entity) -> WithError<Arc<TypeCheckResults<FullInferred>>>;
+
+    /// The (base) type inferred for `expression` within the fn body
+    /// of `key`, if it was type-checked at all.
+    #[salsa::invoke(base_inference::query_definition::ty_of_expression)]
+    fn ty_of_expression(
+        &self,
+        key: Entity,
+        expression: hir::Expression,
+    ) -> Option<Ty<BaseInferred>>;
+
+    /// The source spans of the type-check errors (but not warnings)
+    /// in the fn body of `key`.
+    #[salsa::invoke(base_inference::query_definition::error_spans)]
+    fn error_spans(&self, key: Entity) -> Vec<Span<FileName>>;
+
+    /// The kind that full inference decided on for each permission
+    /// variable in the fn body of `key`. Every variable that appears
+    /// in a permission constraint has an entry; those that are never
+    /// forced to be borrow or own are share.
+    #[salsa::invoke(full_inference::query_definition::perm_kinds)]
+    fn perm_kinds(&self, key: Entity) -> Arc<FxIndexMap<PermVar, PermKind>>;
+
+    /// Runs full inference and the safety analysis on the fn body of
+    /// `key` just once; `full_type_check` and `perm_kinds` are both
+    /// projections of this result.
+    #[salsa::invoke(full_inference::query_definition::full_inference)]
+    fn full_inference(
+        &self,
+        key: Entity,
+    ) -> WithError<(
+        Arc<TypeCheckResults<FullInferred>>,
+        Arc<FxIndexMap<PermVar, PermKind>>,
+    )>;
+
+    /// All the diagnostics -- parse errors, type errors, and
+    /// warnings -- for the given input file.
+    #[salsa::invoke(diagnostics::file_diagnostics)]
+    fn file_diagnostics(&self, file: FileName) -> Arc<Vec<Diagnostic>>;
+
+    /// The diagnostics for every input file, in the order of
+    /// `file_names`. Gathered via `file_diagnostics`, so editing one
+    /// file does not recompute the diagnostics of the others.
+    #[salsa::invoke(diagnostics::all_diagnostics)]
+    fn all_diagnostics(&self, key: ()) -> Arc<Vec<Diagnostic>>;
+
+    /// The entity referenced at `index` in `file` (e.g., the field in
+    /// `foo.bar` or the fn in `foo(..)`), along with the span where
+    /// it is defined. When several references contain `index`, the
+    /// innermost wins. `None` for positions that reference nothing,
+    /// such as whitespace or literals, and for built-in entities like
+    /// `uint`, which have no definition site.
+    #[salsa::invoke(ide::definition_at)]
+    fn definition_at(&self, file: FileName, index: ByteIndex) -> Option<(Entity, Span<FileName>)>;
+
+    /// What to show when hovering over `index` in `file`: the type
+    /// of the innermost expression there or, when that is (or refers
+    /// to) an item, the item's declaration, along with its doc
+    /// comment.
+    #[salsa::invoke(ide::hover_at)]
+    fn hover_at(&self, file: FileName, index: ByteIndex) -> Option<HoverInfo>;
+
+    /// The declared signature of a fn or method, with its parameter
+    /// names and types and its return type, e.g. `def foo(x: uint) ->
+    /// bool`; generic parameters are printed with their names. `None`
+    /// for entities that have no signature.
+    #[salsa::invoke(ide::signature_string)]
+    fn signature_string(&self, entity: Entity) -> Option<String>;
+
+    /// Classifies the tokens of `file` for semantic highlighting,
+    /// using the type-check results to tell fields, fns and locals
+    /// apart. Whitespace, comments and names that refer to nothing
+    /// are omitted; the rest are in order of their start.
+    #[salsa::invoke(ide::semantic_tokens)]
+    fn semantic_tokens(&self, file: FileName) -> Arc<Vec<SemanticToken>>;
+
+    /// The spans of every reference to `entity` from a fn body,
+    /// preceded by the span of its name where it is defined (if it
+    /// is defined in some input file).
+    #[salsa::invoke(ide::references_to)]
+    fn references_to(&self, entity: Entity) -> Arc<Vec<Span<FileName>>>;
+
+    /// The spans of the references to `entity` from fn bodies in
+    /// `file`, in order.
+    #[salsa::invoke(ide::references_in_file)]
+    fn references_in_file(&self, file: FileName, entity: Entity) -> Arc<Vec<Span<FileName>>>;
+
+    /// What could be typed at `index` in `file`. After `recv.`, these
+    /// are the fields and methods of the type of `recv` (none, if it
+    /// is not a struct); otherwise, the locals in scope followed by
+    /// the items of the file.
+    #[salsa::invoke(ide::completions_at)]
+    fn completions_at(&self, file: FileName, index: ByteIndex) -> Arc<Vec<Completion>>;
 }
 
+pub use error::{Error, ErrorKind, GenericOperation, Operator, PlaceExpressionKind, Severity};
+pub use full_inference::perm::PermVar;
+pub use ide::{Completion, CompletionKind, HoverInfo, SemanticToken, SemanticTokenKind};
 pub use results::TypeCheckResults;
+#[cfg(feature = "serde")]
+pub use serialize::SerializedTypeCheckResults;
 
 struct TypeChecker<'me, F: TypeCheckerFamily, S> {
     /// Salsa database.
@@ -87,8 +191,42 @@ struct TypeChecker<'me, F: TypeCheckerFamily, S> {
     /// Information about each universe that we have created.
     universe_binders: IndexVec<Universe, UniverseBinder>,
 
+    /// Placeholders for the generic parameters of `fn_entity`, used
+    /// to instantiate types that appear in its signature and body.
+    fn_generics: Generics<F>,
+
+    /// The declared return type of `fn_entity`, once known; consulted
+    /// by `return` expressions.
+    return_ty: Option<Ty<F>>,
+
     /// Errors that we encountered during the type-check.
-    errors: Vec<Diagnostic>,
+    errors: Vec<Error>,
+
+    /// The loops enclosing the expression currently being checked,
+    /// innermost last; consulted by `break` expressions.
+    enclosing_loops: Vec<EnclosingLoop<F>>,
+
+    /// Each integer literal along with its type, which starts out as
+    /// an inference variable; see `default_integer_literals`.
+    integer_literals: Vec<(hir::Expression, Ty<F>)>,
+
+    /// Integer literals that are the operand of a negation, which may
+    /// be one larger than `int` would otherwise allow (so that
+    /// `-2147483648` is accepted); see `check_integer_literal`.
+    negated_literals: FxIndexSet<hir::Expression>,
+
+    /// The type of each expression that never produces a value, when
+    /// nothing else determined it; see `never_type`.
+    diverging_variables: Vec<(hir::Expression, Ty<F>)>,
+}
+
+struct EnclosingLoop<F: TypeCheckerFamily> {
+    /// Type of the values given to `break`; this is the type of the
+    /// loop expression as a whole.
+    break_ty: Ty<F>,
+
+    /// Set once we see a `break` that targets this loop.
+    has_break: bool,
 }
 
 enum UniverseBinder {
@@ -136,6 +274,16 @@ where
         field_ty: Ty<F>,
     ) -> Ty<F>;
 
+    /// Adjust the type `place_ty` of a place to account for the user
+    /// explicitly requesting a `perm` reference to it (e.g., `shared x`).
+    fn apply_user_perm(
+        &mut self,
+        cause: impl Into<hir::MetaIndex>,
+        location: impl Into<HirLocation>,
+        perm: PermKind,
+        place_ty: Ty<F>,
+    ) -> Ty<F>;
+
     /// Requests the type for a given HIR variable. Upon the first
     /// request, the result may be a fresh inference variable.
     fn request_variable_ty(&mut self, var: hir::Variable) -> Ty<F>;
@@ -235,6 +383,7 @@ enum HirLocation {
     Expression(hir::Expression),
     AfterExpression(hir::Expression),
     Place(hir::Place),
+    Pattern(hir::Pattern),
     Error,
 }
 
@@ -249,3 +398,9 @@ impl Into<HirLocation> for hir::Place {
         HirLocation::Place(self)
     }
 }
+
+impl Into<HirLocation> for hir::Pattern {
+    fn into(self) -> HirLocation {
+        HirLocation::Pattern(self)
+    }
+}
@@ -1,4 +1,7 @@
+use crate::Error;
+use crate::ErrorKind;
 use crate::HirLocation;
+use crate::Severity;
 use crate::TypeChecker;
 use crate::TypeCheckerFamily;
 use crate::TypeCheckerFamilyDependentExt;
@@ -7,11 +10,14 @@ use crate::UniverseBinder;
 use lark_entity::Entity;
 use lark_entity::EntityData;
 use lark_entity::LangItem;
-use lark_error::{Diagnostic, ErrorReported};
+use lark_error::ErrorReported;
 use lark_hir as hir;
 use lark_intern::Intern;
+use lark_pretty_print::PrettyPrint;
 use lark_ty::BaseData;
 use lark_ty::BaseKind;
+use lark_ty::BoundVar;
+use lark_ty::Constraint;
 use lark_ty::GenericDeclarations;
 use lark_ty::GenericKind;
 use lark_ty::Generics;
@@ -62,6 +68,14 @@ where
         self.primitive_type(LangItem::Uint)
     }
 
+    crate fn float_type(&self) -> Ty<F> {
+        self.primitive_type(LangItem::Float)
+    }
+
+    crate fn char_type(&self) -> Ty<F> {
+        self.primitive_type(LangItem::Char)
+    }
+
     crate fn string_type(&self) -> Ty<F> {
         self.primitive_type(LangItem::String)
     }
@@ -70,11 +84,28 @@ where
         self.primitive_type(LangItem::Tuple(0))
     }
 
+    /// The type `(T1, ..., TN)` of a tuple with the given element types.
+    crate fn tuple_type(&self, element_tys: Vec<Ty<F>>) -> Ty<F> {
+        let item = LangItem::Tuple(element_tys.len());
+        let generics = element_tys.into_iter().map(GenericKind::Ty).collect();
+        self.lang_item_type(item, generics)
+    }
+
+    /// The type `Range[T]` of a range whose endpoints have type `element_ty`.
+    crate fn range_type(&self, element_ty: Ty<F>) -> Ty<F> {
+        let generics = std::iter::once(GenericKind::Ty(element_ty)).collect();
+        self.lang_item_type(LangItem::Range, generics)
+    }
+
     crate fn error_type(&self) -> Ty<F> {
         F::error_type(self)
     }
 
     fn primitive_type(&self, item: LangItem) -> Ty<F> {
+        self.lang_item_type(item, Generics::empty())
+    }
+
+    fn lang_item_type(&self, item: LangItem, generics: Generics<F>) -> Ty<F> {
         let entity = EntityData::LangItem(item).intern(self);
         Ty {
             repr: F::direct_repr(self),
@@ -83,20 +114,30 @@ where
                 self,
                 BaseData {
                     kind: BaseKind::Named(entity),
-                    generics: Generics::empty(),
+                    generics,
                 },
             ),
         }
     }
 
     /// Record that an error occurred at the given location.
-    crate fn record_error(
-        &mut self,
-        label: impl Into<String>,
-        location: impl Into<hir::MetaIndex>,
-    ) {
-        let span = self.hir.span(location.into());
-        self.errors.push(Diagnostic::new(label.into(), span));
+    crate fn record_error(&mut self, kind: ErrorKind, location: impl Into<hir::MetaIndex>) {
+        self.errors.push(Error {
+            location: location.into(),
+            kind,
+            severity: Severity::Error,
+        });
+    }
+
+    /// Record a warning at the given location. Warnings are reported
+    /// as diagnostics with `Severity::Warning` but, unlike errors, do
+    /// not cause the results to carry `ErrorReported`.
+    crate fn record_warning(&mut self, kind: ErrorKind, location: impl Into<hir::MetaIndex>) {
+        self.errors.push(Error {
+            location: location.into(),
+            kind,
+            severity: Severity::Warning,
+        });
     }
 
     crate fn own_perm(&mut self) -> F::Perm {
@@ -123,6 +164,7 @@ where
         let GenericDeclarations {
             parent_item,
             declarations,
+            ..
         } = &*self
             .db
             .generic_declarations(def_id)
@@ -160,6 +202,7 @@ where
         let GenericDeclarations {
             parent_item,
             declarations,
+            ..
         } = &*self
             .db
             .generic_declarations(entity)
@@ -185,6 +228,86 @@ where
         generics
     }
 
+    /// Checks that the values of `generics`, which instantiate the
+    /// generics of `entity`, satisfy its where clauses. Since those
+    /// values are often not yet inferred, the checks are deferred
+    /// until they are known.
+    crate fn check_generic_constraints(
+        &mut self,
+        cause: impl Into<hir::MetaIndex>,
+        entity: Entity,
+        generics: &Generics<F>,
+    ) {
+        let cause: hir::MetaIndex = cause.into();
+        let generic_declarations = match self.db.generic_declarations(entity).into_value() {
+            Ok(generic_declarations) => generic_declarations,
+            Err(ErrorReported(_)) => return,
+        };
+
+        // The constraints refer to our own generics, which come after
+        // those of our parent item.
+        let offset = generics.len() - generic_declarations.declarations.len();
+        for &Constraint {
+            bound_var,
+            trait_entity,
+        } in generic_declarations.constraints.iter()
+        {
+            let ty = generics[BoundVar::new(offset + bound_var.as_usize())].assert_ty();
+            self.with_base_data_equate(
+                ty.base,
+                move |this, base_data| this.check_constraint(cause, base_data, trait_entity),
+                |_, ()| (),
+            );
+        }
+    }
+
+    fn check_constraint(
+        &mut self,
+        cause: hir::MetaIndex,
+        base_data: BaseData<F>,
+        trait_entity: Entity,
+    ) {
+        let satisfied = match base_data.kind {
+            BaseKind::Named(entity) => self.db.implements_trait(entity, trait_entity),
+
+            // Within a generic item, its generics (and those of the
+            // items it is nested in) satisfy the constraints declared
+            // on them. Each item's generics get their own universe
+            // (see `placeholders_for`), and the placeholder's
+            // `bound_var` indexes that item's own declarations.
+            BaseKind::Placeholder(placeholder) => {
+                match self.universe_binders[placeholder.universe] {
+                    UniverseBinder::FromItem(item) => self
+                        .db
+                        .generic_declarations(item)
+                        .into_value()
+                        .map(|generic_declarations| {
+                            generic_declarations.constraints.contains(&Constraint {
+                                bound_var: placeholder.bound_var,
+                                trait_entity,
+                            })
+                        })
+                        .unwrap_or(true),
+
+                    // The root universe has no placeholders.
+                    UniverseBinder::Root => false,
+                }
+            }
+
+            BaseKind::Error => true,
+        };
+
+        if !satisfied {
+            self.record_error(
+                ErrorKind::TraitNotImplemented {
+                    trait_name: trait_entity.pretty_print(self.db),
+                    ty: base_data.kind.pretty_print(self.db),
+                },
+                cause,
+            );
+        }
+    }
+
     /// Create a fresh universe (one that did not exist before) with
     /// the given binder. This universe will be able to see names
     /// from all previously existing universes.
@@ -233,7 +356,7 @@ where
     /// inferred, so check again: if so, invoke `op` and invoke
     /// `equate` (which will combine the result with that dummy
     /// value). If not, enqueue us up for later.
-    fn with_base_data_equate<O: 'static>(
+    crate fn with_base_data_equate<O: 'static>(
         &mut self,
         base: F::Base,
         op: impl FnOnce(&mut Self, BaseData<F>) -> O + 'static,
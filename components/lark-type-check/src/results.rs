@@ -1,29 +1,49 @@
+use crate::Error;
 use lark_entity::Entity;
 use lark_hir as hir;
 use lark_ty::map_family::{FamilyMapper, Map};
 use lark_ty::Generics;
 use lark_ty::Ty;
 use lark_ty::TypeFamily;
-
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// With the `serde` feature, results can be serialized (e.g., to
+/// cache them on disk). Types, permissions and entities are written
+/// as their interned ids; wrap the results in a
+/// `SerializedTypeCheckResults` to read them back into another
+/// database.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "Ty<F>: Serialize, F::Perm: Serialize",
+        deserialize = "Ty<F>: Deserialize<'de>, F::Perm: Deserialize<'de>"
+    ))
+)]
 pub struct TypeCheckResults<F: TypeFamily> {
     /// The "maximum type" computed for expressions,
     /// identified-expressions, and other things that have a type. The
     /// maximum type is the full set of permissions available from
     /// that expression.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::map"))]
     pub max_types: std::collections::BTreeMap<hir::MetaIndex, Ty<F>>,
 
     /// The "access type" for a given expression -- this is the set of
     /// permissions required by that particular expression. These
     /// cannot exceed the "max types" and are determined by how the
     /// result of the expression is used.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::map"))]
     pub access_types: std::collections::BTreeMap<hir::Expression, Ty<F>>,
 
     /// The "permission variable" recorded for a given expression
     /// instance.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::map"))]
     pub access_permissions: std::collections::BTreeMap<hir::Expression, F::Perm>,
 
     /// For references to entities, the generics applied.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::map"))]
     pub generics: std::collections::BTreeMap<hir::MetaIndex, Generics<F>>,
 
     /// For "type-relative" identifiers, stores the entity that we resolved
@@ -33,7 +53,12 @@ pub struct TypeCheckResults<F: TypeFamily> {
     /// - `foo.bar(..)` -- attached to the identifier `bar`, entity of the method
     /// - `Foo { a: b }` -- attached to the identifier `a`, entity of the field
     /// - `foo` -- when an identifier refers to an entity
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::map"))]
     pub entities: std::collections::BTreeMap<hir::MetaIndex, Entity>,
+
+    /// Errors found during the type-check (also reported as
+    /// diagnostics by the query).
+    pub errors: Vec<Error>,
 }
 
 impl<F: TypeFamily> TypeCheckResults<F> {
@@ -88,6 +113,13 @@ impl<F: TypeFamily> TypeCheckResults<F> {
 
     /// Access the type stored for the given `index`, usually the
     /// index of an expression. Indicates the "maximum type".
+    ///
+    /// # Panics
+    ///
+    /// Panics if no type was recorded for `index`. Every expression
+    /// the type checker visits gets a type (the error type, if
+    /// nothing better), but tooling that may see indices from
+    /// elsewhere should prefer `try_ty`.
     pub fn ty(&self, index: impl Into<hir::MetaIndex>) -> Ty<F> {
         self.max_types[&index.into()]
     }
@@ -99,7 +131,7 @@ impl<F: TypeFamily> TypeCheckResults<F> {
     }
 
     /// Load the type for `index`, if any is stored, else return `None`.
-    pub fn opt_ty(&self, index: impl Into<hir::MetaIndex>) -> Option<Ty<F>> {
+    pub fn try_ty(&self, index: impl Into<hir::MetaIndex>) -> Option<Ty<F>> {
         self.max_types.get(&index.into()).cloned()
     }
 
@@ -117,6 +149,7 @@ impl<F: TypeFamily> Default for TypeCheckResults<F> {
             access_permissions: Default::default(),
             generics: Default::default(),
             entities: Default::default(),
+            errors: Default::default(),
         }
     }
 }
@@ -135,12 +168,14 @@ where
             access_permissions,
             generics,
             entities,
+            errors,
         } = self;
         TypeCheckResults {
             max_types: max_types.map(mapper),
             access_types: access_types.map(mapper),
             generics: generics.map(mapper),
             entities: entities.map(mapper),
+            errors: errors.clone(),
             access_permissions: access_permissions
                 .iter()
                 .map(|(&key, &value)| (key, mapper.map_perm(value)))
@@ -0,0 +1,124 @@
+//! Helpers for serializing `TypeCheckResults` with serde (enabled by
+//! the `serde` feature).
+//!
+//! The results are mostly maps keyed by HIR indices. These are
+//! written as sequences of `(key, value)` pairs, since formats like
+//! JSON only permit strings as map keys.
+//!
+//! Types, permissions and entities are written as their interned ids.
+//! `SerializedTypeCheckResults` bundles a side table with the data
+//! behind each of those ids, so that they can be re-interned into
+//! another database.
+
+use crate::TypeCheckResults;
+use lark_entity::EntityTables;
+use lark_string::GlobalIdentifierTables;
+use lark_ty::serialize::{SerializeFamily, SideTable, SideTableBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Type-check results along with the data behind every type,
+/// permission and entity they mention.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "SideTable<F>: Serialize, TypeCheckResults<F>: Serialize",
+    deserialize = "SideTable<F>: Deserialize<'de>, TypeCheckResults<F>: Deserialize<'de>"
+))]
+pub struct SerializedTypeCheckResults<F: SerializeFamily> {
+    pub side_table: SideTable<F>,
+
+    pub results: TypeCheckResults<F>,
+}
+
+impl<F: SerializeFamily> SerializedTypeCheckResults<F> {
+    pub fn new<DB>(db: &DB, results: TypeCheckResults<F>) -> Self
+    where
+        DB: AsRef<GlobalIdentifierTables> + AsRef<EntityTables> + AsRef<F::InternTables>,
+    {
+        let mut builder = SideTableBuilder::new(db);
+        for &ty in results.max_types.values() {
+            builder.record_ty(ty);
+        }
+        for &ty in results.access_types.values() {
+            builder.record_ty(ty);
+        }
+        for &perm in results.access_permissions.values() {
+            builder.record_perm(perm);
+        }
+        for generics in results.generics.values() {
+            builder.record_generics(generics);
+        }
+        for &entity in results.entities.values() {
+            builder.record_entity(entity);
+        }
+
+        SerializedTypeCheckResults {
+            side_table: builder.finish(),
+            results,
+        }
+    }
+
+    /// Re-interns the data in the side table into `db`, returning the
+    /// results with each id replaced by its id in `db`.
+    pub fn into_results<DB>(self, db: &DB) -> TypeCheckResults<F>
+    where
+        DB: AsRef<GlobalIdentifierTables> + AsRef<EntityTables> + AsRef<F::InternTables>,
+    {
+        let reinterner = self.side_table.reintern(db);
+        let TypeCheckResults {
+            max_types,
+            access_types,
+            access_permissions,
+            generics,
+            entities,
+            errors,
+        } = self.results;
+        TypeCheckResults {
+            max_types: max_types
+                .into_iter()
+                .map(|(index, ty)| (index, reinterner.ty(ty)))
+                .collect(),
+            access_types: access_types
+                .into_iter()
+                .map(|(index, ty)| (index, reinterner.ty(ty)))
+                .collect(),
+            access_permissions: access_permissions
+                .into_iter()
+                .map(|(index, perm)| (index, reinterner.perm(perm)))
+                .collect(),
+            generics: generics
+                .into_iter()
+                .map(|(index, generics)| (index, reinterner.generics(&generics)))
+                .collect(),
+            entities: entities
+                .into_iter()
+                .map(|(index, entity)| (index, reinterner.entity(entity)))
+                .collect(),
+            errors,
+        }
+    }
+}
+
+/// For a map keyed by any serializable type (e.g., `hir::MetaIndex`).
+crate mod map {
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    crate fn serialize<S, K, V>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+        K: Serialize,
+        V: Serialize,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    crate fn deserialize<'de, D, K, V>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        K: Deserialize<'de> + Ord,
+        V: Deserialize<'de>,
+    {
+        let pairs: Vec<(K, V)> = Deserialize::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
@@ -0,0 +1,369 @@
+use lark_entity::Entity;
+use lark_entity::EntityData;
+use lark_entity::ItemKind;
+use lark_entity::MemberKind;
+use lark_error::Diagnostic;
+use lark_intern::Intern;
+use lark_parser::ParserDatabase;
+use lark_parser::ParserDatabaseExt;
+use lark_query_system::ls_ops::diagnostics_to_lsp;
+use lark_query_system::LarkDatabase;
+use lark_span::ByteIndex;
+use lark_span::FileName;
+use lark_span::Span;
+use lark_test::*;
+use lark_type_check::Completion;
+use lark_type_check::CompletionKind;
+use lark_type_check::HoverInfo;
+use lark_type_check::SemanticTokenKind;
+use lark_type_check::TypeCheckDatabase;
+use serde_json::json;
+
+const POINT: &str = "\
+struct Point {
+    x: uint,
+    y: uint,
+}
+
+/// Gets the x coordinate.
+def get_x(p: Point) -> uint {
+    p.x
+}
+
+def main() -> uint {
+    get_x(Point(x: 1, y: 2))
+}
+";
+
+fn item(db: &impl ParserDatabase, file: FileName, kind: ItemKind, name: &str) -> Entity {
+    EntityData::ItemName {
+        base: EntityData::InputFile { file }.intern(db),
+        kind,
+        id: name.intern(db),
+    }
+    .intern(db)
+}
+
+#[test]
+fn definition_of_field_access() {
+    let (file_name, db) = lark_parser_db(POINT);
+    let index = ByteIndex::from(POINT.find("p.x").unwrap() + 2);
+
+    let point = item(&db, file_name, ItemKind::Struct, "Point");
+    let x = db
+        .member_entity(point, MemberKind::Field, "x".intern(&db))
+        .unwrap();
+    assert_eq!(
+        db.definition_at(file_name, index),
+        Some((x, db.entity_span(x)))
+    );
+}
+
+#[test]
+fn definition_of_call() {
+    let (file_name, db) = lark_parser_db(POINT);
+    let index = ByteIndex::from(POINT.rfind("get_x").unwrap() + 1);
+
+    let get_x = item(&db, file_name, ItemKind::Function, "get_x");
+    assert_eq!(
+        db.definition_at(file_name, index),
+        Some((get_x, db.entity_span(get_x)))
+    );
+}
+
+#[test]
+fn no_definition_of_whitespace_or_literal() {
+    let (file_name, db) = lark_parser_db(POINT);
+
+    let whitespace = ByteIndex::from(POINT.rfind("    get_x").unwrap() + 1);
+    assert_eq!(db.definition_at(file_name, whitespace), None);
+
+    let literal = ByteIndex::from(POINT.find("1,").unwrap());
+    assert_eq!(db.definition_at(file_name, literal), None);
+}
+
+#[test]
+fn hover_local_binding() {
+    let text = "\
+def main() -> uint {
+    let total = 22
+    total + 1
+}
+";
+    let (file_name, db) = lark_parser_db(text);
+    let index = ByteIndex::from(text.rfind("total").unwrap() + 1);
+
+    let info = db.hover_at(file_name, index).unwrap();
+    assert!(info.text.ends_with("uint"), "unexpected hover {:?}", info);
+    assert_eq!(info.doc, None);
+}
+
+#[test]
+fn hover_function_call() {
+    let (file_name, db) = lark_parser_db(POINT);
+    let index = ByteIndex::from(POINT.rfind("get_x").unwrap() + 1);
+
+    assert_eq!(
+        db.hover_at(file_name, index),
+        Some(HoverInfo {
+            text: "def get_x(Point) -> uint".to_string(),
+            doc: Some("Gets the x coordinate.".to_string()),
+        })
+    );
+}
+
+#[test]
+fn signature_string_of_function() {
+    let (file_name, db) = lark_parser_db(POINT);
+
+    let get_x = item(&db, file_name, ItemKind::Function, "get_x");
+    assert_eq!(
+        db.signature_string(get_x),
+        Some("def get_x(p: Point) -> uint".to_string())
+    );
+
+    let point = item(&db, file_name, ItemKind::Struct, "Point");
+    assert_eq!(db.signature_string(point), None);
+}
+
+#[test]
+fn signature_string_of_generic_function() {
+    let text = "\
+def pick[T, U](x: T, y: U, first: bool) -> T {
+    x
+}
+";
+    let (file_name, db) = lark_parser_db(text);
+
+    let pick = item(&db, file_name, ItemKind::Function, "pick");
+    assert_eq!(
+        db.signature_string(pick),
+        Some("def pick[T, U](x: T, y: U, first: bool) -> T".to_string())
+    );
+}
+
+#[test]
+fn semantic_tokens_of_field_and_call() {
+    let (file_name, db) = lark_parser_db(POINT);
+    let tokens = db.semantic_tokens(file_name);
+
+    let kind_at = |offset: usize| {
+        tokens
+            .iter()
+            .find(|token| token.span.start() == ByteIndex::from(offset))
+            .map(|token| (&POINT[token.span], token.kind))
+    };
+
+    let field = POINT.find("p.x").unwrap() + 2;
+    assert_eq!(kind_at(field), Some(("x", SemanticTokenKind::Field)));
+
+    let call = POINT.rfind("get_x").unwrap();
+    assert_eq!(kind_at(call), Some(("get_x", SemanticTokenKind::Function)));
+
+    let local = POINT.find("p.x").unwrap();
+    assert_eq!(kind_at(local), Some(("p", SemanticTokenKind::Local)));
+
+    let def = POINT.find("def").unwrap();
+    assert_eq!(kind_at(def), Some(("def", SemanticTokenKind::Keyword)));
+
+    let ty = POINT.find("-> uint").unwrap() + 3;
+    assert_eq!(kind_at(ty), Some(("uint", SemanticTokenKind::Type)));
+
+    // Sorted and non-overlapping.
+    for pair in tokens.windows(2) {
+        assert!(pair[0].span.end() <= pair[1].span.start());
+    }
+}
+
+#[test]
+fn semantic_tokens_of_loop_and_const() {
+    let text = "\
+const LIMIT: uint = 3
+
+def main() {
+    loop {
+        break
+    }
+    debug(LIMIT)
+}
+";
+    let (file_name, db) = lark_parser_db(text);
+    let tokens = db.semantic_tokens(file_name);
+
+    let kind_at = |offset: usize| {
+        tokens
+            .iter()
+            .find(|token| token.span.start() == ByteIndex::from(offset))
+            .map(|token| (&text[token.span], token.kind))
+    };
+
+    let keyword = text.find("loop").unwrap();
+    assert_eq!(kind_at(keyword), Some(("loop", SemanticTokenKind::Keyword)));
+
+    let keyword = text.find("break").unwrap();
+    assert_eq!(kind_at(keyword), Some(("break", SemanticTokenKind::Keyword)));
+
+    let declaration = text.find("LIMIT").unwrap();
+    assert_eq!(kind_at(declaration), Some(("LIMIT", SemanticTokenKind::Constant)));
+
+    let reference = text.rfind("LIMIT").unwrap();
+    assert_eq!(kind_at(reference), Some(("LIMIT", SemanticTokenKind::Constant)));
+}
+
+#[test]
+fn references_to_field_across_files() {
+    let mut db = LarkDatabase::default();
+    db.add_file("geometry.lark", POINT);
+    let main_text = "\
+def norm(p: geometry::Point) -> uint {
+    p.x * p.x
+}
+";
+    db.add_file("main.lark", main_text);
+    let geometry = "geometry.lark".into_file_name(&db);
+    let main = "main.lark".into_file_name(&db);
+
+    let point = item(&db, geometry, ItemKind::Struct, "Point");
+    let x = db
+        .member_entity(point, MemberKind::Field, "x".intern(&db))
+        .unwrap();
+
+    // The declaration, `p.x` in `get_x`, `x: 1` in `main`, and the two
+    // uses in `norm`.
+    let references = db.references_to(x);
+    let files: Vec<_> = references.iter().map(|span| span.file()).collect();
+    assert_eq!(files, vec![geometry, geometry, geometry, main, main]);
+
+    for span in references.iter() {
+        let text = if span.file() == geometry {
+            POINT
+        } else {
+            main_text
+        };
+        assert_eq!(&text[*span], "x");
+    }
+}
+
+fn completion_summary(completions: &[Completion]) -> Vec<(&str, CompletionKind)> {
+    completions
+        .iter()
+        .map(|completion| (&completion.label[..], completion.kind))
+        .collect()
+}
+
+#[test]
+fn complete_struct_fields() {
+    let (file_name, db) = lark_parser_db(POINT);
+    let index = ByteIndex::from(POINT.find("p.x").unwrap() + 2);
+
+    let completions = db.completions_at(file_name, index);
+    assert_eq!(
+        completion_summary(&completions),
+        vec![("x", CompletionKind::Field), ("y", CompletionKind::Field)]
+    );
+}
+
+#[test]
+fn complete_fields_of_non_struct() {
+    let text = "\
+def main(a: uint) -> uint {
+    a.foo
+}
+";
+    let (file_name, db) = lark_parser_db(text);
+    let index = ByteIndex::from(text.find("a.").unwrap() + 2);
+    assert!(db.completions_at(file_name, index).is_empty());
+}
+
+#[test]
+fn complete_at_invalid_index() {
+    let text = "def main() {\n    let é = 1\n}\n";
+    let (file_name, db) = lark_parser_db(text);
+
+    let inside_char = ByteIndex::from(text.find('é').unwrap() + 1);
+    assert!(db.completions_at(file_name, inside_char).is_empty());
+
+    let past_end = ByteIndex::from(text.len() + 10);
+    assert!(db.completions_at(file_name, past_end).is_empty());
+}
+
+#[test]
+fn complete_names() {
+    let text = "\
+def helper() -> uint {
+    1
+}
+
+def main(a: uint) -> uint {
+    let b = a
+    b + helper()
+}
+";
+    let (file_name, db) = lark_parser_db(text);
+    let index = ByteIndex::from(text.find("b + helper").unwrap());
+
+    let completions = db.completions_at(file_name, index);
+    assert_eq!(
+        completion_summary(&completions),
+        vec![
+            ("a", CompletionKind::Variable),
+            ("b", CompletionKind::Variable),
+            ("helper", CompletionKind::Item(ItemKind::Function)),
+            ("main", CompletionKind::Item(ItemKind::Function)),
+        ]
+    );
+
+    let helper = item(&db, file_name, ItemKind::Function, "helper");
+    assert_eq!(completions[2].entity, Some(helper));
+}
+
+#[test]
+fn diagnostics_as_lsp_json() {
+    let text = "def main() {\r\n    bar\r\n}";
+    let (file_name, db) = lark_parser_db(text);
+    let bar = text.find("bar").unwrap();
+    let diagnostics = vec![
+        Diagnostic::new(
+            "cannot find `bar`".to_string(),
+            Span::new(file_name, bar, bar + 3),
+        ),
+        Diagnostic::warning(
+            "unused function".to_string(),
+            Span::new(file_name, 4usize, 8usize),
+        ),
+        Diagnostic::new(
+            "unexpected end of file".to_string(),
+            Span::new(file_name, text.len(), text.len()),
+        ),
+    ];
+
+    assert_eq!(
+        diagnostics_to_lsp(&diagnostics, &db.line_index(file_name)),
+        json!([
+            {
+                "range": {
+                    "start": { "line": 1, "character": 4 },
+                    "end": { "line": 1, "character": 7 }
+                },
+                "severity": 1,
+                "message": "cannot find `bar`"
+            },
+            {
+                "range": {
+                    "start": { "line": 0, "character": 4 },
+                    "end": { "line": 0, "character": 8 }
+                },
+                "severity": 2,
+                "message": "unused function"
+            },
+            {
+                "range": {
+                    "start": { "line": 2, "character": 1 },
+                    "end": { "line": 2, "character": 1 }
+                },
+                "severity": 1,
+                "message": "unexpected end of file"
+            }
+        ])
+    );
+}
@@ -1,5 +1,16 @@
+use lark_entity::EntityData;
+use lark_entity::ItemKind;
+use lark_entity::LangItem;
+use lark_entity::MemberKind;
+use lark_intern::{Intern, Untern};
+use lark_parser::LexToken;
 use lark_parser::ParserDatabase;
-use lark_span::ByteIndex;
+use lark_parser::ParserDatabaseExt;
+use lark_parser::SymbolKind;
+use lark_parser::SymbolNode;
+use lark_query_system::LarkDatabase;
+use lark_span::{ByteIndex, Span};
+use lark_string::Text;
 use lark_test::*;
 
 #[test]
@@ -42,3 +53,546 @@ fn location() {
         &loc_4,
     );
 }
+
+#[test]
+fn line_index_is_per_file() {
+    let mut db = db_with_test("a.lark", "def a() {}\r\ndef b() {}\r\n");
+    db.add_file("b.lark", "def c() {}\rdef d() {}\n");
+    let a = "a.lark".into_file_name(&db);
+    let b = "b.lark".into_file_name(&db);
+
+    let location = db.location(a, ByteIndex::from(16));
+    assert_eq!((location.line, location.column), (1, 4));
+    let location = db.location(b, ByteIndex::from(15));
+    assert_eq!((location.line, location.column), (1, 4));
+    assert_eq!(db.byte_index(b, 1, 4), ByteIndex::from(15));
+
+    // Editing `a.lark` does not rebuild the line index of `b.lark`.
+    let b_index = db.line_index(b);
+    db.set_file_text(a, Text::from("def a() {}\n"));
+    assert!(std::sync::Arc::ptr_eq(&db.line_index(b), &b_index));
+    assert_eq!(db.line_index(a).line_count(), 2);
+}
+
+fn all_entity_names(db: &LarkDatabase) -> Vec<String> {
+    db.all_entities(())
+        .iter()
+        .map(|entity| match entity.untern(db) {
+            EntityData::ItemName { id, .. } | EntityData::MemberName { id, .. } => {
+                id.untern(db).to_string()
+            }
+            data => panic!("unexpected entity {:?}", data),
+        })
+        .collect()
+}
+
+#[test]
+fn all_entities() {
+    let mut db = db_with_test("a.lark", "struct Point {\n    x: uint,\n    y: uint,\n}\n");
+    db.add_file("b.lark", "def foo() {}\n");
+    let a = "a.lark".into_file_name(&db);
+    let b = "b.lark".into_file_name(&db);
+    assert_eq!(all_entity_names(&db), vec!["Point", "x", "y", "foo"]);
+
+    let b_entity = EntityData::InputFile { file: b }.intern(&db);
+    let b_entities = db.descendant_entities(b_entity);
+
+    db.set_file_text(
+        a,
+        Text::from("struct Point {\n    x: uint,\n    y: uint,\n}\ndef bar() {}\n"),
+    );
+    assert_eq!(all_entity_names(&db), vec!["Point", "bar", "x", "y", "foo"]);
+
+    // `b.lark` was not edited, so its entities were not recomputed.
+    assert_eq!(
+        db.descendant_entities(b_entity).as_ptr(),
+        b_entities.as_ptr()
+    );
+}
+
+#[test]
+fn entity_input_file() {
+    let mut db = db_with_test("a.lark", "struct Point {\n    x: uint,\n}\n");
+    db.add_file(
+        "b.lark",
+        "enum Shape {\n    Circle(uint),\n}\ndef area() {}\n",
+    );
+    let a = "a.lark".into_file_name(&db);
+    let b = "b.lark".into_file_name(&db);
+
+    // Items and their members all report the file they were
+    // declared in.
+    let files: Vec<_> = db
+        .all_entities(())
+        .iter()
+        .map(|entity| entity.input_file(&db))
+        .collect();
+    assert_eq!(files, vec![Some(a), Some(a), Some(b), Some(b), Some(b)]);
+
+    // Lang items are not declared in any file.
+    let uint = EntityData::LangItem(LangItem::Uint).intern(&db);
+    assert_eq!(uint.input_file(&db), None);
+}
+
+#[test]
+fn edit_file_shifts_entity_spans() {
+    let mut db = db_with_test("a.lark", "def foo() {}\ndef bar() {}\n");
+    let a = "a.lark".into_file_name(&db);
+    let entities = db.all_entities(());
+    let spans: Vec<_> = entities.iter().map(|&e| db.entity_span(e)).collect();
+
+    db.edit_file(Span::new(a, 0usize, 0usize), "\n\n");
+
+    // The entities are unchanged, but their spans moved down by the
+    // two inserted bytes.
+    assert_eq!(db.all_entities(()), entities);
+    for (&entity, span) in entities.iter().zip(spans) {
+        let shifted = Span::new(a, span.start().to_usize() + 2, span.end().to_usize() + 2);
+        assert_eq!(db.entity_span(entity), shifted);
+    }
+}
+
+#[test]
+fn edit_file_clamps_stale_spans() {
+    let mut db = db_with_test("a.lark", "def é() {}\n");
+    let a = "a.lark".into_file_name(&db);
+
+    // A span past the end of the text appends.
+    db.edit_file(Span::new(a, 20usize, 30usize), "//");
+    assert_eq!(&db.file_text(a)[..], "def é() {}\n//");
+
+    // A span that splits `é` is rounded down to its start.
+    db.edit_file(Span::new(a, 5usize, 6usize), "e");
+    assert_eq!(&db.file_text(a)[..], "def e() {}\n//");
+}
+
+#[test]
+fn file_offsets() {
+    let mut db = db_with_test("a.lark", "def a() {}\n");
+    db.add_file("b.lark", "def b() {}\ndef c() {}\n");
+    db.set_file("c.lark", "def d() {}\n");
+    let files: Vec<_> = db.file_names().iter().cloned().collect();
+    assert_eq!(files.len(), 3);
+
+    let offsets = db.file_offsets(());
+    let ranges: Vec<_> = files
+        .iter()
+        .map(|&file| {
+            let start = offsets[&file];
+            (start, start + db.file_text(file).len())
+        })
+        .collect();
+    assert_eq!(ranges, vec![(0, 11), (11, 33), (33, 44)]);
+
+    // Setting the text of a known file replaces it rather than adding
+    // the file again, and shifts the files after it.
+    db.set_file("b.lark", "def b() {}\n");
+    assert_eq!(db.file_names().len(), 3);
+    let c = "c.lark".into_file_name(&db);
+    assert_eq!(db.file_offsets(())[&c], 22);
+}
+
+#[test]
+fn document_symbols() {
+    let text = "\
+struct Point {
+    x: uint,
+    y: uint,
+}
+
+def scale(p: Point, factor: uint) -> uint {
+    p.x * factor
+}
+";
+    let db = db_with_test("a.lark", text);
+    let symbols = db.document_symbols("a.lark".into_file_name(&db));
+
+    fn shape(node: &SymbolNode) -> (String, SymbolKind, Vec<(String, SymbolKind)>) {
+        let children = node
+            .children
+            .iter()
+            .map(|child| {
+                assert!(child.children.is_empty());
+                (child.name.clone(), child.kind)
+            })
+            .collect();
+        (node.name.clone(), node.kind, children)
+    }
+    let field = SymbolKind::Member(MemberKind::Field);
+    assert_eq!(
+        symbols.iter().map(shape).collect::<Vec<_>>(),
+        vec![
+            (
+                "Point".to_string(),
+                SymbolKind::Item(ItemKind::Struct),
+                vec![("x".to_string(), field), ("y".to_string(), field)],
+            ),
+            (
+                "scale".to_string(),
+                SymbolKind::Item(ItemKind::Function),
+                vec![
+                    ("p".to_string(), SymbolKind::Parameter),
+                    ("factor".to_string(), SymbolKind::Parameter),
+                ],
+            ),
+        ]
+    );
+
+    // Each child's span starts with its name and lies within its parent's.
+    for node in symbols.iter() {
+        for child in &node.children {
+            assert!(text[child.span].starts_with(&child.name[..]));
+            assert!(node.span.start() <= child.span.start());
+            assert!(child.span.end() <= node.span.end());
+        }
+    }
+    assert!(text[symbols[0].span].starts_with("struct Point"));
+    assert!(text[symbols[1].span].starts_with("def scale"));
+}
+
+#[test]
+fn entities_to_dot() {
+    let mut db = db_with_test(
+        "a.lark",
+        "\
+struct Point {
+    x: uint,
+}
+
+impl Point {
+    get(shared self) -> uint {
+        self.x
+    }
+}
+",
+    );
+    db.add_file("b.lark", "enum Shape {\n    Circle(uint),\n}\n");
+
+    assert_eq!(
+        lark_parser::dot::entities_to_dot(&db),
+        r#"digraph entities {
+    n0 [label="file a.lark"];
+    n1 [label="struct Point"];
+    n2 [label="field x"];
+    n3 [label="impl Point"];
+    n4 [label="def get"];
+    n5 [label="file b.lark"];
+    n6 [label="enum Shape"];
+    n7 [label="variant Circle"];
+    n0 -> n1;
+    n1 -> n2;
+    n0 -> n3;
+    n3 -> n4;
+    n5 -> n6;
+    n6 -> n7;
+}
+"#
+    );
+}
+
+fn sexpr(text: &str, spans: bool) -> String {
+    let db = db_with_test("a.lark", text);
+    lark_parser::sexpr::file_sexpr(&db, "a.lark".into_file_name(&db), spans)
+}
+
+#[test]
+fn sexpr_struct() {
+    let text = "\
+struct Point {
+    x: uint,
+    y: uint,
+}
+";
+    assert_eq!(
+        sexpr(text, false),
+        "(file (struct Point (field x uint) (field y uint)))"
+    );
+}
+
+#[test]
+fn sexpr_def_with_parameters() {
+    let text = "\
+def add(a: uint, b: uint) -> uint {
+    a + b
+}
+";
+    assert_eq!(
+        sexpr(text, false),
+        unindent::unindent(
+            "
+            (file
+              (def add
+                (param a uint)
+                (param b uint)
+                (returns uint)
+                (+ a b)))"
+        )
+    );
+}
+
+#[test]
+fn sexpr_nested_block() {
+    let text = "\
+def main() -> uint {
+    let x = 1
+    if x == 1 {
+        let y = x
+        y + 1
+    } else {
+        0
+    }
+}
+";
+    assert_eq!(
+        sexpr(text, false),
+        unindent::unindent(
+            "
+            (file
+              (def main
+                (returns uint)
+                (let x 1 (if (== x 1) (let y x (+ y 1)) 0))))"
+        )
+    );
+}
+
+#[test]
+fn sexpr_with_spans() {
+    let text = "def one() -> uint { 1 }";
+    assert_eq!(
+        sexpr(text, true),
+        "(file (def one (returns uint) 1@20..21))"
+    );
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let (file_name, db) = lark_parser_db("struct Foo { }\n/* a /* b */\nstruct Bar { }\n");
+    let errors = db.file_tokens(file_name).errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].label, "unterminated block comment");
+    assert_eq!(errors[0].span, Span::new(file_name, 15usize, 17usize));
+}
+
+#[test]
+fn token_stream() {
+    let text = "struct Foo { x: uint } /* pt */\n";
+    let (file_name, db) = lark_parser_db(text);
+
+    let tokens = db.tokens(file_name, false);
+    let kinds: Vec<_> = tokens
+        .iter()
+        .map(|token| (token.value, &text[token.span]))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (LexToken::Identifier, "struct"),
+            (LexToken::Identifier, "Foo"),
+            (LexToken::Sigil, "{"),
+            (LexToken::Identifier, "x"),
+            (LexToken::Sigil, ":"),
+            (LexToken::Identifier, "uint"),
+            (LexToken::Sigil, "}"),
+            (LexToken::Newline, "\n"),
+        ]
+    );
+    assert_eq!(tokens[1].span, Span::new(file_name, 7usize, 10usize));
+
+    // With trivia, the tokens cover the whole text, in order.
+    let tokens = db.tokens(file_name, true);
+    let mut end = 0;
+    for token in tokens.iter() {
+        assert_eq!(token.span.start().to_usize(), end);
+        end = token.span.end().to_usize();
+    }
+    assert_eq!(end, text.len());
+
+    let trivia: Vec<_> = tokens
+        .iter()
+        .filter(|token| token.value.is_trivia())
+        .map(|token| (token.value, &text[token.span]))
+        .collect();
+    assert_eq!(trivia.len(), 7);
+    assert_eq!(trivia[6], (LexToken::Comment, "/* pt */"));
+}
+
+#[test]
+fn identifiers_are_nfc_normalized() {
+    // `cafe\u{301}` spells `caf\u{e9}` with a combining accent, and
+    // likewise for the local `e\u{301}`.
+    let text = "\
+struct cafe\u{301} {
+    \u{3b1}\u{3b2}: uint,
+}
+def get(c: caf\u{e9}) -> uint {
+    let e\u{301} = c.\u{3b1}\u{3b2}
+    \u{e9}
+}
+";
+    let db = db_with_test("a.lark", text);
+    assert_eq!(
+        all_entity_names(&db),
+        vec!["caf\u{e9}", "\u{3b1}\u{3b2}", "get"]
+    );
+
+    let get = db.all_entities(())[2];
+    assert!(db.fn_body(get).errors.is_empty());
+}
+
+#[test]
+fn sexpr_type_modes() {
+    let text = "\
+struct Point {
+    x: uint,
+}
+
+struct Holder {
+    a: owned Point,
+    b: shared Point,
+    c: borrowed Point,
+    d: Point,
+}
+";
+    assert_eq!(
+        sexpr(text, false),
+        unindent::unindent(
+            "
+            (file
+              (struct Point (field x uint))
+              (struct Holder
+                (field a Point)
+                (field b (shared Point))
+                (field c (borrowed Point))
+                (field d Point)))"
+        )
+    );
+}
+
+/// The names of the structs in `text` that contain themselves by value.
+fn recursive_structs(text: &str) -> Vec<String> {
+    let db = db_with_test("a.lark", text);
+    db.all_entities(())
+        .iter()
+        .filter(|&&entity| db.struct_is_recursive(entity))
+        .map(|entity| match entity.untern(&db) {
+            EntityData::ItemName { id, .. } => id.untern(&db).to_string(),
+            data => panic!("unexpected entity {:?}", data),
+        })
+        .collect()
+}
+
+#[test]
+fn struct_is_recursive() {
+    assert_eq!(
+        recursive_structs("struct Node {\n    next: Node,\n}\n"),
+        vec!["Node"]
+    );
+
+    // A cycle through another struct, where one link is inside a tuple.
+    assert_eq!(
+        recursive_structs("struct A {\n    b: B,\n}\nstruct B {\n    a: (A, uint),\n}\n"),
+        vec!["A", "B"]
+    );
+
+    // A struct that only refers to a recursive struct is not itself
+    // recursive (though it is just as large).
+    assert_eq!(
+        recursive_structs("struct A {\n    a: A,\n}\nstruct B {\n    a: A,\n}\n"),
+        vec!["A"]
+    );
+
+    // A `shared` or `borrowed` field breaks the cycle.
+    assert!(recursive_structs(
+        "struct A {\n    b: B,\n}\nstruct B {\n    a: shared A,\n    b: borrowed B,\n}\n"
+    )
+    .is_empty());
+}
+
+/// Copies every node of `tables` into a fresh arena, in index
+/// order, via `FnBodyTables::add`.
+fn rebuild_tables(tables: &lark_hir::FnBodyTables) -> lark_hir::FnBodyTables {
+    fn copy<I: lark_hir::HirIndex>(from: &lark_hir::FnBodyTables, to: &mut lark_hir::FnBodyTables)
+    where
+        I::Data: lark_hir::HirIndexData<Index = I>,
+    {
+        for (index, data) in I::index_vec(from).iter_enumerated() {
+            assert_eq!(to.add(from.span(index), data.clone()), index);
+        }
+    }
+
+    let mut rebuilt = lark_hir::FnBodyTables::default();
+    copy::<lark_hir::Expression>(tables, &mut rebuilt);
+    copy::<lark_hir::IdentifiedExpression>(tables, &mut rebuilt);
+    copy::<lark_hir::Place>(tables, &mut rebuilt);
+    copy::<lark_hir::MatchArm>(tables, &mut rebuilt);
+    copy::<lark_hir::Pattern>(tables, &mut rebuilt);
+    copy::<lark_hir::IdentifiedPattern>(tables, &mut rebuilt);
+    copy::<lark_hir::Variable>(tables, &mut rebuilt);
+    copy::<lark_hir::Identifier>(tables, &mut rebuilt);
+    copy::<lark_hir::Error>(tables, &mut rebuilt);
+    rebuilt.list_entries = tables.list_entries.clone();
+    rebuilt
+}
+
+#[test]
+fn fn_body_arena_round_trip() {
+    let db = db_with_test("a.lark", &synthetic_source(50));
+    let entities = db.top_level_entities_in_file("a.lark");
+    assert_eq!(entities.len(), 51);
+
+    for &entity in entities.iter().skip(1) {
+        let fn_body = db.fn_body(entity).assert_no_errors();
+        assert_eq!(rebuild_tables(&fn_body.tables), fn_body.tables);
+    }
+}
+
+#[test]
+fn fn_body_serde_round_trip() {
+    let text = "\
+struct Point {
+    x: uint,
+    y: uint,
+}
+
+def norm(p: Point) -> uint {
+    let n: uint = p.x * p.x + p.y * p.y
+    match n {
+        0 => 1,
+        other => other + missing,
+    }
+}
+";
+    let db = db_with_test("a.lark", text);
+    let norm = db.top_level_entities_in_file("a.lark")[1];
+    let fn_body = db.fn_body(norm).into_value();
+
+    let serialized = lark_hir::SerializedFnBody::new(&db, (*fn_body).clone());
+    let json = serde_json::to_string(&serialized).unwrap();
+    let deserialized: lark_hir::SerializedFnBody = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, serialized);
+    assert_eq!(deserialized.fn_body, *fn_body);
+
+    // The side-table has the text of each identifier, literal and
+    // unresolved name, and of the file name in the spans.
+    let strings: Vec<&str> = deserialized
+        .side_table
+        .strings
+        .iter()
+        .map(|(_, text)| &text[..])
+        .collect();
+    for expected in &["p", "x", "y", "n", "other", "missing", "0", "1", "a.lark"] {
+        assert!(strings.contains(expected), "missing {:?}", expected);
+    }
+
+    // Load the body into a fresh database, which interned another
+    // file first (so the ids differ). The result matches the body
+    // that database computes for the same source.
+    let mut fresh = db_with_test("b.lark", "struct Other {\n    a: int,\n}\n");
+    assert_eq!(fresh.top_level_entities_in_file("b.lark").len(), 1);
+    let reloaded = deserialized.into_fn_body(&fresh);
+
+    fresh.add_file("a.lark", text);
+    let fresh_norm = fresh.top_level_entities_in_file("a.lark")[1];
+    assert_ne!(fresh_norm, norm);
+    assert_eq!(reloaded, *fresh.fn_body(fresh_norm).into_value());
+}
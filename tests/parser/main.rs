@@ -2,12 +2,17 @@ use lark_debug_derive::DebugWith;
 use lark_debug_with::DebugWith;
 use lark_entity::Entity;
 use lark_entity::EntityData;
+use lark_entity::MemberKind;
+use lark_entity::Visibility;
 use lark_hir as hir;
 use lark_intern::Intern;
 use lark_intern::Untern;
 use lark_parser::ParserDatabase;
+use lark_parser::ParserDatabaseExt;
 use lark_query_system::LarkDatabase;
 use lark_span::FileName;
+use lark_span::Spanned;
+use lark_string::GlobalIdentifier;
 use lark_string::GlobalIdentifierTables;
 use lark_test::*;
 
@@ -392,6 +397,433 @@ fn eof_extra_sigil() {
     );
 }
 
+#[test]
+fn generic_struct() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        struct Pair[T, U] {
+            first: T,
+            second: U
+        }
+        ",
+    ));
+
+    let pair = select_entity(&db, file_name, 0);
+    let generic_declarations = db.generic_declarations(pair).assert_no_errors().unwrap();
+    let names: Vec<String> = generic_declarations
+        .declarations
+        .iter()
+        .map(|declaration| declaration.clone().assert_ty().name.untern(&db).to_string())
+        .collect();
+    assert_eq!(names, vec!["T", "U"]);
+
+    // The fields can reference the generic parameters.
+    for member in db.members(pair).unwrap().iter() {
+        db.ty(member.entity).assert_no_errors();
+    }
+}
+
+#[test]
+fn generic_fn() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def id[T](x: T) -> T {
+            x
+        }
+        ",
+    ));
+
+    let id = select_entity(&db, file_name, 0);
+    let generic_declarations = db.generic_declarations(id).assert_no_errors().unwrap();
+    assert_eq!(generic_declarations.declarations.len(), 1);
+
+    // The signature can reference the generic parameter.
+    db.signature(id).assert_no_errors();
+}
+
+#[test]
+fn where_clause() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        trait Show { }
+        struct Foo { }
+        impl Show for Foo { }
+        def show[T](x: T) where T: Show { }
+        ",
+    ));
+
+    let show_trait = select_entity(&db, file_name, 0);
+    let foo = select_entity(&db, file_name, 1);
+    let show_impl = select_entity(&db, file_name, 2);
+    let show_fn = select_entity(&db, file_name, 3);
+
+    assert_eq!(db.impl_trait(show_impl).assert_no_errors(), Some(show_trait));
+    assert!(db.implements_trait(foo, show_trait));
+
+    let generic_declarations = db.generic_declarations(show_fn).assert_no_errors().unwrap();
+    assert_eq!(generic_declarations.constraints.len(), 1);
+    assert_eq!(generic_declarations.constraints[0].trait_entity, show_trait);
+}
+
+#[test]
+fn type_path() {
+    let (file_name, mut db) = lark_parser_db(unindent::unindent(
+        "
+        struct Line {
+            start: geometry::Point
+        }
+        ",
+    ));
+    db.add_file("geometry.lark", "struct Point { x: uint }");
+
+    let line = select_entity(&db, file_name, 0);
+    let point = db.top_level_entities_in_file("geometry.lark")[0];
+    let start = db.members(line).unwrap()[0].entity;
+    assert_eq!(
+        db.ty(start).assert_no_errors(),
+        db.ty(point).assert_no_errors()
+    );
+}
+
+#[test]
+fn type_path_unresolved_component() {
+    let (file_name, mut db) = lark_parser_db(unindent::unindent(
+        "
+        struct Line {
+            start: geometry::Nope::Point
+        }
+        ",
+    ));
+    db.add_file("geometry.lark", "struct Point { x: uint }");
+
+    let line = select_entity(&db, file_name, 0);
+    let start = db.members(line).unwrap()[0].entity;
+    let errors = db.ty(start).errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].label, "unresolved path component: `Nope`");
+    assert_eq!(&db.file_text(file_name)[errors[0].span], "Nope");
+}
+
+#[test]
+fn struct_with_malformed_fields() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        struct Foo {
+            a: uint,
+            b: 22,
+            c: bool,
+            d: uint uint,
+            e: uint
+        }
+        ",
+    ));
+    let text = db.file_text(file_name);
+
+    // Each malformed field is reported, without cascading errors.
+    let errors = db.parsed_file(file_name).errors;
+    let labels: Vec<_> = errors
+        .iter()
+        .map(|error| (&error.label[..], &text[error.span]))
+        .collect();
+    assert_eq!(
+        labels,
+        vec![("expected an identifier", "22"), ("expected `,`", "uint")]
+    );
+
+    // The fields after them are still registered.
+    let foo = select_entity(&db, file_name, 0);
+    let names: Vec<_> = db
+        .members(foo)
+        .unwrap()
+        .iter()
+        .map(|member| member.name.untern(&db).to_string())
+        .collect();
+    assert_eq!(names, vec!["a", "b", "c", "d", "e"]);
+    db.ty(db.members(foo).unwrap()[2].entity).assert_no_errors();
+}
+
+#[test]
+fn function_with_malformed_parameter() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo(a: uint, b: 22, c: bool) {
+        }
+        ",
+    ));
+
+    assert_eq!(db.parsed_file(file_name).errors.len(), 1);
+
+    let foo = select_entity(&db, file_name, 0);
+    let signature = db.signature(foo).value.unwrap();
+    assert_eq!(signature.inputs.len(), 3);
+}
+
+#[test]
+fn type_alias() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        type Id = uint
+
+        struct Account {
+            id: Id,
+            number: uint,
+        }
+        ",
+    ));
+
+    let account = select_entity(&db, file_name, 1);
+    let members = db.members(account).unwrap();
+    assert_eq!(
+        db.ty(members[0].entity).assert_no_errors(),
+        db.ty(members[1].entity).assert_no_errors()
+    );
+}
+
+#[test]
+fn type_alias_cycle() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        type A = B
+        type B = (uint, A)
+
+        struct Foo {
+            a: A,
+        }
+        ",
+    ));
+
+    let a = select_entity(&db, file_name, 0);
+    let errors = db.ty(a).errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].label, "cyclic type alias: `A`");
+    assert_eq!(&db.file_text(file_name)[errors[0].span], "A");
+
+    // Uses of the alias get the error type, without further errors.
+    let foo = select_entity(&db, file_name, 2);
+    let field = db.members(foo).unwrap()[0].entity;
+    assert!(db.ty(field).errors.is_empty());
+}
+
+#[test]
+fn visibility() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        pub struct Point {
+            pub x: uint,
+            y: uint,
+        }
+
+        def helper() { }
+
+        pub def main() { }
+        ",
+    ));
+
+    let point = select_entity(&db, file_name, 0);
+    assert_eq!(db.visibility_of(point), Visibility::Public);
+
+    let members = db.members(point).unwrap();
+    assert_eq!(db.visibility_of(members[0].entity), Visibility::Public);
+    assert_eq!(db.visibility_of(members[1].entity), Visibility::Private);
+
+    let helper = select_entity(&db, file_name, 1);
+    assert_eq!(db.visibility_of(helper), Visibility::Private);
+
+    let main = select_entity(&db, file_name, 2);
+    assert_eq!(db.visibility_of(main), Visibility::Public);
+}
+
+#[test]
+fn doc_comments() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        /// A point.
+        ///   In two dimensions.
+        pub struct Point { x: uint }
+
+        /// Detached from `main` by the blank line.
+
+        def main() { }
+        ",
+    ));
+
+    let doc_of = |entity| db.doc_of_entity(entity).map(|doc| doc.untern(&db).to_string());
+
+    let point = select_entity(&db, file_name, 0);
+    assert_eq!(
+        doc_of(point),
+        Some("A point.\n  In two dimensions.".to_string())
+    );
+
+    let main = select_entity(&db, file_name, 1);
+    assert_eq!(doc_of(main), None);
+}
+
+#[test]
+fn attributes() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        #[inline]
+        #[allow(unused, dead_code)] pub def helper() { }
+
+        def main() { }
+        ",
+    ));
+
+    // Unknown attributes are accepted without complaint.
+    assert!(db.parsed_file(file_name).errors.is_empty());
+
+    let text = db.file_text(file_name);
+    let names = |identifiers: &[Spanned<GlobalIdentifier, FileName>]| -> Vec<String> {
+        identifiers
+            .iter()
+            .map(|identifier| identifier.value.untern(&db).to_string())
+            .collect()
+    };
+
+    let helper = select_entity(&db, file_name, 0);
+    let attributes = db.attributes_of(helper);
+    assert_eq!(attributes.len(), 2);
+
+    assert_eq!(&text[attributes[0].span], "#[inline]");
+    assert_eq!(names(&attributes[0].path), vec!["inline"]);
+    assert!(attributes[0].arguments.is_empty());
+
+    assert_eq!(&text[attributes[1].span], "#[allow(unused, dead_code)]");
+    assert_eq!(names(&attributes[1].path), vec!["allow"]);
+    assert_eq!(names(&attributes[1].arguments), vec!["unused", "dead_code"]);
+
+    // The entity's span covers its attributes.
+    assert!(db.entity_span(helper).contains(attributes[0].span));
+
+    let main = select_entity(&db, file_name, 1);
+    assert!(db.attributes_of(main).is_empty());
+}
+
+#[test]
+fn enum_variants() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        enum Foo {
+            A,
+            B(uint, bool)
+        }
+        ",
+    ));
+
+    let tree = EntityTree::from_file(&db, file_name);
+    assert_expected_debug(
+        &db,
+        &unindent::unindent(
+            r#"EntityTree {
+                name: "InputFile(path1)",
+                children: [
+                    EntityTree {
+                        name: "ItemName(Foo)",
+                        children: [
+                            EntityTree {
+                                name: "MemberName(A)",
+                                children: []
+                            },
+                            EntityTree {
+                                name: "MemberName(B)",
+                                children: []
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        ),
+        &tree,
+    );
+}
+
+#[test]
+fn enum_variant_entities() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        enum Foo {
+            A,
+            B(uint, bool)
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let variants = db.members(foo).unwrap();
+    assert_eq!(variants.len(), 2);
+    assert!(variants.iter().all(|v| v.kind == MemberKind::Variant));
+
+    // Variants are in scope by their bare name.
+    let b = variants[1].entity;
+    assert_eq!(db.resolve_name(foo, "B".intern(&db)), Some(b));
+
+    // The span of `B` covers its fields.
+    let span = db.entity_span(b);
+    assert_eq!((span.start().to_usize(), span.end().to_usize()), (22, 35));
+}
+
+#[test]
+fn trait_methods() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        trait Foo {
+            bar() -> uint,
+            baz(x: uint, y: bool)
+        }
+        ",
+    ));
+
+    let tree = EntityTree::from_file(&db, file_name);
+    assert_expected_debug(
+        &db,
+        &unindent::unindent(
+            r#"EntityTree {
+                name: "InputFile(path1)",
+                children: [
+                    EntityTree {
+                        name: "ItemName(Foo)",
+                        children: [
+                            EntityTree {
+                                name: "MemberName(bar)",
+                                children: []
+                            },
+                            EntityTree {
+                                name: "MemberName(baz)",
+                                children: []
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        ),
+        &tree,
+    );
+}
+
+#[test]
+fn trait_method_signatures() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        trait Foo {
+            bar() -> uint
+            baz(x: uint, y: bool)
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let methods = db.members(foo).unwrap();
+    assert!(methods.iter().all(|m| m.kind == MemberKind::MethodSignature));
+
+    // Each signature has an implicit `self` argument.
+    let arities: Vec<usize> = methods
+        .iter()
+        .map(|m| db.signature(m.entity).assert_no_errors().unwrap().inputs.len())
+        .collect();
+    assert_eq!(arities, vec![1, 3]);
+}
+
 #[test]
 fn some_function() {
     let (file_name, db) = lark_parser_db(unindent::unindent(
@@ -605,6 +1037,59 @@ fn parse_methods_chained_variations() {
     assert_equal(&(), &debug1, &debug2);
 }
 
+#[test]
+fn parse_missing_semicolon_recovers() {
+    let text = unindent::unindent(
+        "
+            def foo() {
+              let bar = 22 let baz = 44
+              bar + baz
+            }
+        ",
+    );
+    let (file_name, db) = lark_parser_db(&text);
+    let fn_body = db.fn_body(select_entity(&db, file_name, 0));
+
+    // One error, right after `22`, where the `;` belongs.
+    assert_eq!(fn_body.errors.len(), 1);
+    assert_eq!(fn_body.errors[0].label, "expected `;`");
+    let offset = text.find("22").unwrap() + 2;
+    assert_eq!(fn_body.errors[0].span.start().to_usize(), offset);
+
+    // The statements after the missing `;` are still parsed.
+    let debug1 = {
+        let fn_body = fn_body.value;
+        fn_body
+            .debug_with(&FnBodyContext {
+                db: &db,
+                fn_body: &fn_body,
+            })
+            .to_string()
+    };
+
+    let debug2 = {
+        let (file_name, db) = lark_parser_db(unindent::unindent(
+            "
+            def foo() {
+              let bar = 22; let baz = 44
+              bar + baz
+            }
+        ",
+        ));
+        let fn_body = db
+            .fn_body(select_entity(&db, file_name, 0))
+            .assert_no_errors();
+        fn_body
+            .debug_with(&FnBodyContext {
+                db: &db,
+                fn_body: &fn_body,
+            })
+            .to_string()
+    };
+
+    assert_equal(&(), &debug1, &debug2);
+}
+
 #[test]
 fn parse_bad_token() {
     // check that unexpected tokens (or utf-8 tokens!) avoid an infinite loop
@@ -703,3 +1188,116 @@ fn parse_call_variations() {
     };
     assert_equal(&(), &debug1, &debug2);
 }
+
+#[test]
+fn parse_assignment_right_associative() {
+    let debug1 = {
+        let (file_name, db) = lark_parser_db(unindent::unindent(
+            "
+            def foo() {
+              let a = 0
+              let b = 0
+              a = b = 22
+            }
+        ",
+        ));
+        let fn_body = db
+            .fn_body(select_entity(&db, file_name, 0))
+            .assert_no_errors();
+        fn_body
+            .debug_with(&FnBodyContext {
+                db: &db,
+                fn_body: &fn_body,
+            })
+            .to_string()
+    };
+
+    let debug2 = {
+        let (file_name, db) = lark_parser_db(unindent::unindent(
+            "
+            def foo() {
+              let a = 0
+              let b = 0
+              a = (b = 22)
+            }
+        ",
+        ));
+        let fn_body = db
+            .fn_body(select_entity(&db, file_name, 0))
+            .assert_no_errors();
+        fn_body
+            .debug_with(&FnBodyContext {
+                db: &db,
+                fn_body: &fn_body,
+            })
+            .to_string()
+    };
+
+    assert_equal(&(), &debug1, &debug2);
+}
+
+#[test]
+fn parse_range_variations() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+            def foo() {
+              let a = 1
+              let b = 2
+              let r1 = a..b
+              let r2 = a..=b
+              let r3 = ..b
+              let r4 = ..=b
+              let r5 = a..
+              let r6 = ..
+            }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    db.fn_body(foo).assert_no_errors();
+}
+
+#[test]
+fn parse_range_binds_loosest() {
+    let debug1 = {
+        let (file_name, db) = lark_parser_db(unindent::unindent(
+            "
+            def foo() {
+              let a = 0
+              a + 1..a * 2
+            }
+        ",
+        ));
+        let fn_body = db
+            .fn_body(select_entity(&db, file_name, 0))
+            .assert_no_errors();
+        fn_body
+            .debug_with(&FnBodyContext {
+                db: &db,
+                fn_body: &fn_body,
+            })
+            .to_string()
+    };
+
+    let debug2 = {
+        let (file_name, db) = lark_parser_db(unindent::unindent(
+            "
+            def foo() {
+              let a = 0
+              (a + 1)..(a * 2)
+            }
+        ",
+        ));
+        let fn_body = db
+            .fn_body(select_entity(&db, file_name, 0))
+            .assert_no_errors();
+        fn_body
+            .debug_with(&FnBodyContext {
+                db: &db,
+                fn_body: &fn_body,
+            })
+            .to_string()
+    };
+
+    assert_equal(&(), &debug1, &debug2);
+}
@@ -0,0 +1,424 @@
+use lark_entity::Entity;
+use lark_entity::EntityData;
+use lark_entity::MemberKind;
+use lark_hir as hir;
+use lark_intern::Intern;
+use lark_intern::Untern;
+use lark_parser::ParserDatabase;
+use lark_parser::ParserDatabaseExt;
+use lark_query_system::LarkDatabase;
+use lark_span::FileName;
+use lark_string::Text;
+use lark_test::*;
+use lark_ty::base_inferred::BaseInferred;
+use lark_type_check::ErrorKind;
+use lark_type_check::SerializedTypeCheckResults;
+use lark_type_check::Severity;
+use lark_type_check::TypeCheckDatabase;
+use std::sync::Arc;
+
+fn select_entity(db: &impl ParserDatabase, file: FileName, index: usize) -> Entity {
+    let file_entity = EntityData::InputFile { file: file }.intern(db);
+    db.child_entities(file_entity)[index]
+}
+
+#[test]
+fn ty_of_resolved_expression() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo() -> uint {
+            22
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let fn_body = db.fn_body(foo).into_value();
+    let results = db.base_type_check(foo).into_value();
+    let root_expression = fn_body.root_expression;
+    assert_eq!(
+        db.ty_of_expression(foo, root_expression),
+        Some(results.ty(root_expression))
+    );
+}
+
+#[test]
+fn ty_of_missing_expression() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo() -> uint {
+            22
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let fn_body = db.fn_body(foo).into_value();
+    let missing = hir::Expression::new(fn_body.tables.expressions.len());
+    assert_eq!(db.ty_of_expression(foo, missing), None);
+}
+
+#[test]
+fn try_ty_after_type_error() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo(x: uint) -> bool {
+            let y = x + true
+            y == 22
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let fn_body = db.fn_body(foo).into_value();
+    let results = db.base_type_check(foo);
+    assert!(!results.errors.is_empty());
+
+    let results = results.into_value();
+    for expression in fn_body.tables.expressions.indices() {
+        assert!(
+            results.try_ty(expression).is_some(),
+            "no type for {:?}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn mismatch_error_kind() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo(x: uint) -> bool {
+            x
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let results = db.base_type_check(foo).into_value();
+    assert_eq!(results.errors.len(), 1);
+    assert_eq!(
+        results.errors[0].kind,
+        ErrorKind::Mismatch {
+            expected: "bool".to_string(),
+            actual: "uint".to_string(),
+        }
+    );
+    assert_eq!(
+        results.errors[0].kind.message(),
+        "mismatched types (uint vs bool)"
+    );
+}
+
+#[test]
+fn unused_variable_warning() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo() {
+            let x = 22
+            let _y = 44
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let results = db.base_type_check(foo);
+    assert!(results.errors.is_empty());
+
+    let results = results.into_value();
+    assert_eq!(results.errors.len(), 1);
+    assert_eq!(results.errors[0].severity, Severity::Warning);
+    assert_eq!(
+        results.errors[0].kind,
+        ErrorKind::UnusedVariable {
+            name: "x".to_string()
+        }
+    );
+}
+
+#[test]
+fn mismatch_error_span() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo(x: uint) -> bool {
+            x
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+    let spans = db.error_spans(foo);
+    assert_eq!(spans.len(), 1);
+
+    let text = db.file_text(file_name);
+    assert_eq!(
+        &text[spans[0].start().to_usize()..spans[0].end().to_usize()],
+        "x"
+    );
+}
+
+#[test]
+fn file_diagnostics_include_warnings() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo(x: uint) -> bool {
+            let y = 22
+            x
+        }
+        ",
+    ));
+
+    let diagnostics = db.file_diagnostics(file_name);
+    let summary: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.severity, &d.label[..]))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (Severity::Error, "mismatched types (uint vs bool)"),
+            (Severity::Warning, "unused variable `y`"),
+        ]
+    );
+}
+
+#[test]
+fn all_diagnostics_only_recomputes_edited_file() {
+    let mut db = LarkDatabase::default();
+    db.add_file("a.lark", "def foo(x: uint) -> bool { x }");
+    db.add_file("b.lark", "def bar(x: uint) -> uint { x }");
+    let a = "a.lark".into_file_name(&db);
+    let b = "b.lark".into_file_name(&db);
+
+    let a_diagnostics = db.file_diagnostics(a);
+    let b_diagnostics = db.file_diagnostics(b);
+    assert_eq!(a_diagnostics.len(), 1);
+    assert!(b_diagnostics.is_empty());
+    assert_eq!(db.all_diagnostics(()), a_diagnostics);
+
+    db.set_file_text(b, Text::from("def bar(x: uint) -> bool { x }"));
+
+    // `a.lark` was not edited, so its diagnostics are not recomputed.
+    assert!(Arc::ptr_eq(&db.file_diagnostics(a), &a_diagnostics));
+    assert_eq!(db.file_diagnostics(b).len(), 1);
+    assert_eq!(db.all_diagnostics(()).len(), 2);
+}
+
+#[test]
+fn unknown_field_in_struct_construction() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        struct Foo {
+            x: uint,
+        }
+
+        def make() -> Foo {
+            Foo(x: 1, nonexistent: 2)
+        }
+        ",
+    ));
+
+    let make = select_entity(&db, file_name, 1);
+    let fn_body = db.fn_body(make).into_value();
+    let results = db.base_type_check(make).into_value();
+    assert_eq!(results.errors.len(), 1);
+    assert_eq!(
+        results.errors[0].kind,
+        ErrorKind::UnknownField {
+            field: "nonexistent".to_string(),
+            owner: "Foo".to_string(),
+        }
+    );
+
+    let text = db.file_text(file_name);
+    let spans = db.error_spans(make);
+    assert_eq!(
+        &text[spans[0].start().to_usize()..spans[0].end().to_usize()],
+        "nonexistent"
+    );
+
+    // The valid field is still resolved to its entity.
+    let x_id = "x".intern(&db);
+    let x = fn_body
+        .tables
+        .identifiers
+        .indices()
+        .find(|&identifier| fn_body.tables[identifier].text == x_id)
+        .unwrap();
+    match results.entities[&hir::MetaIndex::from(x)].untern(&db) {
+        EntityData::MemberName { id, .. } => assert_eq!(id, x_id),
+        data => panic!("unexpected entity {:?}", data),
+    }
+}
+
+#[test]
+fn unresolved_member_errors() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        struct Foo {
+            x: uint,
+        }
+
+        def use_foo(foo: Foo) {
+            debug(foo.y)
+            foo.frob(1)
+        }
+        ",
+    ));
+
+    let use_foo = select_entity(&db, file_name, 1);
+    let results = db.base_type_check(use_foo).into_value();
+    let kinds: Vec<_> = results.errors.iter().map(|error| &error.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            &ErrorKind::UnresolvedMember {
+                member: "y".to_string(),
+                owner: "Foo".to_string(),
+                kind: MemberKind::Field,
+            },
+            &ErrorKind::UnresolvedMember {
+                member: "frob".to_string(),
+                owner: "Foo".to_string(),
+                kind: MemberKind::Method,
+            },
+        ]
+    );
+    assert_eq!(results.errors[0].kind.message(), "no field `y` on `Foo`");
+    assert_eq!(results.errors[1].kind.message(), "no method `frob` on `Foo`");
+}
+
+#[test]
+fn unresolved_name_error() {
+    let (file_name, db) = lark_parser_db(unindent::unindent(
+        "
+        def foo(x: uint) -> bool {
+            debug(missing)
+            x == 22
+        }
+        ",
+    ));
+
+    let foo = select_entity(&db, file_name, 0);
+
+    // The name is left for the type checker to report.
+    let fn_body = db.fn_body(foo);
+    assert!(fn_body.errors.is_empty());
+    let fn_body = fn_body.into_value();
+
+    let results = db.base_type_check(foo).into_value();
+    assert_eq!(results.errors.len(), 1);
+    assert_eq!(
+        results.errors[0].kind,
+        ErrorKind::UnresolvedName {
+            name: "missing".to_string()
+        }
+    );
+    assert_eq!(results.errors[0].kind.message(), "cannot find `missing`");
+
+    let text = db.file_text(file_name);
+    let spans = db.error_spans(foo);
+    assert_eq!(
+        &text[spans[0].start().to_usize()..spans[0].end().to_usize()],
+        "missing"
+    );
+
+    // The rest of the body is still type-checked.
+    for expression in fn_body.tables.expressions.indices() {
+        assert!(
+            results.try_ty(expression).is_some(),
+            "no type for {:?}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn parallel_type_check_matches_sequential() {
+    let text = unindent::unindent(
+        "
+        struct Point {
+            x: uint,
+        }
+
+        def a(p: Point) -> bool {
+            p.x
+        }
+
+        def b() -> uint {
+            let unused = 22
+            missing
+        }
+
+        def c(x: uint) -> uint {
+            x + 1
+        }
+
+        def d() -> Point {
+            Point(x: true)
+        }
+        ",
+    );
+
+    let (_, sequential) = lark_parser_db(text.clone());
+    let (_, parallel) = lark_parser_db(text);
+
+    let results = parallel.type_check_all_in_parallel();
+    let checked: Vec<_> = results.iter().map(|(entity, _)| *entity).collect();
+    let expected: Vec<_> = parallel
+        .all_entities(())
+        .iter()
+        .cloned()
+        .filter(|entity| entity.untern(&parallel).has_fn_body())
+        .collect();
+    assert_eq!(checked, expected);
+
+    // The diagnostics are gathered from the memoized results, and so
+    // match those computed sequentially (in the same order).
+    let diagnostics = parallel.all_diagnostics(());
+    assert_eq!(diagnostics.len(), 4);
+    assert_eq!(diagnostics, sequential.all_diagnostics(()));
+}
+
+#[test]
+fn base_type_check_results_serde_round_trip() {
+    let text = unindent::unindent(
+        "
+        struct Point {
+            x: uint,
+            y: uint,
+        }
+
+        def norm(p: Point) -> uint {
+            let unused = 1
+            p.x * p.x + p.y * missing
+        }
+        ",
+    );
+    let (file_name, db) = lark_parser_db(&text);
+
+    let norm = select_entity(&db, file_name, 1);
+    let results = db.base_type_check(norm).into_value();
+    assert!(!results.max_types.is_empty());
+    assert!(!results.entities.is_empty());
+    assert!(!results.errors.is_empty());
+
+    let serialized = SerializedTypeCheckResults::new(&db, (*results).clone());
+    let json = serde_json::to_string(&serialized).unwrap();
+    let deserialized: SerializedTypeCheckResults<BaseInferred> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, serialized);
+    assert_eq!(deserialized.results, *results);
+
+    // Load the results into a fresh database, which interned another
+    // file first (so the ids differ). They match the results that
+    // database computes for the same source.
+    let mut fresh = db_with_test("other.lark", "struct Other {\n    a: int,\n}\n");
+    assert_eq!(fresh.top_level_entities_in_file("other.lark").len(), 1);
+    let reloaded = deserialized.into_results(&fresh);
+
+    fresh.add_file("path1", text);
+    let fresh_norm = fresh.top_level_entities_in_file("path1")[1];
+    assert_ne!(fresh_norm, norm);
+    assert_eq!(reloaded, *fresh.base_type_check(fresh_norm).into_value());
+}